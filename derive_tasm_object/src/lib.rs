@@ -4,9 +4,41 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
+use quote::ToTokens;
 use syn::DeriveInput;
 
-/// Derives `TasmObject` for structs.
+/// Derives `TasmObject` for structs and enums.
+///
+/// Fields can be annotated with `#[tasm_object(..)]`:
+///  - `#[tasm_object(ignore)]` makes the field invisible to the VM. When decoding, the
+///    field is set to its default value. Fields of type `PhantomData` are ignored
+///    without this attribute.
+///  - `#[tasm_object(rename = "name")]` makes the field accessible under the given name
+///    instead of its Rust identifier, *e.g.*, in `get_field` and `FIELD_NAMES`.
+///  - `#[tasm_object(with = "path::to::module")]` hands the tasm code for accessing the
+///    field to the given module, which must provide the functions
+///    - `static_length() -> Option<usize>`, like `BFieldCodec::static_length`;
+///    - `jumper() -> Vec<LabelledInstruction>`, with stack signature
+///      `_ *field_start -> _ *field_start field_jump_distance`;
+///    - `getter() -> Vec<LabelledInstruction>`, with stack signature
+///      `_ *field_start field_jump_distance -> _ *field`;
+///    - `sizer() -> Vec<LabelledInstruction>`, with stack signature
+///      `_ *field_start field_jump_distance -> _ *field field_size`;
+///    - `decode(&[BFieldElement]) -> Result<Box<T>, Box<dyn Error + Send + Sync>>`,
+///      which receives the field's encoding without size indicator.
+///
+///    Like all other fields, the field is prepended with its size if `static_length`
+///    returns `None`. The field's type is not required to implement `BFieldCodec`.
+///  - `#[tasm_object(by_pointer)]` stores the field by pointer: the object holds the address
+///    of the field's encoding instead of the encoding itself, and the getters dereference
+///    that address. The field's type must implement `TasmObject`. Objects with such fields
+///    can only be decoded from memory, and only structs support them.
+///  - `#[tasm_object(for_each)]` on a field of type `Vec<T>` generates the method
+///    `for_each_<field>`, which returns tasm code for calling an inner function on every
+///    element of the list; see `ForEachListElement`.
+///  - `#[tasm_object(setter)]` on a statically-sized field generates the method
+///    `set_<field>`, which returns tasm code for overwriting the field in memory with a
+///    value from the stack.
 #[proc_macro_derive(TasmObject, attributes(tasm_object))]
 pub fn derive_tasm_object(input: TokenStream) -> TokenStream {
     // ...
@@ -20,15 +52,48 @@ pub fn derive_tasm_object(input: TokenStream) -> TokenStream {
 
 struct ParseResult {
     field_names: Vec<syn::Ident>,
+    field_labels: Vec<String>,
     field_types: Vec<syn::Type>,
     getters: Vec<quote::__private::TokenStream>,
     sizers: Vec<quote::__private::TokenStream>,
     jumpers: Vec<quote::__private::TokenStream>,
+    field_modules: Vec<Option<syn::Path>>,
+    stored_by_pointer: Vec<bool>,
     ignored_fields: Vec<syn::Field>,
 }
 
 fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
+    if let syn::Data::Enum(data_enum) = &ast.data {
+        return impl_derive_tasm_object_macro_for_enum(&ast, data_enum);
+    }
+
     let parse_result = generate_parse_result(&ast);
+    assert_unique_field_labels(&parse_result.field_labels);
+    let static_lengths = parse_result
+        .field_types
+        .iter()
+        .zip(parse_result.field_modules.iter())
+        .map(|(field_type, module)| generate_static_length(field_type, module.as_ref()))
+        .collect::<Vec<_>>();
+
+    // If all fields are statically sized, every field lives at a fixed offset from the
+    // start of the object, and the general computation of its start can be skipped. Fields
+    // accessed through a custom module always use that module's code.
+    let field_count = parse_result.field_names.len();
+    let has_custom_fields = parse_result.field_modules.iter().any(Option::is_some);
+    let static_offset_getter = |index: usize, with_size: bool| {
+        if has_custom_fields {
+            return quote! {};
+        }
+        let static_length = with_size.then_some(&static_lengths[index]);
+        generate_static_offset_getter(field_count - 1 - index, static_length)
+    };
+    let static_offset_getters = (0..field_count)
+        .map(|index| static_offset_getter(index, false))
+        .collect::<Vec<_>>();
+    let static_offset_getters_with_size = (0..field_count)
+        .map(|index| static_offset_getter(index, true))
+        .collect::<Vec<_>>();
 
     // generate clauses for match statements
     let get_current_field_start_with_jump = (0..parse_result.field_names.len()).map(|index| {
@@ -36,7 +101,7 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
         match index {
             0 => jumper.to_owned(),
             not_zero => {
-                let previous_field_name_as_string = &parse_result.field_names[not_zero - 1].to_string();
+                let previous_field_name_as_string = &parse_result.field_labels[not_zero - 1];
                 quote! {
                     [
                         Self::get_field_start_with_jump_distance(#previous_field_name_as_string),
@@ -52,49 +117,60 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
     });
 
     let just_field_clauses = parse_result
-        .field_names
+        .field_labels
         .iter()
         .zip(parse_result.getters.iter())
         .zip(get_current_field_start_with_jump.clone())
-        .map(|((name, getter), current)| {
-            let name_as_string = name.to_string();
-            quote! {
-                #name_as_string => {
-                    let current = { #current }; // _ *current_field_start current_field_jump_amount
-                    let getter = { #getter };   // _ *current_field
-                    [current, getter].concat()
+        .zip(static_offset_getters.iter())
+        .map(
+            |(((name_as_string, getter), current), static_offset_getter)| {
+                quote! {
+                    #name_as_string => {
+                        #static_offset_getter
+                        let current = { #current }; // _ *current_field_start current_field_jump_amount
+                        let getter = { #getter };   // _ *current_field
+                        [current, getter].concat()
+                    }
                 }
-            }
-        });
+            },
+        );
     let field_with_size_clauses = parse_result
-        .field_names
+        .field_labels
         .iter()
         .zip(parse_result.sizers.iter())
         .zip(get_current_field_start_with_jump.clone())
-        .map(|((name, getter_sizer), current)| {
-            let name_as_string = name.to_string();
-            quote! {
-                #name_as_string => {
-                    let current = { #current };             // _ *current_field_start current_field_jump_amount
-                    let getter_sizer = { #getter_sizer };   // _ *current_field current_field_size
-                    [current,  getter_sizer].concat()
+        .zip(static_offset_getters_with_size.iter())
+        .map(
+            |(((name_as_string, getter_sizer), current), static_offset_getter)| {
+                quote! {
+                    #name_as_string => {
+                        #static_offset_getter
+                        let current = { #current };             // _ *current_field_start current_field_jump_amount
+                        let getter_sizer = { #getter_sizer };   // _ *current_field current_field_size
+                        [current,  getter_sizer].concat()
+                    }
                 }
-            }
-        });
-    let field_starter_clauses = parse_result.field_names
+            },
+        );
+    let field_starter_clauses = parse_result.field_labels
         .iter()
         .zip(parse_result.jumpers.iter())
         .enumerate()
-        .map(|(index,(name, jumper))| {
-            let name_as_string = name.to_string();
+        .map(|(index,(name_as_string, jumper))| {
+            // for statically-sized fields, the field start is the field and the jump distance is the size
+            let static_offset_getter = &static_offset_getters_with_size[index];
             match index {
                 0 => quote!{
-                    #name_as_string => { #jumper }
+                    #name_as_string => {
+                        #static_offset_getter
+                        #jumper
+                    }
                 },
                 not_zero => {
-                    let previous_field_name_as_string = parse_result.field_names[not_zero-1].to_string();
+                    let previous_field_name_as_string = &parse_result.field_labels[not_zero-1];
                     quote! {
                         #name_as_string => {
+                            #static_offset_getter
                             let prev =
                             [
                                 Self::get_field_start_with_jump_distance(#previous_field_name_as_string),
@@ -114,8 +190,15 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
         .field_names
         .iter()
         .cloned()
+        .zip(parse_result.field_labels.iter())
         .zip(parse_result.field_types.iter().cloned())
-        .map(|(fnm, ftp)| get_field_decoder(fnm, ftp));
+        .zip(parse_result.field_modules.iter())
+        .zip(parse_result.stored_by_pointer.iter())
+        .map(
+            |((((fnm, field_label), ftp), module), &stored_by_pointer)| {
+                get_field_decoder(fnm, field_label, ftp, module.as_ref(), stored_by_pointer)
+            },
+        );
 
     let field_names = parse_result.field_names.clone();
     let ignored_field_names = parse_result
@@ -132,33 +215,436 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
             quote! { Self { #( #field_names ,)* #( #ignored_field_names : Default::default(), )* } }
         }
         syn::Data::Struct(syn::DataStruct {
-            fields: syn::Fields::Unnamed(_),
+            fields: syn::Fields::Unnamed(fields),
             ..
         }) => {
-            let reversed_field_names = parse_result.field_names.iter().rev();
-            let defaults = vec![quote! { Default::default() }; ignored_field_names.len()];
-            quote! { Self( #( #reversed_field_names ,)* #( #defaults , )* ) }
+            let constructor_arguments = fields.unnamed.iter().enumerate().map(|(i, field)| {
+                if field_is_ignored(field) {
+                    quote! { Default::default() }
+                } else {
+                    let field_name = quote::format_ident!("field_{}", i);
+                    quote! { #field_name }
+                }
+            });
+            quote! { Self( #( #constructor_arguments ,)* ) }
         }
-        _ => unreachable!("expected a struct with named fields, or with unnamed fields"),
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        }) => quote! { Self },
+        _ => unreachable!("expected a struct"),
     };
 
     let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
     let ignored_field_types = parse_result.ignored_fields.iter().map(|f| f.ty.clone());
+    let ignored_field_types_as_strings = parse_result
+        .ignored_fields
+        .iter()
+        .map(|f| f.ty.to_token_stream().to_string())
+        .collect::<Vec<_>>();
+    // fields accessed through a custom module need not implement BFieldCodec
+    let (codec_field_labels, codec_field_types): (Vec<_>, Vec<_>) = parse_result
+        .field_labels
+        .iter()
+        .zip(parse_result.field_types.iter())
+        .zip(parse_result.field_modules.iter())
+        .filter(|(_, module)| module.is_none())
+        .map(|((label, field_type), _)| (label.to_owned(), field_type.clone()))
+        .unzip();
+    let bounded_types = [
+        codec_field_types.clone(),
+        list_element_types(&codec_field_types),
+    ]
+    .concat();
+    let generic_field_types = field_types_with_generic_parameters(&ast.generics, &bounded_types)
+        .into_iter()
+        .filter(|ty| !ignored_field_types_as_strings.contains(&ty.to_token_stream().to_string()));
     let new_where_clause = if let Some(old_where_clause) = where_clause {
         quote! {
             #old_where_clause,
             #(#ignored_field_types : Default ,)*
+            #(#generic_field_types : twenty_first::shared_math::bfield_codec::BFieldCodec ,)*
         }
     } else {
         quote! {
             where #(#ignored_field_types : Default ,)*
+            #(#generic_field_types : twenty_first::shared_math::bfield_codec::BFieldCodec ,)*
+        }
+    };
+
+    let field_labels = parse_result.field_labels.clone();
+    let get_field_chain = generate_get_field_chain(&field_labels, &parse_result.field_types);
+    let get_list_element = generate_get_list_element(&codec_field_labels, &codec_field_types);
+    let field_offsets = generate_field_offsets(&static_lengths[..field_count]);
+    // fields are stored in reverse order; list them in order of declaration
+    let fields = generate_fields(
+        &field_labels.iter().rev().cloned().collect::<Vec<_>>(),
+        &static_lengths[..field_count]
+            .iter()
+            .rev()
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    let encode_from_fields = generate_encode_from_fields(&static_lengths);
+    let build_in_memory = generate_build_in_memory(&static_lengths[..field_count]);
+    let destructure = generate_destructure(&static_lengths);
+    let validate_encoding = generate_validate_encoding(
+        &static_lengths[..field_count],
+        &parse_result.field_types[..field_count],
+        &parse_result.field_modules[..field_count],
+    );
+    // the field stored last marks the end of the object
+    let size_in_memory = match field_labels.last() {
+        Some(last_field_label) => generate_tasm_for_size_from_last_field(last_field_label),
+        None => quote! {
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(0u64))),
+            ].to_vec()
+        },
+    };
+    // fields are stored in reverse order; declare their names in order of declaration
+    let declared_field_names = field_labels.iter().rev();
+
+    let inherent_methods = [
+        generate_for_each_methods(&ast),
+        generate_setter_methods(&ast),
+    ]
+    .concat();
+    let name = &ast.ident;
+    let inherent_impl = if inherent_methods.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #name #ty_generics #new_where_clause {
+                #( #inherent_methods )*
+            }
+        }
+    };
+
+    // Following a pointer requires access to all of memory, which an iterator over the
+    // object's encoding does not give.
+    let pointer_field_label = field_labels
+        .iter()
+        .zip(parse_result.stored_by_pointer.iter())
+        .find_map(|(label, &stored_by_pointer)| stored_by_pointer.then_some(label));
+    let decode_methods = match pointer_field_label {
+        None => quote! {
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn decode_iter_with_limits<Itr: Iterator<Item=triton_vm::BFieldElement>>(
+                iterator: &mut Itr,
+                limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
+            ) -> ::std::result::Result<
+                    ::std::boxed::Box<Self>,
+                    ::tasm_lib::structure::tasm_object::TasmObjectDecodeError
+            > {
+                let mut decoded_size: usize = 0;
+                #( #field_decoders )*
+                ::std::result::Result::Ok(::std::boxed::Box::new(#self_builder))
+            }
+        },
+        Some(pointer_field_label) => {
+            let reason = format!(
+                "Field `{pointer_field_label}` of `{name}` is stored by pointer; \
+                decode the object from memory instead."
+            );
+            quote! {
+                #[allow(unused_variables)]
+                fn decode_iter_with_limits<Itr: Iterator<Item=triton_vm::BFieldElement>>(
+                    iterator: &mut Itr,
+                    limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
+                ) -> ::std::result::Result<
+                        ::std::boxed::Box<Self>,
+                        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError
+                > {
+                    ::std::result::Result::Err(
+                        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError::Unsupported(
+                            #reason.to_owned()
+                        )
+                    )
+                }
+
+                #[allow(unused_assignments, unused_mut, unused_variables)]
+                fn decode_from_memory_with_limits(
+                    memory: &::std::collections::HashMap<triton_vm::BFieldElement, triton_vm::BFieldElement>,
+                    address: triton_vm::BFieldElement,
+                    limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
+                ) -> ::std::result::Result<
+                        ::std::boxed::Box<Self>,
+                        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError
+                > {
+                    let iterator = &mut ::tasm_lib::structure::tasm_object::MemoryIter::new(memory, address);
+                    let mut decoded_size: usize = 0;
+                    #( #field_decoders )*
+                    ::std::result::Result::Ok(::std::boxed::Box::new(#self_builder))
+                }
+            }
+        }
+    };
+
+    let gen = quote! {
+        #inherent_impl
+
+        impl #impl_generics ::tasm_lib::structure::tasm_object::TasmObject
+        for #name #ty_generics #new_where_clause {
+            const FIELD_NAMES: &'static [&'static str] = &[ #( #declared_field_names ,)* ];
+
+            fn get_field( field_name : &str ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+                match field_name {
+                    #( #just_field_clauses ,)*
+                    unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+                }
+            }
+
+            fn get_field_with_size( field_name : &str ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+                match field_name {
+                    #( #field_with_size_clauses ,)*
+                    unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+                }
+            }
+
+            fn get_field_start_with_jump_distance( field_name : &str ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+                match field_name {
+                    #( #field_starter_clauses ,)*
+                    unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+                }
+            }
+
+            #get_field_chain
+
+            #get_list_element
+
+            #field_offsets
+
+            #fields
+
+            fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+                #size_in_memory
+            }
+
+            #encode_from_fields
+
+            #build_in_memory
+
+            fn destructure() -> Vec<triton_vm::instruction::LabelledInstruction> {
+                #destructure
+            }
+
+            #validate_encoding
+
+            #decode_methods
+        }
+    };
+
+    gen.into()
+}
+
+/// Enums are encoded as their discriminant, followed by the fields of the active variant
+/// in declaration order, where every dynamically-sized field is prepended by its length.
+///
+/// The fields of a variant are addressed by the name `Variant::field`, where unnamed
+/// fields are named `field_n` like for tuple structs. The field getters do not check the
+/// discriminant; use `get_variant_discriminant` for that.
+fn impl_derive_tasm_object_macro_for_enum(
+    ast: &DeriveInput,
+    data_enum: &syn::DataEnum,
+) -> TokenStream {
+    let mut just_field_clauses = vec![];
+    let mut field_with_size_clauses = vec![];
+    let mut field_starter_clauses = vec![];
+    let mut decode_clauses = vec![];
+    let mut variant_size_clauses = vec![];
+    let mut all_field_labels = vec![];
+    let mut all_field_types = vec![];
+    let mut all_field_modules = vec![];
+
+    for (discriminant, variant) in data_enum.variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let (field_names, field_types): (Vec<syn::Ident>, Vec<syn::Type>) = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let field_name = field
+                    .ident
+                    .clone()
+                    .unwrap_or_else(|| quote::format_ident!("field_{}", i));
+                (field_name, field.ty.clone())
+            })
+            .unzip();
+        if variant.fields.iter().any(field_is_stored_by_pointer) {
+            panic!("Fields of enum variants cannot be stored by pointer.");
+        }
+        let field_modules = variant.fields.iter().map(field_module).collect::<Vec<_>>();
+        let field_labels = variant
+            .fields
+            .iter()
+            .zip(field_names.iter())
+            .map(|(field, field_name)| {
+                let field_label = field_label(field, field_name);
+                format!("{variant_name}::{field_label}")
+            })
+            .collect::<Vec<_>>();
+        assert_unique_field_labels(&field_labels);
+        all_field_labels.extend(field_labels.iter().cloned());
+        all_field_types.extend(field_types.iter().cloned());
+        all_field_modules.extend(field_modules.iter().cloned());
+
+        for (index, ((label, field_type), module)) in field_labels
+            .iter()
+            .zip(field_types.iter())
+            .zip(field_modules.iter())
+            .enumerate()
+        {
+            let module = module.as_ref();
+            let jumper = generate_tasm_for_extend_field_start_with_jump_amount(field_type, module);
+            let getter = generate_tasm_for_getter_postprocess(field_type, module);
+            let sizer = generate_tasm_for_sizer_postprocess(field_type, module);
+
+            let current = match index {
+                0 => quote! {
+                    [
+                        [
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                        ].to_vec(),
+                            // _ *first_field_start
+                        { #jumper },
+                            // _ *first_field_start first_field_jump_amount
+                    ].concat()
+                },
+                not_zero => {
+                    let previous_label = &field_labels[not_zero - 1];
+                    quote! {
+                        [
+                            Self::get_field_start_with_jump_distance(#previous_label),
+                                // _ *prev_field_start prev_jump_amount
+                            [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),
+                                // _ *current_field_start
+                            { #jumper },
+                                // _ *current_field_start current_field_jump_amount
+                        ].concat()
+                    }
+                }
+            };
+
+            just_field_clauses.push(quote! {
+                #label => {
+                    let current = { #current }; // _ *current_field_start current_field_jump_amount
+                    let getter = { #getter };   // _ *current_field
+                    [current, getter].concat()
+                }
+            });
+            field_with_size_clauses.push(quote! {
+                #label => {
+                    let current = { #current };     // _ *current_field_start current_field_jump_amount
+                    let getter_sizer = { #sizer };  // _ *current_field current_field_size
+                    [current, getter_sizer].concat()
+                }
+            });
+            field_starter_clauses.push(quote! {
+                #label => { #current }
+            });
+        }
+
+        // Every variant's size is computed, as if it were the active one, and multiplied by
+        // the indicator `discriminant == i`. This avoids branching but reads memory that
+        // may belong to another variant; the results of such reads are discarded.
+        let variant_size = match field_labels.last() {
+            Some(last_field_label) => generate_tasm_for_size_from_last_field(last_field_label),
+            None => quote! {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                ].to_vec()
+            },
+        };
+        let discriminant_as_u64 = discriminant as u64;
+        variant_size_clauses.push(quote! {
+            [
+                [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1))].to_vec(),
+                    // _ *object acc *object
+                { #variant_size },
+                    // _ *object acc variant_size
+                [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST2))].to_vec(),
+                Self::get_variant_discriminant(),
+                    // _ *object acc variant_size discriminant
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(#discriminant_as_u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Eq),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Mul),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                ].to_vec(),
+                    // _ *object acc'
+            ].concat()
+        });
+
+        let field_decoders = field_names
+            .iter()
+            .cloned()
+            .zip(field_labels.iter())
+            .zip(field_types.iter().cloned())
+            .zip(field_modules.iter())
+            .map(|(((fnm, label), ftp), module)| {
+                get_field_decoder(fnm, label, ftp, module.as_ref(), false)
+            });
+        let variant_builder = match &variant.fields {
+            syn::Fields::Named(_) => quote! { Self::#variant_name { #( #field_names ,)* } },
+            syn::Fields::Unnamed(_) => quote! { Self::#variant_name( #( #field_names ,)* ) },
+            syn::Fields::Unit => quote! { Self::#variant_name },
+        };
+        decode_clauses.push(quote! {
+            #discriminant_as_u64 => {
+                #( #field_decoders )*
+                ::std::result::Result::Ok(::std::boxed::Box::new(#variant_builder))
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let (codec_field_labels, codec_field_types): (Vec<_>, Vec<_>) = all_field_labels
+        .iter()
+        .cloned()
+        .zip(all_field_types.iter().cloned())
+        .zip(all_field_modules.iter())
+        .filter(|(_, module)| module.is_none())
+        .map(|(label_and_type, _)| label_and_type)
+        .unzip();
+    let bounded_types = [
+        codec_field_types.clone(),
+        list_element_types(&codec_field_types),
+    ]
+    .concat();
+    let generic_field_types = field_types_with_generic_parameters(&ast.generics, &bounded_types);
+    let new_where_clause = if let Some(old_where_clause) = where_clause {
+        quote! {
+            #old_where_clause,
+            #(#generic_field_types : twenty_first::shared_math::bfield_codec::BFieldCodec ,)*
+        }
+    } else {
+        quote! {
+            where #(#generic_field_types : twenty_first::shared_math::bfield_codec::BFieldCodec ,)*
         }
     };
 
+    let get_field_chain = generate_get_field_chain(&all_field_labels, &all_field_types);
+    let get_list_element = generate_get_list_element(&codec_field_labels, &codec_field_types);
+    let all_static_lengths = all_field_types
+        .iter()
+        .zip(all_field_modules.iter())
+        .map(|(field_type, module)| generate_static_length(field_type, module.as_ref()))
+        .collect::<Vec<_>>();
+    let fields = generate_fields(&all_field_labels, &all_static_lengths);
+
     let name = &ast.ident;
+    let name_as_string = name.to_string();
     let gen = quote! {
         impl #impl_generics ::tasm_lib::structure::tasm_object::TasmObject
         for #name #ty_generics #new_where_clause {
+            const FIELD_NAMES: &'static [&'static str] = &[ #( #all_field_labels ,)* ];
+
+            #fields
+
             fn get_field( field_name : &str ) -> Vec<triton_vm::instruction::LabelledInstruction> {
                 match field_name {
                     #( #just_field_clauses ,)*
@@ -180,16 +666,50 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
                 }
             }
 
-            fn decode_iter<Itr: Iterator<Item=triton_vm::BFieldElement>>(
-                iterator: &mut Itr
+            #get_field_chain
+
+            #get_list_element
+
+            fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+                [
+                    [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(0u64)))].to_vec(),
+                        // _ *object 0
+                    #( #variant_size_clauses ,)*
+                        // _ *object size
+                    [
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                    ].to_vec(),
+                        // _ size
+                ].concat()
+            }
+
+            fn get_variant_discriminant() -> Vec<triton_vm::instruction::LabelledInstruction> {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                ].to_vec()
+            }
+
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn decode_iter_with_limits<Itr: Iterator<Item=triton_vm::BFieldElement>>(
+                iterator: &mut Itr,
+                limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
             ) -> ::std::result::Result<
                     ::std::boxed::Box<Self>,
-                    ::std::boxed::Box<dyn std::error::Error
-                        + ::core::marker::Send
-                        + ::core::marker::Sync>
+                    ::tasm_lib::structure::tasm_object::TasmObjectDecodeError
             > {
-                #( #field_decoders )*
-                ::std::result::Result::Ok(::std::boxed::Box::new(#self_builder))
+                let mut decoded_size = limits.check_total_size(0, 1)?;
+                let discriminant = ::tasm_lib::structure::tasm_object::next_word(iterator)?.value();
+                match discriminant {
+                    #( #decode_clauses ,)*
+                    unknown_discriminant => ::std::result::Result::Err(
+                        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError::InvalidDiscriminant {
+                            type_name: #name_as_string.to_owned(),
+                            discriminant: unknown_discriminant,
+                        }
+                    ),
+                }
             }
         }
     };
@@ -197,6 +717,597 @@ fn impl_derive_tasm_object_macro(ast: DeriveInput) -> TokenStream {
     gen.into()
 }
 
+/// Generates the method `get_field_chain`, which follows the first field name and then
+/// hands the remaining field names to the field's type. Whether that type implements
+/// `TasmObject` is only known when the generated code is compiled, which is why the
+/// dispatch goes through a `FieldChainProbe`.
+fn generate_get_field_chain(
+    field_labels: &[String],
+    field_types: &[syn::Type],
+) -> quote::__private::TokenStream {
+    if field_labels.is_empty() {
+        // without fields, there is nothing to follow; the default implementation suffices
+        return quote! {};
+    }
+
+    let chain_clauses = field_labels.iter().zip(field_types.iter()).map(|(label, field_type)| {
+        quote! {
+            #label => (&::tasm_lib::structure::tasm_object::FieldChainProbe::<#field_type>(::core::marker::PhantomData))
+                .get_field_chain_probe(remaining_field_names)
+        }
+    });
+
+    quote! {
+        fn get_field_chain( field_names : &[&str] ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+            #[allow(unused_imports)]
+            use ::tasm_lib::structure::tasm_object::FieldChainFallback as _;
+            #[allow(unused_imports)]
+            use ::tasm_lib::structure::tasm_object::FieldChainViaTasmObject as _;
+
+            let Some((first_field_name, remaining_field_names)) = field_names.split_first() else {
+                return vec![];
+            };
+            let first_field = Self::get_field(first_field_name); // _ *first_field
+            if remaining_field_names.is_empty() {
+                return first_field;
+            }
+
+            let remaining_fields = match *first_field_name {
+                #( #chain_clauses ,)*
+                unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+            };
+            [first_field, remaining_fields].concat()
+        }
+    }
+}
+
+/// Generates tasm code that computes the size of an object from the end of the field that
+/// is stored last.
+///
+/// BEFORE: _ *object
+///
+/// AFTER: _ size
+fn generate_tasm_for_size_from_last_field(last_field_label: &str) -> quote::__private::TokenStream {
+    quote! {
+        [
+            [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0))].to_vec(),
+                // _ *object *object
+            Self::get_field_start_with_jump_distance(#last_field_label),
+                // _ *object *last_field_start last_field_jump_amount
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    // _ *object *end
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Mul),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    // _ size
+            ].to_vec(),
+        ].concat()
+    }
+}
+
+/// Generates the method `fields` from the fields' labels and static lengths.
+fn generate_fields(
+    field_labels: &[String],
+    static_lengths: &[quote::__private::TokenStream],
+) -> quote::__private::TokenStream {
+    quote! {
+        fn fields() -> Vec<(String, ::core::option::Option<usize>)> {
+            vec![ #( (#field_labels.to_owned(), #static_lengths) ,)* ]
+        }
+    }
+}
+
+/// Generates the method `field_offsets`, which returns the offsets of all fields in order
+/// of declaration if all of them are statically sized. The static lengths of the fields are
+/// given in order of storage.
+fn generate_field_offsets(
+    static_lengths: &[quote::__private::TokenStream],
+) -> quote::__private::TokenStream {
+    quote! {
+        #[allow(unused_assignments, unused_mut)]
+        fn field_offsets() -> ::core::option::Option<Vec<usize>> {
+            let mut offsets = vec![];
+            let mut offset = 0;
+            #(
+                offsets.push(offset);
+                offset += #static_lengths?;
+            )*
+            offsets.reverse();
+            ::core::option::Option::Some(offsets)
+        }
+    }
+}
+
+/// Generates code that returns early with tasm code that adds the static offset of the
+/// field to the object pointer, along with the field's size if a static length is given,
+/// if all fields are statically sized. The field is given by its index in order of
+/// declaration.
+///
+/// BEFORE: _ *object
+///
+/// AFTER: _ *field (field_size)
+fn generate_static_offset_getter(
+    field_index: usize,
+    static_length: Option<&quote::__private::TokenStream>,
+) -> quote::__private::TokenStream {
+    let push_size = static_length.map(|static_length| {
+        quote! {
+            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(#static_length.unwrap() as u64))),
+        }
+    });
+    quote! {
+        if let ::core::option::Option::Some(field_offsets) = Self::field_offsets() {
+            return [
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(field_offsets[#field_index] as u64))),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                #push_size
+            ].to_vec();
+        }
+    }
+}
+
+/// Generates the method `encode_from_fields`, which copies the fields, given in order of
+/// storage, to the destination and prepends the dynamically-sized ones with their size.
+/// The fields are given by their static lengths, as generated by `generate_static_length`.
+fn generate_encode_from_fields(
+    static_lengths: &[quote::__private::TokenStream],
+) -> quote::__private::TokenStream {
+    if static_lengths.is_empty() {
+        return quote! {
+            fn encode_from_fields(
+                _library : &mut ::tasm_lib::library::Library,
+            ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+                vec![]
+            }
+        };
+    }
+
+    let field_encoders = static_lengths.iter().map(|static_length| {
+        quote! {
+            if #static_length.is_some() {
+                ::std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
+            } else {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::WriteMem(::triton_vm::op_stack::NumberOfWords::N1)),
+                ].to_vec()
+            },
+                // _ *field field_size *field_destination
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST2)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    // _ *field field_size *field_destination *next_destination
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST3)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST2)),
+                    // _ *next_destination *field *field_destination field_size
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(memcpy.clone())),
+                    // _ *next_destination
+            ].to_vec()
+        }
+    });
+
+    quote! {
+        fn encode_from_fields(
+            library : &mut ::tasm_lib::library::Library,
+        ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+            let memcpy = library.import(::std::boxed::Box::new(::tasm_lib::memory::memcpy::MemCpy));
+            [ #( #field_encoders ,)* ].concat()
+        }
+    }
+}
+
+/// Generates the method `build_in_memory`, which copies the fields, given in order of
+/// storage, to the first free address of the dynamic allocator, and then allocates the
+/// written words. Unlike `encode_from_fields`, the pointer to the object is kept below the
+/// pointer to the next destination, so the number of fields is not limited by the depth of
+/// the accessible stack.
+fn generate_build_in_memory(
+    static_lengths: &[quote::__private::TokenStream],
+) -> quote::__private::TokenStream {
+    let field_builders = static_lengths.iter().map(|static_length| {
+        quote! {
+            if #static_length.is_some() {
+                ::std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
+            } else {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST2)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::WriteMem(::triton_vm::op_stack::NumberOfWords::N1)),
+                ].to_vec()
+            },
+                // _ *field field_size *object *field_destination
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST3)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    // _ *field field_size *object *field_destination *next_destination
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST3)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST2)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST4)),
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST2)),
+                    // _ *object *next_destination *field *field_destination field_size
+                triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(memcpy.clone())),
+                    // _ *object *next_destination
+            ].to_vec()
+        }
+    });
+
+    quote! {
+        #[allow(unused_variables)]
+        fn build_in_memory(
+            library : &mut ::tasm_lib::library::Library,
+        ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+            let dyn_malloc = library.import(::std::boxed::Box::new(::tasm_lib::memory::dyn_malloc::DynMalloc));
+            let memcpy = library.import(::std::boxed::Box::new(::tasm_lib::memory::memcpy::MemCpy));
+            [
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(0u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(dyn_malloc.clone())),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                        // _ [*field field_size]* *object *destination
+                ].to_vec(),
+                #( #field_builders ,)*
+                    // _ *object *end
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Mul),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                        // _ *object size
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(dyn_malloc)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Eq),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Assert),
+                        // _ *object
+                ].to_vec(),
+            ].concat()
+        }
+    }
+}
+
+/// Generates tasm code that walks over all fields in order of storage, leaving a pointer to
+/// every field, along with its size if that size is not static, on the stack.
+///
+/// BEFORE: _ *object
+///
+/// AFTER: _ [*field (field_size)]*
+fn generate_destructure(
+    static_lengths: &[quote::__private::TokenStream],
+) -> quote::__private::TokenStream {
+    let field_walkers = static_lengths.iter().map(|static_length| {
+        quote! {
+            if let Some(size) = #static_length {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                        // _ *field *next_field_start
+                ].to_vec()
+            } else {
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(2u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                        // _ *field field_size
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                        // _ *field field_size *next_field_start
+                ].to_vec()
+            }
+        }
+    });
+
+    quote! {
+        [
+            #( #field_walkers ,)*
+            [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1))].to_vec(),
+        ].concat()
+    }
+}
+
+/// Generates the method `validate_encoding`, which walks over all fields in order of
+/// storage. The size indicator of every dynamically-sized field is checked to be a u32 and
+/// to agree with the size of the field, as reported by the field type's own
+/// `validate_encoding`. Fields accessed through a custom module are considered opaque.
+fn generate_validate_encoding(
+    static_lengths: &[quote::__private::TokenStream],
+    field_types: &[syn::Type],
+    field_modules: &[Option<syn::Path>],
+) -> quote::__private::TokenStream {
+    let field_validators = static_lengths
+        .iter()
+        .zip(field_types.iter())
+        .zip(field_modules.iter())
+        .map(|((static_length, field_type), module)| {
+            let validate_field = match module {
+                Some(_) => quote! {
+                    [
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                    ].to_vec()
+                },
+                None => quote! {
+                    (&::tasm_lib::structure::tasm_object::ValidateEncodingProbe::<#field_type>(::core::marker::PhantomData))
+                        .validate_encoding_probe(library)
+                },
+            };
+            quote! {
+                if let Some(size) = #static_length {
+                    [
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64))),
+                        triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                            // _ region_size *object *next_field_start
+                    ].to_vec()
+                } else {
+                    [
+                        [
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(2u64))),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                                // _ region_size *object field_size *field
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST1)),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Split),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(0u64))),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Eq),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Assert),
+                                // _ region_size *object field_size *field
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST2)),
+                                // _ region_size *object field_size *field *field field_size
+                        ].to_vec(),
+                        { #validate_field },
+                            // _ region_size *object field_size *field validated_field_size
+                        [
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST2)),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Eq),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Assert),
+                            triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                                // _ region_size *object *next_field_start
+                        ].to_vec(),
+                    ].concat()
+                }
+            }
+        });
+
+    quote! {
+        fn validate_encoding(
+            library : &mut ::tasm_lib::library::Library,
+        ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+            #[allow(unused_imports)]
+            use ::tasm_lib::structure::tasm_object::ValidateEncodingFallback as _;
+            #[allow(unused_imports)]
+            use ::tasm_lib::structure::tasm_object::ValidateEncodingViaTasmObject as _;
+
+            [
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                        // _ region_size *object *first_field_start
+                ].to_vec(),
+                #( #field_validators ,)*
+                    // _ region_size *object *end
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Mul),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                        // _ region_size size
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Dup(::triton_vm::op_stack::OpStackElement::ST0)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST2)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Lt),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Assert),
+                        // _ size
+                ].to_vec(),
+            ].concat()
+        }
+    }
+}
+
+/// Generates the method `get_list_element` for all fields of type `Vec<T>`. The element
+/// size is statically known or not depending on `T`, which is resolved when the generated
+/// code is compiled.
+fn generate_get_list_element(
+    field_labels: &[String],
+    field_types: &[syn::Type],
+) -> quote::__private::TokenStream {
+    let element_size_clauses = field_labels
+        .iter()
+        .zip(field_types.iter())
+        .filter_map(|(label, field_type)| {
+            let element_type = list_element_type(field_type)?;
+            Some(quote! {
+                #label => <#element_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
+            })
+        })
+        .collect::<Vec<_>>();
+    if element_size_clauses.is_empty() {
+        // without list fields, the default implementation suffices
+        return quote! {};
+    }
+
+    quote! {
+        fn get_list_element(
+            field_name : &str,
+            library : &mut ::tasm_lib::library::Library,
+        ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+            let element_size = match field_name {
+                #( #element_size_clauses ,)*
+                other_field_name => panic!("Field `{other_field_name}` is not a list."),
+            };
+            let get_list_element = library.import(::std::boxed::Box::new(
+                ::tasm_lib::structure::get_list_element::GetListElement { element_size },
+            ));
+            [
+                [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1))].to_vec(),
+                    // _ index *object
+                Self::get_field(field_name),
+                    // _ index *list
+                [
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),
+                    triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(get_list_element)),
+                ].to_vec(),
+                    // _ *element
+            ].concat()
+        }
+    }
+}
+
+/// Generates a method `for_each_<field>` for every field annotated with
+/// `#[tasm_object(for_each)]`, which calls an inner function on every element of the list.
+fn generate_for_each_methods(ast: &DeriveInput) -> Vec<quote::__private::TokenStream> {
+    let syn::Data::Struct(data_struct) = &ast.data else {
+        return vec![];
+    };
+
+    data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| parse_field_attributes(field).for_each)
+        .map(|(i, field)| {
+            let field_name = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| quote::format_ident!("field_{}", i));
+            let field_label = field_label(field, &field_name);
+            let Some(element_type) = list_element_type(&field.ty) else {
+                panic!("Cannot iterate over field `{field_label}`, which is not a `Vec<T>`.");
+            };
+            let method_name = quote::format_ident!("for_each_{}", field_name);
+            let doc = format!(
+                "Returns tasm code that calls the given function on a pointer to every element \
+                of the field `{field_label}`.\n\nBEFORE: _ *object\n\nAFTER: _\n\nSee \
+                `ForEachListElement` for the stack signature of the inner function."
+            );
+            quote! {
+                #[doc = #doc]
+                pub fn #method_name(
+                    f: ::tasm_lib::list::higher_order::inner_function::InnerFunction,
+                    library: &mut ::tasm_lib::library::Library,
+                ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+                    let for_each = library.import(::std::boxed::Box::new(
+                        ::tasm_lib::structure::for_each_list_element::ForEachListElement {
+                            element_size: <#element_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length(),
+                            f,
+                        },
+                    ));
+                    [
+                        <Self as ::tasm_lib::structure::tasm_object::TasmObject>::get_field(#field_label),
+                            // _ *list
+                        [triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Call(for_each))].to_vec(),
+                            // _
+                    ].concat()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the methods `set_<field>` for all fields marked `#[tasm_object(setter)]`.
+/// Whether the field is statically sized is resolved when the generated code is compiled.
+fn generate_setter_methods(ast: &DeriveInput) -> Vec<quote::__private::TokenStream> {
+    let syn::Data::Struct(data_struct) = &ast.data else {
+        return vec![];
+    };
+
+    data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| parse_field_attributes(field).setter)
+        .map(|(i, field)| {
+            let field_name = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| quote::format_ident!("field_{}", i));
+            let field_label = field_label(field, &field_name);
+            let static_length = generate_static_length(&field.ty, field_module(field).as_ref());
+            let method_name = quote::format_ident!("set_{}", field_name);
+            let doc = format!(
+                "Returns tasm code that overwrites the field `{field_label}` of the object in \
+                memory with the value on the stack.\n\nBEFORE: _ [value] *object\n\nAFTER: _"
+            );
+            quote! {
+                #[doc = #doc]
+                pub fn #method_name() -> Vec<triton_vm::instruction::LabelledInstruction> {
+                    let Some(size) = #static_length else {
+                        panic!("Cannot set field `{}`, which is not statically sized.", #field_label);
+                    };
+                    [
+                        <Self as ::tasm_lib::structure::tasm_object::TasmObject>::get_field(#field_label),
+                            // _ [value] *field
+                        ::tasm_lib::memory::write_words_to_memory_pop_pointer(size),
+                            // _
+                    ].concat()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns `T` if the given type is `Vec<T>`, and `None` otherwise.
+fn list_element_type(field_type: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(type_path) = field_type else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Vec" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &last_segment.arguments else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(syn::GenericArgument::Type(element_type)) if arguments.args.len() == 1 => {
+            Some(element_type.clone())
+        }
+        _ => None,
+    }
+}
+
+fn list_element_types(field_types: &[syn::Type]) -> Vec<syn::Type> {
+    field_types.iter().filter_map(list_element_type).collect()
+}
+
+/// Returns the field types that mention any of the generic type or const parameters. The
+/// emitted impl requires these types to implement `BFieldCodec`, which saves the user from
+/// having to add the corresponding bounds to the type definition.
+fn field_types_with_generic_parameters(
+    generics: &syn::Generics,
+    field_types: &[syn::Type],
+) -> Vec<syn::Type> {
+    let type_parameters = generics
+        .type_params()
+        .map(|type_parameter| type_parameter.ident.to_string())
+        .chain(
+            generics
+                .const_params()
+                .map(|const_parameter| const_parameter.ident.to_string()),
+        )
+        .collect::<Vec<_>>();
+    field_types
+        .iter()
+        .filter(|field_type| {
+            field_type
+                .to_token_stream()
+                .to_string()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| type_parameters.iter().any(|tp| tp == word))
+        })
+        .cloned()
+        .collect()
+}
+
 fn generate_parse_result(ast: &DeriveInput) -> ParseResult {
     match &ast.data {
         syn::Data::Struct(syn::DataStruct {
@@ -207,25 +1318,173 @@ fn generate_parse_result(ast: &DeriveInput) -> ParseResult {
             fields: syn::Fields::Unnamed(fields),
             ..
         }) => generate_tokens_for_struct_with_unnamed_fields(fields),
-        _ => panic!("expected a struct with named fields, or with unnamed fields"),
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        }) => ParseResult {
+            field_names: vec![],
+            field_labels: vec![],
+            field_types: vec![],
+            getters: vec![],
+            sizers: vec![],
+            jumpers: vec![],
+            field_modules: vec![],
+            stored_by_pointer: vec![],
+            ignored_fields: vec![],
+        },
+        _ => panic!("expected a struct"),
     }
 }
 
-fn field_is_ignored(field: &syn::Field) -> bool {
+/// The arguments of the `#[tasm_object(..)]` attribute on a field.
+#[derive(Default)]
+struct FieldAttributes {
+    /// The field is invisible to the VM and set to its default value when decoding.
+    ignore: bool,
+
+    /// The module supplying the tasm code for accessing the field, as well as its decoder.
+    with: Option<syn::Path>,
+
+    /// Generate a method `for_each_<field>` for iterating over the elements of the field,
+    /// which must be of type `Vec<T>`.
+    for_each: bool,
+
+    /// The object holds a pointer to the field's encoding instead of the encoding itself.
+    by_pointer: bool,
+
+    /// The name under which the field is accessible, if it differs from its identifier.
+    rename: Option<String>,
+
+    /// Generate a method `set_<field>` for overwriting the field, which must be statically
+    /// sized.
+    setter: bool,
+}
+
+fn parse_field_attributes(field: &syn::Field) -> FieldAttributes {
+    let mut field_attributes = FieldAttributes::default();
     for attribute in field.attrs.iter() {
         if !attribute.path().is_ident("tasm_object") {
             continue;
         }
         attribute
             .parse_nested_meta(|meta| match meta.path.get_ident() {
-                Some(ident) if ident == "ignore" => Ok(()),
+                Some(ident) if ident == "ignore" => {
+                    field_attributes.ignore = true;
+                    Ok(())
+                }
+                Some(ident) if ident == "with" => {
+                    let module: syn::LitStr = meta.value()?.parse()?;
+                    field_attributes.with = Some(module.parse()?);
+                    Ok(())
+                }
+                Some(ident) if ident == "for_each" => {
+                    field_attributes.for_each = true;
+                    Ok(())
+                }
+                Some(ident) if ident == "by_pointer" => {
+                    field_attributes.by_pointer = true;
+                    Ok(())
+                }
+                Some(ident) if ident == "setter" => {
+                    field_attributes.setter = true;
+                    Ok(())
+                }
+                Some(ident) if ident == "rename" => {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    field_attributes.rename = Some(name.value());
+                    Ok(())
+                }
                 Some(ident) => Err(meta.error(format!("Unknown identifier \"{ident}\"."))),
                 _ => Err(meta.error("Expected an identifier.")),
             })
             .unwrap();
-        return true;
     }
-    false
+    if field_attributes.ignore && field_attributes.with.is_some() {
+        panic!("A field cannot be both ignored and accessed with a custom module.");
+    }
+    if field_attributes.ignore && field_attributes.for_each {
+        panic!("A field cannot be both ignored and iterated over.");
+    }
+    if field_attributes.ignore && field_attributes.by_pointer {
+        panic!("A field cannot be both ignored and stored by pointer.");
+    }
+    if field_attributes.with.is_some() && field_attributes.by_pointer {
+        panic!("A field stored by pointer cannot be accessed with a custom module.");
+    }
+    if field_attributes.ignore && field_attributes.rename.is_some() {
+        panic!("A field cannot be both ignored and renamed.");
+    }
+    if field_attributes.ignore && field_attributes.setter {
+        panic!("A field cannot be both ignored and set.");
+    }
+    if field_attributes.by_pointer && field_attributes.setter {
+        panic!("A field stored by pointer cannot be set; set the fields of the pointee instead.");
+    }
+    field_attributes
+}
+
+/// Fields of type `PhantomData` take up no space in the encoding and are ignored
+/// implicitly.
+fn field_is_ignored(field: &syn::Field) -> bool {
+    parse_field_attributes(field).ignore || type_is_phantom_data(&field.ty)
+}
+
+fn type_is_phantom_data(field_type: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = field_type else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "PhantomData")
+}
+
+/// The module supplying the tasm code for accessing the field, if any. Fields stored by
+/// pointer are accessed through `StoredByPointer`.
+fn field_module(field: &syn::Field) -> Option<syn::Path> {
+    let field_attributes = parse_field_attributes(field);
+    if field_attributes.by_pointer {
+        let field_type = &field.ty;
+        return Some(syn::parse_quote! {
+            ::tasm_lib::structure::tasm_object::StoredByPointer::<#field_type>
+        });
+    }
+    field_attributes.with
+}
+
+fn field_is_stored_by_pointer(field: &syn::Field) -> bool {
+    parse_field_attributes(field).by_pointer
+}
+
+/// The name under which the field is accessible: its identifier, or `field_n` for unnamed
+/// fields, unless the field is renamed.
+fn field_label(field: &syn::Field, field_name: &syn::Ident) -> String {
+    parse_field_attributes(field)
+        .rename
+        .unwrap_or_else(|| field_name.to_string())
+}
+
+fn assert_unique_field_labels(field_labels: &[String]) {
+    for (i, field_label) in field_labels.iter().enumerate() {
+        if field_labels[..i].contains(field_label) {
+            panic!("Several fields are accessible under the name `{field_label}`.");
+        }
+    }
+}
+
+/// Returns an expression for the static length of the field, or `None` if the field is
+/// dynamically sized.
+fn generate_static_length(
+    field_type: &syn::Type,
+    field_module: Option<&syn::Path>,
+) -> quote::__private::TokenStream {
+    match field_module {
+        Some(module) => quote! { #module::static_length() },
+        None => quote! {
+            <#field_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
+        },
+    }
 }
 
 fn generate_tokens_for_struct_with_named_fields(fields: &syn::FieldsNamed) -> ParseResult {
@@ -242,25 +1501,31 @@ fn generate_tokens_for_struct_with_named_fields(fields: &syn::FieldsNamed) -> Pa
         .clone()
         .map(|field| field.ident.as_ref().unwrap().to_owned());
     let field_names_list = field_names.clone().collect::<std::vec::Vec<_>>();
+    let field_labels = named_fields
+        .clone()
+        .zip(field_names_list.iter())
+        .map(|(field, field_name)| field_label(field, field_name))
+        .collect::<std::vec::Vec<_>>();
 
     let getters = named_fields
         .clone()
         .enumerate()
         .map(|(i, _f)| {
-            generate_tasm_for_getter_postprocess(
-                &named_fields.clone().cloned().collect::<Vec<_>>()[i].ty,
-            )
+            let field = &named_fields.clone().cloned().collect::<Vec<_>>()[i];
+            generate_tasm_for_getter_postprocess(&field.ty, field_module(field).as_ref())
         })
         .collect::<std::vec::Vec<_>>();
 
     let sizers = named_fields
         .clone()
-        .map(|f| generate_tasm_for_sizer_postprocess(&f.ty))
+        .map(|f| generate_tasm_for_sizer_postprocess(&f.ty, field_module(f).as_ref()))
         .collect::<std::vec::Vec<_>>();
 
     let jumpers = named_fields
         .clone()
-        .map(|f| generate_tasm_for_extend_field_start_with_jump_amount(&f.ty))
+        .map(|f| {
+            generate_tasm_for_extend_field_start_with_jump_amount(&f.ty, field_module(f).as_ref())
+        })
         .collect::<std::vec::Vec<_>>();
 
     let field_types = named_fields
@@ -268,12 +1533,25 @@ fn generate_tokens_for_struct_with_named_fields(fields: &syn::FieldsNamed) -> Pa
         .map(|f| f.ty.clone())
         .collect::<std::vec::Vec<_>>();
 
+    let field_modules = named_fields
+        .clone()
+        .map(field_module)
+        .collect::<std::vec::Vec<_>>();
+
+    let stored_by_pointer = named_fields
+        .clone()
+        .map(field_is_stored_by_pointer)
+        .collect::<std::vec::Vec<_>>();
+
     ParseResult {
         field_names: field_names_list,
+        field_labels,
         field_types,
         getters,
         sizers,
         jumpers,
+        field_modules,
+        stored_by_pointer,
         ignored_fields,
     }
 }
@@ -284,7 +1562,13 @@ fn generate_tokens_for_struct_with_named_fields(fields: &syn::FieldsNamed) -> Pa
 ///
 /// The complication arises from *field_start == *field when the field size is statically
 /// known, but otherwise *field_start+1 == *field.
-fn generate_tasm_for_getter_postprocess(field_type: &syn::Type) -> quote::__private::TokenStream {
+fn generate_tasm_for_getter_postprocess(
+    field_type: &syn::Type,
+    field_module: Option<&syn::Path>,
+) -> quote::__private::TokenStream {
+    if let Some(module) = field_module {
+        return quote! { #module::getter() };
+    }
     quote! {
         if <#field_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
             [
@@ -306,7 +1590,13 @@ fn generate_tasm_for_getter_postprocess(field_type: &syn::Type) -> quote::__priv
 ///
 /// The complication arises from *field_start == *field when the field size is statically
 /// known, but otherwise *field_start+1 == *field.
-fn generate_tasm_for_sizer_postprocess(field_type: &syn::Type) -> quote::__private::TokenStream {
+fn generate_tasm_for_sizer_postprocess(
+    field_type: &syn::Type,
+    field_module: Option<&syn::Path>,
+) -> quote::__private::TokenStream {
+    if let Some(module) = field_module {
+        return quote! { #module::sizer() };
+    }
     quote! {
         if <#field_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
             std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
@@ -328,7 +1618,11 @@ fn generate_tasm_for_sizer_postprocess(field_type: &syn::Type) -> quote::__priva
 ///  - leaves the stack in the state _ *field_start jump_amount
 fn generate_tasm_for_extend_field_start_with_jump_amount(
     field_type: &syn::Type,
+    field_module: Option<&syn::Path>,
 ) -> quote::__private::TokenStream {
+    if let Some(module) = field_module {
+        return quote! { #module::jumper() };
+    }
     quote! {
         if let Some(size) = <#field_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
             [
@@ -348,41 +1642,43 @@ fn generate_tasm_for_extend_field_start_with_jump_amount(
 }
 
 fn generate_tokens_for_struct_with_unnamed_fields(fields: &syn::FieldsUnnamed) -> ParseResult {
-    let fields_iterator = fields.unnamed.iter().rev();
-    let ignored_fields = fields_iterator
+    // fields are numbered by their position in the declaration, ignored fields included
+    let numbered_fields = fields.unnamed.iter().enumerate().rev();
+    let ignored_fields = numbered_fields
         .clone()
-        .filter(|f| field_is_ignored(f))
-        .cloned()
+        .filter(|(_, f)| field_is_ignored(f))
+        .map(|(_, f)| f.clone())
         .collect::<Vec<_>>();
+    let fields_iterator = numbered_fields
+        .clone()
+        .filter(|(_, f)| !field_is_ignored(f))
+        .map(|(_, f)| f);
 
-    let field_count = fields_iterator.clone().count();
-    let field_names = fields_iterator
+    let field_names = numbered_fields
+        .filter(|(_, f)| !field_is_ignored(f))
+        .map(|(i, _f)| quote::format_ident!("field_{}", i))
+        .collect::<Vec<_>>();
+    let field_labels = fields_iterator
         .clone()
-        .filter(|f| !field_is_ignored(f))
-        .enumerate()
-        .map(|(i, _f)| quote::format_ident!("field_{}", field_count - 1 - i))
+        .zip(field_names.iter())
+        .map(|(field, field_name)| field_label(field, field_name))
         .collect::<Vec<_>>();
 
     let getters = fields_iterator
         .clone()
-        .enumerate()
-        .map(|(i, _f)| {
-            generate_tasm_for_getter_postprocess(
-                &fields_iterator.clone().nth(i).cloned().unwrap().ty,
-            )
-        })
+        .map(|f| generate_tasm_for_getter_postprocess(&f.ty, field_module(f).as_ref()))
         .collect::<Vec<_>>();
 
     let sizers = fields_iterator
         .clone()
-        .enumerate()
-        .map(|(_i, f)| generate_tasm_for_sizer_postprocess(&f.ty))
+        .map(|f| generate_tasm_for_sizer_postprocess(&f.ty, field_module(f).as_ref()))
         .collect::<Vec<_>>();
 
     let jumpers = fields_iterator
         .clone()
-        .enumerate()
-        .map(|(_i, f)| generate_tasm_for_extend_field_start_with_jump_amount(&f.ty))
+        .map(|f| {
+            generate_tasm_for_extend_field_start_with_jump_amount(&f.ty, field_module(f).as_ref())
+        })
         .collect::<Vec<_>>();
 
     let field_types = fields_iterator
@@ -390,27 +1686,62 @@ fn generate_tokens_for_struct_with_unnamed_fields(fields: &syn::FieldsUnnamed) -
         .map(|field| field.ty.clone())
         .collect::<Vec<_>>();
 
+    let field_modules = fields_iterator
+        .clone()
+        .map(field_module)
+        .collect::<Vec<_>>();
+
+    let stored_by_pointer = fields_iterator
+        .clone()
+        .map(field_is_stored_by_pointer)
+        .collect::<Vec<_>>();
+
     ParseResult {
         field_names,
+        field_labels,
         field_types,
         getters,
         sizers,
         jumpers,
+        field_modules,
+        stored_by_pointer,
         ignored_fields,
     }
 }
 
+/// Generates code that decodes one field, after checking its size against the decoding
+/// limits. The variables `iterator`, `limits`, and `decoded_size` must be in scope, as
+/// well as `memory` if the field is stored by pointer.
 fn get_field_decoder(
     field_name: syn::Ident,
+    field_label: &str,
     field_type: syn::Type,
+    field_module: Option<&syn::Path>,
+    stored_by_pointer: bool,
 ) -> quote::__private::TokenStream {
+    let static_length = generate_static_length(&field_type, field_module);
+    let decode = match field_module {
+        Some(module) if stored_by_pointer => quote! {
+            #module::decode_from_memory_with_limits(memory, &sequence, limits)?
+        },
+        Some(module) => quote! {
+            #module::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?
+        },
+        None => quote! {
+            twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?
+        },
+    };
     quote! {
-        let length : usize = if let Some(static_length) = <#field_type as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+        let length : usize = if let Some(static_length) = #static_length {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length).map(|_| iterator.next().unwrap()).collect::<Vec<_>>();
-        let #field_name : #field_type = *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+        decoded_size = limits.check_field_size(#field_label, length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
+        let #field_name : #field_type = *#decode;
     }
 }
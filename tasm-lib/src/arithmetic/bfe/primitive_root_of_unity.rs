@@ -398,7 +398,9 @@ mod tests {
     use super::*;
     use crate::execute_with_terminal_state;
     use crate::linker::link_for_isolated_run;
-    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_helpers::{
+        assert_assertion_failure, test_rust_equivalence_given_complete_state,
+    };
     use crate::traits::closure::ShadowedClosure;
     use crate::traits::rust_shadow::RustShadow;
 
@@ -487,9 +489,10 @@ mod tests {
             );
 
             assert!(
-                rust_result.is_err() && tvm_result.is_err(),
+                rust_result.is_err(),
                 "Test case: primitive root of order {order} must fail since it does not exist"
             );
+            assert_assertion_failure(tvm_result);
         }
     }
 }
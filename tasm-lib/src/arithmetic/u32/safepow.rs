@@ -1,4 +1,5 @@
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
 use triton_vm::{triton_asm, BFieldElement};
 
 use crate::data_type::DataType;
@@ -189,19 +190,64 @@ impl Closure for Safepow {
 
         vec![zero_pow_zero]
     }
+
+    fn failing_initial_states(&self) -> Vec<(Vec<BFieldElement>, InstructionError)> {
+        [
+            (2, 32),
+            (3, 21),
+            (4, 16),
+            (5, 14),
+            (6, 13),
+            (7, 12),
+            (8, 11),
+            (9, 11),
+            (10, 10),
+            (11, 10),
+            (12, 10),
+            (u32::MAX, 2),
+            (u32::MAX, 3),
+            (u32::MAX, 4),
+            (u32::MAX, 5),
+            (u32::MAX, 6),
+            (u32::MAX, 7),
+            (u32::MAX, 8),
+            (u32::MAX, 9),
+            (1 << 16, 2),
+            (1 << 16, 3),
+            (1 << 16, 4),
+            (1 << 16, 5),
+            (1 << 16, 6),
+            (1 << 16, 7),
+            (1 << 16, 8),
+            (1 << 8, 4),
+            (1 << 8, 8),
+            (1 << 8, 16),
+            (1 << 8, 32),
+        ]
+        .into_iter()
+        .map(|(base, exp): (u32, u32)| {
+            let init_stack = [
+                empty_stack(),
+                vec![
+                    BFieldElement::new(base as u64),
+                    BFieldElement::new(exp as u64),
+                ],
+            ]
+            .concat();
+            (init_stack, InstructionError::AssertionFailed)
+        })
+        .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::cell::RefCell;
-    use std::collections::HashMap;
-    use std::rc::Rc;
-    use triton_vm::{NonDeterminism, Program};
+    use triton_vm::NonDeterminism;
 
     use super::*;
-    use crate::execute_with_terminal_state;
-    use crate::linker::link_for_isolated_run;
-    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_helpers::{
+        test_rust_equivalence_given_complete_state, test_rust_tasm_equivalence_expect_failure,
+    };
     use crate::traits::closure::ShadowedClosure;
     use crate::traits::rust_shadow::RustShadow;
 
@@ -270,75 +316,16 @@ mod tests {
 
     #[test]
     fn u32_pow_negative_test() {
-        let safe_pow = Safepow;
-
-        let code = link_for_isolated_run(Rc::new(RefCell::new(safe_pow)), 0);
-
-        for (base, exp) in [
-            (2, 32),
-            (3, 21),
-            (4, 16),
-            (5, 14),
-            (6, 13),
-            (7, 12),
-            (8, 11),
-            (9, 11),
-            (10, 10),
-            (11, 10),
-            (12, 10),
-            (u32::MAX, 2),
-            (u32::MAX, 3),
-            (u32::MAX, 4),
-            (u32::MAX, 5),
-            (u32::MAX, 6),
-            (u32::MAX, 7),
-            (u32::MAX, 8),
-            (u32::MAX, 9),
-            (1 << 16, 2),
-            (1 << 16, 3),
-            (1 << 16, 4),
-            (1 << 16, 5),
-            (1 << 16, 6),
-            (1 << 16, 7),
-            (1 << 16, 8),
-            (1 << 8, 4),
-            (1 << 8, 8),
-            (1 << 8, 16),
-            (1 << 8, 32),
-        ] {
-            let init_stack = [
-                empty_stack(),
-                vec![
-                    BFieldElement::new(base as u64),
-                    BFieldElement::new(exp as u64),
-                ],
-            ]
-            .concat();
-            // run rust shadow
-            let rust_result = std::panic::catch_unwind(|| {
-                let mut rust_stack = init_stack.clone();
-                ShadowedClosure::new(Safepow).rust_shadow_wrapper(
-                    &[],
-                    &NonDeterminism::new(vec![]),
-                    &mut rust_stack,
-                    &mut HashMap::default(),
-                    &mut None,
-                )
-            });
-
-            // Run on Triton
-            let program = Program::new(&code);
-            let tvm_result = execute_with_terminal_state(
-                &program,
-                &[],
+        let closure = ShadowedClosure::new(Safepow);
+        for (init_stack, expected_error) in Safepow.failing_initial_states() {
+            test_rust_tasm_equivalence_expect_failure(
+                &closure,
                 &init_stack,
-                &NonDeterminism::new(vec![]),
-                None,
-            );
-
-            assert!(
-                rust_result.is_err() && tvm_result.is_err(),
-                "Test case: {base}**{exp} failed to fail"
+                &[],
+                &NonDeterminism::default(),
+                &None,
+                0,
+                expected_error,
             );
         }
     }
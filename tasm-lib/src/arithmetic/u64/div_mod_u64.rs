@@ -71,7 +71,8 @@ impl DeprecatedSnippet for DivModU64 {
         let sub_u32 = library.import(Box::new(Safesub));
         let leading_zeros_u64 = library.import(Box::new(LeadingZerosU64));
         let add_u32 = library.import(Box::new(Safeadd));
-        let mem_address_for_spilled_divisor = library.kmalloc(2);
+        let mem_address_for_spilled_divisor =
+            library.kmalloc(&format!("{entrypoint}_spilled_divisor"), 2);
         let last_mem_address_for_spilled_divisor =
             mem_address_for_spilled_divisor + BFieldElement::one();
 
@@ -194,8 +194,9 @@ mod tests {
     use num::Zero;
     use twenty_first::shared_math::bfield_codec::BFieldCodec;
 
+    use crate::execute_with_execution_state_deprecated;
     use crate::test_helpers::{
-        test_rust_equivalence_given_input_values_deprecated,
+        assert_assertion_failure, test_rust_equivalence_given_input_values_deprecated,
         test_rust_equivalence_multiple_deprecated,
     };
 
@@ -206,7 +207,6 @@ mod tests {
         test_rust_equivalence_multiple_deprecated(&SafeMulU64, true);
     }
 
-    #[should_panic]
     #[test]
     fn overflow_test_1() {
         // Crash because (rhs_hi * lhs_hi) != 0
@@ -220,11 +220,15 @@ mod tests {
             init_stack.push(elem);
         }
 
-        SafeMulU64
-            .link_and_run_tasm_from_state_for_test(&mut ExecutionState::with_stack(init_stack));
+        let stack_diff = SafeMulU64.stack_diff();
+        let execution_result = execute_with_execution_state_deprecated(
+            SafeMulU64,
+            ExecutionState::with_stack(init_stack),
+            stack_diff,
+        );
+        assert_assertion_failure(execution_result);
     }
 
-    #[should_panic]
     #[test]
     fn overflow_test_2() {
         // Crash because (rhs_lo * lhs_hi)_hi != 0
@@ -238,11 +242,15 @@ mod tests {
             init_stack.push(elem);
         }
 
-        SafeMulU64
-            .link_and_run_tasm_from_state_for_test(&mut ExecutionState::with_stack(init_stack));
+        let stack_diff = SafeMulU64.stack_diff();
+        let execution_result = execute_with_execution_state_deprecated(
+            SafeMulU64,
+            ExecutionState::with_stack(init_stack),
+            stack_diff,
+        );
+        assert_assertion_failure(execution_result);
     }
 
-    #[should_panic]
     #[test]
     fn overflow_test_3() {
         // Crash because (lhs_lo * rhs_hi)_hi != 0
@@ -256,11 +264,15 @@ mod tests {
             init_stack.push(elem);
         }
 
-        SafeMulU64
-            .link_and_run_tasm_from_state_for_test(&mut ExecutionState::with_stack(init_stack));
+        let stack_diff = SafeMulU64.stack_diff();
+        let execution_result = execute_with_execution_state_deprecated(
+            SafeMulU64,
+            ExecutionState::with_stack(init_stack),
+            stack_diff,
+        );
+        assert_assertion_failure(execution_result);
     }
 
-    #[should_panic]
     #[test]
     fn overflow_test_4() {
         // Crash because (c_lo + a_hi + b_lo)_hi != 0
@@ -274,8 +286,13 @@ mod tests {
             init_stack.push(elem);
         }
 
-        SafeMulU64
-            .link_and_run_tasm_from_state_for_test(&mut ExecutionState::with_stack(init_stack));
+        let stack_diff = SafeMulU64.stack_diff();
+        let execution_result = execute_with_execution_state_deprecated(
+            SafeMulU64,
+            ExecutionState::with_stack(init_stack),
+            stack_diff,
+        );
+        assert_assertion_failure(execution_result);
     }
 
     #[test]
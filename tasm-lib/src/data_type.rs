@@ -65,6 +65,34 @@ impl DataType {
         }
     }
 
+    /// Whether the given words, in stack order, are a valid value of this type. For example,
+    /// every word of a `u64` must fit in 32 bits, and a `bool` must be 0 or 1. Pointers and
+    /// field elements are always valid.
+    pub fn is_valid_stack_value(&self, words: &[BFieldElement]) -> bool {
+        if words.len() != self.stack_size() {
+            return false;
+        }
+
+        let fits_u32 = |word: &BFieldElement| word.value() <= u32::MAX as u64;
+        match self {
+            DataType::Bool => words[0].value() <= 1,
+            DataType::U32 | DataType::U64 | DataType::U128 => words.iter().all(fits_u32),
+            DataType::Bfe
+            | DataType::Xfe
+            | DataType::Digest
+            | DataType::List(_)
+            | DataType::VoidPointer => true,
+            DataType::Tuple(data_types) => {
+                let mut remaining_words = words;
+                data_types.iter().all(|data_type| {
+                    let (value, rest) = remaining_words.split_at(data_type.stack_size());
+                    remaining_words = rest;
+                    data_type.is_valid_stack_value(value)
+                })
+            }
+        }
+    }
+
     /// Return the code to read a value of this type from memory.
     /// Leaves mutated point on top of stack.
     ///
@@ -43,10 +43,17 @@ use crate::{
         },
     },
     hashing::{
-        eq_digest::EqDigest, hash_varlen::HashVarlen, reverse_digest::ReverseDigest,
-        sample_indices::SampleIndices, swap_digest::SwapDigest,
+        eq_digest::EqDigest, hash_varlen::HashVarlen, merkle_step::MerkleStep,
+        reverse_digest::ReverseDigest, sample_indices::SampleIndices, swap_digest::SwapDigest,
+    },
+    io::{
+        assert_stdout_matches_expected::AssertStdoutMatchesExpected,
+        divine_digest_list::DivineDigestList, divine_u128::DivineU128, divine_u64::DivineU64,
+        divine_value::DivineValue, read_bytes_from_stdin::ReadBytesFromStdin,
+        read_digest::ReadDigest, read_from_stdin::ReadFromStdin, read_input::ReadInput,
+        write_bytes_to_stdout::WriteBytesToStdout, write_digest_to_stdout::WriteDigestToStdout,
+        write_to_stdout::WriteToStdout, DigestWordOrder,
     },
-    io::{read_input::ReadInput, write_to_stdout::WriteToStdout},
     list::{
         contiguous_list,
         range::Range,
@@ -60,7 +67,21 @@ use crate::{
         },
         ListType,
     },
-    memory::{dyn_malloc::DynMalloc, memcpy::MemCpy},
+    memory::{
+        arena::{alloc::ArenaAlloc, checkpoint::ArenaCheckpoint, new::ArenaNew, reset::ArenaReset},
+        committed_blob::VerifyCommittedBlob,
+        dyn_free::DynFree,
+        dyn_malloc::DynMalloc,
+        dyn_malloc_checked::DynMallocChecked,
+        dyn_malloc_free_list::DynMallocFreeList,
+        load_words::LoadWords,
+        memcmp::MemCmp,
+        memcpy::MemCpy,
+        memmove::MemMove,
+        nondeterministic_pages::AssertOutsideNondeterministicPages,
+        realloc::Realloc,
+        zeroize_region::ZeroizeRegion,
+    },
     mmr::{
         bag_peaks::BagPeaks, calculate_new_peaks_from_append::CalculateNewPeaksFromAppend,
         calculate_new_peaks_from_leaf_mutation::MmrCalculateNewPeaksFromLeafMutationMtIndices,
@@ -69,7 +90,16 @@ use crate::{
     },
     neptune::mutator_set::{commit::Commit, get_swbf_indices::GetSwbfIndices},
     other_snippets::bfe_add::BfeAdd,
-    recufier::{merkle_verify::MerkleVerify, proof_stream::dequeue::Dequeue},
+    recufier::{
+        deduplicate_indices::DeduplicateIndices,
+        hash_table_rows::HashTableRows,
+        merkle_verify::MerkleVerify,
+        proof_stream::dequeue::Dequeue,
+        proof_stream::dequeue_next_as::{DequeueNextAs, ProofItemVariant},
+        proof_stream::instantiate_fiat_shamir_with_claim::InstantiateFiatShamirWithClaim,
+        verify_leaf_openings::VerifyLeafOpenings,
+        verify_table_rows::VerifyTableRows,
+    },
 };
 
 pub fn name_to_snippet(fn_name: &str) -> Box<dyn BasicSnippet> {
@@ -194,8 +224,47 @@ pub fn name_to_snippet(fn_name: &str) -> Box<dyn BasicSnippet> {
         "tasm_hashing_sample_indices_to_safeimplu32_list" => Box::new(SampleIndices{list_type: ListType::Safe}),
         "tasm_hashing_sample_indices_to_unsafeimplu32_list" => Box::new(SampleIndices{list_type: ListType::Unsafe}),
         "tasm_hashing_reverse_digest" => Box::new(ReverseDigest),
+        "tasm_hashing_merkle_step_u32_index" => Box::new(MerkleStep { index_type: DataType::U32, hash_children: true }),
+        "tasm_hashing_merkle_step_u32_index_unhashed" => Box::new(MerkleStep { index_type: DataType::U32, hash_children: false }),
+        "tasm_hashing_merkle_step_u64_index" => Box::new(MerkleStep { index_type: DataType::U64, hash_children: true }),
+        "tasm_hashing_merkle_step_u64_index_unhashed" => Box::new(MerkleStep { index_type: DataType::U64, hash_children: false }),
 
         // io
+        "tasm_io_assert_stdout_matches_expected___1" => Box::new(AssertStdoutMatchesExpected { num_words: 1 }),
+        "tasm_io_assert_stdout_matches_expected___5" => Box::new(AssertStdoutMatchesExpected { num_words: 5 }),
+        "tasm_io_assert_stdout_matches_expected___10" => Box::new(AssertStdoutMatchesExpected { num_words: 10 }),
+
+        "tasm_io_divine_digest_list_safeimplu32" => Box::new(DivineDigestList { list_type: ListType::Safe, length_from_stdin: false }),
+        "tasm_io_divine_digest_list_safeimplu32_length_from_stdin" => Box::new(DivineDigestList { list_type: ListType::Safe, length_from_stdin: true }),
+        "tasm_io_divine_digest_list_unsafeimplu32" => Box::new(DivineDigestList { list_type: ListType::Unsafe, length_from_stdin: false }),
+        "tasm_io_divine_digest_list_unsafeimplu32_length_from_stdin" => Box::new(DivineDigestList { list_type: ListType::Unsafe, length_from_stdin: true }),
+
+        "tasm_io_divine_u64" => Box::new(DivineU64),
+        "tasm_io_divine_u128" => Box::new(DivineU128),
+
+        "tasm_io_divine_value___bool" => Box::new(DivineValue { data_type: DataType::Bool }),
+        "tasm_io_divine_value___u32" => Box::new(DivineValue { data_type: DataType::U32 }),
+        "tasm_io_divine_value___u64" => Box::new(DivineValue { data_type: DataType::U64 }),
+        "tasm_io_divine_value___u128" => Box::new(DivineValue { data_type: DataType::U128 }),
+        "tasm_io_divine_value___bfe" => Box::new(DivineValue { data_type: DataType::Bfe }),
+        "tasm_io_divine_value___xfe" => Box::new(DivineValue { data_type: DataType::Xfe }),
+        "tasm_io_divine_value___digest" => Box::new(DivineValue { data_type: DataType::Digest }),
+
+        "tasm_io_read_bytes_from_stdin" => Box::new(ReadBytesFromStdin),
+
+        "tasm_io_read_digest_stdin___little_endian" => Box::new(ReadDigest { input_source: InputSource::StdIn, word_order: DigestWordOrder::LittleEndian }),
+        "tasm_io_read_digest_stdin___big_endian" => Box::new(ReadDigest { input_source: InputSource::StdIn, word_order: DigestWordOrder::BigEndian }),
+        "tasm_io_read_digest_secin___little_endian" => Box::new(ReadDigest { input_source: InputSource::SecretIn, word_order: DigestWordOrder::LittleEndian }),
+        "tasm_io_read_digest_secin___big_endian" => Box::new(ReadDigest { input_source: InputSource::SecretIn, word_order: DigestWordOrder::BigEndian }),
+
+        "tasm_io_read_from_stdin___bool" => Box::new(ReadFromStdin { data_type: DataType::Bool }),
+        "tasm_io_read_from_stdin___u32" => Box::new(ReadFromStdin { data_type: DataType::U32 }),
+        "tasm_io_read_from_stdin___u64" => Box::new(ReadFromStdin { data_type: DataType::U64 }),
+        "tasm_io_read_from_stdin___u128" => Box::new(ReadFromStdin { data_type: DataType::U128 }),
+        "tasm_io_read_from_stdin___bfe" => Box::new(ReadFromStdin { data_type: DataType::Bfe }),
+        "tasm_io_read_from_stdin___xfe" => Box::new(ReadFromStdin { data_type: DataType::Xfe }),
+        "tasm_io_read_from_stdin___digest" => Box::new(ReadFromStdin { data_type: DataType::Digest }),
+
         "tasm_io_read_secin___bool" => Box::new(ReadInput {
             data_type: DataType::Bool,
             input_source: InputSource::SecretIn,
@@ -254,6 +323,11 @@ pub fn name_to_snippet(fn_name: &str) -> Box<dyn BasicSnippet> {
             input_source: InputSource::StdIn,
         }),
 
+        "tasm_io_write_bytes_to_stdout" => Box::new(WriteBytesToStdout),
+
+        "tasm_io_write_digest_to_stdout___little_endian" => Box::new(WriteDigestToStdout { word_order: DigestWordOrder::LittleEndian }),
+        "tasm_io_write_digest_to_stdout___big_endian" => Box::new(WriteDigestToStdout { word_order: DigestWordOrder::BigEndian }),
+
         "tasm_io_write_to_stdout___bool" => Box::new(WriteToStdout{ data_type: DataType::Bool}),
         "tasm_io_write_to_stdout___u32" => Box::new(WriteToStdout{ data_type: DataType::U32}),
         "tasm_io_write_to_stdout___u64" => Box::new(WriteToStdout{ data_type: DataType::U64}),
@@ -419,11 +493,64 @@ data_type: DataType::Digest
 
         // recufy
         "tasm_recufier_mt_ap_verify" => Box::new(MerkleVerify),
+        "tasm_recufier_verify_leaf_openings_safeimplu32" => Box::new(VerifyLeafOpenings { list_type: ListType::Safe }),
+        "tasm_recufier_verify_leaf_openings_unsafeimplu32" => Box::new(VerifyLeafOpenings { list_type: ListType::Unsafe }),
+        "tasm_recufier_verify_table_rows_safeimplu32" => Box::new(VerifyTableRows { list_type: ListType::Safe }),
+        "tasm_recufier_verify_table_rows_unsafeimplu32" => Box::new(VerifyTableRows { list_type: ListType::Unsafe }),
+        "tasm_recufier_hash_table_rows_safeimplu32" => Box::new(HashTableRows { list_type: ListType::Safe }),
+        "tasm_recufier_hash_table_rows_unsafeimplu32" => Box::new(HashTableRows { list_type: ListType::Unsafe }),
+        "tasm_recufier_deduplicate_indices_safeimplu32" => Box::new(DeduplicateIndices { list_type: ListType::Safe }),
+        "tasm_recufier_deduplicate_indices_unsafeimplu32" => Box::new(DeduplicateIndices { list_type: ListType::Unsafe }),
         "tasm_recufier_proof_stream_dequeue" => Box::new(Dequeue),
+        "tasm_recufier_proof_stream_dequeue_next_as_authentication_structure" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::AuthenticationStructure }),
+        "tasm_recufier_proof_stream_dequeue_next_as_master_base_table_rows" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::MasterBaseTableRows }),
+        "tasm_recufier_proof_stream_dequeue_next_as_master_ext_table_rows" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::MasterExtTableRows }),
+        "tasm_recufier_proof_stream_dequeue_next_as_out_of_domain_base_row" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::OutOfDomainBaseRow }),
+        "tasm_recufier_proof_stream_dequeue_next_as_out_of_domain_ext_row" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::OutOfDomainExtRow }),
+        "tasm_recufier_proof_stream_dequeue_next_as_out_of_domain_quotient_segments" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::OutOfDomainQuotientSegments }),
+        "tasm_recufier_proof_stream_dequeue_next_as_merkle_root" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::MerkleRoot }),
+        "tasm_recufier_proof_stream_dequeue_next_as_log2_padded_height" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::Log2PaddedHeight }),
+        "tasm_recufier_proof_stream_dequeue_next_as_quotient_segments_elements" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::QuotientSegmentsElements }),
+        "tasm_recufier_proof_stream_dequeue_next_as_fri_codeword" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::FriCodeword }),
+        "tasm_recufier_proof_stream_dequeue_next_as_fri_response" => Box::new(DequeueNextAs { proof_item: ProofItemVariant::FriResponse }),
+        "tasm_recufier_proof_stream_instantiate_fiat_shamir_with_claim" => Box::new(InstantiateFiatShamirWithClaim),
 
         // memory
+        "tasm_memory_arena_alloc" => Box::new(ArenaAlloc),
+        "tasm_memory_arena_checkpoint" => Box::new(ArenaCheckpoint),
+        "tasm_memory_arena_new" => Box::new(ArenaNew),
+        "tasm_memory_arena_reset" => Box::new(ArenaReset),
+        "tasm_memory_assert_outside_nondeterministic_pages" => {
+            Box::new(AssertOutsideNondeterministicPages)
+        }
+        "tasm_memory_dyn_free" => Box::new(DynFree),
         "tasm_memory_dyn_malloc" => Box::new(DynMalloc),
+        "tasm_memory_dyn_malloc_checked" => Box::new(DynMallocChecked),
+        "tasm_memory_dyn_malloc_free_list" => Box::new(DynMallocFreeList),
+        "tasm_memory_load_words_1" => Box::new(LoadWords::<1>),
+        "tasm_memory_load_words_2" => Box::new(LoadWords::<2>),
+        "tasm_memory_load_words_3" => Box::new(LoadWords::<3>),
+        "tasm_memory_load_words_4" => Box::new(LoadWords::<4>),
+        "tasm_memory_load_words_5" => Box::new(LoadWords::<5>),
+        "tasm_memory_load_words_6" => Box::new(LoadWords::<6>),
+        "tasm_memory_load_words_7" => Box::new(LoadWords::<7>),
+        "tasm_memory_load_words_8" => Box::new(LoadWords::<8>),
+        "tasm_memory_load_words_9" => Box::new(LoadWords::<9>),
+        "tasm_memory_load_words_10" => Box::new(LoadWords::<10>),
+        "tasm_memory_load_words_11" => Box::new(LoadWords::<11>),
+        "tasm_memory_load_words_12" => Box::new(LoadWords::<12>),
+        "tasm_memory_load_words_13" => Box::new(LoadWords::<13>),
+        "tasm_memory_load_words_14" => Box::new(LoadWords::<14>),
+        "tasm_memory_load_words_15" => Box::new(LoadWords::<15>),
+        "tasm_memory_load_words_16" => Box::new(LoadWords::<16>),
+        "tasm_memory_memcmp" => Box::new(MemCmp { report_first_difference: false }),
+        "tasm_memory_memcmp_with_first_difference" => Box::new(MemCmp { report_first_difference: true }),
         "tasm_memory_memcpy" => Box::new(MemCpy),
+        "tasm_memory_memmove" => Box::new(MemMove),
+        "tasm_memory_realloc" => Box::new(Realloc { grow_in_place: false }),
+        "tasm_memory_realloc_grow_in_place" => Box::new(Realloc { grow_in_place: true }),
+        "tasm_memory_verify_committed_blob" => Box::new(VerifyCommittedBlob),
+        "tasm_memory_zeroize_region" => Box::new(ZeroizeRegion),
 
         // structure
 
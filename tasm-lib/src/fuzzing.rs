@@ -0,0 +1,170 @@
+//! Derivation of snippet initial states from fuzzer-provided bytes, for use in fuzz targets.
+//! Requires feature `fuzz`.
+//!
+//! Every `Shadowed*` wrapper has a method `fuzz` taking the raw bytes, for example:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     ShadowedFunction::new(BagPeaks).fuzz(data);
+//! });
+//! ```
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use arbitrary::{Result, Unstructured};
+use triton_vm::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::{empty_stack, DIGEST_LENGTH};
+
+/// Addresses of objects placed in memory are drawn from this range, which lies in
+/// nondeterministically initialized memory.
+const OBJECT_ADDRESSES: RangeInclusive<u64> = 0..=(1 << 31) - 1;
+
+pub fn arbitrary_bfe(u: &mut Unstructured) -> Result<BFieldElement> {
+    Ok(BFieldElement::new(u.int_in_range(0..=BFieldElement::MAX)?))
+}
+
+fn arbitrary_u32_words(u: &mut Unstructured, num_words: usize) -> Result<Vec<BFieldElement>> {
+    (0..num_words)
+        .map(|_| Ok(BFieldElement::new(u.arbitrary::<u32>()? as u64)))
+        .collect()
+}
+
+fn arbitrary_bfes(u: &mut Unstructured, num_words: usize) -> Result<Vec<BFieldElement>> {
+    (0..num_words).map(|_| arbitrary_bfe(u)).collect()
+}
+
+/// A value of the given data type, as the words it occupies on the stack. Lists are
+/// represented by their pointer.
+pub fn arbitrary_stack_value(
+    u: &mut Unstructured,
+    data_type: &DataType,
+) -> Result<Vec<BFieldElement>> {
+    match data_type {
+        DataType::Bool => Ok(vec![BFieldElement::new(u.arbitrary::<bool>()? as u64)]),
+        DataType::U32 => arbitrary_u32_words(u, 1),
+        DataType::U64 => arbitrary_u32_words(u, 2),
+        DataType::U128 => arbitrary_u32_words(u, 4),
+        DataType::Bfe | DataType::VoidPointer | DataType::List(_) => arbitrary_bfes(u, 1),
+        DataType::Xfe => arbitrary_bfes(u, 3),
+        DataType::Digest => arbitrary_bfes(u, DIGEST_LENGTH),
+        DataType::Tuple(data_types) => Ok(data_types
+            .iter()
+            .map(|data_type| arbitrary_stack_value(u, data_type))
+            .collect::<Result<Vec<_>>>()?
+            .concat()),
+    }
+}
+
+/// An initial stack holding one value for each of the given data types, the first one
+/// deepest in the stack.
+pub fn arbitrary_stack(
+    u: &mut Unstructured,
+    data_types: &[DataType],
+) -> Result<Vec<BFieldElement>> {
+    let mut stack = empty_stack();
+    for data_type in data_types {
+        stack.extend(arbitrary_stack_value(u, data_type)?);
+    }
+
+    Ok(stack)
+}
+
+/// A well-formed list of the given list type and element type in memory, together with its
+/// pointer. A safe list's capacity matches its length.
+pub fn arbitrary_list_in_memory(
+    u: &mut Unstructured,
+    list_type: ListType,
+    element_type: &DataType,
+) -> Result<(BFieldElement, HashMap<BFieldElement, BFieldElement>)> {
+    let list_pointer = BFieldElement::new(u.int_in_range(OBJECT_ADDRESSES)?);
+    let mut elements = vec![];
+    while u.arbitrary()? {
+        elements.push(arbitrary_stack_value(u, element_type)?);
+    }
+
+    let element_size = element_type.stack_size();
+    let mut memory = HashMap::new();
+    match list_type {
+        ListType::Safe => {
+            safe_list::safe_list_new(list_pointer, elements.len() as u32, &mut memory);
+            for element in elements {
+                safe_list::safe_list_push(list_pointer, element, &mut memory, element_size);
+            }
+        }
+        ListType::Unsafe => {
+            unsafe_list::unsafe_list_new(list_pointer, &mut memory);
+            for element in elements {
+                unsafe_list::unsafe_list_push(list_pointer, element, &mut memory, element_size);
+            }
+        }
+    }
+
+    Ok((list_pointer, memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::arithmetic::u32::safepow::Safepow;
+    use crate::arithmetic::u64::wrapping_sub_u64::WrappingSub;
+    use crate::list::swap_unchecked::SwapUnchecked;
+    use crate::traits::algorithm::ShadowedAlgorithm;
+    use crate::traits::closure::ShadowedClosure;
+
+    use super::*;
+
+    fn random_bytes() -> Vec<u8> {
+        let mut bytes = vec![0; 1 << 12];
+        thread_rng().fill_bytes(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn generated_lists_are_well_formed() {
+        let element_type = DataType::Digest;
+        for _ in 0..10 {
+            let bytes = random_bytes();
+            let mut u = Unstructured::new(&bytes);
+            let (list_pointer, memory) =
+                arbitrary_list_in_memory(&mut u, ListType::Safe, &element_type).unwrap();
+            let length = memory[&list_pointer].value() as usize;
+            assert_eq!(2 + length * element_type.stack_size(), memory.len());
+        }
+    }
+
+    #[test]
+    fn fuzz_closure() {
+        for _ in 0..10 {
+            ShadowedClosure::new(WrappingSub).fuzz(&random_bytes());
+        }
+    }
+
+    #[test]
+    fn fuzz_closure_with_failing_inputs() {
+        for _ in 0..10 {
+            ShadowedClosure::new(Safepow).fuzz(&random_bytes());
+        }
+    }
+
+    #[test]
+    fn fuzz_algorithm_with_structured_initial_state() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            let snippet = SwapUnchecked::new(list_type, DataType::Xfe);
+            let shadowed = ShadowedAlgorithm::new(snippet);
+            for _ in 0..10 {
+                shadowed.fuzz(&random_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn insufficient_bytes_are_ignored() {
+        ShadowedAlgorithm::new(SwapUnchecked::new(ListType::Unsafe, DataType::Bfe)).fuzz(&[]);
+    }
+}
@@ -3,6 +3,7 @@ pub mod divine_sibling_u64_index;
 pub mod eq_digest;
 pub mod hash_varlen;
 pub mod merkle_root;
+pub mod merkle_step;
 pub mod reverse_digest;
 pub mod sample_indices;
 pub mod squeeze_repeatedly;
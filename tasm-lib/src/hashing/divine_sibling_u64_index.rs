@@ -1,17 +1,18 @@
 use rand::{random, rngs::StdRng, Rng, SeedableRng};
 use triton_vm::{triton_asm, BFieldElement, Digest, NonDeterminism};
 
+use super::merkle_step::MerkleStep;
 use crate::traits::procedure::ProcedureInitialState;
 use crate::{
     data_type::DataType,
     empty_stack,
     library::Library,
-    push_encodable,
     snippet_bencher::BenchmarkCase,
     traits::{basic_snippet::BasicSnippet, procedure::Procedure},
 };
 
-/// `divine_sibling` but for index of type `u64`
+/// `divine_sibling` but for index of type `u64`. See [`MerkleStep`], which also hashes the
+/// children if requested.
 #[derive(Clone, Debug)]
 pub struct DivineSiblingU64Index;
 
@@ -36,41 +37,13 @@ impl BasicSnippet for DivineSiblingU64Index {
     }
 
     fn code(&self, _library: &mut Library) -> Vec<triton_vm::instruction::LabelledInstruction> {
-        const TWO_POW_31: u32 = 1u32 << 31;
         let entrypoint = self.entrypoint();
+        let shift_index_hi = MerkleStep::shift_u64_index_hi();
 
         triton_asm!(
             {entrypoint}:
                 divine_sibling
-
-                push 2
-                push 0
-                // _ mt_index_hi (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] 2 0
-
-                swap 13
-                // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] 2 mt_index_hi
-
-                div_mod
-                // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] (mt_index_hi / 2) (mt_index_hi % 2)
-
-                push {TWO_POW_31}
-                mul
-                // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] (mt_index_hi / 2) carry
-
-                swap 1
-                swap 13
-                // _ (mt_index_hi / 2) (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] carry 0
-
-                swap 12
-                // _ (mt_index_hi / 2) 0 [digest (right_node)] [digest (left_node)] carry (mt_index_lo / 2)
-
-                add
-                // _ (mt_index_hi / 2) 0 [digest (right_node)] [digest (left_node)] (mt_index / 2)_lo
-
-                swap 11
-                pop 1
-                // _ (mt_index_hi / 2) (mt_index / 2)_lo [digest (right_node)] [digest (left_node)]
-
+                {&shift_index_hi}
                 return
         )
     }
@@ -80,37 +53,12 @@ impl Procedure for DivineSiblingU64Index {
     fn rust_shadow(
         &self,
         stack: &mut Vec<BFieldElement>,
-        _memory: &mut std::collections::HashMap<BFieldElement, BFieldElement>,
+        memory: &mut std::collections::HashMap<BFieldElement, BFieldElement>,
         nondeterminism: &NonDeterminism<BFieldElement>,
-        _public_input: &[BFieldElement],
-        _sponge_state: &mut Option<crate::VmHasherState>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<crate::VmHasherState>,
     ) -> Vec<BFieldElement> {
-        let stack_digest: Digest = Digest::new([
-            stack.pop().unwrap(),
-            stack.pop().unwrap(),
-            stack.pop().unwrap(),
-            stack.pop().unwrap(),
-            stack.pop().unwrap(),
-        ]);
-        let ap_digest: Digest = nondeterminism.digests[0];
-
-        let leaf_index_lo: u32 = stack.pop().unwrap().try_into().unwrap();
-        let leaf_index_hi: u32 = stack.pop().unwrap().try_into().unwrap();
-        let leaf_index: u64 = ((leaf_index_hi as u64) << 32) | (leaf_index_lo as u64);
-        let (left_digest, right_digest) = if leaf_index % 2 == 1 {
-            (ap_digest, stack_digest)
-        } else {
-            (stack_digest, ap_digest)
-        };
-
-        let parent_index = leaf_index / 2;
-        stack.push(BFieldElement::new(parent_index >> 32));
-        stack.push(BFieldElement::new(parent_index & u32::MAX as u64));
-
-        push_encodable(stack, &right_digest);
-        push_encodable(stack, &left_digest);
-
-        vec![]
+        Self::merkle_step().rust_shadow(stack, memory, nondeterminism, public_input, sponge_state)
     }
 
     fn pseudorandom_initial_state(
@@ -136,6 +84,13 @@ impl Procedure for DivineSiblingU64Index {
 }
 
 impl DivineSiblingU64Index {
+    fn merkle_step() -> MerkleStep {
+        MerkleStep {
+            index_type: DataType::U64,
+            hash_children: false,
+        }
+    }
+
     fn prepare_stack_and_non_determinism(
         &self,
         leaf_index: u64,
@@ -0,0 +1,329 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism};
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::algorithm::DigestCursor;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
+use crate::{empty_stack, push_encodable, Digest, VmHasher, VmHasherState};
+
+/// Move up one level in a Merkle tree: divine the sibling of the node with the given node
+/// index and digest through Triton VM's `divine_sibling` and, if `hash_children` is set,
+/// hash the two children. The node index is of type `index_type`, either `u32` or `u64`.
+/// Indices of type `u32` are handled by `divine_sibling` directly, indices of type `u64`
+/// are shifted with carry from the high to the low word.
+///
+/// ```text
+/// BEFORE: _ [node_index] [digest]
+/// AFTER:  _ [parent_index] [right_child] [left_child]    if !hash_children
+/// AFTER:  _ [parent_index] [parent_digest]               if hash_children
+/// ```
+#[derive(Clone, Debug)]
+pub struct MerkleStep {
+    pub index_type: DataType,
+    pub hash_children: bool,
+}
+
+impl MerkleStep {
+    fn assert_supported_index_type(&self) {
+        assert!(
+            matches!(self.index_type, DataType::U32 | DataType::U64),
+            "Merkle tree node indices must be of type u32 or u64, not {:?}",
+            self.index_type
+        );
+    }
+
+    /// Code to move the high word of a `u64` node index's parent into place, with carry.
+    /// ```text
+    /// BEFORE: _ node_index_hi (node_index_lo / 2) [right_child] [left_child]
+    /// AFTER:  _ parent_index_hi parent_index_lo [right_child] [left_child]
+    /// ```
+    pub(super) fn shift_u64_index_hi() -> Vec<LabelledInstruction> {
+        const TWO_POW_31: u32 = 1u32 << 31;
+
+        triton_asm!(
+            push 2
+            push 0
+            // _ mt_index_hi (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] 2 0
+
+            swap 13
+            // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] 2 mt_index_hi
+
+            div_mod
+            // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] (mt_index_hi / 2) (mt_index_hi % 2)
+
+            push {TWO_POW_31}
+            mul
+            // _ 0 (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] (mt_index_hi / 2) carry
+
+            swap 1
+            swap 13
+            // _ (mt_index_hi / 2) (mt_index_lo / 2) [digest (right_node)] [digest (left_node)] carry 0
+
+            swap 12
+            // _ (mt_index_hi / 2) 0 [digest (right_node)] [digest (left_node)] carry (mt_index_lo / 2)
+
+            add
+            // _ (mt_index_hi / 2) 0 [digest (right_node)] [digest (left_node)] (mt_index / 2)_lo
+
+            swap 11
+            pop 1
+            // _ (mt_index_hi / 2) (mt_index / 2)_lo [digest (right_node)] [digest (left_node)]
+        )
+    }
+}
+
+impl BasicSnippet for MerkleStep {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (self.index_type.clone(), "node_index".to_owned()),
+            (DataType::Digest, "digest".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        let parent_index = (self.index_type.clone(), "parent_index".to_owned());
+        match self.hash_children {
+            true => vec![parent_index, (DataType::Digest, "parent_digest".to_owned())],
+            false => vec![
+                parent_index,
+                (DataType::Digest, "right_child".to_owned()),
+                (DataType::Digest, "left_child".to_owned()),
+            ],
+        }
+    }
+
+    fn entrypoint(&self) -> String {
+        let unhashed = match self.hash_children {
+            true => "",
+            false => "_unhashed",
+        };
+        format!(
+            "tasm_hashing_merkle_step_{}_index{unhashed}",
+            self.index_type.label_friendly_name()
+        )
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        self.assert_supported_index_type();
+        let entrypoint = self.entrypoint();
+        let shift_index_hi = match self.index_type {
+            DataType::U64 => Self::shift_u64_index_hi(),
+            _ => vec![],
+        };
+        let hash_children = match self.hash_children {
+            true => triton_asm!(hash),
+            false => vec![],
+        };
+
+        triton_asm!(
+            {entrypoint}:
+                divine_sibling
+                {&shift_index_hi}
+                {&hash_children}
+                return
+        )
+    }
+}
+
+impl Procedure for MerkleStep {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        self.rust_shadow_with_digest_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut TokenCursor::new(&nondeterminism.individual_tokens),
+            &mut DigestCursor::new(&nondeterminism.digests),
+        )
+    }
+
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+        _tokens: &mut TokenCursor,
+        digests: &mut DigestCursor,
+    ) -> Vec<BFieldElement> {
+        self.assert_supported_index_type();
+        let stack_digest: Digest = Digest::new([
+            stack.pop().unwrap(),
+            stack.pop().unwrap(),
+            stack.pop().unwrap(),
+            stack.pop().unwrap(),
+            stack.pop().unwrap(),
+        ]);
+        let sibling_digest = digests.read();
+
+        let node_index_lo: u32 = stack.pop().unwrap().try_into().unwrap();
+        let node_index_hi: u32 = match self.index_type {
+            DataType::U64 => stack.pop().unwrap().try_into().unwrap(),
+            _ => 0,
+        };
+        let node_index: u64 = ((node_index_hi as u64) << 32) | (node_index_lo as u64);
+        let (left_digest, right_digest) = if node_index % 2 == 1 {
+            (sibling_digest, stack_digest)
+        } else {
+            (stack_digest, sibling_digest)
+        };
+
+        let parent_index = node_index / 2;
+        if self.index_type == DataType::U64 {
+            stack.push(BFieldElement::new(parent_index >> 32));
+        }
+        stack.push(BFieldElement::new(parent_index & u32::MAX as u64));
+
+        if self.hash_children {
+            let parent_digest = VmHasher::hash_pair(left_digest, right_digest);
+            push_encodable(stack, &parent_digest);
+        } else {
+            push_encodable(stack, &right_digest);
+            push_encodable(stack, &left_digest);
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let node_index = match (&self.index_type, bench_case) {
+            (DataType::U32, Some(BenchmarkCase::CommonCase)) => 1 << 16,
+            (DataType::U32, Some(BenchmarkCase::WorstCase)) => 1 << 31,
+            (DataType::U32, None) => rng.gen::<u32>() as u64,
+            (_, Some(BenchmarkCase::CommonCase)) => 1 << 33,
+            (_, Some(BenchmarkCase::WorstCase)) => 1 << 63,
+            (_, None) => rng.gen(),
+        };
+
+        self.initial_state(node_index, rng.gen(), rng.gen())
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        let node_indices = match self.index_type {
+            DataType::U32 => vec![1, 2, 3, u32::MAX as u64],
+            _ => vec![1, 2, 1 << 32, (1 << 32) + 1, (1 << 33) + 1, u64::MAX],
+        };
+
+        let mut rng: StdRng = SeedableRng::from_seed([0x5e; 32]);
+        node_indices
+            .into_iter()
+            .map(|node_index| self.initial_state(node_index, rng.gen(), rng.gen()))
+            .collect()
+    }
+}
+
+impl MerkleStep {
+    pub(crate) fn initial_state(
+        &self,
+        node_index: u64,
+        digest: Digest,
+        sibling: Digest,
+    ) -> ProcedureInitialState {
+        let mut stack = empty_stack();
+        if self.index_type == DataType::U64 {
+            stack.push(BFieldElement::new(node_index >> 32));
+        }
+        stack.push(BFieldElement::new(node_index & u32::MAX as u64));
+        push_encodable(&mut stack, &digest);
+
+        ProcedureInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default().with_digests(vec![sibling]),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::tasm_final_state;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn merkle_step_test() {
+        for index_type in [DataType::U32, DataType::U64] {
+            for hash_children in [false, true] {
+                let index_type = index_type.clone();
+                ShadowedProcedure::new(MerkleStep {
+                    index_type,
+                    hash_children,
+                })
+                .test();
+            }
+        }
+    }
+
+    #[test]
+    fn u32_and_u64_indices_give_same_parent_digest() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let (digest, sibling) = (rng.gen(), rng.gen());
+
+        for node_index in [2, 3, 1 << 20, (1 << 20) + 1] {
+            let parent_digests = [DataType::U32, DataType::U64].map(|index_type| {
+                let snippet = MerkleStep {
+                    index_type,
+                    hash_children: true,
+                };
+                let initial_state = snippet.initial_state(node_index, digest, sibling);
+                let final_state = tasm_final_state(
+                    &ShadowedProcedure::new(snippet),
+                    &initial_state.stack,
+                    &[],
+                    initial_state.nondeterminism,
+                    &None,
+                    0,
+                );
+                let final_stack = final_state.final_stack;
+                final_stack[final_stack.len() - 5..].to_vec()
+            });
+            assert_eq!(parent_digests[0], parent_digests[1]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_integer_index_type_is_rejected() {
+        let snippet = MerkleStep {
+            index_type: DataType::Bfe,
+            hash_children: true,
+        };
+        snippet.code(&mut Library::new());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn merkle_step_u64_index_benchmark() {
+        ShadowedProcedure::new(MerkleStep {
+            index_type: DataType::U64,
+            hash_children: true,
+        })
+        .bench();
+    }
+}
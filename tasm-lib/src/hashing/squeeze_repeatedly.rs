@@ -9,6 +9,7 @@ use twenty_first::{shared_math::tip5::RATE, util_types::algebraic_hasher::Sponge
 use crate::data_type::DataType;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::known_cycle_count::KnownCycleCount;
 use crate::traits::procedure::Procedure;
 use crate::{empty_stack, VmHasher, VmHasherState};
 
@@ -16,6 +17,23 @@ use crate::{empty_stack, VmHasher, VmHasherState};
 /// contiguously in memory. It is the caller's responsibility to allocate enough memory.
 pub struct SqueezeRepeatedly;
 
+impl SqueezeRepeatedly {
+    fn initial_state(rng: &mut impl Rng, num_squeezes: usize) -> ProcedureInitialState {
+        let sponge_state = VmHasherState { state: rng.gen() };
+        let mut stack = empty_stack();
+        let address = BFieldElement::new(rng.next_u64() % (1 << 20));
+        stack.push(address);
+        stack.push(BFieldElement::new(num_squeezes as u64));
+
+        ProcedureInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default(),
+            public_input: vec![],
+            sponge_state: Some(sponge_state),
+        }
+    }
+}
+
 impl BasicSnippet for SqueezeRepeatedly {
     fn inputs(&self) -> Vec<(crate::data_type::DataType, String)> {
         vec![
@@ -106,18 +124,30 @@ impl Procedure for SqueezeRepeatedly {
             None => rng.gen_range(0..10),
         };
 
-        let sponge_state = VmHasherState { state: rng.gen() };
-        let mut stack = empty_stack();
-        let address = BFieldElement::new(rng.next_u64() % (1 << 20));
-        stack.push(address);
-        stack.push(BFieldElement::new(num_squeezes as u64));
+        Self::initial_state(&mut rng, num_squeezes)
+    }
+}
 
-        ProcedureInitialState {
-            stack,
-            nondeterminism: NonDeterminism::default(),
-            public_input: vec![],
-            sponge_state: Some(sponge_state),
-        }
+impl KnownCycleCount for SqueezeRepeatedly {
+    fn size_parameters(&self) -> Vec<String> {
+        vec!["num_squeezes".to_string()]
+    }
+
+    fn cycle_count(&self, sizes: &[usize]) -> usize {
+        // 13 cycles per squeeze, and 5 for the final termination check including `return`
+        let num_squeezes = sizes[0];
+        13 * num_squeezes + 5
+    }
+
+    fn initial_state_for_sizes(&self, sizes: &[usize]) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::seed_from_u64(sizes[0] as u64);
+        Self::initial_state(&mut rng, sizes[0])
+    }
+
+    fn sizes_to_verify(&self) -> Vec<Vec<usize>> {
+        [0, 1, 2, 10, 57]
+            .map(|num_squeezes| vec![num_squeezes])
+            .to_vec()
     }
 }
 
@@ -130,6 +160,7 @@ mod test {
         rust_final_state, tasm_final_state, verify_memory_equivalence, verify_sponge_equivalence,
         verify_stack_equivalence, verify_stack_growth,
     };
+    use crate::traits::known_cycle_count::verify_cycle_count;
     use crate::traits::procedure::Procedure;
     use crate::traits::procedure::ShadowedProcedure;
     use crate::traits::rust_shadow::RustShadow;
@@ -185,6 +216,11 @@ mod test {
             verify_sponge_equivalence(&rust.final_sponge_state, &tasm.final_sponge_state);
         }
     }
+
+    #[test]
+    fn cycle_count_formula_is_exact() {
+        verify_cycle_count(SqueezeRepeatedly);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,267 @@
+//! A builder for the initial states that snippets are tested and benchmarked on.
+
+use std::collections::HashMap;
+
+use triton_vm::{BFieldElement, NonDeterminism};
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use crate::data_type::DataType;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::traits::algorithm::AlgorithmInitialState;
+use crate::traits::function::FunctionInitialState;
+use crate::traits::procedure::ProcedureInitialState;
+use crate::{empty_stack, push_encodable, Digest, ExecutionState, VmHasherState};
+
+/// Address of the first list the builder places in memory, unless memory is already used
+/// there.
+const FIRST_LIST_ADDRESS: u64 = 1;
+
+/// Assembles the initial state of a snippet step by step, starting from an
+/// [empty stack](empty_stack), empty memory, and empty input streams.
+///
+/// ```
+/// # use tasm_lib::data_type::DataType;
+/// # use tasm_lib::initial_state::InitialStateBuilder;
+/// # use tasm_lib::list::ListType;
+/// # use triton_vm::BFieldElement;
+/// let elements = vec![vec![BFieldElement::new(3)], vec![BFieldElement::new(4)]];
+/// let state = InitialStateBuilder::new()
+///     .with_list(ListType::Unsafe, &DataType::Bfe, elements)
+///     .with_stack_values(&[BFieldElement::new(1)])
+///     .build_function_state();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InitialStateBuilder {
+    stack: Vec<BFieldElement>,
+    memory: HashMap<BFieldElement, BFieldElement>,
+    stdin: Vec<BFieldElement>,
+    individual_tokens: Vec<BFieldElement>,
+    digests: Vec<Digest>,
+    sponge_state: Option<VmHasherState>,
+    words_statically_allocated: u32,
+}
+
+impl Default for InitialStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InitialStateBuilder {
+    pub fn new() -> Self {
+        Self {
+            stack: empty_stack(),
+            memory: HashMap::new(),
+            stdin: vec![],
+            individual_tokens: vec![],
+            digests: vec![],
+            sponge_state: None,
+            words_statically_allocated: 0,
+        }
+    }
+
+    /// Push the given words onto the stack, the last one on top.
+    pub fn with_stack_values(mut self, words: &[BFieldElement]) -> Self {
+        self.stack.extend_from_slice(words);
+        self
+    }
+
+    /// Push the stack representation of the given value onto the stack.
+    pub fn with_stack_value<T: BFieldCodec>(mut self, value: &T) -> Self {
+        push_encodable(&mut self.stack, value);
+        self
+    }
+
+    /// Place a list with the given elements in memory, directly after all memory used so
+    /// far, and push the pointer to the list onto the stack. A safe list's capacity matches
+    /// its length.
+    pub fn with_list(
+        mut self,
+        list_type: ListType,
+        element_type: &DataType,
+        elements: Vec<Vec<BFieldElement>>,
+    ) -> Self {
+        let element_size = element_type.stack_size();
+        assert!(
+            elements.iter().all(|element| element.len() == element_size),
+            "every list element must have the size of a {}",
+            element_type.label_friendly_name()
+        );
+
+        let list_pointer = self.first_unused_address();
+        match list_type {
+            ListType::Safe => {
+                let capacity = elements.len() as u32;
+                safe_list::safe_list_new(list_pointer, capacity, &mut self.memory);
+                for element in elements {
+                    safe_list::safe_list_push(
+                        list_pointer,
+                        element,
+                        &mut self.memory,
+                        element_size,
+                    );
+                }
+            }
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_new(list_pointer, &mut self.memory);
+                for element in elements {
+                    unsafe_list::unsafe_list_push(
+                        list_pointer,
+                        element,
+                        &mut self.memory,
+                        element_size,
+                    );
+                }
+            }
+        }
+
+        self.stack.push(list_pointer);
+        self
+    }
+
+    /// Store the encoding of the given object in memory, starting at the given address.
+    pub fn with_object<T: BFieldCodec>(mut self, address: BFieldElement, object: &T) -> Self {
+        for (offset, word) in object.encode().into_iter().enumerate() {
+            self.memory
+                .insert(address + BFieldElement::new(offset as u64), word);
+        }
+        self
+    }
+
+    /// Add the given words to memory, overwriting any words already stored at their
+    /// addresses.
+    pub fn with_memory(mut self, memory: HashMap<BFieldElement, BFieldElement>) -> Self {
+        self.memory.extend(memory);
+        self
+    }
+
+    pub fn with_stdin(mut self, stdin: Vec<BFieldElement>) -> Self {
+        self.stdin.extend(stdin);
+        self
+    }
+
+    pub fn with_individual_tokens(mut self, individual_tokens: Vec<BFieldElement>) -> Self {
+        self.individual_tokens.extend(individual_tokens);
+        self
+    }
+
+    pub fn with_digests(mut self, digests: Vec<Digest>) -> Self {
+        self.digests.extend(digests);
+        self
+    }
+
+    pub fn with_sponge_state(mut self, sponge_state: VmHasherState) -> Self {
+        self.sponge_state = Some(sponge_state);
+        self
+    }
+
+    pub fn with_words_statically_allocated(mut self, words_statically_allocated: u32) -> Self {
+        self.words_statically_allocated = words_statically_allocated;
+        self
+    }
+
+    /// The address after the highest address used in memory so far.
+    fn first_unused_address(&self) -> BFieldElement {
+        self.memory
+            .keys()
+            .map(|address| address.value() + 1)
+            .fold(FIRST_LIST_ADDRESS, u64::max)
+            .into()
+    }
+
+    fn nondeterminism(&self) -> NonDeterminism<BFieldElement> {
+        NonDeterminism::new(self.individual_tokens.clone())
+            .with_digests(self.digests.clone())
+            .with_ram(self.memory.clone())
+    }
+
+    pub fn build_execution_state(self) -> ExecutionState {
+        ExecutionState {
+            nondeterminism: self.nondeterminism(),
+            stack: self.stack,
+            std_in: self.stdin,
+            words_allocated: self.words_statically_allocated,
+        }
+    }
+
+    /// Panics if the state holds anything but a stack and memory.
+    pub fn build_function_state(self) -> FunctionInitialState {
+        assert!(
+            self.stdin.is_empty()
+                && self.individual_tokens.is_empty()
+                && self.digests.is_empty()
+                && self.sponge_state.is_none(),
+            "initial state of a function must only consist of stack and memory"
+        );
+        FunctionInitialState {
+            stack: self.stack,
+            memory: self.memory,
+        }
+    }
+
+    /// Panics if the state holds standard input or a sponge state.
+    pub fn build_algorithm_state(self) -> AlgorithmInitialState {
+        assert!(
+            self.stdin.is_empty() && self.sponge_state.is_none(),
+            "initial state of an algorithm must not have standard input or a sponge state"
+        );
+        AlgorithmInitialState {
+            nondeterminism: self.nondeterminism(),
+            stack: self.stack,
+        }
+    }
+
+    pub fn build_procedure_state(self) -> ProcedureInitialState {
+        ProcedureInitialState {
+            nondeterminism: self.nondeterminism(),
+            stack: self.stack,
+            public_input: self.stdin,
+            sponge_state: self.sponge_state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_are_placed_after_used_memory() {
+        let element_type = DataType::U64;
+        let elements = element_type.random_elements(3);
+        let object_address = BFieldElement::new(10);
+        let state = InitialStateBuilder::new()
+            .with_object(object_address, &vec![BFieldElement::new(7); 2])
+            .with_list(ListType::Safe, &element_type, elements.clone())
+            .with_list(ListType::Unsafe, &element_type, elements)
+            .build_function_state();
+
+        // object: length indicator and two elements at addresses 10 through 12
+        let [.., safe_list_pointer, unsafe_list_pointer] = state.stack[..] else {
+            panic!("list pointers must be on the stack");
+        };
+        assert_eq!(BFieldElement::new(13), safe_list_pointer);
+        assert_eq!(BFieldElement::new(13 + 2 + 3 * 2), unsafe_list_pointer);
+        assert_eq!(3, state.memory[&unsafe_list_pointer].value());
+    }
+
+    #[test]
+    fn stack_values_are_pushed_in_order() {
+        let state = InitialStateBuilder::new()
+            .with_stack_values(&[BFieldElement::new(1)])
+            .with_stack_value(&(1u64 << 40))
+            .build_procedure_state();
+
+        let expected = [1, 1 << 8, 0].map(BFieldElement::new);
+        assert_eq!(&expected, &state.stack[state.stack.len() - 3..]);
+    }
+
+    #[should_panic(expected = "initial state of a function must only consist of stack and memory")]
+    #[test]
+    fn function_states_cannot_have_input_streams() {
+        InitialStateBuilder::new()
+            .with_stdin(vec![BFieldElement::new(1)])
+            .build_function_state();
+    }
+}
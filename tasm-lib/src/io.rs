@@ -1,8 +1,27 @@
 use std::fmt::Display;
 
+use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
 use triton_vm::{instruction::LabelledInstruction, triton_asm, triton_instr};
+use twenty_first::shared_math::b_field_element::BFieldElement;
 
+use crate::data_type::DataType;
+use crate::DIGEST_LENGTH;
+
+pub mod assert_stdout_matches_expected;
+pub mod claim_input;
+pub mod divine_digest_list;
+pub mod divine_u128;
+pub mod divine_u64;
+pub mod divine_value;
+pub mod read_bytes_from_stdin;
+pub mod read_digest;
+pub mod read_from_stdin;
 pub mod read_input;
+pub mod read_object_from_stdin;
+pub mod write_array_to_stdout;
+pub mod write_bytes_to_stdout;
+pub mod write_digest_to_stdout;
+pub mod write_object_to_stdout;
 pub mod write_to_stdout;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -22,6 +41,123 @@ impl Display for InputSource {
     }
 }
 
+/// The order in which the words of a digest appear in a stream of words. External systems
+/// disagree on it, and mixing them up yields a valid-looking but wrong digest.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DigestWordOrder {
+    /// Least significant word first, the order of `digest.encode()`.
+    LittleEndian,
+
+    /// Most significant word first, the reverse of `digest.encode()`.
+    BigEndian,
+}
+
+impl DigestWordOrder {
+    /// Return a string identifiying the word order and usable as assembly label
+    pub fn label_friendly_name(&self) -> &str {
+        match self {
+            DigestWordOrder::LittleEndian => "little_endian",
+            DigestWordOrder::BigEndian => "big_endian",
+        }
+    }
+
+    /// Arrange the words of a digest's encoding in this order.
+    pub fn arrange(&self, encoding: &[BFieldElement]) -> Vec<BFieldElement> {
+        match self {
+            DigestWordOrder::LittleEndian => encoding.to_vec(),
+            DigestWordOrder::BigEndian => encoding.iter().rev().copied().collect(),
+        }
+    }
+
+    /// Return the code to write a digest to output, its words in this order.
+    /// ```text
+    /// BEFORE: _ [digest]
+    /// AFTER: _
+    /// ```
+    pub fn write_digest(&self) -> Vec<LabelledInstruction> {
+        let reverse_words = match self {
+            DigestWordOrder::LittleEndian => vec![],
+            DigestWordOrder::BigEndian => reverse_top_words(DIGEST_LENGTH),
+        };
+
+        [reverse_words, write_words(DIGEST_LENGTH)].concat()
+    }
+}
+
+/// The number of bytes packed into one word of a byte string's stream encoding, see
+/// [`pack_bytes`].
+pub const BYTES_PER_WORD: usize = 4;
+
+/// Encode a byte string as a stream of words: the number of bytes, followed by the bytes
+/// packed into u32s, [`BYTES_PER_WORD`] at a time, little-endian. The unused high bytes of the
+/// last word are zero. This is the encoding that
+/// [`ReadBytesFromStdin`](read_bytes_from_stdin::ReadBytesFromStdin) expects and
+/// [`WriteBytesToStdout`](write_bytes_to_stdout::WriteBytesToStdout) produces.
+pub fn pack_bytes(bytes: &[u8]) -> Vec<BFieldElement> {
+    let packed_words = bytes.chunks(BYTES_PER_WORD).map(|chunk| {
+        let mut word = [0; BYTES_PER_WORD];
+        word[..chunk.len()].copy_from_slice(chunk);
+        BFieldElement::new(u32::from_le_bytes(word) as u64)
+    });
+
+    [BFieldElement::new(bytes.len() as u64)]
+        .into_iter()
+        .chain(packed_words)
+        .collect()
+}
+
+/// Decode a byte string from its stream encoding as produced by [`pack_bytes`]. Returns
+/// `None` if the stream is too short or not canonical.
+pub fn unpack_bytes(stream: &[BFieldElement]) -> Option<Vec<u8>> {
+    let (byte_length, packed_words) = stream.split_first()?;
+    let byte_length: u32 = byte_length.value().try_into().ok()?;
+    let byte_length = byte_length as usize;
+    let num_words = byte_length.div_ceil(BYTES_PER_WORD);
+    if packed_words.len() < num_words {
+        return None;
+    }
+
+    let mut bytes = vec![];
+    for word in &packed_words[..num_words] {
+        let word: u32 = word.value().try_into().ok()?;
+        bytes.extend(word.to_le_bytes());
+    }
+    if bytes[byte_length..].iter().any(|&padding| padding != 0) {
+        return None;
+    }
+
+    bytes.truncate(byte_length);
+    Some(bytes)
+}
+
+/// Return the code to reverse the order of the top `n` words on the stack.
+/// ```text
+/// BEFORE: _ [words; n]
+/// AFTER: _ [reversed_words; n]
+/// ```
+fn reverse_top_words(n: usize) -> Vec<LabelledInstruction> {
+    (0..n / 2)
+        .flat_map(|i| {
+            let j = n - 1 - i;
+            match i {
+                0 => triton_asm!(swap { j }),
+                _ => triton_asm!(swap { i } swap { j } swap { i }),
+            }
+        })
+        .collect()
+}
+
+/// Return the code to assert that each of the top `n` words on the stack is a valid `u32`.
+/// ```text
+/// BEFORE: _ [words; n]
+/// AFTER: _ [words; n]
+/// ```
+fn assert_top_words_are_u32(n: usize) -> Vec<LabelledInstruction> {
+    (0..n)
+        .flat_map(|i| triton_asm!(dup { i } split pop 1 push 0 eq assert))
+        .collect()
+}
+
 /// Return the code to write `n` words to output
 /// ```text
 /// BEFORE: _ [words; n]
@@ -75,4 +211,46 @@ impl InputSource {
 
         instructions
     }
+
+    /// Return the code used to read `n` words from the input source, such that the first
+    /// word read ends up on top of the stack. This is the order of a value's encoding,
+    /// which limits `n` to the number of op stack registers.
+    /// ```text
+    /// BEFORE: _
+    /// AFTER: _ [read_words; n]
+    /// ```
+    pub fn read_words_first_on_top(&self, n: usize) -> Vec<LabelledInstruction> {
+        assert!(
+            n <= NUM_OP_STACK_REGISTERS,
+            "Can only read values of at most {NUM_OP_STACK_REGISTERS} words from {self}"
+        );
+
+        [self.read_words(n), reverse_top_words(n)].concat()
+    }
+
+    /// Return the code used to read a value of the given type from the input source. The
+    /// words are expected in the order of the value's encoding, and the first one ends up
+    /// on top of the stack, as [`push_encodable`](crate::push_encodable) arranges them.
+    /// See [`read_words_first_on_top`](Self::read_words_first_on_top).
+    /// ```text
+    /// BEFORE: _
+    /// AFTER: _ [value]
+    /// ```
+    pub fn read_value(&self, data_type: &DataType) -> Vec<LabelledInstruction> {
+        self.read_words_first_on_top(data_type.stack_size())
+    }
+
+    /// Return the code used to read a digest from the input source, its words in the given
+    /// order. The digest ends up on the stack in the layout that all snippets expect, as
+    /// [`push_encodable`](crate::push_encodable) would put it there.
+    /// ```text
+    /// BEFORE: _
+    /// AFTER: _ [digest]
+    /// ```
+    pub fn read_digest(&self, word_order: DigestWordOrder) -> Vec<LabelledInstruction> {
+        match word_order {
+            DigestWordOrder::LittleEndian => self.read_value(&DataType::Digest),
+            DigestWordOrder::BigEndian => self.read_words(DIGEST_LENGTH),
+        }
+    }
 }
@@ -0,0 +1,230 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Write `num_words` words to standard output, like [`write_words`](super::write_words),
+/// after asserting that they equal the expected output held in the memory region starting at
+/// `*expected`. Lets a program check its public output against a committed expectation, for
+/// example the output of a sub-program it re-verifies.
+///
+/// The `i`th word in the memory region is the expected `i`th word of output, where the word
+/// on top of the stack is written first.
+#[derive(Clone, Debug)]
+pub struct AssertStdoutMatchesExpected {
+    pub num_words: usize,
+}
+
+impl BasicSnippet for AssertStdoutMatchesExpected {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (
+                DataType::Tuple(vec![DataType::Bfe; self.num_words]),
+                "words".to_string(),
+            ),
+            (DataType::VoidPointer, "*expected".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_assert_stdout_matches_expected___{}",
+            self.num_words
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let check_and_write_words = format!("{entrypoint}_check_and_write_words");
+
+        triton_asm!(
+            // BEFORE: _ [words; num_words] *expected
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                push {self.num_words}
+                add
+                swap 1                      // _ [words; num_words] *expected_end *expected
+                call {check_and_write_words}
+                                            // _ *expected_end *expected_end
+                pop 2
+                return
+
+            // INVARIANT: _ [words; k] *expected_end *expected_word
+            {check_and_write_words}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 2
+                dup 1
+                read_mem 1
+                pop 1                       // _ [words; k] *expected_end *expected_word word expected_word
+                eq
+                assert                      // _ [words; k] *expected_end *expected_word
+
+                swap 1
+                swap 2
+                write_io 1                  // _ [words; k - 1] *expected_end *expected_word
+                push 1
+                add
+                recurse
+        )
+    }
+}
+
+impl Procedure for AssertStdoutMatchesExpected {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let expected = stack.pop().unwrap();
+        let words = (0..self.num_words)
+            .map(|_| stack.pop().unwrap())
+            .collect::<Vec<_>>();
+
+        let expected_words = Self::expected_words(expected, self.num_words, memory);
+        assert_eq!(expected_words, words);
+
+        words
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let words = (0..self.num_words).map(|_| rng.gen()).collect::<Vec<_>>();
+        let expected = BFieldElement::new(rng.gen_range(0..1 << 30));
+
+        Self::initial_state(&words, expected, &words)
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xe0; 32]);
+        let expected = BFieldElement::new(1 << 20);
+
+        (0..self.num_words)
+            .map(|wrong_word_index| {
+                let words = (0..self.num_words).map(|_| rng.gen()).collect::<Vec<_>>();
+                let mut expected_words = words.clone();
+                expected_words[wrong_word_index].increment();
+                Self::initial_state(&words, expected, &expected_words)
+            })
+            .map(|state| (state, InstructionError::AssertionFailed))
+            .collect()
+    }
+
+    fn public_output_types(
+        &self,
+        _initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<(DataType, usize)>> {
+        Some(vec![(DataType::Bfe, self.num_words)])
+    }
+
+    fn expected_public_output(
+        &self,
+        initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<BFieldElement>> {
+        let expected = *initial_state.stack.last().unwrap();
+        let memory = &initial_state.nondeterminism.ram;
+        Some(Self::expected_words(expected, self.num_words, memory))
+    }
+}
+
+impl AssertStdoutMatchesExpected {
+    fn expected_words(
+        expected: BFieldElement,
+        num_words: usize,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Vec<BFieldElement> {
+        (0..num_words as u64)
+            .map(|i| expected + BFieldElement::new(i))
+            .map(|address| memory.get(&address).copied().unwrap_or_default())
+            .collect()
+    }
+
+    /// The state in which `words` are about to be written, the first of them on top of the
+    /// stack, and `expected_words` are the expected output.
+    fn initial_state(
+        words: &[BFieldElement],
+        expected: BFieldElement,
+        expected_words: &[BFieldElement],
+    ) -> ProcedureInitialState {
+        let mut stack = empty_stack();
+        stack.extend(words.iter().rev());
+        stack.push(expected);
+
+        let memory = expected_words
+            .iter()
+            .enumerate()
+            .map(|(i, &word)| (expected + BFieldElement::new(i as u64), word))
+            .collect();
+
+        ProcedureInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn assert_stdout_matches_expected_test() {
+        for num_words in [0, 1, 5, 6, 20] {
+            ShadowedProcedure::new(AssertStdoutMatchesExpected { num_words }).test();
+        }
+    }
+
+    #[test]
+    fn output_is_written_in_order_of_write_words() {
+        let words = [1, 2, 3].map(BFieldElement::new);
+        let expected = BFieldElement::new(1 << 20);
+        let initial_state = AssertStdoutMatchesExpected::initial_state(&words, expected, &words);
+
+        let snippet = AssertStdoutMatchesExpected { num_words: 3 };
+        let output = snippet.rust_shadow(
+            &mut initial_state.stack.clone(),
+            &mut initial_state.nondeterminism.ram.clone(),
+            &NonDeterminism::default(),
+            &[],
+            &mut None,
+        );
+        assert_eq!(words.to_vec(), output);
+        assert_eq!(Some(output), snippet.expected_public_output(&initial_state));
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_asserting_stdout_matches_expected() {
+        ShadowedProcedure::new(AssertStdoutMatchesExpected { num_words: 20 }).bench();
+    }
+}
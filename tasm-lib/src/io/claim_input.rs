@@ -0,0 +1,489 @@
+//! Parse a program's public input according to a declared schema, instead of unpacking it
+//! by hand. Values that fit on the stack end up there, lists and objects are stored in
+//! memory and represented on the stack by pointers. See [`ClaimInput`].
+
+use itertools::Itertools;
+use rand::distributions::{Distribution, Standard};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use super::read_object_from_stdin::ReadObjectFromStdin;
+use super::InputSource;
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::structure::tasm_object::TasmObject;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// A part of the schema of a program's public input.
+#[derive(Debug, Clone)]
+pub enum ClaimInputPart {
+    /// A value that fits on the stack, given in the order of its encoding. It ends up on
+    /// the stack as [`push_encodable`](crate::push_encodable) would put it there.
+    Value(DataType),
+
+    /// A list of values of the given type, given as the number of elements followed by the
+    /// elements, each in the order of its encoding. It is stored in memory as a list of the
+    /// [`ClaimInput`]'s list type, and a pointer to it ends up on the stack.
+    List(DataType),
+
+    /// An object, given as the length of its encoding followed by the encoding, as
+    /// [`ReadObjectFromStdin`] expects it. It is validated and stored in memory, and a
+    /// pointer to it ends up on the stack. Construct with [`ClaimInputPart::object`].
+    Object(ObjectSchema),
+}
+
+/// The type of an object in the schema of a program's public input.
+#[derive(Clone)]
+pub struct ObjectSchema {
+    type_name: String,
+    import_reader: fn(&mut Library) -> String,
+    store_object: fn(&[BFieldElement], &mut HashMap<BFieldElement, BFieldElement>) -> BFieldElement,
+    sample_encoding: fn(&mut StdRng) -> Vec<BFieldElement>,
+}
+
+impl std::fmt::Debug for ObjectSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectSchema")
+            .field("type_name", &self.type_name)
+            .finish()
+    }
+}
+
+impl ClaimInputPart {
+    /// An object of type `T`, see [`ClaimInputPart::Object`].
+    pub fn object<T>() -> Self
+    where
+        T: TasmObject + BFieldCodec + 'static,
+        Standard: Distribution<T>,
+    {
+        let type_name = std::any::type_name::<T>()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        ClaimInputPart::Object(ObjectSchema {
+            type_name,
+            import_reader: import_object_reader::<T>,
+            store_object: store_object::<T>,
+            sample_encoding: sample_encoding::<T>,
+        })
+    }
+
+    fn label_friendly_name(&self) -> String {
+        match self {
+            ClaimInputPart::Value(data_type) => data_type.label_friendly_name(),
+            ClaimInputPart::List(element_type) => {
+                DataType::List(Box::new(element_type.clone())).label_friendly_name()
+            }
+            ClaimInputPart::Object(schema) => format!("object_L{}R", schema.type_name),
+        }
+    }
+
+    /// The type of what ends up on the stack.
+    fn output_type(&self) -> DataType {
+        match self {
+            ClaimInputPart::Value(data_type) => data_type.clone(),
+            ClaimInputPart::List(element_type) => DataType::List(Box::new(element_type.clone())),
+            ClaimInputPart::Object(_) => DataType::VoidPointer,
+        }
+    }
+
+    fn sample_input(&self, rng: &mut StdRng) -> Vec<BFieldElement> {
+        match self {
+            ClaimInputPart::Value(data_type) => data_type.seeded_random_elements(1, rng).concat(),
+            ClaimInputPart::List(element_type) => {
+                let length = rng.gen_range(0..10);
+                let elements = element_type.seeded_random_elements(length, rng).concat();
+                [vec![BFieldElement::new(length as u64)], elements].concat()
+            }
+            ClaimInputPart::Object(schema) => {
+                let encoding = (schema.sample_encoding)(rng);
+                [vec![BFieldElement::new(encoding.len() as u64)], encoding].concat()
+            }
+        }
+    }
+}
+
+fn import_object_reader<T: TasmObject + BFieldCodec + 'static>(library: &mut Library) -> String {
+    library.import(Box::new(ReadObjectFromStdin::<T>::new(true)))
+}
+
+fn store_object<T: TasmObject + BFieldCodec>(
+    public_input: &[BFieldElement],
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) -> BFieldElement {
+    ReadObjectFromStdin::<T>::new(true).store_object(public_input, memory)
+}
+
+fn sample_encoding<T: BFieldCodec>(rng: &mut StdRng) -> Vec<BFieldElement>
+where
+    Standard: Distribution<T>,
+{
+    rng.gen::<T>().encode()
+}
+
+/// Read a program's public input according to the given schema, one part after the other.
+/// Each part's result ends up on the stack, the first part's deepest; see
+/// [`ClaimInputPart`]. The input is not checked to consist of valid values of the
+/// declared types, except for objects and the lengths of lists and objects.
+#[derive(Debug, Clone)]
+pub struct ClaimInput {
+    pub schema: Vec<ClaimInputPart>,
+    pub list_type: ListType,
+}
+
+impl BasicSnippet for ClaimInput {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        self.schema
+            .iter()
+            .enumerate()
+            .map(|(i, part)| (part.output_type(), format!("part_{i}")))
+            .collect()
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_claim_input_{}___{}",
+            self.list_type,
+            self.schema
+                .iter()
+                .map(|part| part.label_friendly_name())
+                .join("___")
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        let mut read_parts = vec![];
+        let mut subroutines = vec![];
+        for (i, part) in self.schema.iter().enumerate() {
+            match part {
+                ClaimInputPart::Value(data_type) => {
+                    read_parts.extend(InputSource::StdIn.read_value(data_type));
+                }
+                ClaimInputPart::List(element_type) => {
+                    let read_elements = format!("{entrypoint}_read_elements_of_part_{i}");
+                    let (read_list, read_elements) =
+                        self.read_list(element_type, &read_elements, library);
+                    read_parts.extend(read_list);
+                    subroutines.extend(read_elements);
+                }
+                ClaimInputPart::Object(schema) => {
+                    let read_object = (schema.import_reader)(library);
+                    read_parts.extend(triton_asm!(call { read_object }));
+                }
+            }
+        }
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ [parts]
+            {entrypoint}:
+                {&read_parts}
+                return
+
+            {&subroutines}
+        )
+    }
+}
+
+impl ClaimInput {
+    /// Return the code to read a list of elements of the given type, and the subroutine it
+    /// calls.
+    /// ```text
+    /// BEFORE: _
+    /// AFTER: _ *list
+    /// ```
+    fn read_list(
+        &self,
+        element_type: &DataType,
+        read_elements: &str,
+        library: &mut Library,
+    ) -> (Vec<LabelledInstruction>, Vec<LabelledInstruction>) {
+        let element_size = element_type.stack_size();
+        assert!(
+            element_size < 15,
+            "List elements of claim inputs must take up less than 15 words"
+        );
+
+        let new_list = library.import(self.list_type.new_list_snippet(element_type.clone()));
+        let set_length = library.import(self.list_type.set_length(element_type.clone()));
+        let metadata_size = self.list_type.metadata_size();
+        let read_element = InputSource::StdIn.read_value(element_type);
+        let write_element = element_type.write_value_to_memory_leave_pointer();
+
+        let read_list = triton_asm!(
+            read_io 1
+            dup 0
+            split
+            pop 1
+            push 0
+            eq
+            assert                  // _ length
+
+            dup 0
+            call {new_list}         // _ length *list
+            dup 1
+            call {set_length}       // _ length *list
+
+            dup 0
+            push {metadata_size}
+            add
+            dup 2                   // _ length *list *first_element length
+            call {read_elements}    // _ length *list *list_end 0
+            pop 2
+            swap 1
+            pop 1                   // _ *list
+        );
+
+        let read_elements = triton_asm!(
+            // INVARIANT: _ *element num_elements_left
+            {read_elements}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                {&read_element}     // _ *element num_elements_left [element]
+                dup {element_size + 1}
+                {&write_element}    // _ *element num_elements_left *next_element
+                swap 2
+                pop 1
+                push -1
+                add                 // _ *next_element (num_elements_left - 1)
+                recurse
+        );
+
+        (read_list, read_elements)
+    }
+
+    /// Store the list at the start of `input` in memory, as the snippet does. Returns the
+    /// pointer to the list and the rest of the input.
+    fn store_list<'a>(
+        &self,
+        element_type: &DataType,
+        input: &'a [BFieldElement],
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) -> (BFieldElement, &'a [BFieldElement]) {
+        let length: u32 = input[0].value().try_into().unwrap();
+        let length = length as usize;
+        let element_size = element_type.stack_size();
+
+        let list_pointer = dynamic_allocator(
+            self.list_type.metadata_size() + element_size * length,
+            memory,
+        );
+        match self.list_type {
+            ListType::Safe => {
+                safe_list::safe_list_new(list_pointer, length as u32, memory);
+                safe_list::safe_list_set_length(list_pointer, length, memory);
+            }
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_new(list_pointer, memory);
+                unsafe_list::unsafe_list_set_length(list_pointer, length, memory);
+            }
+        }
+
+        let (elements, rest) = input[1..].split_at(element_size * length);
+        for (i, element) in elements.chunks(element_size).enumerate() {
+            self.list_type
+                .rust_shadowing_set(list_pointer, i, element.to_vec(), memory);
+        }
+
+        (list_pointer, rest)
+    }
+
+    fn initial_state(public_input: Vec<BFieldElement>) -> ProcedureInitialState {
+        ProcedureInitialState {
+            stack: empty_stack(),
+            public_input,
+            ..Default::default()
+        }
+    }
+}
+
+impl Procedure for ClaimInput {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut input = public_input;
+        for part in self.schema.iter() {
+            match part {
+                ClaimInputPart::Value(data_type) => {
+                    let (value, rest) = input.split_at(data_type.stack_size());
+                    stack.extend(value.iter().rev());
+                    input = rest;
+                }
+                ClaimInputPart::List(element_type) => {
+                    let (list_pointer, rest) = self.store_list(element_type, input, memory);
+                    stack.push(list_pointer);
+                    input = rest;
+                }
+                ClaimInputPart::Object(schema) => {
+                    let object_pointer = (schema.store_object)(input, memory);
+                    stack.push(object_pointer);
+                    input = &input[1 + input[0].value() as usize..];
+                }
+            }
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let public_input = self
+            .schema
+            .iter()
+            .flat_map(|part| part.sample_input(&mut rng))
+            .collect();
+
+        Self::initial_state(public_input)
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let first_length_prefixed_part = self
+            .schema
+            .iter()
+            .position(|part| !matches!(part, ClaimInputPart::Value(_)));
+        let Some(first_length_prefixed_part) = first_length_prefixed_part else {
+            return vec![];
+        };
+
+        let mut rng: StdRng = SeedableRng::from_seed([0xc1; 32]);
+        let mut part_inputs = self
+            .schema
+            .iter()
+            .take(first_length_prefixed_part)
+            .map(|part| part.sample_input(&mut rng))
+            .collect_vec();
+        part_inputs.push(vec![BFieldElement::new(1 << 32)]);
+
+        let state = Self::initial_state(part_inputs.concat());
+        vec![(state, InstructionError::AssertionFailed)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::{push_encodable, Digest};
+
+    fn schema() -> Vec<ClaimInputPart> {
+        vec![
+            ClaimInputPart::Value(DataType::Digest),
+            ClaimInputPart::List(DataType::Xfe),
+            ClaimInputPart::Value(DataType::U64),
+            ClaimInputPart::object::<(Digest, XFieldElement)>(),
+            ClaimInputPart::List(DataType::Tuple(vec![DataType::Digest, DataType::U32])),
+        ]
+    }
+
+    #[test]
+    fn claim_input_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            let schema = schema();
+            ShadowedProcedure::new(ClaimInput { schema, list_type }).test();
+        }
+
+        let schema = vec![ClaimInputPart::Value(DataType::Bfe)];
+        let list_type = ListType::Unsafe;
+        ShadowedProcedure::new(ClaimInput { schema, list_type }).test();
+    }
+
+    #[test]
+    fn parts_end_up_on_stack_and_in_memory() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let program_digest: Digest = rng.gen();
+        let xfes: Vec<XFieldElement> = (0..4).map(|_| rng.gen()).collect();
+        let object: (Digest, XFieldElement) = rng.gen();
+        let public_input = [
+            program_digest.encode(),
+            xfes.encode(),
+            vec![BFieldElement::new(object.encode().len() as u64)],
+            object.encode(),
+        ]
+        .concat();
+
+        let claim_input = ClaimInput {
+            schema: vec![
+                ClaimInputPart::Value(DataType::Digest),
+                ClaimInputPart::List(DataType::Xfe),
+                ClaimInputPart::object::<(Digest, XFieldElement)>(),
+            ],
+            list_type: ListType::Unsafe,
+        };
+        let mut stack = empty_stack();
+        let mut memory = HashMap::new();
+        claim_input.rust_shadow(
+            &mut stack,
+            &mut memory,
+            &NonDeterminism::default(),
+            &public_input,
+            &mut None,
+        );
+
+        let object_pointer = stack.pop().unwrap();
+        let list_pointer = stack.pop().unwrap();
+        let mut expected_stack = empty_stack();
+        push_encodable(&mut expected_stack, &program_digest);
+        assert_eq!(expected_stack, stack);
+
+        assert_eq!(
+            xfes,
+            *Vec::<XFieldElement>::decode_from_memory(&memory, list_pointer).unwrap()
+        );
+        assert_eq!(
+            object,
+            *<(Digest, XFieldElement)>::decode_from_memory(&memory, object_pointer).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn bench_for_claim_input() {
+        ShadowedProcedure::new(ClaimInput {
+            schema: vec![
+                ClaimInputPart::Value(DataType::Digest),
+                ClaimInputPart::List(DataType::Bfe),
+                ClaimInputPart::object::<[Digest; 4]>(),
+            ],
+            list_type: ListType::Unsafe,
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,294 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use super::InputSource;
+use crate::data_type::DataType;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
+use crate::{empty_stack, Digest, VmHasherState, DIGEST_LENGTH};
+
+/// Divine a number of digests from the individual tokens of nondeterminism and store them
+/// as a list in freshly allocated memory, *e.g.*, to load an authentication path. The list's
+/// length is taken from the stack or, if `length_from_stdin` is set, read from standard
+/// input. Each digest's tokens are expected in the order of the digest's encoding.
+#[derive(Clone, Debug)]
+pub struct DivineDigestList {
+    pub list_type: ListType,
+    pub length_from_stdin: bool,
+}
+
+impl BasicSnippet for DivineDigestList {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        match self.length_from_stdin {
+            true => vec![],
+            false => vec![(DataType::U32, "length".to_string())],
+        }
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(
+            DataType::List(Box::new(DataType::Digest)),
+            "*list".to_string(),
+        )]
+    }
+
+    fn entrypoint(&self) -> String {
+        let length_source = match self.length_from_stdin {
+            true => "_length_from_stdin",
+            false => "",
+        };
+        format!(
+            "tasm_io_divine_digest_list_{}{length_source}",
+            self.list_type
+        )
+    }
+
+    fn code(&self, library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let divine_digests = format!("{entrypoint}_divine_digests");
+        let new_list = library.import(self.list_type.new_list_snippet(DataType::Digest));
+        let set_length = library.import(self.list_type.set_length(DataType::Digest));
+        let divine_digest = InputSource::SecretIn.read_value(&DataType::Digest);
+        let metadata_size = self.list_type.metadata_size();
+
+        let read_length = match self.length_from_stdin {
+            true => triton_asm!(
+                read_io 1
+                dup 0
+                split
+                pop 1
+                push 0
+                eq
+                assert                  // _ length
+            ),
+            false => vec![],
+        };
+
+        triton_asm!(
+            // BEFORE: _ [length]
+            // AFTER:  _ *list
+            {entrypoint}:
+                {&read_length}          // _ length
+                dup 0
+                call {new_list}         // _ length *list
+                dup 1
+                call {set_length}       // _ length *list
+
+                dup 0
+                push {metadata_size}
+                add
+                dup 2                   // _ length *list *first_element length
+                call {divine_digests}   // _ length *list *list_end 0
+                pop 2
+                swap 1
+                pop 1                   // _ *list
+                return
+
+            // INVARIANT: _ *element num_digests_left
+            {divine_digests}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                {&divine_digest}        // _ *element num_digests_left [digest]
+                dup 6
+                write_mem {DIGEST_LENGTH}
+                                        // _ *element num_digests_left *next_element
+                swap 2
+                pop 1
+                push -1
+                add                     // _ *next_element (num_digests_left - 1)
+                recurse
+        )
+    }
+}
+
+impl Procedure for DivineDigestList {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+    ) -> Vec<BFieldElement> {
+        let length = match self.length_from_stdin {
+            true => public_input[0],
+            false => stack.pop().unwrap(),
+        };
+        let length: u32 = length.value().try_into().unwrap();
+        let length = length as usize;
+
+        let list_pointer = dynamic_allocator(
+            self.list_type.metadata_size() + DIGEST_LENGTH * length,
+            memory,
+        );
+        match self.list_type {
+            ListType::Safe => {
+                safe_list::safe_list_new(list_pointer, length as u32, memory);
+                safe_list::safe_list_set_length(list_pointer, length, memory);
+            }
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_new(list_pointer, memory);
+                unsafe_list::unsafe_list_set_length(list_pointer, length, memory);
+            }
+        }
+
+        for i in 0..length {
+            let digest = (0..DIGEST_LENGTH).map(|_| tokens.read()).collect_vec();
+            self.list_type
+                .rust_shadowing_set(list_pointer, i, digest, memory);
+        }
+
+        stack.push(list_pointer);
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 20,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.gen_range(0..50),
+        };
+        let digests = (0..length).map(|_| rng.gen()).collect();
+
+        self.initial_state(length, digests)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xd1; 32]);
+        vec![
+            self.initial_state(0, vec![]),
+            self.initial_state(1, vec![rng.gen()]),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        if !self.length_from_stdin {
+            return vec![];
+        }
+
+        let mut state = self.initial_state(0, vec![]);
+        state.public_input = vec![BFieldElement::new(1 << 32)];
+        vec![(state, InstructionError::AssertionFailed)]
+    }
+}
+
+impl DivineDigestList {
+    fn initial_state(&self, length: u32, digests: Vec<Digest>) -> ProcedureInitialState {
+        let length = BFieldElement::from(length);
+        let individual_tokens = digests.iter().flat_map(|digest| digest.encode()).collect();
+
+        let mut stack = empty_stack();
+        let mut public_input = vec![];
+        match self.length_from_stdin {
+            true => public_input.push(length),
+            false => stack.push(length),
+        }
+
+        ProcedureInitialState {
+            stack,
+            nondeterminism: NonDeterminism::new(individual_tokens),
+            public_input,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn divine_digest_list_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            for length_from_stdin in [false, true] {
+                ShadowedProcedure::new(DivineDigestList {
+                    list_type: list_type.clone(),
+                    length_from_stdin,
+                })
+                .test();
+            }
+        }
+    }
+
+    #[test]
+    fn list_elements_are_the_divined_digests() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let digests: Vec<Digest> = (0..5).map(|_| rng.gen()).collect();
+        let snippet = DivineDigestList {
+            list_type: ListType::Unsafe,
+            length_from_stdin: false,
+        };
+        let initial_state = snippet.initial_state(5, digests.clone());
+
+        let mut stack = initial_state.stack;
+        let mut memory = HashMap::new();
+        snippet.rust_shadow(
+            &mut stack,
+            &mut memory,
+            &initial_state.nondeterminism,
+            &[],
+            &mut None,
+        );
+        let list_pointer = stack.pop().unwrap();
+
+        for (i, digest) in digests.into_iter().enumerate() {
+            let element = unsafe_list::unsafe_list_get(list_pointer, i, &memory, DIGEST_LENGTH);
+            assert_eq!(digest, *Digest::decode(&element).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn divine_digest_list_benchmark() {
+        ShadowedProcedure::new(DivineDigestList {
+            list_type: ListType::Unsafe,
+            length_from_stdin: false,
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,183 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::{assert_top_words_are_u32, InputSource};
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
+use crate::{empty_stack, VmHasherState};
+
+/// Divine a `u64` from the individual tokens of nondeterminism, least significant limb
+/// first, and assert that each of its 2 limbs is a valid `u32`. The value ends up on the
+/// stack in its canonical layout, least significant limb on top.
+#[derive(Clone, Debug)]
+pub struct DivineU64;
+
+impl BasicSnippet for DivineU64 {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U64, "value".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_io_divine_u64".to_string()
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let divine_limbs = InputSource::SecretIn.read_value(&DataType::U64);
+        let assert_limbs_are_u32 = assert_top_words_are_u32(DataType::U64.stack_size());
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ [value: u64]
+            {entrypoint}:
+                {&divine_limbs}
+                {&assert_limbs_are_u32}
+                return
+        )
+    }
+}
+
+impl Procedure for DivineU64 {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+    ) -> Vec<BFieldElement> {
+        let limbs = (0..DataType::U64.stack_size())
+            .map(|_| tokens.read())
+            .collect_vec();
+        for limb in limbs.iter().rev() {
+            let _: u32 = limb.value().try_into().unwrap();
+            stack.push(*limb);
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        Self::initial_state(Self::limbs(rng.gen()))
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        let mut states = [0, 1, u32::MAX as u64, 1 << 32, u64::MAX]
+            .into_iter()
+            .map(|value| Self::initial_state(Self::limbs(value)))
+            .collect_vec();
+
+        // tokens meant for later snippets must be left unread
+        let limbs_and_more_tokens = [Self::limbs(42), vec![BFieldElement::new(7); 3]].concat();
+        states.push(Self::initial_state(limbs_and_more_tokens));
+        states
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xd6; 32]);
+        (0..DataType::U64.stack_size())
+            .map(|non_u32_limb| {
+                let mut limbs = Self::limbs(rng.gen());
+                limbs[non_u32_limb] += BFieldElement::new(1 << 32);
+                Self::initial_state(limbs)
+            })
+            .map(|state| (state, InstructionError::AssertionFailed))
+            .collect()
+    }
+}
+
+impl DivineU64 {
+    /// The limbs of the value, least significant first.
+    fn limbs(value: u64) -> Vec<BFieldElement> {
+        (0..DataType::U64.stack_size())
+            .map(|i| (value >> (32 * i)) & u32::MAX as u64)
+            .map(|limb| BFieldElement::new(limb as u64))
+            .collect()
+    }
+
+    fn initial_state(limbs: Vec<BFieldElement>) -> ProcedureInitialState {
+        ProcedureInitialState {
+            stack: empty_stack(),
+            nondeterminism: NonDeterminism::new(limbs),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn divine_u64_test() {
+        ShadowedProcedure::new(DivineU64).test();
+    }
+
+    #[test]
+    fn least_significant_limb_ends_up_on_top() {
+        let value: u64 = 0x0123_4567_89ab_cdef;
+        let mut stack = empty_stack();
+        DivineU64.rust_shadow(
+            &mut stack,
+            &mut HashMap::new(),
+            &NonDeterminism::new(DivineU64::limbs(value)),
+            &[],
+            &mut None,
+        );
+
+        let mut expected_stack = empty_stack();
+        expected_stack.extend(DivineU64::limbs(value).into_iter().rev());
+        assert_eq!(expected_stack, stack);
+        assert_eq!(BFieldElement::new(0x89ab_cdef), stack[stack.len() - 1]);
+        assert_eq!(BFieldElement::new(0x0123_4567), stack[stack.len() - 2]);
+        assert!(DataType::U64.is_valid_stack_value(&stack[stack.len() - 2..]));
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_divine_u64() {
+        ShadowedProcedure::new(DivineU64).bench();
+    }
+}
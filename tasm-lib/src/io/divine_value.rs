@@ -0,0 +1,164 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::InputSource;
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
+use crate::{empty_stack, VmHasherState};
+
+/// Divine a value of type `DataType` from the individual tokens of nondeterminism. The
+/// tokens are expected in the order of the value's encoding, *e.g.*, as
+/// `digest.encode()`, and the value ends up on the stack in the layout that all snippets
+/// expect, as [`push_encodable`](crate::push_encodable) would put it there. See
+/// [`InputSource::read_value`].
+#[derive(Clone, Debug)]
+pub struct DivineValue {
+    pub data_type: DataType,
+}
+
+impl BasicSnippet for DivineValue {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.data_type.clone(), "value".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_divine_value___{}",
+            self.data_type.label_friendly_name()
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let divine_value = InputSource::SecretIn.read_value(&self.data_type);
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ [value]
+            {entrypoint}:
+                {&divine_value}
+                return
+        )
+    }
+}
+
+impl Procedure for DivineValue {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+    ) -> Vec<BFieldElement> {
+        let stack_size = self.data_type.stack_size();
+        let words = (0..stack_size).map(|_| tokens.read()).collect_vec();
+        for word in words.into_iter().rev() {
+            stack.push(word);
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let tokens = self.data_type.seeded_random_elements(1, &mut rng)[0].clone();
+
+        ProcedureInitialState {
+            stack: empty_stack(),
+            nondeterminism: NonDeterminism::new(tokens),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
+    use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+    use super::*;
+    use crate::push_encodable;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn divine_value_auto_test() {
+        for data_type in DataType::big_random_generatable_type_collection() {
+            if data_type.stack_size() <= NUM_OP_STACK_REGISTERS {
+                ShadowedProcedure::new(DivineValue { data_type }).test();
+            }
+        }
+    }
+
+    #[test]
+    fn divined_digest_is_laid_out_like_a_pushed_digest() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let digest: Digest = rng.gen();
+
+        let mut expected_stack = empty_stack();
+        push_encodable(&mut expected_stack, &digest);
+
+        let mut stack = empty_stack();
+        DivineValue {
+            data_type: DataType::Digest,
+        }
+        .rust_shadow(
+            &mut stack,
+            &mut HashMap::new(),
+            &NonDeterminism::new(digest.encode()),
+            &[],
+            &mut None,
+        );
+        assert_eq!(expected_stack, stack);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_digest_divining() {
+        ShadowedProcedure::new(DivineValue {
+            data_type: DataType::Digest,
+        })
+        .bench();
+    }
+}
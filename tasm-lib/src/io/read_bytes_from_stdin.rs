@@ -0,0 +1,270 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::{pack_bytes, unpack_bytes, BYTES_PER_WORD};
+use crate::data_type::DataType;
+use crate::memory::dyn_malloc::DynMalloc;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Read a byte string from standard input, encoded as by [`pack_bytes`](super::pack_bytes),
+/// and unpack it into freshly allocated memory, one byte per word. Returns the pointer to
+/// the first byte and the number of bytes. Crashes the VM if the encoding is not canonical.
+#[derive(Clone, Debug)]
+pub struct ReadBytesFromStdin;
+
+impl BasicSnippet for ReadBytesFromStdin {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*bytes".to_string()),
+            (DataType::U32, "byte_length".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_io_read_bytes_from_stdin".to_string()
+    }
+
+    fn code(&self, library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let unpack_words = format!("{entrypoint}_unpack_words");
+        let check_padding = format!("{entrypoint}_check_padding");
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+
+        let unpack_byte = triton_asm!(
+            // _ *byte num_words_left word
+            push 256
+            swap 1
+            div_mod                 // _ *byte num_words_left (word >> 8) byte
+            dup 3
+            write_mem 1             // _ *byte num_words_left (word >> 8) (*byte + 1)
+            swap 3
+            pop 1                   // _ (*byte + 1) num_words_left (word >> 8)
+        );
+        let unpack_word = vec![unpack_byte; BYTES_PER_WORD].concat();
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ *bytes byte_length
+            {entrypoint}:
+                read_io 1
+                dup 0
+                split
+                pop 1
+                push 0
+                eq
+                assert                  // _ byte_length
+
+                push {BYTES_PER_WORD}
+                dup 1
+                push {BYTES_PER_WORD - 1}
+                add
+                div_mod
+                pop 1                   // _ byte_length num_words
+
+                dup 0
+                push {BYTES_PER_WORD}
+                mul
+                call {dyn_malloc}       // _ byte_length num_words *bytes
+                swap 1
+                dup 1
+                swap 1                  // _ byte_length *bytes *bytes num_words
+                call {unpack_words}     // _ byte_length *bytes *bytes_end 0
+                pop 1
+
+                dup 1
+                dup 3
+                add                     // _ byte_length *bytes *bytes_end *padding
+                call {check_padding}    // _ byte_length *bytes *bytes_end *bytes_end
+                pop 2
+                swap 1                  // _ *bytes byte_length
+                return
+
+            // INVARIANT: _ *byte num_words_left
+            {unpack_words}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                read_io 1
+                dup 0
+                split
+                pop 1
+                push 0
+                eq
+                assert                  // _ *byte num_words_left word
+
+                {&unpack_word}          // _ (*byte + 4) num_words_left 0
+                pop 1
+                push -1
+                add                     // _ (*byte + 4) (num_words_left - 1)
+                recurse
+
+            // The unused high bytes of the last word must be zero.
+            // INVARIANT: _ *bytes_end *padding
+            {check_padding}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 0
+                read_mem 1
+                pop 1
+                push 0
+                eq
+                assert                  // _ *bytes_end *padding
+                push 1
+                add                     // _ *bytes_end (*padding + 1)
+                recurse
+        )
+    }
+}
+
+impl Procedure for ReadBytesFromStdin {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let bytes = unpack_bytes(public_input).unwrap();
+        let num_words = bytes.len().div_ceil(BYTES_PER_WORD);
+
+        let bytes_pointer = dynamic_allocator(num_words * BYTES_PER_WORD, memory);
+        for i in 0..num_words * BYTES_PER_WORD {
+            let byte = bytes.get(i).copied().unwrap_or_default();
+            let address = bytes_pointer + BFieldElement::new(i as u64);
+            memory.insert(address, BFieldElement::new(byte as u64));
+        }
+
+        stack.push(bytes_pointer);
+        stack.push(BFieldElement::new(bytes.len() as u64));
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let byte_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 100,
+            Some(BenchmarkCase::WorstCase) => 1000,
+            None => rng.gen_range(0..200),
+        };
+        let bytes = (0..byte_length).map(|_| rng.gen()).collect::<Vec<u8>>();
+
+        Self::initial_state(pack_bytes(&bytes))
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        [0, 1, 3, 4, 5]
+            .into_iter()
+            .map(|byte_length| vec![0xff; byte_length])
+            .map(|bytes| Self::initial_state(pack_bytes(&bytes)))
+            .collect()
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let non_u32_length = vec![BFieldElement::new(1 << 32)];
+
+        let mut non_u32_word = pack_bytes(b"abcdefgh");
+        non_u32_word[2] += BFieldElement::new(1 << 32);
+
+        let mut nonzero_padding = pack_bytes(b"abcde");
+        nonzero_padding[2] = BFieldElement::new(0x0165);
+
+        [non_u32_length, non_u32_word, nonzero_padding]
+            .into_iter()
+            .map(|public_input| {
+                let state = Self::initial_state(public_input);
+                (state, InstructionError::AssertionFailed)
+            })
+            .collect()
+    }
+}
+
+impl ReadBytesFromStdin {
+    fn initial_state(public_input: Vec<BFieldElement>) -> ProcedureInitialState {
+        ProcedureInitialState {
+            stack: empty_stack(),
+            public_input,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn read_bytes_from_stdin_test() {
+        ShadowedProcedure::new(ReadBytesFromStdin).test();
+    }
+
+    #[test]
+    fn bytes_are_packed_little_endian() {
+        let expected = [5, 0x6463_6261, 0x65].map(BFieldElement::new).to_vec();
+        assert_eq!(expected, pack_bytes(b"abcde"));
+        assert_eq!(Some(b"abcde".to_vec()), unpack_bytes(&expected));
+    }
+
+    #[test]
+    fn unpacked_bytes_are_in_memory() {
+        let bytes = b"an address, or a note".to_vec();
+        let mut stack = empty_stack();
+        let mut memory = HashMap::new();
+        ReadBytesFromStdin.rust_shadow(
+            &mut stack,
+            &mut memory,
+            &NonDeterminism::default(),
+            &pack_bytes(&bytes),
+            &mut None,
+        );
+
+        let byte_length = stack.pop().unwrap().value();
+        let bytes_pointer = stack.pop().unwrap();
+        let bytes_in_memory = (0..byte_length)
+            .map(|i| memory[&(bytes_pointer + BFieldElement::new(i))].value() as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(bytes, bytes_in_memory);
+    }
+
+    #[test]
+    fn non_canonical_encodings_are_rejected() {
+        assert_eq!(None, unpack_bytes(&[]));
+        assert_eq!(None, unpack_bytes(&[BFieldElement::new(5)]));
+        assert_eq!(None, unpack_bytes(&[1, 0x100].map(BFieldElement::new)));
+        assert_eq!(None, unpack_bytes(&[1, 1 << 32].map(BFieldElement::new)));
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_bytes_reading() {
+        ShadowedProcedure::new(ReadBytesFromStdin).bench();
+    }
+}
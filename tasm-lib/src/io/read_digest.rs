@@ -0,0 +1,192 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use super::{DigestWordOrder, InputSource};
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
+use crate::{empty_stack, Digest, VmHasherState, DIGEST_LENGTH};
+
+/// Read a digest from standard input or divine it from the individual tokens of
+/// nondeterminism, its words in the given [`DigestWordOrder`]. Either way, the digest ends
+/// up on the stack in the layout that all snippets expect.
+#[derive(Clone, Debug)]
+pub struct ReadDigest {
+    pub input_source: InputSource,
+    pub word_order: DigestWordOrder,
+}
+
+impl BasicSnippet for ReadDigest {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Digest, "digest".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_read_digest_{}___{}",
+            self.input_source.label_friendly_name(),
+            self.word_order.label_friendly_name()
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let read_digest = self.input_source.read_digest(self.word_order);
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ [digest]
+            {entrypoint}:
+                {&read_digest}
+                return
+        )
+    }
+}
+
+impl Procedure for ReadDigest {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+    ) -> Vec<BFieldElement> {
+        let input_words = match self.input_source {
+            InputSource::StdIn => public_input[..DIGEST_LENGTH].to_vec(),
+            InputSource::SecretIn => (0..DIGEST_LENGTH).map(|_| tokens.read()).collect(),
+        };
+        let words = self.word_order.arrange(&input_words);
+        for word in words.into_iter().rev() {
+            stack.push(word);
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        self.initial_state(rng.gen())
+    }
+}
+
+impl ReadDigest {
+    fn initial_state(&self, digest: Digest) -> ProcedureInitialState {
+        let input_stream = self.word_order.arrange(&digest.encode());
+        let mut state = ProcedureInitialState {
+            stack: empty_stack(),
+            ..Default::default()
+        };
+        match self.input_source {
+            InputSource::StdIn => state.public_input = input_stream,
+            InputSource::SecretIn => state.nondeterminism = NonDeterminism::new(input_stream),
+        }
+
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push_encodable;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn read_digest_test() {
+        for input_source in [InputSource::StdIn, InputSource::SecretIn] {
+            for word_order in [DigestWordOrder::LittleEndian, DigestWordOrder::BigEndian] {
+                ShadowedProcedure::new(ReadDigest {
+                    input_source,
+                    word_order,
+                })
+                .test();
+            }
+        }
+    }
+
+    #[test]
+    fn digest_on_stack_is_independent_of_word_order() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let digest: Digest = rng.gen();
+        let mut expected_stack = empty_stack();
+        push_encodable(&mut expected_stack, &digest);
+
+        for word_order in [DigestWordOrder::LittleEndian, DigestWordOrder::BigEndian] {
+            let snippet = ReadDigest {
+                input_source: InputSource::StdIn,
+                word_order,
+            };
+            let initial_state = snippet.initial_state(digest);
+            let mut stack = initial_state.stack;
+            snippet.rust_shadow(
+                &mut stack,
+                &mut HashMap::new(),
+                &initial_state.nondeterminism,
+                &initial_state.public_input,
+                &mut None,
+            );
+            assert_eq!(expected_stack, stack);
+        }
+    }
+
+    #[test]
+    fn big_endian_stream_is_reversed_encoding() {
+        let mut rng: StdRng = SeedableRng::from_seed([1; 32]);
+        let digest: Digest = rng.gen();
+        let mut encoding = digest.encode();
+        assert_eq!(encoding, DigestWordOrder::LittleEndian.arrange(&encoding));
+
+        let big_endian = DigestWordOrder::BigEndian.arrange(&encoding);
+        encoding.reverse();
+        assert_eq!(encoding, big_endian);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_big_endian_digest_reading() {
+        ShadowedProcedure::new(ReadDigest {
+            input_source: InputSource::StdIn,
+            word_order: DigestWordOrder::BigEndian,
+        })
+        .bench();
+    }
+}
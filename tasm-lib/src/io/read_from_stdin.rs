@@ -0,0 +1,164 @@
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::InputSource;
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Read a value of type `DataType` from standard input onto the stack. This is the inverse
+/// of [`WriteToStdout`](super::write_to_stdout::WriteToStdout): reading what it wrote
+/// restores the value, with the value's first word on top of the stack, which is also the
+/// order of a value's encoding.
+///
+/// Unlike [`ReadInput`](super::read_input::ReadInput), which leaves the words in the order
+/// in which `read_io` pushes them, this snippet reverses them. See
+/// [`InputSource::read_value`].
+#[derive(Clone, Debug)]
+pub struct ReadFromStdin {
+    pub data_type: DataType,
+}
+
+impl BasicSnippet for ReadFromStdin {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.data_type.clone(), "value".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_read_from_stdin___{}",
+            self.data_type.label_friendly_name()
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let read_value = InputSource::StdIn.read_value(&self.data_type);
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ [value]
+            {entrypoint}:
+                {&read_value}
+                return
+        )
+    }
+}
+
+impl Procedure for ReadFromStdin {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let stack_size = self.data_type.stack_size();
+        for elem in public_input[..stack_size].iter().rev() {
+            stack.push(*elem);
+        }
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let public_input = self.data_type.seeded_random_elements(1, &mut rng)[0].clone();
+
+        ProcedureInitialState {
+            stack: empty_stack(),
+            public_input,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
+
+    use super::*;
+    use crate::io::write_to_stdout::WriteToStdout;
+    use crate::library::Library;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    fn readable_data_types() -> Vec<DataType> {
+        DataType::big_random_generatable_type_collection()
+            .into_iter()
+            .filter(|data_type| data_type.stack_size() <= NUM_OP_STACK_REGISTERS)
+            .collect()
+    }
+
+    #[test]
+    fn read_from_stdin_auto_test() {
+        for data_type in readable_data_types() {
+            ShadowedProcedure::new(ReadFromStdin { data_type }).test();
+        }
+    }
+
+    #[test]
+    fn reading_what_was_written_restores_the_value() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        for data_type in readable_data_types() {
+            let value = data_type.seeded_random_elements(1, &mut rng)[0].clone();
+            let stack = [empty_stack(), value.into_iter().rev().collect()].concat();
+
+            let mut write_stack = stack.clone();
+            let output = WriteToStdout {
+                data_type: data_type.clone(),
+            }
+            .rust_shadow(
+                &mut write_stack,
+                &mut HashMap::new(),
+                &NonDeterminism::default(),
+                &[],
+                &mut None,
+            );
+
+            let mut read_stack = write_stack;
+            ReadFromStdin { data_type }.rust_shadow(
+                &mut read_stack,
+                &mut HashMap::new(),
+                &NonDeterminism::default(),
+                &output,
+                &mut None,
+            );
+            assert_eq!(stack, read_stack);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn values_exceeding_the_op_stack_are_rejected() {
+        let data_type = DataType::Tuple(vec![DataType::Digest; 4]);
+        ReadFromStdin { data_type }.code(&mut Library::new());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_digest_reading() {
+        ShadowedProcedure::new(ReadFromStdin {
+            data_type: DataType::Digest,
+        })
+        .bench();
+    }
+}
@@ -5,9 +5,9 @@ use twenty_first::shared_math::{b_field_element::BFieldElement, bfield_codec::BF
 
 use super::InputSource;
 use crate::data_type::DataType;
-use crate::empty_stack;
+use crate::initial_state::InitialStateBuilder;
 use crate::traits::basic_snippet::BasicSnippet;
-use crate::traits::procedure::Procedure;
+use crate::traits::procedure::{Procedure, TokenCursor};
 
 /// Move an element of type `DataType` from standard-in or secret-in's token stream to the stack
 #[derive(Clone, Debug)]
@@ -48,17 +48,43 @@ impl Procedure for ReadInput {
     fn rust_shadow(
         &self,
         stack: &mut Vec<BFieldElement>,
-        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
         nondeterminism: &NonDeterminism<BFieldElement>,
         public_input: &[BFieldElement],
+        sponge_state: &mut Option<crate::VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
         _sponge_state: &mut Option<crate::VmHasherState>,
+        tokens: &mut TokenCursor,
     ) -> Vec<BFieldElement> {
-        let input_source = match self.input_source {
-            InputSource::StdIn => public_input,
-            InputSource::SecretIn => &nondeterminism.individual_tokens,
+        let elements: Vec<BFieldElement> = match self.input_source {
+            InputSource::StdIn => public_input
+                .iter()
+                .take(self.data_type.stack_size())
+                .copied()
+                .collect(),
+            InputSource::SecretIn => (0..self.data_type.stack_size())
+                .map(|_| tokens.read())
+                .collect(),
         };
-        for elem in input_source.iter().take(self.data_type.stack_size()) {
-            stack.push(*elem);
+        for elem in elements {
+            stack.push(elem);
         }
 
         // Output nothing
@@ -72,17 +98,13 @@ impl Procedure for ReadInput {
     ) -> ProcedureInitialState {
         let input_stream: Vec<BFieldElement> = self.data_type.random_elements(1)[0].encode();
 
-        let (std_in, secret_in) = match self.input_source {
-            InputSource::StdIn => (input_stream, vec![]),
-            InputSource::SecretIn => (vec![], input_stream),
+        let builder = InitialStateBuilder::new();
+        let builder = match self.input_source {
+            InputSource::StdIn => builder.with_stdin(input_stream),
+            InputSource::SecretIn => builder.with_individual_tokens(input_stream),
         };
 
-        ProcedureInitialState {
-            stack: empty_stack(),
-            nondeterminism: NonDeterminism::new(secret_in),
-            public_input: std_in,
-            sponge_state: None,
-        }
+        builder.build_procedure_state()
     }
 }
 
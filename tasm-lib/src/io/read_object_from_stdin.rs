@@ -0,0 +1,307 @@
+use rand::distributions::{Distribution, Standard};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use super::InputSource;
+use crate::data_type::DataType;
+use crate::memory::dyn_malloc::DynMalloc;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::structure::tasm_object::TasmObject;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Read the encoding of an object of type `T` from standard input, prefixed with the
+/// encoding's length, and store it in memory allocated through [`DynMalloc`]. Returns the
+/// pointer to the object, such that the object can be accessed through `T`'s
+/// [`TasmObject`] implementation.
+///
+/// If `validate` is set, the object is checked with [`TasmObject::validate_encoding`] and
+/// the sizes reported by it and by [`TasmObject::size_in_memory`] must both equal the length
+/// prefix. Otherwise, the input is trusted to be a valid encoding.
+///
+/// The entrypoint contains `T`'s type name, made label-friendly.
+#[derive(Debug, Clone)]
+pub struct ReadObjectFromStdin<T> {
+    pub validate: bool,
+    _object: PhantomData<T>,
+}
+
+impl<T> ReadObjectFromStdin<T> {
+    pub fn new(validate: bool) -> Self {
+        Self {
+            validate,
+            _object: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject + BFieldCodec> BasicSnippet for ReadObjectFromStdin<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*object".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        let type_name = std::any::type_name::<T>()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        match self.validate {
+            true => format!("tasm_io_read_validated_object_from_stdin___{type_name}"),
+            false => format!("tasm_io_read_object_from_stdin___{type_name}"),
+        }
+    }
+
+    fn code(&self, library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let read_chunks = format!("{entrypoint}_read_chunks");
+        let read_words = format!("{entrypoint}_read_words");
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+        let read_chunk = InputSource::StdIn.read_words_first_on_top(5);
+
+        let validate = match self.validate {
+            true => {
+                let validate_encoding = T::validate_encoding(library);
+                let size_in_memory = T::size_in_memory();
+                triton_asm!(
+                    dup 0
+                    dup 2                   // _ length *object *object length
+                    {&validate_encoding}    // _ length *object validated_size
+                    dup 2
+                    eq
+                    assert                  // _ length *object
+
+                    dup 0
+                    {&size_in_memory}       // _ length *object size
+                    dup 2
+                    eq
+                    assert                  // _ length *object
+                )
+            }
+            false => vec![],
+        };
+
+        triton_asm!(
+            // BEFORE: _
+            // AFTER:  _ *object
+            {entrypoint}:
+                read_io 1
+                dup 0
+                split
+                pop 1
+                push 0
+                eq
+                assert                  // _ length
+
+                dup 0
+                call {dyn_malloc}       // _ length *object
+                dup 0
+                dup 2                   // _ length *object *object length
+                call {read_chunks}      // _ length *object *word (length % 5)
+                call {read_words}       // _ length *object *object_end 0
+                pop 2
+
+                {&validate}
+                swap 1
+                pop 1                   // _ *object
+                return
+
+            // Read 5 words at a time while at least 5 words remain.
+            // INVARIANT: _ *word num_words_left
+            {read_chunks}:
+                push 5
+                dup 1
+                lt                      // _ *word num_words_left (num_words_left < 5)
+                skiz return
+
+                {&read_chunk}           // _ *word num_words_left [words; 5]
+                dup 6
+                write_mem 5             // _ *word num_words_left (*word + 5)
+                swap 2
+                pop 1
+                push -5
+                add                     // _ (*word + 5) (num_words_left - 5)
+                recurse
+
+            // INVARIANT: _ *word num_words_left
+            {read_words}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                read_io 1
+                dup 2
+                write_mem 1             // _ *word num_words_left (*word + 1)
+                swap 2
+                pop 1
+                push -1
+                add                     // _ (*word + 1) (num_words_left - 1)
+                recurse
+        )
+    }
+}
+
+impl<T: TasmObject + BFieldCodec> ReadObjectFromStdin<T> {
+    /// Store the length-prefixed object at the start of `public_input` in memory, as the
+    /// snippet does, and return the pointer to it.
+    pub(crate) fn store_object(
+        &self,
+        public_input: &[BFieldElement],
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) -> BFieldElement {
+        let length: u32 = public_input[0].value().try_into().unwrap();
+        let encoding = &public_input[1..1 + length as usize];
+
+        if self.validate {
+            let object = T::decode_iter(&mut encoding.iter().copied()).unwrap();
+            assert_eq!(encoding.len(), object.encode().len());
+        }
+
+        let object = dynamic_allocator(length as usize, memory);
+        for (i, &word) in encoding.iter().enumerate() {
+            memory.insert(object + BFieldElement::new(i as u64), word);
+        }
+
+        object
+    }
+}
+
+impl<T> Procedure for ReadObjectFromStdin<T>
+where
+    T: TasmObject + BFieldCodec,
+    Standard: Distribution<T>,
+{
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        stack.push(self.store_object(public_input, memory));
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        Self::initial_state(rng.gen::<T>().encode())
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let mut non_u32_length = Self::initial_state(vec![]);
+        non_u32_length.public_input = vec![BFieldElement::new(1 << 32)];
+        let mut failing_states = vec![non_u32_length];
+
+        if self.validate {
+            let mut rng: StdRng = SeedableRng::from_seed([0x0b; 32]);
+            let encoding = rng.gen::<T>().encode();
+            let mut trailing_word = encoding.clone();
+            trailing_word.push(BFieldElement::new(0));
+            let truncated = encoding[..encoding.len() - 1].to_vec();
+            failing_states.push(Self::initial_state(trailing_word));
+            failing_states.push(Self::initial_state(truncated));
+        }
+
+        failing_states
+            .into_iter()
+            .map(|state| (state, InstructionError::AssertionFailed))
+            .collect()
+    }
+}
+
+impl<T> ReadObjectFromStdin<T> {
+    fn initial_state(encoding: Vec<BFieldElement>) -> ProcedureInitialState {
+        ProcedureInitialState {
+            stack: empty_stack(),
+            public_input: [vec![BFieldElement::new(encoding.len() as u64)], encoding].concat(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
+    struct Claim {
+        program_digest: Digest,
+        input: Vec<BFieldElement>,
+        output: Vec<BFieldElement>,
+    }
+
+    impl Distribution<Claim> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Claim {
+            let input_length = rng.gen_range(0..20);
+            let output_length = rng.gen_range(0..20);
+            Claim {
+                program_digest: rng.gen(),
+                input: (0..input_length).map(|_| rng.gen()).collect(),
+                output: (0..output_length).map(|_| rng.gen()).collect(),
+            }
+        }
+    }
+
+    #[test]
+    fn read_object_from_stdin_test() {
+        for validate in [false, true] {
+            ShadowedProcedure::new(ReadObjectFromStdin::<Claim>::new(validate)).test();
+            ShadowedProcedure::new(ReadObjectFromStdin::<(Digest, XFieldElement)>::new(
+                validate,
+            ))
+            .test();
+        }
+    }
+
+    #[test]
+    fn object_in_memory_decodes_to_object_from_stdin() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let claim: Claim = rng.gen();
+        let initial_state = ReadObjectFromStdin::<Claim>::initial_state(claim.encode());
+
+        let mut stack = initial_state.stack;
+        let mut memory = HashMap::new();
+        ReadObjectFromStdin::<Claim>::new(true).rust_shadow(
+            &mut stack,
+            &mut memory,
+            &NonDeterminism::default(),
+            &initial_state.public_input,
+            &mut None,
+        );
+        let object = stack.pop().unwrap();
+        assert_eq!(claim, *Claim::decode_from_memory(&memory, object).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn bench_for_object_reading() {
+        ShadowedProcedure::new(ReadObjectFromStdin::<[Digest; 4]>::new(true)).bench();
+    }
+}
@@ -0,0 +1,183 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Write an array of `length` values of type `DataType`, which lives in memory, to standard
+/// output. The output is the array's encoding: the values, one after the other, each in the
+/// order in which [`WriteToStdout`](super::write_to_stdout::WriteToStdout) writes it.
+#[derive(Clone, Debug)]
+pub struct WriteArrayToStdout {
+    pub data_type: DataType,
+    pub length: usize,
+}
+
+impl BasicSnippet for WriteArrayToStdout {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*array".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_write_array_to_stdout___{}_{}",
+            self.data_type.label_friendly_name(),
+            self.length
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let write_elements = format!("{entrypoint}_write_elements");
+        let element_size = self.data_type.stack_size();
+        let read_element = self.data_type.read_value_from_memory_pop_pointer();
+        let write_element = self.data_type.write_value_to_stdout();
+
+        triton_asm!(
+            // BEFORE: _ *array
+            // AFTER:  _
+            {entrypoint}:
+                push {self.length}
+                call {write_elements}   // _ *array_end 0
+                pop 2
+                return
+
+            // INVARIANT: _ *element num_elements_left
+            {write_elements}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                dup 1
+                push {element_size - 1}
+                add                     // _ *element num_elements_left *last_word
+                {&read_element}         // _ *element num_elements_left [element]
+                {&write_element}        // _ *element num_elements_left
+
+                swap 1
+                push {element_size}
+                add
+                swap 1
+                push -1
+                add                     // _ *next_element (num_elements_left - 1)
+                recurse
+        )
+    }
+}
+
+impl Procedure for WriteArrayToStdout {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let array = stack.pop().unwrap();
+        let num_words = self.length * self.data_type.stack_size();
+        (0..num_words as u64)
+            .map(|i| array + BFieldElement::new(i))
+            .map(|address| memory.get(&address).copied().unwrap_or_default())
+            .collect()
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let array = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let words = self
+            .data_type
+            .seeded_random_elements(self.length, &mut rng)
+            .concat();
+        let memory = words
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (array + BFieldElement::new(i as u64), word))
+            .collect();
+
+        ProcedureInitialState {
+            stack: [empty_stack(), vec![array]].concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            ..Default::default()
+        }
+    }
+
+    fn public_output_types(
+        &self,
+        _initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<(DataType, usize)>> {
+        Some(vec![(self.data_type.clone(), self.length)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+    use super::*;
+    use crate::memory::encode_to_memory;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn write_array_to_stdout_test() {
+        for data_type in DataType::big_random_generatable_type_collection() {
+            for length in [0, 1, 4, 10] {
+                let data_type = data_type.clone();
+                ShadowedProcedure::new(WriteArrayToStdout { data_type, length }).test();
+            }
+        }
+    }
+
+    #[test]
+    fn output_is_the_arrays_encoding() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let array: [Digest; 3] = rng.gen();
+        let array_pointer = BFieldElement::new(1 << 20);
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, array_pointer, array);
+
+        let snippet = WriteArrayToStdout {
+            data_type: DataType::Digest,
+            length: 3,
+        };
+        let output = snippet.rust_shadow(
+            &mut [empty_stack(), vec![array_pointer]].concat(),
+            &mut memory,
+            &NonDeterminism::default(),
+            &[],
+            &mut None,
+        );
+        assert_eq!(array.encode(), output);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_digest_array_writing() {
+        ShadowedProcedure::new(WriteArrayToStdout {
+            data_type: DataType::Digest,
+            length: 20,
+        })
+        .bench();
+    }
+}
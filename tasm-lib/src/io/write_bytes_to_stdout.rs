@@ -0,0 +1,247 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::{pack_bytes, BYTES_PER_WORD};
+use crate::data_type::DataType;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Write a byte string, which lives in memory one byte per word, to standard output,
+/// encoded as by [`pack_bytes`](super::pack_bytes). The inverse of
+/// [`ReadBytesFromStdin`](super::read_bytes_from_stdin::ReadBytesFromStdin). Every word of
+/// the byte string is assumed to hold a value smaller than 256.
+#[derive(Clone, Debug)]
+pub struct WriteBytesToStdout;
+
+impl BasicSnippet for WriteBytesToStdout {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*bytes".to_string()),
+            (DataType::U32, "byte_length".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_io_write_bytes_to_stdout".to_string()
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let pack_words = format!("{entrypoint}_pack_words");
+        let pack_word = format!("{entrypoint}_pack_word");
+        let minus_bytes_per_word = -(BYTES_PER_WORD as i32);
+
+        triton_asm!(
+            // BEFORE: _ *bytes byte_length
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                write_io 1              // _ *bytes byte_length
+                call {pack_words}       // _ *bytes_end 0
+                pop 2
+                return
+
+            // INVARIANT: _ *byte num_bytes_left
+            {pack_words}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                push {BYTES_PER_WORD}
+                dup 1
+                lt                      // _ *byte num_bytes_left (num_bytes_left < 4)
+                dup 1
+                push {minus_bytes_per_word}
+                add
+                mul
+                push {BYTES_PER_WORD}
+                add                     // _ *byte num_bytes_left num_packed_bytes
+
+                dup 2
+                dup 1
+                add
+                push -1
+                add                     // _ *byte num_bytes_left num_packed_bytes *last_packed_byte
+                dup 1
+                push 0                  // _ *byte num_bytes_left num_packed_bytes *last_packed_byte num_packed_bytes 0
+                call {pack_word}        // _ *byte num_bytes_left num_packed_bytes *(byte - 1) 0 word
+                write_io 1
+                pop 2                   // _ *byte num_bytes_left num_packed_bytes
+
+                swap 1
+                dup 1
+                push -1
+                mul
+                add                     // _ *byte num_packed_bytes (num_bytes_left - num_packed_bytes)
+                swap 2
+                add
+                swap 1                  // _ (*byte + num_packed_bytes) (num_bytes_left - num_packed_bytes)
+                recurse
+
+            // Accumulate bytes from most to least significant.
+            // INVARIANT: _ *byte num_bytes_left word
+            {pack_word}:
+                dup 1
+                push 0
+                eq
+                skiz return
+
+                push 256
+                mul
+                dup 2
+                read_mem 1
+                pop 1
+                add                     // _ *byte num_bytes_left (word * 256 + byte)
+                swap 2
+                push -1
+                add
+                swap 2
+                swap 1
+                push -1
+                add
+                swap 1                  // _ (*byte - 1) (num_bytes_left - 1) (word * 256 + byte)
+                recurse
+        )
+    }
+}
+
+impl Procedure for WriteBytesToStdout {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let byte_length = stack.pop().unwrap().value();
+        let bytes_pointer = stack.pop().unwrap();
+        let bytes = (0..byte_length)
+            .map(|i| bytes_pointer + BFieldElement::new(i))
+            .map(|address| memory.get(&address).copied().unwrap_or_default())
+            .map(|byte| byte.value() as u8)
+            .collect::<Vec<_>>();
+
+        pack_bytes(&bytes)
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let byte_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 100,
+            Some(BenchmarkCase::WorstCase) => 1000,
+            None => rng.gen_range(0..200),
+        };
+        let bytes = (0..byte_length).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let bytes_pointer = BFieldElement::new(rng.gen_range(0..1 << 30));
+
+        Self::initial_state(bytes_pointer, &bytes)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        [0, 1, 3, 4, 5]
+            .into_iter()
+            .map(|byte_length| {
+                Self::initial_state(BFieldElement::new(1 << 20), &vec![0xff; byte_length])
+            })
+            .collect()
+    }
+}
+
+impl WriteBytesToStdout {
+    fn initial_state(bytes_pointer: BFieldElement, bytes: &[u8]) -> ProcedureInitialState {
+        let memory = bytes
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| {
+                (
+                    bytes_pointer + BFieldElement::new(i as u64),
+                    BFieldElement::new(byte as u64),
+                )
+            })
+            .collect();
+        let byte_length = BFieldElement::new(bytes.len() as u64);
+
+        ProcedureInitialState {
+            stack: [empty_stack(), vec![bytes_pointer, byte_length]].concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::read_bytes_from_stdin::ReadBytesFromStdin;
+    use crate::io::unpack_bytes;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn write_bytes_to_stdout_test() {
+        ShadowedProcedure::new(WriteBytesToStdout).test();
+    }
+
+    #[test]
+    fn reading_written_bytes_round_trips() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        for byte_length in [0, 1, 4, 7, 100] {
+            let bytes = (0..byte_length).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let initial_state =
+                WriteBytesToStdout::initial_state(BFieldElement::new(1 << 20), &bytes);
+            let mut memory = initial_state.nondeterminism.ram.clone();
+
+            let output = WriteBytesToStdout.rust_shadow(
+                &mut initial_state.stack.clone(),
+                &mut memory,
+                &NonDeterminism::default(),
+                &[],
+                &mut None,
+            );
+            assert_eq!(Some(bytes.clone()), unpack_bytes(&output));
+
+            let mut stack = empty_stack();
+            ReadBytesFromStdin.rust_shadow(
+                &mut stack,
+                &mut memory,
+                &NonDeterminism::default(),
+                &output,
+                &mut None,
+            );
+            let output_again = WriteBytesToStdout.rust_shadow(
+                &mut stack,
+                &mut memory,
+                &NonDeterminism::default(),
+                &[],
+                &mut None,
+            );
+            assert_eq!(output, output_again);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_bytes_writing() {
+        ShadowedProcedure::new(WriteBytesToStdout).bench();
+    }
+}
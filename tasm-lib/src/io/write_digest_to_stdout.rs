@@ -0,0 +1,166 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use super::DigestWordOrder;
+use crate::data_type::DataType;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, push_encodable, Digest, VmHasherState, DIGEST_LENGTH};
+
+/// Write a digest to standard output, its words in the given [`DigestWordOrder`]. The
+/// inverse of [`ReadDigest`](super::read_digest::ReadDigest) with the same word order.
+#[derive(Clone, Debug)]
+pub struct WriteDigestToStdout {
+    pub word_order: DigestWordOrder,
+}
+
+impl BasicSnippet for WriteDigestToStdout {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Digest, "digest".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_io_write_digest_to_stdout___{}",
+            self.word_order.label_friendly_name()
+        )
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let write_digest = self.word_order.write_digest();
+
+        triton_asm!(
+            // BEFORE: _ [digest]
+            // AFTER:  _
+            {entrypoint}:
+                {&write_digest}
+                return
+        )
+    }
+}
+
+impl Procedure for WriteDigestToStdout {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let encoding = (0..DIGEST_LENGTH)
+            .map(|_| stack.pop().unwrap())
+            .collect::<Vec<_>>();
+        self.word_order.arrange(&encoding)
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let mut stack = empty_stack();
+        push_encodable(&mut stack, &rng.gen::<Digest>());
+
+        ProcedureInitialState {
+            stack,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+    use super::*;
+    use crate::io::read_digest::ReadDigest;
+    use crate::io::InputSource;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn write_digest_to_stdout_test() {
+        for word_order in [DigestWordOrder::LittleEndian, DigestWordOrder::BigEndian] {
+            ShadowedProcedure::new(WriteDigestToStdout { word_order }).test();
+        }
+    }
+
+    #[test]
+    fn output_is_digest_in_word_order() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let digest: Digest = rng.gen();
+        let mut stack = empty_stack();
+        push_encodable(&mut stack, &digest);
+
+        let write = |word_order| {
+            WriteDigestToStdout { word_order }.rust_shadow(
+                &mut stack.clone(),
+                &mut HashMap::new(),
+                &NonDeterminism::default(),
+                &[],
+                &mut None,
+            )
+        };
+        let little_endian = write(DigestWordOrder::LittleEndian);
+        let mut big_endian = write(DigestWordOrder::BigEndian);
+        assert_eq!(digest.encode(), little_endian);
+        assert_ne!(little_endian, big_endian);
+
+        big_endian.reverse();
+        assert_eq!(little_endian, big_endian);
+    }
+
+    #[test]
+    fn reading_written_digest_in_same_word_order_round_trips() {
+        let mut rng: StdRng = SeedableRng::from_seed([1; 32]);
+        let mut initial_stack = empty_stack();
+        push_encodable(&mut initial_stack, &rng.gen::<Digest>());
+
+        for word_order in [DigestWordOrder::LittleEndian, DigestWordOrder::BigEndian] {
+            let mut stack = initial_stack.clone();
+            let output = WriteDigestToStdout { word_order }.rust_shadow(
+                &mut stack,
+                &mut HashMap::new(),
+                &NonDeterminism::default(),
+                &[],
+                &mut None,
+            );
+            ReadDigest {
+                input_source: InputSource::StdIn,
+                word_order,
+            }
+            .rust_shadow(
+                &mut stack,
+                &mut HashMap::new(),
+                &NonDeterminism::default(),
+                &output,
+                &mut None,
+            );
+            assert_eq!(initial_stack, stack);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench_for_big_endian_digest_writing() {
+        ShadowedProcedure::new(WriteDigestToStdout {
+            word_order: DigestWordOrder::BigEndian,
+        })
+        .bench();
+    }
+}
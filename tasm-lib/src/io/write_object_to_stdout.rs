@@ -0,0 +1,218 @@
+use rand::distributions::{Distribution, Standard};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use crate::data_type::DataType;
+use crate::memory::encode_to_memory;
+use crate::structure::tasm_object::TasmObject;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Write the full encoding of an object of type `T`, which lives in memory as `T`'s
+/// [`TasmObject`] implementation expects it, to standard output. The object's size is
+/// computed through [`TasmObject::size_in_memory`], and the output decodes to the object.
+///
+/// The entrypoint contains `T`'s type name, made label-friendly.
+#[derive(Debug, Clone)]
+pub struct WriteObjectToStdout<T> {
+    _object: PhantomData<T>,
+}
+
+impl<T> WriteObjectToStdout<T> {
+    pub fn new() -> Self {
+        Self {
+            _object: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for WriteObjectToStdout<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: TasmObject + BFieldCodec> BasicSnippet for WriteObjectToStdout<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*object".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let type_name = std::any::type_name::<T>()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        format!("tasm_io_write_object_to_stdout___{type_name}")
+    }
+
+    fn code(&self, _library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let write_chunks = format!("{entrypoint}_write_chunks");
+        let write_words = format!("{entrypoint}_write_words");
+        let size_in_memory = T::size_in_memory();
+
+        triton_asm!(
+            // BEFORE: _ *object
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                {&size_in_memory}       // _ *object size
+                call {write_chunks}     // _ *remainder (size % 5)
+                call {write_words}      // _ *object_end 0
+                pop 2
+                return
+
+            // Write 5 words at a time while at least 5 words remain.
+            // INVARIANT: _ *word num_words_left
+            {write_chunks}:
+                push 5
+                dup 1
+                lt                      // _ *word num_words_left (num_words_left < 5)
+                skiz return
+
+                dup 1
+                push 4
+                add
+                read_mem 5
+                pop 1                   // _ *word num_words_left [words; 5]
+                write_io 5              // _ *word num_words_left
+                swap 1
+                push 5
+                add
+                swap 1
+                push -5
+                add                     // _ (*word + 5) (num_words_left - 5)
+                recurse
+
+            // INVARIANT: _ *word num_words_left
+            {write_words}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                dup 1
+                read_mem 1
+                pop 1
+                write_io 1              // _ *word num_words_left
+                swap 1
+                push 1
+                add
+                swap 1
+                push -1
+                add                     // _ (*word + 1) (num_words_left - 1)
+                recurse
+        )
+    }
+}
+
+impl<T> Procedure for WriteObjectToStdout<T>
+where
+    T: TasmObject + BFieldCodec,
+    Standard: Distribution<T>,
+{
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let object = stack.pop().unwrap();
+        T::decode_from_memory(memory, object).unwrap().encode()
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<crate::snippet_bencher::BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let object = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, object, rng.gen::<T>());
+
+        ProcedureInitialState {
+            stack: [empty_stack(), vec![object]].concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
+    struct Claim {
+        program_digest: Digest,
+        input: Vec<BFieldElement>,
+        output: Vec<BFieldElement>,
+    }
+
+    impl Distribution<Claim> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Claim {
+            let input_length = rng.gen_range(0..20);
+            let output_length = rng.gen_range(0..20);
+            Claim {
+                program_digest: rng.gen(),
+                input: (0..input_length).map(|_| rng.gen()).collect(),
+                output: (0..output_length).map(|_| rng.gen()).collect(),
+            }
+        }
+    }
+
+    #[test]
+    fn write_object_to_stdout_test() {
+        ShadowedProcedure::new(WriteObjectToStdout::<Claim>::new()).test();
+        ShadowedProcedure::new(WriteObjectToStdout::<(Digest, XFieldElement)>::new()).test();
+        ShadowedProcedure::new(WriteObjectToStdout::<[Digest; 3]>::new()).test();
+    }
+
+    #[test]
+    fn output_decodes_to_object() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let claim: Claim = rng.gen();
+        let object = BFieldElement::new(1 << 20);
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, object, claim.clone());
+
+        let output = WriteObjectToStdout::<Claim>::new().rust_shadow(
+            &mut [empty_stack(), vec![object]].concat(),
+            &mut memory,
+            &NonDeterminism::default(),
+            &[],
+            &mut None,
+        );
+        assert_eq!(claim, *Claim::decode(&output).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn bench_for_object_writing() {
+        ShadowedProcedure::new(WriteObjectToStdout::<[Digest; 4]>::new()).bench();
+    }
+}
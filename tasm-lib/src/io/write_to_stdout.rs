@@ -71,6 +71,13 @@ impl Procedure for WriteToStdout {
             ..Default::default()
         }
     }
+
+    fn public_output_types(
+        &self,
+        _initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<(DataType, usize)>> {
+        Some(vec![(self.data_type.clone(), 1)])
+    }
 }
 
 #[cfg(test)]
@@ -14,6 +14,7 @@ extern crate self as tasm_lib;
 
 use std::cmp::min;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::io::Write;
 use std::time::SystemTime;
 
@@ -34,6 +35,7 @@ use twenty_first::shared_math::tip5::Tip5State;
 use twenty_first::shared_math::tip5::{self, Tip5};
 
 use library::Library;
+use linker::SourceMap;
 use memory::dyn_malloc;
 use traits::basic_snippet::BasicSnippet;
 use traits::deprecated_snippet::DeprecatedSnippet;
@@ -41,19 +43,27 @@ use traits::deprecated_snippet::DeprecatedSnippet;
 pub mod arithmetic;
 pub mod data_type;
 pub mod exported_snippets;
+#[cfg(feature = "fuzz")]
+pub mod fuzzing;
 pub mod hashing;
+pub mod initial_state;
 pub mod io;
 pub mod library;
 pub mod linker;
 pub mod list;
 pub mod memory;
+pub mod memory_snapshot;
 pub mod mmr;
 pub mod neptune;
+pub mod optimizer;
 pub mod other_snippets;
 pub mod recufier;
 pub mod rust_shadowing_helper_functions;
 pub mod snippet_bencher;
+#[cfg(feature = "proptest")]
+pub mod strategies;
 pub mod structure;
+pub mod test_executor;
 pub mod test_helpers;
 pub mod traits;
 
@@ -119,6 +129,12 @@ pub struct VmOutputState {
     pub final_stack: Vec<BFieldElement>,
     pub final_ram: HashMap<BFieldElement, BFieldElement>,
     pub final_sponge_state: Option<VmHasherState>,
+
+    /// The number of nondeterministic individual tokens read during execution.
+    pub individual_tokens_read: usize,
+
+    /// The number of nondeterministic digests read during execution.
+    pub digests_read: usize,
 }
 
 pub fn empty_stack() -> Vec<BFieldElement> {
@@ -139,7 +155,9 @@ pub(crate) fn execute_with_execution_state_deprecated<T: DeprecatedSnippet>(
     let entrypoint = snippet.entrypoint();
     let insert_me = snippet.code(&mut library);
     let insert_library = library.all_imports();
+    let static_data_initialization = library.static_data_initialization();
     let code = triton_asm!(
+        {&static_data_initialization}
         call {entrypoint}
         halt
         {&insert_me}
@@ -220,6 +238,29 @@ pub fn execute_test(
     std_in: Vec<BFieldElement>,
     nondeterminism: NonDeterminism<BFieldElement>,
     maybe_sponge_state: Option<VmHasherState>,
+) -> VmOutputState {
+    execute_test_with_source_map(
+        code,
+        stack,
+        expected_stack_diff,
+        std_in,
+        nondeterminism,
+        maybe_sponge_state,
+        &SourceMap::default(),
+    )
+}
+
+/// Like [`execute_test`], but if execution fails, the panic message reports the snippet in
+/// which it failed, according to the `source_map`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_test_with_source_map(
+    code: &[LabelledInstruction],
+    stack: &mut Vec<BFieldElement>,
+    expected_stack_diff: isize,
+    std_in: Vec<BFieldElement>,
+    nondeterminism: NonDeterminism<BFieldElement>,
+    maybe_sponge_state: Option<VmHasherState>,
+    source_map: &SourceMap,
 ) -> VmOutputState {
     let initial_stack_height = stack.len();
     let public_input = PublicInput::new(std_in.clone());
@@ -232,7 +273,8 @@ pub fn execute_test(
     maybe_write_debuggable_program_to_disk(&program, &vm_state);
 
     if let Err(err) = vm_state.run() {
-        panic!("{err}\n\nFinal state was: {vm_state}")
+        let context = failure_context(source_map, &vm_state, &err);
+        panic!("{context}\n\nFinal state was: {vm_state}")
     }
     let terminal_state = vm_state;
 
@@ -276,6 +318,9 @@ pub fn execute_test(
         final_sponge_state: terminal_state
             .sponge_state
             .map(|state| VmHasherState { state }),
+        individual_tokens_read: nondeterminism.individual_tokens.len()
+            - terminal_state.secret_individual_tokens.len(),
+        digests_read: nondeterminism.digests.len() - terminal_state.secret_digests.len(),
     }
 }
 
@@ -309,6 +354,26 @@ pub fn execute_with_terminal_state(
     stack: &[BFieldElement],
     nondeterminism: &NonDeterminism<BFieldElement>,
     maybe_sponge_state: Option<VmHasherState>,
+) -> anyhow::Result<VMState> {
+    execute_with_terminal_state_and_source_map(
+        program,
+        std_in,
+        stack,
+        nondeterminism,
+        maybe_sponge_state,
+        &SourceMap::default(),
+    )
+}
+
+/// Like [`execute_with_terminal_state`], but if execution fails, the error reports the
+/// snippet in which it failed, according to the `source_map`.
+pub fn execute_with_terminal_state_and_source_map(
+    program: &Program,
+    std_in: &[BFieldElement],
+    stack: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    maybe_sponge_state: Option<VmHasherState>,
+    source_map: &SourceMap,
 ) -> anyhow::Result<VMState> {
     let public_input = PublicInput::new(std_in.into());
     let mut vm_state = VMState::new(program, public_input, nondeterminism.to_owned());
@@ -326,11 +391,25 @@ pub fn execute_with_terminal_state(
                 println!("{}", sponge_state.iter().join(", "));
             }
             println!("Triton VM execution failed. Final state:\n{vm_state}");
-            bail!("VM execution failed with error: {err}")
+            let context = failure_context(source_map, &vm_state, &err);
+
+            // keep the VM error, such that callers can inspect the reason of failure
+            Err(anyhow::Error::from(err).context(context))
         }
     }
 }
 
+/// Describe the failure of the execution that ended in `vm_state`, naming the snippet in
+/// which it failed if the `source_map` knows it.
+fn failure_context(source_map: &SourceMap, vm_state: &VMState, err: &impl Display) -> String {
+    match source_map.lookup(vm_state.instruction_pointer) {
+        Some((snippet, offset)) => format!(
+            "VM execution failed inside {snippet} at relative offset {offset} with error: {err}"
+        ),
+        None => format!("VM execution failed with error: {err}"),
+    }
+}
+
 // If you run this, make sure `opt-level` is set to 3.
 fn prove_and_verify(
     program: &Program,
@@ -1,10 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use anyhow::{anyhow, bail, Result};
 use itertools::Itertools;
 use num::One;
-use triton_vm::instruction::LabelledInstruction;
+use serde::{Deserialize, Serialize};
+use triton_vm::instruction::{AnInstruction, LabelledInstruction};
+use triton_vm::parser::{to_labelled_instructions, tokenize};
+use triton_vm::triton_asm;
 use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::tip5;
 
+use crate::linker::SourceMap;
 use crate::traits::deprecated_snippet::DeprecatedSnippet;
 use crate::{data_type::DataType, traits::basic_snippet::BasicSnippet};
 
@@ -13,12 +20,93 @@ use crate::{data_type::DataType, traits::basic_snippet::BasicSnippet};
 // by convention, always on address 0.
 pub const STATIC_MEMORY_START_ADDRESS: BFieldElement = BFieldElement::new(BFieldElement::MAX);
 
+/// Render code as Triton assembly: one instruction per line, with everything except labels
+/// indented.
+pub fn format_tasm(code: &[LabelledInstruction]) -> String {
+    code.iter()
+        .map(|instruction| match instruction {
+            LabelledInstruction::Label(_) => format!("{instruction}\n"),
+            _ => format!("    {instruction}\n"),
+        })
+        .join("")
+}
+
+/// Write `code`, *e.g.*, a fully linked program, to the Triton assembly file at `path`.
+pub fn write_tasm_file(code: &[LabelledInstruction], path: impl AsRef<Path>) -> Result<()> {
+    std::fs::write(path, format_tasm(code))?;
+    Ok(())
+}
+
+/// Size statistics of a piece of linked code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CodeStatistics {
+    /// The number of instructions, not counting labels.
+    pub num_instructions: usize,
+
+    /// The number of words the encoded code occupies. Instructions with an argument take up
+    /// two words.
+    pub num_words: usize,
+
+    /// The length of the encoded code after padding it for hashing. This is the height of
+    /// the program table, if the code is the entire program.
+    pub padded_program_length: usize,
+}
+
+impl CodeStatistics {
+    pub fn of(code: &[LabelledInstruction]) -> Self {
+        let instructions = code.iter().filter_map(|instruction| match instruction {
+            LabelledInstruction::Instruction(instruction) => Some(instruction),
+            _ => None,
+        });
+        let num_instructions = instructions.clone().count();
+        let num_words = instructions.map(|instruction| instruction.size()).sum();
+
+        // Padding appends a 1 and then 0s until the length is a multiple of the rate.
+        let padded_program_length = (num_words + 1).div_ceil(tip5::RATE) * tip5::RATE;
+
+        Self {
+            num_instructions,
+            num_words,
+            padded_program_length,
+        }
+    }
+
+    /// The smallest padded height of the execution trace of a program made up of this code.
+    pub fn min_padded_height(&self) -> usize {
+        self.padded_program_length.next_power_of_two()
+    }
+}
+
+/// The order in which a [`Library`] emits its imported snippets. Every order is
+/// deterministic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImportOrder {
+    /// Sort snippets alphabetically by name.
+    #[default]
+    Alphabetical,
+
+    /// Emit every snippet before the snippets it calls, unless they call each other.
+    CallersFirst,
+
+    /// Emit every snippet after the snippets it calls, unless they call each other.
+    CalleesFirst,
+}
+
 /// A Library represents a set of imports for a single Program or Snippet, and moreover
 /// tracks some data used for initializing the memory allocator.
 #[derive(Clone, Debug)]
 pub struct Library {
     seen_snippets: HashMap<String, Vec<LabelledInstruction>>,
     free_pointer: BFieldElement,
+    statics: Vec<(String, BFieldElement, u32)>,
+    static_data: Vec<(BFieldElement, Vec<BFieldElement>)>,
+    validate_imports: bool,
+    external_labels: HashSet<String>,
+
+    /// Statically allocated regions released through [`kfree`](Self::kfree), as pairs of
+    /// address and size in words, sorted by address.
+    free_regions: Vec<(BFieldElement, u32)>,
+    num_words_preallocated: u32,
 }
 
 impl Default for Library {
@@ -32,6 +120,12 @@ impl Library {
         Self {
             seen_snippets: Default::default(),
             free_pointer: STATIC_MEMORY_START_ADDRESS,
+            statics: vec![],
+            static_data: vec![],
+            validate_imports: false,
+            external_labels: Default::default(),
+            free_regions: vec![],
+            num_words_preallocated: 0,
         }
     }
 
@@ -45,10 +139,23 @@ impl Library {
         Library {
             free_pointer: STATIC_MEMORY_START_ADDRESS
                 - BFieldElement::new(words_statically_allocated as u64),
+            num_words_preallocated: words_statically_allocated,
             ..Self::new()
         }
     }
 
+    /// Validate every newly imported body, see [`validate_body`](Self::validate_body).
+    pub fn with_import_validation(mut self) -> Self {
+        self.validate_imports = true;
+        self
+    }
+
+    /// Declare that `label` is defined outside of this library, *e.g.*, by the program that
+    /// uses the library. Calls to such labels pass [import validation](Self::validate_body).
+    pub fn declare_external(&mut self, label: &str) {
+        self.external_labels.insert(label.to_owned());
+    }
+
     /// Import `T: Snippet` into the library.
     ///
     /// This recursively imports `T`'s dependencies.
@@ -57,36 +164,178 @@ impl Library {
     ///
     /// Avoid cyclic dependencies by only calling `T::function_code()` which
     /// may call `.import()` if `.import::<T>()` wasn't already called once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [import validation](Self::with_import_validation) is enabled and the
+    /// snippet's code is invalid.
     pub fn import(&mut self, snippet: Box<dyn BasicSnippet>) -> String {
         let dep_entrypoint = snippet.entrypoint();
 
         let is_new_dependency = !self.seen_snippets.contains_key(&dep_entrypoint);
         if is_new_dependency {
             let dep_body = snippet.code(self);
+            if self.validate_imports {
+                if let Err(err) = self.validate_body(&dep_entrypoint, &dep_body) {
+                    panic!("invalid code for snippet `{dep_entrypoint}`: {err}");
+                }
+            }
             self.seen_snippets.insert(dep_entrypoint.clone(), dep_body);
         }
 
         dep_entrypoint
     }
 
-    pub fn explicit_import(&mut self, name: &str, body: &[LabelledInstruction]) -> String {
-        // The linter's suggestion doesn't work. This suppression is fine imo.
-        #[allow(clippy::map_entry)]
-        if !self.seen_snippets.contains_key(name) {
-            self.seen_snippets.insert(name.to_owned(), body.to_vec());
+    /// Import the code `body` under the name `name`.
+    ///
+    /// Importing the same body under the same name more than once is fine. Returns an error
+    /// if a different body was already imported under the same name, as only one of them can
+    /// end up in the program, or if [import validation](Self::with_import_validation) is
+    /// enabled and the body is invalid.
+    pub fn explicit_import(&mut self, name: &str, body: &[LabelledInstruction]) -> Result<String> {
+        match self.seen_snippets.get(name) {
+            Some(existing_body) if existing_body != body => {
+                bail!("label collision: a different body was already imported under `{name}`")
+            }
+            Some(_) => (),
+            None => {
+                if self.validate_imports {
+                    self.validate_body(name, body)?;
+                }
+                self.seen_snippets.insert(name.to_owned(), body.to_vec());
+            }
+        }
+
+        Ok(name.to_string())
+    }
+
+    /// Check that `body` is fit to be imported under the name `name`. Specifically, check that
+    /// - the first label defined in `body` is `name`,
+    /// - no label is defined twice, neither within `body` nor across imported snippets, and
+    /// - every call target is defined in `body`, in an imported snippet, or is
+    ///   [declared external](Self::declare_external).
+    pub fn validate_body(&self, name: &str, body: &[LabelledInstruction]) -> Result<()> {
+        let labels = body
+            .iter()
+            .filter_map(|instruction| match instruction {
+                LabelledInstruction::Label(label) => Some(label.as_str()),
+                _ => None,
+            })
+            .collect_vec();
+
+        match labels.first() {
+            Some(&first_label) if first_label == name => (),
+            Some(first_label) => bail!("expected first label `{name}`, found `{first_label}`"),
+            None => bail!("expected first label `{name}`, found no labels"),
+        }
+
+        let owner_of_label = self.owner_of_label();
+        let mut defined_labels = HashSet::new();
+        for &label in labels.iter() {
+            if !defined_labels.insert(label) {
+                bail!("label `{label}` is defined more than once");
+            }
+            if let Some(owner) = owner_of_label.get(label).filter(|&&owner| owner != name) {
+                bail!("label `{label}` is already defined by imported snippet `{owner}`");
+            }
+        }
+
+        for instruction in body.iter() {
+            let LabelledInstruction::Instruction(AnInstruction::Call(target)) = instruction else {
+                continue;
+            };
+            let target = target.as_str();
+            let is_known = defined_labels.contains(target)
+                || owner_of_label.contains_key(target)
+                || self.external_labels.contains(target);
+            if !is_known {
+                bail!("call target `{target}` is not defined");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge all imports and static allocations of `other` into this library.
+    ///
+    /// Snippets imported into both libraries must have identical bodies. Static allocations
+    /// with the same name, address, and size in both libraries are shared. All other static
+    /// allocations of `other` must not overlap with memory statically allocated by this
+    /// library, because their addresses are already baked into `other`'s code and cannot be
+    /// rebased after the fact. To build a library that can be absorbed, create it with
+    /// [`with_preallocated_memory`](Self::with_preallocated_memory), passing this library's
+    /// [`num_words_statically_allocated`](Self::num_words_statically_allocated).
+    ///
+    /// Returns an error and leaves this library untouched if the two libraries conflict.
+    pub fn absorb(&mut self, other: Library) -> Result<()> {
+        for (name, body) in other.seen_snippets.iter() {
+            if self.seen_snippets.get(name).is_some_and(|own| own != body) {
+                bail!("label collision: libraries contain different bodies for `{name}`");
+            }
+        }
+
+        let self_lowest_address = self.free_pointer.value() + 1;
+        let mut new_statics = vec![];
+        for allocation in other.statics.iter() {
+            let (name, address, size) = allocation;
+            if self.statics.contains(allocation) {
+                continue;
+            }
+            if self.static_address(name).is_some() {
+                bail!("static allocation `{name}` differs between libraries");
+            }
+            let last_address = address.value() + *size as u64 - 1;
+            if *size > 0 && last_address >= self_lowest_address {
+                bail!(
+                    "static allocation `{name}` of {size} words at address {address} overlaps \
+                    with memory statically allocated by this library"
+                );
+            }
+            new_statics.push(allocation.to_owned());
+        }
+
+        let mut new_static_data = vec![];
+        for (address, data) in other.static_data.iter() {
+            match self.static_data.iter().find(|(a, _)| a == address) {
+                Some((_, own_data)) if own_data != data => {
+                    bail!("libraries initialize static address {address} with different data")
+                }
+                Some(_) => (),
+                None => new_static_data.push((*address, data.to_owned())),
+            }
+        }
+
+        self.seen_snippets.extend(other.seen_snippets);
+        self.external_labels.extend(other.external_labels);
+        self.statics.extend(new_statics);
+        self.static_data.extend(new_static_data);
+        if other.free_pointer.value() < self.free_pointer.value() {
+            self.free_pointer = other.free_pointer;
         }
 
-        name.to_string()
+        Ok(())
+    }
+
+    /// Return the number of words statically allocated so far, including preallocated memory.
+    pub fn num_words_statically_allocated(&self) -> u32 {
+        (STATIC_MEMORY_START_ADDRESS.value() - self.free_pointer.value()) as u32
     }
 
     /// Return a list of all external dependencies sorted by name
     /// All snippets are sorted
     /// alphabetically to ensure that generated programs are deterministic.
     pub fn all_external_dependencies(&self) -> Vec<Vec<LabelledInstruction>> {
-        self.seen_snippets
-            .iter()
-            .sorted_by_key(|(k, _)| *k)
-            .map(|(_, code)| code.clone())
+        self.all_external_dependencies_in_order(ImportOrder::Alphabetical)
+    }
+
+    /// Return a list of all external dependencies in the given order.
+    pub fn all_external_dependencies_in_order(
+        &self,
+        order: ImportOrder,
+    ) -> Vec<Vec<LabelledInstruction>> {
+        self.snippet_names_in_order(order)
+            .into_iter()
+            .map(|name| self.seen_snippets[name].clone())
             .collect()
     }
 
@@ -106,12 +355,533 @@ impl Library {
         self.all_external_dependencies().concat()
     }
 
-    /// Statically allocate `num_words` words of memory.
-    pub fn kmalloc(&mut self, num_words: u32) -> BFieldElement {
-        let address = self.free_pointer - BFieldElement::new(num_words as u64 - 1);
-        self.free_pointer -= BFieldElement::new(num_words as u64);
+    /// Render all imported snippets as Triton assembly, in the same order as
+    /// [`all_imports`](Self::all_imports). Snippets are separated by an empty line.
+    pub fn to_tasm(&self) -> String {
+        self.all_external_dependencies()
+            .iter()
+            .map(|code| format_tasm(code))
+            .join("\n")
+    }
+
+    /// Write all imported snippets to the Triton assembly file at `path`. See
+    /// [`import_tasm_file`](Self::import_tasm_file) for the reverse direction.
+    pub fn write_tasm_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_tasm())?;
+        Ok(())
+    }
+
+    /// Import all snippets from the Triton assembly file at `path`. Snippets must be
+    /// separated by at least one empty line, and every snippet's first label is its name.
+    ///
+    /// All snippets are [validated](Self::validate_body), where calls to snippets defined in
+    /// the same file are allowed. If any snippet is invalid, nothing is imported. Returns the
+    /// names of the snippets in the file.
+    pub fn import_tasm_file(&mut self, path: impl AsRef<Path>) -> Result<Vec<String>> {
+        let source = std::fs::read_to_string(path)?;
+        let mut snippets = vec![];
+        for section in source
+            .split("\n\n")
+            .filter(|section| !section.trim().is_empty())
+        {
+            // parse without link-checking, as calls may target other snippets
+            let (_, tokens) = tokenize(section).map_err(|err| anyhow!("{err}"))?;
+            let body = to_labelled_instructions(&tokens);
+            let Some(LabelledInstruction::Label(name)) = body.first() else {
+                bail!("snippet does not start with a label:\n{section}");
+            };
+            snippets.push((name.to_owned(), body));
+        }
+
+        let mut library = self.clone();
+        for (name, body) in snippets.iter() {
+            library.explicit_import(name, body)?;
+        }
+        for (name, body) in snippets.iter() {
+            library.validate_body(name, body)?;
+        }
+        *self = library;
+
+        Ok(snippets.into_iter().map(|(name, _)| name).collect())
+    }
+
+    /// Return size statistics of the code returned by [`all_imports`](Self::all_imports).
+    /// Use [`CodeStatistics::of`] to get the statistics of a fully linked program.
+    pub fn code_statistics(&self) -> CodeStatistics {
+        CodeStatistics::of(&self.all_imports())
+    }
+
+    /// Return a [`SourceMap`] for the code returned by [`all_imports`](Self::all_imports).
+    pub fn source_map(&self) -> SourceMap {
+        let mut source_map = SourceMap::default();
+        for name in self.snippet_names_in_order(ImportOrder::Alphabetical) {
+            source_map.append(name, &self.seen_snippets[name]);
+        }
+
+        source_map
+    }
+
+    /// Return a list of instructions containing all imported snippets in the given order.
+    pub fn all_imports_in_order(&self, order: ImportOrder) -> Vec<LabelledInstruction> {
+        self.all_external_dependencies_in_order(order).concat()
+    }
+
+    fn snippet_names_in_order(&self, order: ImportOrder) -> Vec<&str> {
+        let alphabetical = self.seen_snippets.keys().map(|name| name.as_str()).sorted();
+        if order == ImportOrder::Alphabetical {
+            return alphabetical.collect();
+        }
+
+        // Reverse post-order of a depth-first traversal lists callers before callees. Visit
+        // everything in reverse alphabetical order so that, where the call graph leaves a
+        // choice, the result is alphabetical.
+        fn visit<'a>(
+            name: &'a str,
+            call_graph: &HashMap<&'a str, Vec<&'a str>>,
+            visited: &mut HashSet<&'a str>,
+            post_order: &mut Vec<&'a str>,
+        ) {
+            if !visited.insert(name) {
+                return;
+            }
+            for &callee in call_graph[name].iter().rev() {
+                visit(callee, call_graph, visited, post_order);
+            }
+            post_order.push(name);
+        }
+
+        let call_graph = self.call_graph();
+        let mut visited = HashSet::new();
+        let mut post_order = vec![];
+        for name in alphabetical.rev() {
+            visit(name, &call_graph, &mut visited, &mut post_order);
+        }
+
+        match order {
+            ImportOrder::CallersFirst => post_order.into_iter().rev().collect(),
+            _ => post_order,
+        }
+    }
+
+    /// Remove all imported snippets that cannot be reached from the label `entrypoint`.
+    ///
+    /// Reachability is determined by following `call` instructions, starting from the
+    /// imported snippet that defines `entrypoint`. Calls to labels defined inside another
+    /// snippet's body (*e.g.*, loop labels) keep that entire snippet. Calls to labels that
+    /// no imported snippet defines are ignored, as they are presumably defined elsewhere in
+    /// the program.
+    ///
+    /// Call this before [`all_imports`](Self::all_imports) to avoid shipping helpers that
+    /// are imported but never called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no imported snippet defines the label `entrypoint`.
+    pub fn prune_unreachable(&mut self, entrypoint: &str) {
+        let Some(root) = self
+            .owner_of_label()
+            .get(entrypoint)
+            .map(|&root| root.to_owned())
+        else {
+            panic!("no imported snippet defines the label `{entrypoint}`");
+        };
+
+        let call_graph = self.call_graph();
+        let mut reachable = HashSet::from([root.as_str()]);
+        let mut to_visit = vec![root.as_str()];
+        while let Some(snippet_name) = to_visit.pop() {
+            for &callee in call_graph[snippet_name].iter() {
+                if reachable.insert(callee) {
+                    to_visit.push(callee);
+                }
+            }
+        }
+
+        let reachable: HashSet<String> =
+            reachable.into_iter().map(|name| name.to_owned()).collect();
+        self.seen_snippets
+            .retain(|name, _| reachable.contains(name));
+    }
+
+    /// Splice imported snippets with at most `max_num_instructions` instructions into the
+    /// bodies of the imported snippets that call them, instead of calling them. This is
+    /// repeated for snippets that become small enough through inlining.
+    ///
+    /// Only snippets that consist of a single label, followed by instructions without any
+    /// `return` or `recurse`, followed by a final `return`, are inlined. The inlined snippets
+    /// remain imported, since code outside of the library might still call them; use
+    /// [`inline_calls`](Self::inline_calls) to inline into such code, and
+    /// [`prune_unreachable`](Self::prune_unreachable) to remove snippets no longer called.
+    pub fn inline_small_snippets(&mut self, max_num_instructions: usize) {
+        for _ in 0..self.seen_snippets.len() {
+            let mut changed = false;
+            for name in self.get_all_snippet_names() {
+                let body = &self.seen_snippets[&name];
+                let inlined = self.inline_calls(body, max_num_instructions);
+                if &inlined != body {
+                    self.seen_snippets.insert(name, inlined);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Replace every call to an imported snippet with at most `max_num_instructions`
+    /// instructions by that snippet's body. See
+    /// [`inline_small_snippets`](Self::inline_small_snippets) for which snippets are inlined.
+    ///
+    /// Calls directly following a `skiz` are not replaced, since `skiz` might skip only the
+    /// first instruction of the inlined body.
+    pub fn inline_calls(
+        &self,
+        code: &[LabelledInstruction],
+        max_num_instructions: usize,
+    ) -> Vec<LabelledInstruction> {
+        let mut inlined = Vec::with_capacity(code.len());
+        let mut follows_skiz = false;
+        for instruction in code {
+            let inline_body = match instruction {
+                LabelledInstruction::Instruction(AnInstruction::Call(label)) if !follows_skiz => {
+                    self.seen_snippets
+                        .get(label)
+                        .and_then(|body| Self::inlinable_body(label, body, max_num_instructions))
+                }
+                _ => None,
+            };
+            match inline_body {
+                Some(body) => inlined.extend_from_slice(body),
+                None => inlined.push(instruction.to_owned()),
+            }
+            follows_skiz = matches!(
+                instruction,
+                LabelledInstruction::Instruction(AnInstruction::Skiz)
+            );
+        }
+
+        inlined
+    }
+
+    /// Return the body of snippet `name` without its label and final `return`, if the
+    /// snippet can be inlined.
+    fn inlinable_body<'a>(
+        name: &str,
+        body: &'a [LabelledInstruction],
+        max_num_instructions: usize,
+    ) -> Option<&'a [LabelledInstruction]> {
+        let [LabelledInstruction::Label(label), inner @ .., last] = body else {
+            return None;
+        };
+        let ends_with_return = matches!(
+            last,
+            LabelledInstruction::Instruction(AnInstruction::Return)
+        );
+        if label != name || !ends_with_return {
+            return None;
+        }
+
+        let mut num_instructions = 0;
+        for instruction in inner {
+            match instruction {
+                LabelledInstruction::Label(_) => return None,
+                LabelledInstruction::Instruction(AnInstruction::Return)
+                | LabelledInstruction::Instruction(AnInstruction::Recurse) => return None,
+                LabelledInstruction::Instruction(AnInstruction::Call(callee)) if callee == name => {
+                    return None
+                }
+                LabelledInstruction::Instruction(_) => num_instructions += 1,
+                _ => (),
+            }
+        }
+
+        (num_instructions <= max_num_instructions).then_some(inner)
+    }
+
+    /// Map every label defined in some imported snippet to the name of that snippet.
+    fn owner_of_label(&self) -> HashMap<&str, &str> {
+        self.seen_snippets
+            .iter()
+            .flat_map(|(name, body)| {
+                body.iter()
+                    .filter_map(move |instruction| match instruction {
+                        LabelledInstruction::Label(label) => Some((label.as_str(), name.as_str())),
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+
+    /// Map the name of every imported snippet to the names of the imported snippets it calls,
+    /// sorted alphabetically. Calls to labels that no imported snippet defines are ignored.
+    fn call_graph(&self) -> HashMap<&str, Vec<&str>> {
+        let owner_of_label = self.owner_of_label();
+        self.seen_snippets
+            .iter()
+            .map(|(name, body)| {
+                let callees = body
+                    .iter()
+                    .filter_map(|instruction| match instruction {
+                        LabelledInstruction::Instruction(AnInstruction::Call(label)) => {
+                            owner_of_label.get(label.as_str()).copied()
+                        }
+                        _ => None,
+                    })
+                    .sorted()
+                    .dedup()
+                    .collect();
+                (name.as_str(), callees)
+            })
+            .collect()
+    }
+
+    /// Statically allocate `num_words` words of memory under the name `name`.
+    ///
+    /// The name is recorded in the library's symbol table, see [`statics`](Self::statics).
+    ///
+    /// Memory released through [`kfree`](Self::kfree) is reused if possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some static allocation with the same name already exists, as that
+    /// indicates that two snippets would otherwise unknowingly share their scratch space.
+    pub fn kmalloc(&mut self, name: &str, num_words: u32) -> BFieldElement {
+        if let Some((_, address, size)) = self.statics.iter().find(|(n, _, _)| n == name) {
+            panic!("static allocation `{name}` already exists: {size} words at address {address}");
+        }
+
+        let address = match self.take_from_free_regions(num_words) {
+            Some(address) => address,
+            None => {
+                let address = self.free_pointer - BFieldElement::new(num_words as u64 - 1);
+                self.free_pointer -= BFieldElement::new(num_words as u64);
+                address
+            }
+        };
+        self.statics.push((name.to_owned(), address, num_words));
         address
     }
+
+    /// Release the static allocation of `num_words` words at `address`, such that later
+    /// calls to [`kmalloc`](Self::kmalloc) can reuse the memory.
+    ///
+    /// The released memory is handed out again regardless of whether code using the
+    /// original allocation is still part of the program. Only release memory whose contents
+    /// are not needed after the code using it has returned, *e.g.*, scratch space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no static allocation of `num_words` words exists at `address`.
+    pub fn kfree(&mut self, address: BFieldElement, num_words: u32) {
+        let Some(index) = self
+            .statics
+            .iter()
+            .position(|&(_, a, size)| a == address && size == num_words)
+        else {
+            panic!("no static allocation of {num_words} words exists at address {address}");
+        };
+        self.statics.remove(index);
+        self.static_data.retain(|(a, _)| *a != address);
+
+        if num_words == 0 {
+            return;
+        }
+        self.free_regions.push((address, num_words));
+        self.free_regions.sort_by_key(|(a, _)| a.value());
+
+        // merge adjacent regions
+        let mut merged: Vec<(BFieldElement, u32)> = vec![];
+        for &(region_address, region_size) in self.free_regions.iter() {
+            match merged.last_mut() {
+                Some((last_address, last_size))
+                    if last_address.value() + *last_size as u64 == region_address.value() =>
+                {
+                    *last_size += region_size
+                }
+                _ => merged.push((region_address, region_size)),
+            }
+        }
+        self.free_regions = merged;
+    }
+
+    /// Take `num_words` words from the first released region that is large enough, if any.
+    fn take_from_free_regions(&mut self, num_words: u32) -> Option<BFieldElement> {
+        if num_words == 0 {
+            return None;
+        }
+
+        let index = self
+            .free_regions
+            .iter()
+            .position(|&(_, size)| size >= num_words)?;
+        let (address, size) = self.free_regions[index];
+        if size == num_words {
+            self.free_regions.remove(index);
+        } else {
+            self.free_regions[index] = (
+                address + BFieldElement::new(num_words as u64),
+                size - num_words,
+            );
+        }
+
+        Some(address)
+    }
+
+    /// Statically allocate memory under the name `name` and register `data` as its
+    /// constant content.
+    ///
+    /// The data is written to memory by the routine returned from
+    /// [`static_data_initialization`](Self::static_data_initialization), or can be supplied
+    /// as initial RAM through [`static_data_memory`](Self::static_data_memory).
+    pub fn kmalloc_constant(&mut self, name: &str, data: &[BFieldElement]) -> BFieldElement {
+        let address = self.kmalloc(name, data.len() as u32);
+        self.static_data.push((address, data.to_vec()));
+        address
+    }
+
+    /// Return code that writes all data registered through
+    /// [`kmalloc_constant`](Self::kmalloc_constant) to its statically allocated address.
+    /// The code leaves the stack unchanged and must run before the program's entrypoint.
+    pub fn static_data_initialization(&self) -> Vec<LabelledInstruction> {
+        let mut code = vec![];
+        for (address, data) in self.static_data.iter() {
+            for (chunk_index, chunk) in data.chunks(5).enumerate() {
+                let chunk_address = *address + BFieldElement::new(5 * chunk_index as u64);
+                let push_chunk = chunk
+                    .iter()
+                    .rev()
+                    .map(|word| triton_asm!(push { word }))
+                    .concat();
+                let num_words = chunk.len();
+                code.extend(triton_asm!(
+                    {&push_chunk}
+                    push {chunk_address}
+                    write_mem {num_words}
+                    pop 1
+                ));
+            }
+        }
+
+        code
+    }
+
+    /// Return the memory state that the routine returned from
+    /// [`static_data_initialization`](Self::static_data_initialization) produces. Useful
+    /// for initializing RAM through non-determinism instead.
+    pub fn static_data_memory(&self) -> HashMap<BFieldElement, BFieldElement> {
+        self.static_data
+            .iter()
+            .flat_map(|(address, data)| {
+                data.iter()
+                    .enumerate()
+                    .map(move |(i, &word)| (*address + BFieldElement::new(i as u64), word))
+            })
+            .collect()
+    }
+
+    /// Return the name, address, and size in words of all static allocations, in order of
+    /// allocation.
+    ///
+    /// Static allocations made through [`with_preallocated_memory`](Self::with_preallocated_memory)
+    /// are not named and hence not listed.
+    pub fn statics(&self) -> Vec<(String, BFieldElement, u32)> {
+        self.statics.clone()
+    }
+
+    /// Return the address of the static allocation named `name`, if it exists.
+    pub fn static_address(&self, name: &str) -> Option<BFieldElement> {
+        self.statics
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|&(_, address, _)| address)
+    }
+
+    /// Return a report of the entire static memory layout.
+    pub fn static_memory_layout(&self) -> StaticMemoryLayout {
+        let allocations = self
+            .statics
+            .iter()
+            .map(|(name, address, num_words)| StaticAllocation {
+                name: name.to_owned(),
+                first_address: address.value(),
+                num_words: *num_words,
+                has_static_data: self.static_data.iter().any(|(a, _)| a == address),
+            })
+            .collect();
+        let free_regions = self
+            .free_regions
+            .iter()
+            .map(|&(address, num_words)| (address.value(), num_words))
+            .collect();
+
+        StaticMemoryLayout {
+            num_words_preallocated: self.num_words_preallocated,
+            allocations,
+            free_regions,
+            lowest_address: self.free_pointer.value() + 1,
+        }
+    }
+}
+
+/// A report of a [`Library`]'s static memory layout. All addresses are given as the
+/// canonical representation of the respective [`BFieldElement`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticMemoryLayout {
+    /// The number of words allocated through
+    /// [`with_preallocated_memory`](Library::with_preallocated_memory), located right below
+    /// [`STATIC_MEMORY_START_ADDRESS`], inclusive.
+    pub num_words_preallocated: u32,
+
+    /// All current named allocations, in order of allocation.
+    pub allocations: Vec<StaticAllocation>,
+
+    /// Regions released through [`kfree`](Library::kfree) and not yet reused, as pairs of
+    /// first address and size in words.
+    pub free_regions: Vec<(u64, u32)>,
+
+    /// The lowest address of statically allocated memory. Memory from here up to and
+    /// including [`STATIC_MEMORY_START_ADDRESS`] is reserved for static allocations.
+    pub lowest_address: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticAllocation {
+    pub name: String,
+    pub first_address: u64,
+    pub num_words: u32,
+
+    /// Whether the allocation is initialized with data registered through
+    /// [`kmalloc_constant`](Library::kmalloc_constant).
+    pub has_static_data: bool,
+}
+
+impl StaticAllocation {
+    pub fn contains(&self, address: BFieldElement) -> bool {
+        let address = address.value();
+        self.first_address <= address && address < self.first_address + self.num_words as u64
+    }
+}
+
+impl StaticMemoryLayout {
+    /// Return all allocations containing at least one of the given addresses, *e.g.*, the
+    /// addresses of nondeterministically initialized RAM.
+    pub fn allocations_overlapping(
+        &self,
+        addresses: impl IntoIterator<Item = BFieldElement>,
+    ) -> Vec<&StaticAllocation> {
+        let addresses = addresses.into_iter().collect_vec();
+        self.allocations
+            .iter()
+            .filter(|allocation| addresses.iter().any(|&a| allocation.contains(a)))
+            .collect()
+    }
+
+    /// Whether the given address lies in statically allocated memory, including preallocated
+    /// and released memory.
+    pub fn is_reserved(&self, address: BFieldElement) -> bool {
+        address.value() >= self.lowest_address
+    }
 }
 
 #[derive(Debug)]
@@ -326,9 +1096,10 @@ mod tests {
     use std::collections::HashMap;
 
     use triton_vm::program::Program;
-    use triton_vm::triton_asm;
+    use triton_vm::{NonDeterminism, PublicInput};
 
     use crate::empty_stack;
+    use crate::linker::execute_bench;
     use crate::list::ListType;
     use crate::memory::memcpy::MemCpy;
     use crate::mmr::calculate_new_peaks_from_leaf_mutation::MmrCalculateNewPeaksFromLeafMutationMtIndices;
@@ -400,6 +1171,240 @@ mod tests {
         let _ret = lib.all_imports();
     }
 
+    #[test]
+    fn prune_unreachable_drops_uncalled_snippets() {
+        let mut lib = Library::new();
+        lib.import(Box::new(DummyTestSnippetA));
+        lib.import(Box::new(MemCpy));
+        let memcpy_entrypoint = MemCpy.entrypoint();
+
+        let mut pruned_from_a = lib.clone();
+        pruned_from_a.prune_unreachable("tasm_a_dummy_test_value");
+        assert_eq!(
+            vec![
+                "tasm_a_dummy_test_value",
+                "tasm_b_dummy_test_value",
+                "tasm_c_dummy_test_value"
+            ],
+            pruned_from_a.get_all_snippet_names()
+        );
+
+        let mut pruned_from_b = lib.clone();
+        pruned_from_b.prune_unreachable("tasm_b_dummy_test_value");
+        assert_eq!(
+            vec!["tasm_b_dummy_test_value", "tasm_c_dummy_test_value"],
+            pruned_from_b.get_all_snippet_names()
+        );
+
+        let mut pruned_from_memcpy = lib.clone();
+        pruned_from_memcpy.prune_unreachable(&memcpy_entrypoint);
+        assert_eq!(
+            vec![memcpy_entrypoint],
+            pruned_from_memcpy.get_all_snippet_names()
+        );
+    }
+
+    #[test]
+    fn prune_unreachable_follows_calls_to_inner_labels() {
+        let mut lib = Library::new();
+        lib.explicit_import(
+            "outer",
+            &triton_asm!(
+                outer:
+                    call inner_loop
+                    return
+            ),
+        )
+        .unwrap();
+        lib.explicit_import(
+            "looping",
+            &triton_asm!(
+                looping:
+                    return
+                inner_loop:
+                    call leaf
+                    return
+            ),
+        )
+        .unwrap();
+        lib.explicit_import("leaf", &triton_asm!(leaf: return))
+            .unwrap();
+        lib.explicit_import("unused", &triton_asm!(unused: call leaf return))
+            .unwrap();
+
+        lib.prune_unreachable("outer");
+        assert_eq!(
+            vec!["leaf", "looping", "outer"],
+            lib.get_all_snippet_names()
+        );
+    }
+
+    #[should_panic]
+    #[test]
+    fn prune_unreachable_panics_on_unknown_entrypoint() {
+        let mut lib = Library::new();
+        lib.import(Box::new(DummyTestSnippetC));
+        lib.prune_unreachable("tasm_a_dummy_test_value");
+    }
+
+    #[test]
+    fn imports_in_dependency_order() {
+        let mut lib = Library::new();
+        lib.explicit_import("a_leaf", &triton_asm!(a_leaf: return))
+            .unwrap();
+        lib.explicit_import("b_caller", &triton_asm!(b_caller: call z_middle return))
+            .unwrap();
+        lib.explicit_import("z_middle", &triton_asm!(z_middle: call a_leaf return))
+            .unwrap();
+        lib.explicit_import("c_unrelated", &triton_asm!(c_unrelated: return))
+            .unwrap();
+
+        let first_labels = |order| {
+            lib.all_external_dependencies_in_order(order)
+                .into_iter()
+                .map(|body| body[0].to_string())
+                .collect_vec()
+        };
+        assert_eq!(
+            vec!["a_leaf:", "b_caller:", "c_unrelated:", "z_middle:"],
+            first_labels(ImportOrder::Alphabetical)
+        );
+        assert_eq!(
+            vec!["b_caller:", "c_unrelated:", "z_middle:", "a_leaf:"],
+            first_labels(ImportOrder::CallersFirst)
+        );
+        assert_eq!(
+            vec!["a_leaf:", "z_middle:", "c_unrelated:", "b_caller:"],
+            first_labels(ImportOrder::CalleesFirst)
+        );
+        assert_eq!(
+            lib.all_imports(),
+            lib.all_imports_in_order(ImportOrder::default())
+        );
+    }
+
+    #[test]
+    fn inline_small_snippets_test() {
+        let mut lib = Library::new();
+        lib.import(Box::new(DummyTestSnippetA));
+        lib.explicit_import(
+            "conditional",
+            &triton_asm!(conditional: push 1 skiz call tasm_c_dummy_test_value return),
+        )
+        .unwrap();
+
+        let main = triton_asm!(call tasm_a_dummy_test_value call conditional halt);
+        let run = |lib: &Library, main: &[LabelledInstruction]| {
+            let code = [main.to_vec(), lib.all_imports()].concat();
+            execute_bench(
+                &code,
+                &empty_stack(),
+                vec![],
+                NonDeterminism::default(),
+                None,
+            )
+        };
+        let expected = run(&lib, &main);
+
+        lib.inline_small_snippets(1);
+        assert_eq!(
+            vec![
+                triton_asm!(conditional: push 1 skiz call tasm_c_dummy_test_value return),
+                triton_asm!(tasm_a_dummy_test_value: call tasm_b_dummy_test_value push 1 return),
+                triton_asm!(tasm_b_dummy_test_value: push 1 push 1 return),
+                triton_asm!(tasm_c_dummy_test_value: push 1 return),
+            ],
+            lib.all_external_dependencies()
+        );
+
+        let inlined_main = lib.inline_calls(&main, 3);
+        assert_eq!(
+            triton_asm!(
+                call tasm_b_dummy_test_value
+                push 1
+                push 1
+                skiz
+                call tasm_c_dummy_test_value
+                halt
+            ),
+            inlined_main
+        );
+
+        let actual = run(&lib, &inlined_main);
+        assert_eq!(expected.final_stack, actual.final_stack);
+        assert!(actual.cycle_count < expected.cycle_count);
+    }
+
+    #[test]
+    fn code_statistics_test() {
+        let mut lib = Library::new();
+        assert_eq!(
+            CodeStatistics {
+                num_instructions: 0,
+                num_words: 0,
+                padded_program_length: 10,
+            },
+            lib.code_statistics()
+        );
+
+        lib.import(Box::new(DummyTestSnippetA));
+        // a: call, call, return; b: call, call, return; c: push, return
+        let statistics = lib.code_statistics();
+        assert_eq!(8, statistics.num_instructions);
+        assert_eq!(13, statistics.num_words);
+        assert_eq!(20, statistics.padded_program_length);
+        assert_eq!(32, statistics.min_padded_height());
+
+        let program = [
+            triton_asm!(call tasm_a_dummy_test_value halt),
+            lib.all_imports(),
+        ]
+        .concat();
+        assert_eq!(
+            Program::new(&program).len_bwords(),
+            CodeStatistics::of(&program).num_words
+        );
+    }
+
+    #[test]
+    fn export_and_import_tasm_files() {
+        let mut lib = Library::new();
+        lib.import(Box::new(DummyTestSnippetA));
+        lib.import(Box::new(MemCpy));
+
+        let path = std::env::temp_dir().join("tasm_lib_exported_library.tasm");
+        lib.write_tasm_file(&path).unwrap();
+
+        let mut reimported = Library::new();
+        let names = reimported.import_tasm_file(&path).unwrap();
+        assert_eq!(lib.get_all_snippet_names(), names);
+        assert_eq!(lib.all_imports(), reimported.all_imports());
+
+        let program = [
+            triton_asm!(call tasm_a_dummy_test_value halt),
+            lib.all_imports(),
+        ]
+        .concat();
+        let program_path = std::env::temp_dir().join("tasm_lib_exported_program.tasm");
+        write_tasm_file(&program, &program_path).unwrap();
+        let source = std::fs::read_to_string(&program_path).unwrap();
+        assert_eq!(Program::new(&program), Program::from_code(&source).unwrap());
+    }
+
+    #[test]
+    fn importing_invalid_tasm_file_fails() {
+        let path = std::env::temp_dir().join("tasm_lib_invalid_library.tasm");
+        std::fs::write(
+            &path,
+            "caller:\n    call calee\n    return\n\ncallee:\n    return\n",
+        )
+        .unwrap();
+
+        let mut lib = Library::new();
+        assert!(lib.import_tasm_file(&path).is_err());
+        assert!(lib.get_all_snippet_names().is_empty());
+    }
+
     #[test]
     fn program_is_deterministic() {
         // Ensure that a generated program is deterministic, by checking that the imports
@@ -447,15 +1452,252 @@ mod tests {
         let mut lib = Library::new();
 
         // allocate 1 word and verify that -1 is returned
-        let first_free_address = lib.kmalloc(1);
+        let first_free_address = lib.kmalloc("first", 1);
         assert_eq!(B_FIELD_ELEMENT_LAST, first_free_address);
 
         // allocate 7 words and verify that -8 is returned
-        let second_free_address = lib.kmalloc(7);
+        let second_free_address = lib.kmalloc("second", 7);
         assert_eq!(-BFieldElement::new(8), second_free_address,);
 
         // Allocate 1000 words.
-        let third_free_address = lib.kmalloc(1000);
+        let third_free_address = lib.kmalloc("third", 1000);
         assert_eq!(-BFieldElement::new(1008), third_free_address);
+
+        assert_eq!(
+            vec![
+                ("first".to_owned(), first_free_address, 1),
+                ("second".to_owned(), second_free_address, 7),
+                ("third".to_owned(), third_free_address, 1000),
+            ],
+            lib.statics()
+        );
+        assert_eq!(Some(second_free_address), lib.static_address("second"));
+        assert_eq!(None, lib.static_address("fourth"));
+    }
+
+    #[test]
+    fn static_data_is_initialized_before_entrypoint() {
+        let mut lib = Library::new();
+        let table = (0..7).map(|i| BFieldElement::new(100 + i)).collect_vec();
+        let table_address = lib.kmalloc_constant("table", &table);
+        let generator = [BFieldElement::new(7)];
+        let generator_address = lib.kmalloc_constant("generator", &generator);
+
+        let expected_memory: HashMap<_, _> = (0..7)
+            .map(|i| (table_address + BFieldElement::new(i), table[i as usize]))
+            .chain([(generator_address, generator[0])])
+            .collect();
+        assert_eq!(expected_memory, lib.static_data_memory());
+
+        let read_all = (0..7)
+            .map(|i| {
+                let address = table_address + BFieldElement::new(i);
+                triton_asm!(push {address} read_mem 1 pop 1 write_io 1)
+            })
+            .concat();
+        let initialize_static_data = lib.static_data_initialization();
+        let code = triton_asm!(
+            {&initialize_static_data}
+            call main
+            halt
+
+            main:
+                {&read_all}
+                push {generator_address}
+                read_mem 1
+                pop 1
+                write_io 1
+                return
+        );
+
+        let output = Program::new(&code)
+            .run(PublicInput::new(vec![]), NonDeterminism::new(vec![]))
+            .unwrap();
+        assert_eq!([table, generator.to_vec()].concat(), output);
+    }
+
+    #[test]
+    fn explicit_import_detects_label_collisions() {
+        let mut lib = Library::new();
+        let body = triton_asm!(label: push 1 return);
+        assert_eq!("label", lib.explicit_import("label", &body).unwrap());
+        assert_eq!("label", lib.explicit_import("label", &body).unwrap());
+
+        let other_body = triton_asm!(label: push 2 return);
+        assert!(lib.explicit_import("label", &other_body).is_err());
+        assert_eq!(vec![body], lib.all_external_dependencies());
+    }
+
+    #[test]
+    fn absorb_merges_snippets_and_static_allocations() {
+        let mut first = Library::new();
+        first.import(Box::new(DummyTestSnippetB));
+        let first_table = first.kmalloc_constant("first_table", &[BFieldElement::new(1); 3]);
+
+        let mut second = Library::with_preallocated_memory(first.num_words_statically_allocated());
+        second.import(Box::new(DummyTestSnippetA));
+        let second_scratch = second.kmalloc("second_scratch", 2);
+
+        first.absorb(second).unwrap();
+        assert_eq!(
+            vec![
+                "tasm_a_dummy_test_value",
+                "tasm_b_dummy_test_value",
+                "tasm_c_dummy_test_value"
+            ],
+            first.get_all_snippet_names()
+        );
+        assert_eq!(
+            vec![
+                ("first_table".to_owned(), first_table, 3),
+                ("second_scratch".to_owned(), second_scratch, 2),
+            ],
+            first.statics()
+        );
+        assert_eq!(5, first.num_words_statically_allocated());
+        assert_eq!(3, first.static_data_memory().len());
+    }
+
+    #[test]
+    fn absorb_rejects_conflicts() {
+        let mut first = Library::new();
+        first.kmalloc("scratch", 1);
+        let mut second = Library::new();
+        second.kmalloc("other_scratch", 1);
+        assert!(first.clone().absorb(second).is_err());
+
+        let mut third = Library::new();
+        third
+            .explicit_import("tasm_c_dummy_test_value", &triton_asm!(push 2 return))
+            .unwrap();
+        first.import(Box::new(DummyTestSnippetC));
+        assert!(first.clone().absorb(third).is_err());
+
+        let mut fourth = Library::new();
+        fourth.kmalloc("scratch", 1);
+        fourth.import(Box::new(DummyTestSnippetC));
+        assert!(first.absorb(fourth).is_ok());
+    }
+
+    #[test]
+    fn import_validation_accepts_valid_code() {
+        let mut lib = Library::new().with_import_validation();
+        lib.import(Box::new(DummyTestSnippetA));
+        lib.import(Box::new(MemCpy));
+
+        lib.declare_external("elsewhere");
+        lib.explicit_import(
+            "caller",
+            &triton_asm!(caller: call elsewhere call caller_loop return caller_loop: return),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn import_validation_rejects_invalid_code() {
+        let mut lib = Library::new().with_import_validation();
+        lib.import(Box::new(DummyTestSnippetC));
+
+        let wrong_label = triton_asm!(calller: return);
+        assert!(lib.explicit_import("caller", &wrong_label).is_err());
+
+        let duplicate_label = triton_asm!(caller: call inner return inner: return inner: return);
+        assert!(lib.explicit_import("caller", &duplicate_label).is_err());
+
+        let label_of_other_snippet = triton_asm!(caller: return tasm_c_dummy_test_value: return);
+        assert!(lib
+            .explicit_import("caller", &label_of_other_snippet)
+            .is_err());
+
+        let undefined_call = triton_asm!(caller: call undefined return);
+        assert!(lib.explicit_import("caller", &undefined_call).is_err());
+
+        let valid = triton_asm!(caller: call tasm_c_dummy_test_value return);
+        assert!(lib.explicit_import("caller", &valid).is_ok());
+        assert!(Library::new()
+            .explicit_import("caller", &undefined_call)
+            .is_ok());
+    }
+
+    #[test]
+    fn kfree_test() {
+        let mut lib = Library::new();
+        let a = lib.kmalloc("a", 3);
+        let b = lib.kmalloc("b", 2);
+        lib.kfree(a, 3);
+
+        // freed memory is reused, first-fit
+        assert_eq!(a, lib.kmalloc("c", 2));
+        assert_eq!(a + BFieldElement::new(2), lib.kmalloc("d", 1));
+        assert_eq!(b - BFieldElement::new(2), lib.kmalloc("e", 2));
+        assert_eq!(7, lib.num_words_statically_allocated());
+        assert_eq!(None, lib.static_address("a"));
+
+        // adjacent freed regions are merged
+        lib.kfree(b, 2);
+        lib.kfree(a + BFieldElement::new(2), 1);
+        lib.kfree(a, 2);
+        assert_eq!(b, lib.kmalloc("f", 5));
+        assert_eq!(7, lib.num_words_statically_allocated());
+    }
+
+    #[should_panic]
+    #[test]
+    fn kfree_twice_test() {
+        let mut lib = Library::new();
+        let scratch = lib.kmalloc("scratch", 2);
+        lib.kfree(scratch, 2);
+        lib.kfree(scratch, 2);
+    }
+
+    #[test]
+    fn static_memory_layout_test() {
+        let mut lib = Library::with_preallocated_memory(2);
+        let scratch = lib.kmalloc("scratch", 3);
+        let table = lib.kmalloc_constant("table", &[BFieldElement::new(5); 2]);
+        let temp = lib.kmalloc("temp", 1);
+        lib.kfree(temp, 1);
+
+        let layout = lib.static_memory_layout();
+        assert_eq!(2, layout.num_words_preallocated);
+        assert_eq!(
+            vec![
+                StaticAllocation {
+                    name: "scratch".to_owned(),
+                    first_address: scratch.value(),
+                    num_words: 3,
+                    has_static_data: false,
+                },
+                StaticAllocation {
+                    name: "table".to_owned(),
+                    first_address: table.value(),
+                    num_words: 2,
+                    has_static_data: true,
+                },
+            ],
+            layout.allocations
+        );
+        assert_eq!(vec![(temp.value(), 1)], layout.free_regions);
+        assert_eq!(temp.value(), layout.lowest_address);
+
+        let nondeterministic_ram = [BFieldElement::new(0), table + BFieldElement::new(1)];
+        let overlapping = layout.allocations_overlapping(nondeterministic_ram);
+        assert_eq!(
+            vec!["table"],
+            overlapping.iter().map(|a| &a.name).collect_vec()
+        );
+        assert!(!layout.is_reserved(BFieldElement::new(0)));
+        assert!(layout.is_reserved(STATIC_MEMORY_START_ADDRESS));
+
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(layout, serde_json::from_str(&json).unwrap());
+    }
+
+    #[should_panic]
+    #[test]
+    fn kmalloc_same_name_twice_test() {
+        let mut lib = Library::new();
+        lib.kmalloc("scratch", 1);
+        lib.kmalloc("scratch", 2);
     }
 }
@@ -1,34 +1,199 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
 use std::{cell::RefCell, rc::Rc};
 
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use triton_vm::vm::VMState;
 use triton_vm::{
     instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism, Program,
     PublicInput,
 };
 
+use crate::library::CodeStatistics;
+use crate::optimizer::optimize;
 use crate::traits::basic_snippet::BasicSnippet;
 use crate::{library::Library, prove_and_verify, ExecutionResult, VmHasherState};
 
-pub fn link_for_isolated_run<T: BasicSnippet>(
+/// Maps address ranges of a linked program to the names of the snippets the code in those
+/// ranges belongs to. Addresses are counted in words, like Triton VM's instruction pointer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    segments: Vec<(Range<usize>, String)>,
+}
+
+impl SourceMap {
+    /// Record that `code`, belonging to `name`, is placed right after all previously
+    /// recorded code.
+    pub fn append(&mut self, name: &str, code: &[LabelledInstruction]) {
+        let size = CodeStatistics::of(code).num_words;
+        let start = self.len();
+        self.segments.push((start..start + size, name.to_owned()));
+    }
+
+    /// Record that the code described by `other` is placed right after all previously
+    /// recorded code.
+    pub fn extend(&mut self, other: SourceMap) {
+        let offset = self.len();
+        for (range, name) in other.segments {
+            let shifted = range.start + offset..range.end + offset;
+            self.segments.push((shifted, name));
+        }
+    }
+
+    /// The total size, in words, of all recorded code.
+    pub fn len(&self) -> usize {
+        self.segments
+            .last()
+            .map(|(range, _)| range.end)
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the name of the snippet the given address belongs to, and the address
+    /// relative to the start of that snippet's code.
+    pub fn lookup(&self, address: usize) -> Option<(&str, usize)> {
+        self.segments
+            .iter()
+            .find(|(range, _)| range.contains(&address))
+            .map(|(range, name)| (name.as_str(), address - range.start))
+    }
+}
+
+pub fn link_for_isolated_run<T: BasicSnippet + ?Sized>(
     snippet: Rc<RefCell<T>>,
     words_statically_allocated: usize,
 ) -> Vec<LabelledInstruction> {
+    let (code, _) = link_for_isolated_run_with_source_map(snippet, words_statically_allocated);
+    code
+}
+
+/// Like [`link_for_isolated_run`], but with redundant instruction sequences removed from
+/// the linked program by the [peephole optimizer](optimize).
+pub fn link_for_isolated_run_optimized<T: BasicSnippet + ?Sized>(
+    snippet: Rc<RefCell<T>>,
+    words_statically_allocated: usize,
+) -> Vec<LabelledInstruction> {
+    optimize(&link_for_isolated_run(snippet, words_statically_allocated))
+}
+
+/// Like [`link_for_isolated_run`], but additionally return a [`SourceMap`] of the linked
+/// program.
+pub fn link_for_isolated_run_with_source_map<T: BasicSnippet + ?Sized>(
+    snippet: Rc<RefCell<T>>,
+    words_statically_allocated: usize,
+) -> (Vec<LabelledInstruction>, SourceMap) {
     let mut snippet_state = Library::with_preallocated_memory(words_statically_allocated as u32);
     let entrypoint = snippet.borrow().entrypoint();
     let function_body = snippet.borrow().code(&mut snippet_state);
     let library_code = snippet_state.all_imports();
+    let static_data_initialization = snippet_state.static_data_initialization();
 
     // The TASM code is always run through a function call, so the 1st instruction
-    // is a call to the function in question.
-    let code = triton_asm!(
+    // (after initializing static data) is a call to the function in question.
+    let preamble = triton_asm!(
+        {&static_data_initialization}
         call {entrypoint}
         halt
+    );
+    let code = triton_asm!(
+        {&preamble}
 
         {&function_body}
         {&library_code}
     );
 
-    code
+    let mut source_map = SourceMap::default();
+    source_map.append("preamble", &preamble);
+    source_map.append(&entrypoint, &function_body);
+    source_map.extend(snippet_state.source_map());
+
+    (code, source_map)
+}
+
+/// Where a program linked with [`link_for_dispatched_run`] reads the index of the snippet
+/// to run from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchSelector {
+    /// Read the index from standard input.
+    StandardInput,
+
+    /// Pop the index from the top of the stack.
+    Stack,
+}
+
+/// Link several snippets into one program. A dispatch preamble reads a selector and runs the
+/// snippet at that index of `snippets`, then halts. The program crashes if the selector does
+/// not correspond to any snippet.
+pub fn link_for_dispatched_run(
+    snippets: Vec<Box<dyn BasicSnippet>>,
+    selector: DispatchSelector,
+    words_statically_allocated: usize,
+) -> Vec<LabelledInstruction> {
+    let mut library = Library::with_preallocated_memory(words_statically_allocated as u32);
+    let entrypoints = snippets
+        .into_iter()
+        .map(|snippet| library.import(snippet))
+        .collect_vec();
+    let library_code = library.all_imports();
+    let static_data_initialization = library.static_data_initialization();
+
+    let read_selector = match selector {
+        DispatchSelector::StandardInput => triton_asm!(read_io 1),
+        DispatchSelector::Stack => vec![],
+    };
+
+    let dispatch_label = |index: usize| format!("tasm_linker_dispatch_{index}");
+    let dispatch_table = entrypoints
+        .iter()
+        .enumerate()
+        .map(|(index, _)| {
+            let dispatch = dispatch_label(index);
+            triton_asm!(
+                dup 0
+                push {index}
+                eq
+                skiz
+                call {dispatch}
+            )
+        })
+        .concat();
+
+    // Each dispatch subroutine halts, so control only returns to the dispatch table if the
+    // selector does not match.
+    let dispatch_subroutines = entrypoints
+        .iter()
+        .enumerate()
+        .map(|(index, entrypoint)| {
+            let dispatch = dispatch_label(index);
+            triton_asm!(
+                {dispatch}:
+                    pop 1
+                    call {entrypoint}
+                    halt
+            )
+        })
+        .concat();
+
+    triton_asm!(
+        {&static_data_initialization}
+        {&read_selector}
+        // _ selector
+
+        {&dispatch_table}
+
+        // unknown selector
+        push 0
+        assert
+        halt
+
+        {&dispatch_subroutines}
+        {&library_code}
+    )
 }
 
 /// Execute a Triton-VM program and return its output and execution trace length
@@ -65,3 +230,234 @@ pub fn execute_bench(
         u32_table_height: simulation_trace.u32_table_length(),
     }
 }
+
+/// The number of clock cycles spent in one snippet of a linked program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnippetCycleCount {
+    pub snippet: String,
+
+    /// Cycles spent executing the snippet's own instructions.
+    pub self_cycles: usize,
+
+    /// Cycles spent executing the snippet's own instructions or anything it calls.
+    pub total_cycles: usize,
+}
+
+/// Execute a Triton-VM program and attribute every clock cycle to the snippet, according to
+/// the `source_map`, whose code is executed in that cycle. Calls and returns are tracked to
+/// also attribute cycles to all snippets that are (transitively) waiting for a callee.
+///
+/// The result is sorted by total cycle count, in descending order.
+pub fn cycle_breakdown(
+    code: &[LabelledInstruction],
+    source_map: &SourceMap,
+    stack: &[BFieldElement],
+    std_in: Vec<BFieldElement>,
+    nondeterminism: NonDeterminism<BFieldElement>,
+    sponge_state: Option<VmHasherState>,
+) -> anyhow::Result<Vec<SnippetCycleCount>> {
+    let program = Program::new(code);
+    let mut vm_state = VMState::new(&program, PublicInput::new(std_in), nondeterminism);
+    vm_state.op_stack.stack = stack.to_vec();
+    vm_state.sponge_state = sponge_state.map(|state| state.state);
+
+    let snippet_at = |address: usize| {
+        source_map
+            .lookup(address)
+            .map(|(snippet, _)| snippet.to_owned())
+            .unwrap_or_default()
+    };
+
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    // snippets waiting for a callee to return
+    let mut active_snippets: Vec<String> = vec![];
+    while !vm_state.halting {
+        let current_snippet = snippet_at(vm_state.instruction_pointer);
+        counts.entry(current_snippet.clone()).or_default().0 += 1;
+        for snippet in active_snippets.iter().chain([&current_snippet]).unique() {
+            counts.entry(snippet.to_owned()).or_default().1 += 1;
+        }
+
+        let jump_stack_depth = vm_state.jump_stack.len();
+        vm_state.step()?;
+        match vm_state.jump_stack.len().cmp(&jump_stack_depth) {
+            Ordering::Greater => active_snippets.push(current_snippet),
+            Ordering::Less => {
+                active_snippets.pop();
+            }
+            Ordering::Equal => (),
+        }
+    }
+
+    let breakdown = counts
+        .into_iter()
+        .map(|(snippet, (self_cycles, total_cycles))| SnippetCycleCount {
+            snippet,
+            self_cycles,
+            total_cycles,
+        })
+        .sorted_by(|a, b| {
+            b.total_cycles
+                .cmp(&a.total_cycles)
+                .then_with(|| a.snippet.cmp(&b.snippet))
+        })
+        .collect();
+
+    Ok(breakdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::library::{DummyTestSnippetA, DummyTestSnippetB, DummyTestSnippetC};
+    use crate::recufier::verify_out_of_domain_quotient::VerifyOutOfDomainQuotient;
+    use crate::test_helpers::assert_assertion_failure;
+    use crate::traits::function::{Function, FunctionInitialState};
+    use crate::{empty_stack, execute_with_terminal_state_and_source_map};
+
+    use super::*;
+
+    fn dummy_snippets() -> Vec<Box<dyn BasicSnippet>> {
+        vec![
+            Box::new(DummyTestSnippetA),
+            Box::new(DummyTestSnippetB),
+            Box::new(DummyTestSnippetC),
+        ]
+    }
+
+    #[test]
+    fn dispatch_on_selector_from_standard_input() {
+        let code = link_for_dispatched_run(dummy_snippets(), DispatchSelector::StandardInput, 0);
+        for (selector, num_pushed_words) in [(0, 3), (1, 2), (2, 1)] {
+            let std_in = vec![BFieldElement::new(selector)];
+            let result = execute_bench(
+                &code,
+                &empty_stack(),
+                std_in,
+                NonDeterminism::default(),
+                None,
+            );
+            assert_eq!(
+                empty_stack().len() + num_pushed_words,
+                result.final_stack.len()
+            );
+        }
+    }
+
+    #[test]
+    fn dispatch_on_selector_from_stack() {
+        let code = link_for_dispatched_run(dummy_snippets(), DispatchSelector::Stack, 0);
+        for (selector, num_pushed_words) in [(0, 3), (1, 2), (2, 1)] {
+            let stack = [empty_stack(), vec![BFieldElement::new(selector)]].concat();
+            let result = execute_bench(&code, &stack, vec![], NonDeterminism::default(), None);
+            assert_eq!(
+                empty_stack().len() + num_pushed_words,
+                result.final_stack.len()
+            );
+        }
+    }
+
+    #[test]
+    fn source_map_locates_addresses_in_snippets() {
+        let (code, source_map) =
+            link_for_isolated_run_with_source_map(Rc::new(RefCell::new(DummyTestSnippetA)), 0);
+        let program_length = Program::new(&code).len_bwords();
+        assert_eq!(program_length, source_map.len());
+
+        // preamble: `call a` (2 words), `halt` (1 word)
+        assert_eq!(Some(("preamble", 0)), source_map.lookup(0));
+        assert_eq!(Some(("preamble", 2)), source_map.lookup(2));
+
+        // a: `call b` (2), `call c` (2), `return` (1)
+        assert_eq!(Some(("tasm_a_dummy_test_value", 0)), source_map.lookup(3));
+        assert_eq!(Some(("tasm_a_dummy_test_value", 4)), source_map.lookup(7));
+
+        // library imports, sorted alphabetically: b (5 words), c (3 words)
+        assert_eq!(Some(("tasm_b_dummy_test_value", 0)), source_map.lookup(8));
+        assert_eq!(Some(("tasm_c_dummy_test_value", 1)), source_map.lookup(14));
+        assert_eq!(None, source_map.lookup(program_length));
+    }
+
+    #[test]
+    fn failures_are_reported_relative_to_snippet() {
+        let preamble = triton_asm!(call failing halt);
+        let failing = triton_asm!(failing: push 0 assert return);
+        let mut source_map = SourceMap::default();
+        source_map.append("preamble", &preamble);
+        source_map.append("failing", &failing);
+
+        let program = Program::new(&[preamble, failing].concat());
+        let execution_result = execute_with_terminal_state_and_source_map(
+            &program,
+            &[],
+            &empty_stack(),
+            &NonDeterminism::default(),
+            None,
+            &source_map,
+        );
+        let err = execution_result.as_ref().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("inside failing at relative offset 2"));
+        assert_assertion_failure(execution_result);
+    }
+
+    #[test]
+    fn cycles_are_attributed_to_snippets() {
+        let (code, source_map) =
+            link_for_isolated_run_with_source_map(Rc::new(RefCell::new(DummyTestSnippetA)), 0);
+        let breakdown = cycle_breakdown(
+            &code,
+            &source_map,
+            &empty_stack(),
+            vec![],
+            NonDeterminism::default(),
+            None,
+        )
+        .unwrap();
+
+        let count = |snippet: &str| {
+            let count = breakdown.iter().find(|c| c.snippet == snippet).unwrap();
+            (count.self_cycles, count.total_cycles)
+        };
+        assert_eq!((3, 12), count("tasm_a_dummy_test_value"));
+        assert_eq!((3, 7), count("tasm_b_dummy_test_value"));
+        assert_eq!((6, 6), count("tasm_c_dummy_test_value"));
+
+        let preamble = count("preamble");
+        let sum_of_self_cycles: usize = breakdown.iter().map(|c| c.self_cycles).sum();
+        assert_eq!(preamble.1, sum_of_self_cycles);
+        assert_eq!("preamble", breakdown[0].snippet);
+    }
+
+    #[test]
+    fn optimized_linking_preserves_final_state_and_saves_cycles() {
+        let snippet = VerifyOutOfDomainQuotient {
+            num_initial_constraints: 3,
+            num_consistency_constraints: 0,
+            num_transition_constraints: 5,
+            num_terminal_constraints: 2,
+        };
+        let FunctionInitialState { stack, memory } =
+            snippet.pseudorandom_initial_state([0; 32], None);
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let run = |code: &[LabelledInstruction]| {
+            execute_bench(code, &stack, vec![], nondeterminism.clone(), None)
+        };
+
+        let snippet = Rc::new(RefCell::new(snippet));
+        let unoptimized = run(&link_for_isolated_run(snippet.clone(), 0));
+        let optimized = run(&link_for_isolated_run_optimized(snippet, 0));
+        assert_eq!(unoptimized.final_stack, optimized.final_stack);
+        assert_eq!(unoptimized.final_ram, optimized.final_ram);
+        assert_eq!(unoptimized.output, optimized.output);
+        assert!(optimized.cycle_count < unoptimized.cycle_count);
+    }
+
+    #[should_panic]
+    #[test]
+    fn unknown_selector_crashes() {
+        let code = link_for_dispatched_run(dummy_snippets(), DispatchSelector::Stack, 0);
+        let stack = [empty_stack(), vec![BFieldElement::new(3)]].concat();
+        execute_bench(&code, &stack, vec![], NonDeterminism::default(), None);
+    }
+}
@@ -150,12 +150,16 @@ impl BasicSnippet for All {
                 let (_, instructions) = tokenize(&fn_body).unwrap();
                 let labelled_instructions =
                     triton_vm::parser::to_labelled_instructions(&instructions);
-                library.explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                library
+                    .explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                    .unwrap()
             }
             InnerFunction::NoFunctionBody(_) => todo!(),
             InnerFunction::BasicSnippet(bs) => {
                 let labelled_instructions = bs.code(library);
-                library.explicit_import(&bs.entrypoint(), &labelled_instructions)
+                library
+                    .explicit_import(&bs.entrypoint(), &labelled_instructions)
+                    .unwrap()
             }
         };
 
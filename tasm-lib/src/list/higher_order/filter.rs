@@ -113,12 +113,16 @@ impl BasicSnippet for Filter {
                 let (_, instructions) = tokenize(&fn_body).unwrap();
                 let labelled_instructions =
                     triton_vm::parser::to_labelled_instructions(&instructions);
-                library.explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                library
+                    .explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                    .unwrap()
             }
             InnerFunction::NoFunctionBody(_) => todo!(),
             InnerFunction::BasicSnippet(bs) => {
                 let labelled_instructions = bs.code(library);
-                library.explicit_import(&bs.entrypoint(), &labelled_instructions)
+                library
+                    .explicit_import(&bs.entrypoint(), &labelled_instructions)
+                    .unwrap()
             }
         };
 
@@ -597,6 +601,35 @@ mod tests {
         .test();
     }
 
+    #[test]
+    fn test_with_namespaced_raw_function() {
+        let rawcode = RawCode::new(
+            triton_asm!(
+                lsb:
+                    call lsb_of_bfe
+                    return
+                lsb_of_bfe:
+                    split
+                    push 2
+                    swap 1
+                    div_mod
+                    swap 2
+                    pop 2
+                    return
+            ),
+            DataType::Bfe,
+            DataType::Bool,
+        )
+        .with_namespace("filter_test");
+        assert_eq!("filter_test_lsb", rawcode.entrypoint());
+
+        ShadowedFunction::new(Filter {
+            list_type: ListType::Unsafe,
+            f: InnerFunction::RawCode(rawcode),
+        })
+        .test();
+    }
+
     #[test]
     fn test_with_raw_function_lsb_on_xfe() {
         let rawcode = RawCode::new(
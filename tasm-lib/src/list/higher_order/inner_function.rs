@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use triton_vm::instruction::AnInstruction;
 use triton_vm::instruction::LabelledInstruction;
 use triton_vm::triton_asm;
@@ -56,6 +56,35 @@ impl RawCode {
 }
 
 impl RawCode {
+    /// Prefix every label defined in the inner function, and every call to such a label, with
+    /// `namespace`. Use this to avoid collisions between different inner functions that use
+    /// the same labels.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        let defined_labels: HashSet<String> = self
+            .function
+            .iter()
+            .filter_map(|instruction| match instruction {
+                LabelledInstruction::Label(label) => Some(label.to_owned()),
+                _ => None,
+            })
+            .collect();
+        let namespaced = |label: &str| format!("{namespace}_{label}");
+
+        for instruction in self.function.iter_mut() {
+            match instruction {
+                LabelledInstruction::Label(label) => *label = namespaced(label),
+                LabelledInstruction::Instruction(AnInstruction::Call(label))
+                    if defined_labels.contains(label.as_str()) =>
+                {
+                    *label = namespaced(label)
+                }
+                _ => (),
+            }
+        }
+
+        self
+    }
+
     /// Return the entrypoint, label, of the inner function. Used to make a call to this function.
     pub fn entrypoint(&self) -> String {
         let is_label = |x: &_| matches!(x, LabelledInstruction::Label(_));
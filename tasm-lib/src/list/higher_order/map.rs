@@ -131,12 +131,16 @@ impl BasicSnippet for Map {
                 let (_, instructions) = tokenize(&fn_body).unwrap();
                 let labelled_instructions =
                     triton_vm::parser::to_labelled_instructions(&instructions);
-                library.explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                library
+                    .explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                    .unwrap()
             }
             InnerFunction::NoFunctionBody(lnat) => lnat.label_name.to_owned(),
             InnerFunction::BasicSnippet(bs) => {
                 let labelled_instructions = bs.code(library);
-                library.explicit_import(&bs.entrypoint(), &labelled_instructions)
+                library
+                    .explicit_import(&bs.entrypoint(), &labelled_instructions)
+                    .unwrap()
             }
         };
 
@@ -296,6 +296,7 @@ mod tests {
 
     use crate::rust_shadowing_helper_functions::unsafe_list::*;
     use crate::structure::tasm_object::MemoryIter;
+    use crate::test_executor::{run_snippet_tests_in_parallel, SnippetTest};
     use crate::traits::function::ShadowedFunction;
     use crate::traits::rust_shadow::RustShadow;
 
@@ -323,50 +324,30 @@ mod tests {
 
     #[test]
     fn list_prop_test_more_types() {
+        let type_pairs = [
+            (DataType::Bfe, DataType::Bfe),
+            (DataType::U64, DataType::U32),
+            (DataType::Bool, DataType::Digest),
+            (DataType::U128, DataType::VoidPointer),
+            (DataType::U128, DataType::Digest),
+            (DataType::U128, DataType::U128),
+            (DataType::Digest, DataType::Digest),
+        ];
+
+        let mut tests = vec![];
         for list_type in [ListType::Unsafe, ListType::Safe] {
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::Bfe,
-                right_type: DataType::Bfe,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::U64,
-                right_type: DataType::U32,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::Bool,
-                right_type: DataType::Digest,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::U128,
-                right_type: DataType::VoidPointer,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::U128,
-                right_type: DataType::Digest,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::U128,
-                right_type: DataType::U128,
-            })
-            .test();
-            ShadowedFunction::new(Zip {
-                list_type: list_type.clone(),
-                left_type: DataType::Digest,
-                right_type: DataType::Digest,
-            })
-            .test();
+            for (left_type, right_type) in type_pairs.clone() {
+                let list_type = list_type.clone();
+                tests.push(SnippetTest::new(move || {
+                    ShadowedFunction::new(Zip {
+                        list_type: list_type.clone(),
+                        left_type: left_type.clone(),
+                        right_type: right_type.clone(),
+                    })
+                }));
+            }
         }
+        run_snippet_tests_in_parallel(tests);
     }
 
     #[proptest]
@@ -230,9 +230,8 @@ impl DeprecatedSnippet for Range {
 mod tests {
     use crate::{
         execute_with_execution_state_deprecated,
-        test_helpers::test_rust_equivalence_multiple_deprecated,
+        test_helpers::{assert_assertion_failure, test_rust_equivalence_multiple_deprecated},
     };
-    use triton_vm::error::{InstructionError, VMError};
 
     use super::*;
 
@@ -256,9 +255,7 @@ mod tests {
         let stack_diff = snippet.stack_diff();
         let execution_result =
             execute_with_execution_state_deprecated(snippet, init_state, stack_diff);
-        let err = execution_result.unwrap_err();
-        let err = err.downcast::<VMError>().unwrap();
-        assert_eq!(InstructionError::AssertionFailed, err.source);
+        assert_assertion_failure(execution_result);
     }
 
     #[test]
@@ -269,9 +266,7 @@ mod tests {
         let stack_diff = snippet.stack_diff();
         let execution_result =
             execute_with_execution_state_deprecated(snippet, init_state, stack_diff);
-        let err = execution_result.unwrap_err();
-        let err = err.downcast::<VMError>().unwrap();
-        assert_eq!(InstructionError::AssertionFailed, err.source);
+        assert_assertion_failure(execution_result);
     }
 }
 
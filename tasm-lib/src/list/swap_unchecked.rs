@@ -139,6 +139,41 @@ impl BasicSnippet for SwapUnchecked {
                     return
         )
     }
+
+    fn writable_memory_regions(
+        &self,
+        initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        let [.., list_pointer, a, b] = initial_stack else {
+            return None;
+        };
+
+        let element_size = self.element_type.stack_size();
+        let element_region = |index: BFieldElement| {
+            let offset = self.list_type.metadata_size() + index.value() as usize * element_size;
+            (
+                *list_pointer + BFieldElement::new(offset as u64),
+                element_size as u32,
+            )
+        };
+        Some(vec![element_region(*a), element_region(*b)])
+    }
+
+    fn input_objects(
+        &self,
+        initial_stack: &[BFieldElement],
+        initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(usize, u32)>> {
+        let [.., list_pointer, _, _] = initial_stack else {
+            return None;
+        };
+
+        let list_length = initial_memory.get(list_pointer)?.value() as usize;
+        let list_size =
+            self.list_type.metadata_size() + list_length * self.element_type.stack_size();
+        Some(vec![(0, list_size as u32)])
+    }
 }
 
 impl Algorithm for SwapUnchecked {
@@ -185,9 +220,44 @@ impl Algorithm for SwapUnchecked {
             self.initial_state(BFieldElement::one(), 2, 1, 1),
         ]
     }
+
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<AlgorithmInitialState> {
+        let (list_pointer, memory) = crate::fuzzing::arbitrary_list_in_memory(
+            u,
+            self.list_type.clone(),
+            &self.element_type,
+        )?;
+        let list_length = memory[&list_pointer].value() as usize;
+        let a = u.choose_index(list_length)?;
+        let b = u.choose_index(list_length)?;
+
+        Ok(AlgorithmInitialState {
+            stack: [
+                empty_stack(),
+                vec![
+                    list_pointer,
+                    BFieldElement::new(a as u64),
+                    BFieldElement::new(b as u64),
+                ],
+            ]
+            .concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+        })
+    }
 }
 
 impl SwapUnchecked {
+    pub fn new(list_type: ListType, element_type: DataType) -> Self {
+        Self {
+            list_type,
+            element_type,
+        }
+    }
+
     fn initial_state(
         &self,
         list_pointer: BFieldElement,
@@ -221,11 +291,13 @@ impl SwapUnchecked {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_executor::{run_snippet_tests_in_parallel, SnippetTest};
+    use crate::test_helpers::verify_memory_isolation;
     use crate::traits::algorithm::ShadowedAlgorithm;
-    use crate::traits::rust_shadow::RustShadow;
 
     #[test]
     fn test() {
+        let mut tests = vec![];
         for data_type in [
             DataType::Bfe,
             DataType::Bool,
@@ -247,17 +319,42 @@ mod test {
             ]),
             DataType::Tuple(vec![DataType::Digest, DataType::Digest, DataType::Xfe]),
         ] {
-            ShadowedAlgorithm::new(SwapUnchecked {
-                list_type: ListType::Unsafe,
-                element_type: data_type.clone(),
-            })
-            .test();
-            ShadowedAlgorithm::new(SwapUnchecked {
-                list_type: ListType::Safe,
-                element_type: data_type.clone(),
-            })
-            .test();
+            for list_type in [ListType::Unsafe, ListType::Safe] {
+                let element_type = data_type.clone();
+                tests.push(SnippetTest::new(move || {
+                    ShadowedAlgorithm::new(SwapUnchecked::new(
+                        list_type.clone(),
+                        element_type.clone(),
+                    ))
+                }));
+            }
         }
+        run_snippet_tests_in_parallel(tests);
+    }
+
+    #[should_panic(expected = "must only write to allocated or declared memory")]
+    #[test]
+    fn write_to_neighboring_element_is_detected() {
+        let snippet = ShadowedAlgorithm::new(SwapUnchecked {
+            list_type: ListType::Unsafe,
+            element_type: DataType::U64,
+        });
+        let list_pointer = BFieldElement::new(100);
+        let initial_stack = [
+            empty_stack(),
+            vec![list_pointer, BFieldElement::new(0), BFieldElement::new(1)],
+        ]
+        .concat();
+
+        // elements 0 and 1 occupy addresses 101 through 104
+        let final_memory = HashMap::from([
+            (BFieldElement::new(101), BFieldElement::new(1)),
+            (BFieldElement::new(104), BFieldElement::new(1)),
+        ]);
+        verify_memory_isolation(&snippet, &initial_stack, &HashMap::new(), &final_memory, 0);
+
+        let final_memory = HashMap::from([(BFieldElement::new(105), BFieldElement::new(1))]);
+        verify_memory_isolation(&snippet, &initial_stack, &HashMap::new(), &final_memory, 0);
     }
 }
 
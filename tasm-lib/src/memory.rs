@@ -3,10 +3,22 @@ use std::collections::HashMap;
 use triton_vm::{instruction::LabelledInstruction, triton_asm, triton_instr, BFieldElement};
 use twenty_first::shared_math::bfield_codec::BFieldCodec;
 
+pub mod arena;
+pub mod committed_blob;
+pub mod dyn_free;
 pub mod dyn_malloc;
+pub mod dyn_malloc_checked;
 pub mod dyn_malloc_const_size;
+pub mod dyn_malloc_free_list;
+pub mod load_words;
+pub mod lookup_table;
+pub mod memcmp;
 pub mod memcpy;
+pub mod memmove;
+pub mod nondeterministic_pages;
 pub mod push_ram_to_stack;
+pub mod realloc;
+pub mod zeroize_region;
 
 /// Non-deterministially initialized memory lives in the range $[0: 2^{32})$
 pub const FIRST_NON_DETERMINISTICALLY_INITIALIZED_MEMORY_ADDRESS: BFieldElement =
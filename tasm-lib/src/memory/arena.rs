@@ -0,0 +1,105 @@
+//! An arena allocator, for reclaiming all memory allocated in some scope at once, for
+//! example the temporary allocations of one loop iteration.
+//!
+//! An arena is a region of dynamically allocated memory together with a header of
+//! [`ARENA_HEADER_SIZE`] words, which can live anywhere in memory, *e.g.*, in a slot
+//! obtained from [`kmalloc`](crate::library::Library::kmalloc). The header holds the
+//! address of the region, the number of words allocated from the arena so far, and the
+//! arena's capacity. [`ArenaAlloc`](alloc::ArenaAlloc) hands out memory by bumping the
+//! number of allocated words, [`ArenaCheckpoint`](checkpoint::ArenaCheckpoint) returns it,
+//! and [`ArenaReset`](reset::ArenaReset) reclaims everything allocated after a checkpoint.
+
+use std::collections::HashMap;
+
+use triton_vm::BFieldElement;
+
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+
+pub mod alloc;
+pub mod checkpoint;
+pub mod new;
+pub mod reset;
+
+pub const ARENA_HEADER_SIZE: u32 = 3;
+
+/// Offset of the number of allocated words within an arena's header.
+const USED_OFFSET: u64 = 1;
+
+/// Offset of the capacity within an arena's header.
+const CAPACITY_OFFSET: u64 = 2;
+
+/// Memory holding an arena of the given capacity with its header at `arena`, of which
+/// `used` words are allocated.
+pub(crate) fn memory_with_arena(
+    arena: BFieldElement,
+    capacity: u32,
+    used: u32,
+) -> HashMap<BFieldElement, BFieldElement> {
+    let mut memory = HashMap::new();
+    let start = dynamic_allocator(capacity as usize, &mut memory);
+    memory.insert(arena, start);
+    memory.insert(arena + BFieldElement::new(USED_OFFSET), used.into());
+    memory.insert(arena + BFieldElement::new(CAPACITY_OFFSET), capacity.into());
+
+    memory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alloc::ArenaAlloc;
+    use super::checkpoint::ArenaCheckpoint;
+    use super::new::ArenaNew;
+    use super::reset::ArenaReset;
+    use super::*;
+    use crate::empty_stack;
+    use crate::traits::accessor::Accessor;
+    use crate::traits::function::Function;
+
+    fn alloc(
+        arena: BFieldElement,
+        size: u64,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) -> BFieldElement {
+        let mut stack = [empty_stack(), vec![arena, BFieldElement::new(size)]].concat();
+        ArenaAlloc.rust_shadow(&mut stack, memory);
+        stack.pop().unwrap()
+    }
+
+    fn checkpoint(
+        arena: BFieldElement,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> BFieldElement {
+        let mut stack = [empty_stack(), vec![arena]].concat();
+        ArenaCheckpoint.rust_shadow(&mut stack, memory, &Default::default());
+        stack.pop().unwrap()
+    }
+
+    fn reset(
+        arena: BFieldElement,
+        checkpoint: BFieldElement,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let mut stack = [empty_stack(), vec![arena, checkpoint]].concat();
+        ArenaReset.rust_shadow(&mut stack, memory);
+    }
+
+    #[test]
+    fn memory_allocated_after_checkpoint_is_reclaimed() {
+        let arena = BFieldElement::new(7);
+        let mut memory = HashMap::new();
+        let mut stack = [empty_stack(), vec![arena, BFieldElement::new(20)]].concat();
+        ArenaNew.rust_shadow(&mut stack, &mut memory);
+
+        let first_allocation = alloc(arena, 4, &mut memory);
+        let after_first_allocation = checkpoint(arena, &memory);
+        let second_allocation = alloc(arena, 10, &mut memory);
+        alloc(arena, 6, &mut memory);
+        assert_eq!(BFieldElement::new(4), after_first_allocation);
+
+        reset(arena, after_first_allocation, &mut memory);
+        assert_eq!(second_allocation, alloc(arena, 3, &mut memory));
+
+        reset(arena, BFieldElement::new(0), &mut memory);
+        assert_eq!(first_allocation, alloc(arena, 1, &mut memory));
+    }
+}
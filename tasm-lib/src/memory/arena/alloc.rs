@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::arena::{memory_with_arena, CAPACITY_OFFSET, USED_OFFSET};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Allocate `size` words from the arena with its header at `*arena`. Crashes the VM if the
+/// arena's remaining capacity is insufficient.
+#[derive(Clone, Debug)]
+pub struct ArenaAlloc;
+
+impl BasicSnippet for ArenaAlloc {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*arena".to_owned()),
+            (DataType::U32, "size".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*addr".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_arena_alloc".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        triton_asm!(
+        // BEFORE: _ *arena size
+        // AFTER:  _ *addr
+        {entrypoint}:
+            dup 1
+            push {CAPACITY_OFFSET}
+            add
+            read_mem 3
+            pop 1                       // _ *arena size capacity used *start
+
+            dup 1
+            add                         // _ *arena size capacity used *addr
+            swap 1
+            dup 3
+            add                         // _ *arena size capacity *addr used'
+
+            // Ensure that the arena's capacity suffices
+            dup 0
+            dup 3
+            lt
+            push 0
+            eq
+            assert                      // _ *arena size capacity *addr used'
+
+            dup 4
+            push {USED_OFFSET}
+            add
+            write_mem 1
+            pop 1                       // _ *arena size capacity *addr
+
+            swap 3
+            pop 3
+            return
+        )
+    }
+}
+
+impl Function for ArenaAlloc {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let size = stack.pop().unwrap();
+        let arena = stack.pop().unwrap();
+
+        let read = |address: BFieldElement| memory.get(&address).copied().unwrap_or_default();
+        let start = read(arena);
+        let used = read(arena + BFieldElement::new(USED_OFFSET));
+        let capacity = read(arena + BFieldElement::new(CAPACITY_OFFSET));
+
+        let new_used = used.value() + size.value();
+        assert!(
+            new_used <= capacity.value(),
+            "arena capacity must suffice for allocation"
+        );
+        memory.insert(arena + BFieldElement::new(USED_OFFSET), new_used.into());
+        stack.push(start + used);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let arena = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let capacity = rng.gen_range(0..1 << 20);
+        let used = rng.gen_range(0..=capacity);
+        let size = rng.gen_range(0..=capacity - used);
+
+        Self::initial_state(arena, capacity, used, size)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let arena = BFieldElement::new(100);
+        vec![
+            Self::initial_state(arena, 0, 0, 0),
+            Self::initial_state(arena, 10, 0, 10),
+            Self::initial_state(arena, 10, 10, 0),
+            Self::initial_state(arena, 10, 3, 7),
+            Self::initial_state(arena, u32::MAX, 0, u32::MAX),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let arena = BFieldElement::new(100);
+        vec![
+            (
+                Self::initial_state(arena, 0, 0, 1),
+                InstructionError::AssertionFailed,
+            ),
+            (
+                Self::initial_state(arena, 10, 4, 7),
+                InstructionError::AssertionFailed,
+            ),
+        ]
+    }
+}
+
+impl ArenaAlloc {
+    fn initial_state(
+        arena: BFieldElement,
+        capacity: u32,
+        used: u32,
+        size: u32,
+    ) -> FunctionInitialState {
+        let stack = [empty_stack(), vec![arena, size.into()]].concat();
+        let memory = memory_with_arena(arena, capacity, used);
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_alloc_test() {
+        ShadowedFunction::new(ArenaAlloc).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_alloc_benchmark() {
+        ShadowedFunction::new(ArenaAlloc).bench();
+    }
+}
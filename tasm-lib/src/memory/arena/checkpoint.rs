@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::{triton_asm, NonDeterminism};
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::arena::{memory_with_arena, USED_OFFSET};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::accessor::{Accessor, AccessorInitialState};
+use crate::traits::basic_snippet::BasicSnippet;
+
+/// Return a checkpoint of the arena with its header at `*arena`, to which
+/// [`ArenaReset`](crate::memory::arena::reset::ArenaReset) can later reset the arena. The
+/// checkpoint is the number of words allocated from the arena so far.
+#[derive(Clone, Debug)]
+pub struct ArenaCheckpoint;
+
+impl BasicSnippet for ArenaCheckpoint {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*arena".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "checkpoint".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_arena_checkpoint".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        triton_asm!(
+        // BEFORE: _ *arena
+        // AFTER:  _ checkpoint
+        {entrypoint}:
+            push {USED_OFFSET}
+            add
+            read_mem 1
+            pop 1
+            return
+        )
+    }
+}
+
+impl Accessor for ArenaCheckpoint {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let arena = stack.pop().unwrap();
+        let used = memory
+            .get(&(arena + BFieldElement::new(USED_OFFSET)))
+            .copied()
+            .unwrap_or_default();
+        stack.push(used);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> AccessorInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let arena = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let capacity = rng.gen_range(0..1 << 20);
+        let used = rng.gen_range(0..=capacity);
+
+        AccessorInitialState {
+            stack: [empty_stack(), vec![arena]].concat(),
+            nondeterminism: NonDeterminism::default()
+                .with_ram(memory_with_arena(arena, capacity, used)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::accessor::ShadowedAccessor;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_checkpoint_test() {
+        ShadowedAccessor::new(ArenaCheckpoint).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::accessor::ShadowedAccessor;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_checkpoint_benchmark() {
+        ShadowedAccessor::new(ArenaCheckpoint).bench();
+    }
+}
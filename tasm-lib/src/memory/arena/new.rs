@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::arena::{CAPACITY_OFFSET, USED_OFFSET};
+use crate::memory::dyn_malloc::DynMalloc;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Dynamically allocate `capacity` words for a new, empty arena and write the arena's
+/// header to `*arena`, overwriting any arena there.
+#[derive(Clone, Debug)]
+pub struct ArenaNew;
+
+impl BasicSnippet for ArenaNew {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*arena".to_owned()),
+            (DataType::U32, "capacity".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_arena_new".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+
+        triton_asm!(
+        // BEFORE: _ *arena capacity
+        // AFTER:  _
+        {entrypoint}:
+            dup 0
+            call {dyn_malloc}           // _ *arena capacity *start
+            push 0
+            swap 1                      // _ *arena capacity 0 *start
+            dup 3
+            write_mem 3                 // _ *arena (*arena + 3)
+            pop 2
+            return
+        )
+    }
+}
+
+impl Function for ArenaNew {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let capacity = stack.pop().unwrap();
+        let arena = stack.pop().unwrap();
+
+        let start = dynamic_allocator(capacity.value() as usize, memory);
+        memory.insert(arena, start);
+        memory.insert(arena + BFieldElement::new(USED_OFFSET), 0u64.into());
+        memory.insert(arena + BFieldElement::new(CAPACITY_OFFSET), capacity);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let arena = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let capacity = rng.gen_range(0..1 << 20);
+
+        let mut memory = HashMap::new();
+        if rng.gen() {
+            dynamic_allocator(rng.gen_range(0..1000), &mut memory);
+        }
+
+        Self::initial_state(arena, capacity, memory)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let arena = BFieldElement::new(100);
+        let mut allocated_memory = HashMap::new();
+        dynamic_allocator(10, &mut allocated_memory);
+
+        vec![
+            Self::initial_state(arena, 0, HashMap::new()),
+            Self::initial_state(arena, 1, allocated_memory),
+            Self::initial_state(arena, u32::MAX, HashMap::new()),
+        ]
+    }
+}
+
+impl ArenaNew {
+    fn initial_state(
+        arena: BFieldElement,
+        capacity: u32,
+        memory: HashMap<BFieldElement, BFieldElement>,
+    ) -> FunctionInitialState {
+        let stack = [empty_stack(), vec![arena, capacity.into()]].concat();
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_new_test() {
+        ShadowedFunction::new(ArenaNew).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_new_benchmark() {
+        ShadowedFunction::new(ArenaNew).bench();
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::arena::{memory_with_arena, USED_OFFSET};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Reclaim all memory allocated from the arena with its header at `*arena` since the given
+/// [checkpoint](crate::memory::arena::checkpoint::ArenaCheckpoint). Checkpoint 0 reclaims
+/// all of the arena's memory. Crashes the VM if the checkpoint lies beyond the arena's
+/// current allocations.
+#[derive(Clone, Debug)]
+pub struct ArenaReset;
+
+impl BasicSnippet for ArenaReset {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*arena".to_owned()),
+            (DataType::U32, "checkpoint".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_arena_reset".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        triton_asm!(
+        // BEFORE: _ *arena checkpoint
+        // AFTER:  _
+        {entrypoint}:
+            dup 1
+            push {USED_OFFSET}
+            add
+            read_mem 1
+            pop 1                       // _ *arena checkpoint used
+
+            // Ensure that the checkpoint does not lie beyond the current allocations
+            dup 1
+            swap 1
+            lt
+            push 0
+            eq
+            assert                      // _ *arena checkpoint
+
+            swap 1
+            push {USED_OFFSET}
+            add
+            write_mem 1
+            pop 1
+            return
+        )
+    }
+}
+
+impl Function for ArenaReset {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let checkpoint = stack.pop().unwrap();
+        let arena = stack.pop().unwrap();
+
+        let used_address = arena + BFieldElement::new(USED_OFFSET);
+        let used = memory.get(&used_address).copied().unwrap_or_default();
+        assert!(
+            checkpoint.value() <= used.value(),
+            "checkpoint must not lie beyond the arena's allocations"
+        );
+        memory.insert(used_address, checkpoint);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let arena = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let capacity = rng.gen_range(0..1 << 20);
+        let used = rng.gen_range(0..=capacity);
+        let checkpoint = rng.gen_range(0..=used);
+
+        Self::initial_state(arena, capacity, used, checkpoint)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let arena = BFieldElement::new(100);
+        vec![
+            Self::initial_state(arena, 0, 0, 0),
+            Self::initial_state(arena, 10, 10, 0),
+            Self::initial_state(arena, 10, 10, 10),
+            Self::initial_state(arena, 10, 3, 2),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let arena = BFieldElement::new(100);
+        vec![
+            (
+                Self::initial_state(arena, 10, 0, 1),
+                InstructionError::AssertionFailed,
+            ),
+            (
+                Self::initial_state(arena, 10, 3, 10),
+                InstructionError::AssertionFailed,
+            ),
+        ]
+    }
+}
+
+impl ArenaReset {
+    fn initial_state(
+        arena: BFieldElement,
+        capacity: u32,
+        used: u32,
+        checkpoint: u32,
+    ) -> FunctionInitialState {
+        let stack = [empty_stack(), vec![arena, checkpoint.into()]].concat();
+        let memory = memory_with_arena(arena, capacity, used);
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_reset_test() {
+        ShadowedFunction::new(ArenaReset).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn arena_reset_benchmark() {
+        ShadowedFunction::new(ArenaReset).bench();
+    }
+}
@@ -0,0 +1,225 @@
+//! A blob of words the prover supplies through nondeterministically initialized memory,
+//! committed to by its [`hash_varlen`](AlgebraicHasher::hash_varlen) digest.
+//!
+//! The host places the blob into [nondeterministic pages](super::nondeterministic_pages)
+//! with [`CommittedBlob::load`], and the program passes the expected
+//! [commitment](CommittedBlob::commitment) on the stack to [`VerifyCommittedBlob`] before
+//! reading any word of the blob.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+
+use crate::data_type::DataType;
+use crate::hashing::eq_digest::EqDigest;
+use crate::hashing::hash_varlen::HashVarlen;
+use crate::library::Library;
+use crate::memory::nondeterministic_pages::NondeterministicPages;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+use crate::{empty_stack, push_encodable, Digest, VmHasher, DIGEST_LENGTH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedBlob {
+    pub words: Vec<BFieldElement>,
+}
+
+impl CommittedBlob {
+    pub fn new(words: Vec<BFieldElement>) -> Self {
+        Self { words }
+    }
+
+    /// The blob consisting of the object's encoding.
+    pub fn from_object<T: BFieldCodec>(object: &T) -> Self {
+        Self::new(object.encode())
+    }
+
+    pub fn commitment(&self) -> Digest {
+        VmHasher::hash_varlen(&self.words)
+    }
+
+    /// Reserve pages for the blob, write it to `ram`, and return the pointer to its first
+    /// word.
+    pub fn load(
+        &self,
+        pages: &mut NondeterministicPages,
+        ram: &mut HashMap<BFieldElement, BFieldElement>,
+    ) -> BFieldElement {
+        let blob = pages.reserve(self.words.len() as u64);
+        for (i, &word) in self.words.iter().enumerate() {
+            ram.insert(blob + BFieldElement::new(i as u64), word);
+        }
+        blob
+    }
+}
+
+/// Crash the VM unless the `length` words starting at `*blob` hash to the given
+/// commitment.
+#[derive(Clone, Debug)]
+pub struct VerifyCommittedBlob;
+
+impl BasicSnippet for VerifyCommittedBlob {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::Digest, "commitment".to_owned()),
+            (DataType::VoidPointer, "*blob".to_owned()),
+            (DataType::U32, "length".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_verify_committed_blob".to_string()
+    }
+
+    fn writable_memory_regions(
+        &self,
+        _initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        Some(vec![])
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let hash_varlen = library.import(Box::new(HashVarlen));
+        let eq_digest = library.import(Box::new(EqDigest));
+
+        triton_asm!(
+        // BEFORE: _ [commitment] *blob length
+        // AFTER:  _
+        {entrypoint}:
+            call {hash_varlen}          // _ [commitment] [digest]
+            call {eq_digest}            // _ (commitment == digest)
+            assert                      // _
+            return
+        )
+    }
+}
+
+impl Function for VerifyCommittedBlob {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let length = stack.pop().unwrap().value();
+        let blob = stack.pop().unwrap();
+        let commitment = (0..DIGEST_LENGTH)
+            .map(|_| stack.pop().unwrap())
+            .collect::<Vec<_>>();
+        let commitment = *Digest::decode(&commitment).unwrap();
+
+        let words = (0..length)
+            .map(|i| blob + BFieldElement::new(i))
+            .map(|address| memory.get(&address).copied().unwrap_or_default())
+            .collect();
+        assert_eq!(commitment, CommittedBlob::new(words).commitment());
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 1000,
+            Some(BenchmarkCase::WorstCase) => 10000,
+            None => rng.gen_range(0..200),
+        };
+        let blob = CommittedBlob::new((0..length).map(|_| rng.gen()).collect());
+        Self::initial_state(&blob, blob.commitment())
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let empty_blob = CommittedBlob::new(vec![]);
+        let single_word = CommittedBlob::new(vec![BFieldElement::new(42)]);
+        vec![
+            Self::initial_state(&empty_blob, empty_blob.commitment()),
+            Self::initial_state(&single_word, single_word.commitment()),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xb1; 32]);
+        let blob = CommittedBlob::new((0..20).map(|_| rng.gen()).collect());
+
+        let mut tampered_blob = blob.clone();
+        tampered_blob.words[7].increment();
+        let truncated_blob = CommittedBlob::new(blob.words[..19].to_vec());
+
+        [tampered_blob, truncated_blob]
+            .iter()
+            .map(|wrong_blob| Self::initial_state(wrong_blob, blob.commitment()))
+            .map(|state| (state, InstructionError::AssertionFailed))
+            .collect()
+    }
+}
+
+impl VerifyCommittedBlob {
+    fn initial_state(blob: &CommittedBlob, commitment: Digest) -> FunctionInitialState {
+        let mut memory = HashMap::new();
+        let blob_pointer = blob.load(&mut NondeterministicPages::new(), &mut memory);
+
+        let mut stack = empty_stack();
+        push_encodable(&mut stack, &commitment);
+        stack.push(blob_pointer);
+        stack.push(BFieldElement::new(blob.words.len() as u64));
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure::tasm_object::TasmObject;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_committed_blob_test() {
+        ShadowedFunction::new(VerifyCommittedBlob).test();
+    }
+
+    #[test]
+    fn blobs_of_objects_commit_to_their_encoding() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let digests: Vec<Digest> = (0..5).map(|_| rng.gen()).collect();
+        let blob = CommittedBlob::from_object(&digests);
+        assert_eq!(VmHasher::hash_varlen(&digests.encode()), blob.commitment());
+
+        let mut pages = NondeterministicPages::new();
+        let mut ram = HashMap::new();
+        let blob_pointer = blob.load(&mut pages, &mut ram);
+        assert!(pages.contains(blob_pointer));
+        assert_eq!(
+            digests,
+            *Vec::<Digest>::decode_from_memory(&ram, blob_pointer).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_committed_blob_benchmark() {
+        ShadowedFunction::new(VerifyCommittedBlob).bench();
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::dyn_malloc::FREE_LIST_ADDRESS;
+use crate::rust_shadowing_helper_functions::dyn_malloc::{dynamic_allocator, dynamic_free};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Release a block of dynamically allocated memory of the given size, such that
+/// [`DynMallocFreeList`](crate::memory::dyn_malloc_free_list::DynMallocFreeList) can hand
+/// it out again.
+///
+/// Freed blocks form a linked list whose head lives at [`FREE_LIST_ADDRESS`]. A freed block
+/// stores the pointer to the next freed block in its first word and its own size in its
+/// second word, which is why blocks smaller than 2 words are not reused. Freeing a block
+/// twice corrupts the list.
+#[derive(Clone, Debug)]
+pub struct DynFree;
+
+/// Blocks need room for the pointer to the next freed block and for their size.
+const MIN_REUSABLE_BLOCK_SIZE: u64 = 2;
+
+impl BasicSnippet for DynFree {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*block".to_owned()),
+            (DataType::U32, "size".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_dyn_free".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let push_block = format!("{entrypoint}_push_block");
+
+        triton_asm!(
+        // BEFORE: _ *block size
+        // AFTER:  _
+        {entrypoint}:
+            push {MIN_REUSABLE_BLOCK_SIZE}
+            dup 1
+            lt
+            push 0
+            eq                          // _ *block size (size >= 2)
+            skiz
+                call {push_block}
+            pop 2
+            return
+
+        // BEFORE: _ *block size
+        // AFTER:  _ *block size
+        {push_block}:
+            dup 0
+            dup 2
+            push 1
+            add
+            write_mem 1
+            pop 1                       // _ *block size
+
+            push {FREE_LIST_ADDRESS}
+            read_mem 1
+            pop 1                       // _ *block size *head
+            dup 2
+            write_mem 1
+            pop 1                       // _ *block size
+
+            dup 1
+            push {FREE_LIST_ADDRESS}
+            write_mem 1
+            pop 1                       // _ *block size
+            return
+        )
+    }
+}
+
+impl Function for DynFree {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let size = stack.pop().unwrap();
+        let block = stack.pop().unwrap();
+        if size.value() < MIN_REUSABLE_BLOCK_SIZE {
+            return;
+        }
+
+        let head = memory.get(&FREE_LIST_ADDRESS).copied().unwrap_or_default();
+        memory.insert(block + BFieldElement::new(1), size);
+        memory.insert(block, head);
+        memory.insert(FREE_LIST_ADDRESS, block);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let num_freed_blocks = rng.gen_range(0..10);
+        let freed_block_sizes = (0..num_freed_blocks)
+            .map(|_| rng.gen_range(0..20))
+            .collect::<Vec<_>>();
+        Self::initial_state(&freed_block_sizes, rng.gen_range(0..20))
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut states = vec![];
+        for freed_block_sizes in [vec![], vec![2], vec![3, 7, 2]] {
+            for size in [0, 1, 2, 3] {
+                states.push(Self::initial_state(&freed_block_sizes, size));
+            }
+        }
+
+        states
+    }
+}
+
+impl DynFree {
+    /// Free a freshly allocated block of the given size after freeing blocks of the given
+    /// sizes.
+    fn initial_state(freed_block_sizes: &[usize], size: usize) -> FunctionInitialState {
+        let mut memory = memory_with_freed_blocks(freed_block_sizes);
+        let block = dynamic_allocator(size, &mut memory);
+        let stack = [empty_stack(), vec![block, BFieldElement::new(size as u64)]].concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+/// Memory in which blocks of the given sizes were dynamically allocated and then freed, in
+/// order.
+pub(crate) fn memory_with_freed_blocks(
+    block_sizes: &[usize],
+) -> HashMap<BFieldElement, BFieldElement> {
+    let mut memory = HashMap::new();
+    let blocks = block_sizes
+        .iter()
+        .map(|&size| (dynamic_allocator(size, &mut memory), size))
+        .collect::<Vec<_>>();
+    for (block, size) in blocks {
+        dynamic_free(block, size, &mut memory);
+    }
+
+    memory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_free_test() {
+        ShadowedFunction::new(DynFree).test();
+    }
+
+    #[test]
+    fn freed_blocks_are_linked_most_recent_first() {
+        let memory = memory_with_freed_blocks(&[3, 1, 5]);
+        let head = memory[&FREE_LIST_ADDRESS];
+        let next = memory[&head];
+
+        assert_eq!(
+            BFieldElement::new(5),
+            memory[&(head + BFieldElement::new(1))]
+        );
+        assert_eq!(
+            BFieldElement::new(3),
+            memory[&(next + BFieldElement::new(1))]
+        );
+        assert_eq!(BFieldElement::new(0), memory[&next]);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_free_benchmark() {
+        ShadowedFunction::new(DynFree).bench();
+    }
+}
@@ -1,16 +1,24 @@
 use itertools::Itertools;
 use std::collections::HashMap;
 
-use num::{One, Zero};
+use num::Zero;
 use rand::Rng;
 use triton_vm::instruction::LabelledInstruction;
 use triton_vm::triton_asm;
 use twenty_first::shared_math::b_field_element::BFieldElement;
 
 pub const DYN_MALLOC_ADDRESS: BFieldElement = BFieldElement::new(1 << 32);
-pub const FIRST_DYNAMICALLY_ALLOCATED_ADDRESS: BFieldElement = BFieldElement::new((1 << 32) + 1);
+
+/// Holds the pointer to the most recently freed block of dynamically allocated memory, see
+/// [`DynFree`](crate::memory::dyn_free::DynFree). Like the allocator's own state, it lives
+/// outside of nondeterministically initialized memory, such that the prover cannot supply
+/// a free list of its choosing.
+pub const FREE_LIST_ADDRESS: BFieldElement = BFieldElement::new((1 << 32) + 1);
+
+pub const FIRST_DYNAMICALLY_ALLOCATED_ADDRESS: BFieldElement = BFieldElement::new((1 << 32) + 2);
 
 use crate::data_type::DataType;
+use crate::rust_shadowing_helper_functions::dyn_malloc::record_allocation;
 use crate::traits::deprecated_snippet::DeprecatedSnippet;
 use crate::{empty_stack, library::Library, ExecutionState};
 
@@ -50,8 +58,6 @@ impl DeprecatedSnippet for DynMalloc {
 
     fn function_code(&self, _library: &mut Library) -> String {
         let entrypoint = self.entrypoint_name();
-        let first_dynamically_allocated_address: BFieldElement =
-            DYN_MALLOC_ADDRESS + BFieldElement::new(1);
         triton_asm!(
         // Return a pointer to a free address and allocate `size` words for this pointer
 
@@ -65,8 +71,8 @@ impl DeprecatedSnippet for DynMalloc {
             dup 0                                      // _ size *next_addr' *next_addr'
             push 0                                     // _ size *next_addr' *next_addr' 0
             eq                                         // _ size *next_addr' (*next_addr' == 0)
-            push {first_dynamically_allocated_address}
-            mul                                        // _ size *next_addr' (*next_addr' == 0) * (2^{32} + 1)
+            push {FIRST_DYNAMICALLY_ALLOCATED_ADDRESS}
+            mul                                        // _ size *next_addr' (*next_addr' == 0) * (2^{32} + 2)
             add                                        // _ size *next_addr
 
             dup 0                                      // _ size *next_addr *next_addr
@@ -152,14 +158,15 @@ impl DeprecatedSnippet for DynMalloc {
             .entry(DYN_MALLOC_ADDRESS)
             .and_modify(|e| {
                 *e = if e.is_zero() {
-                    DYN_MALLOC_ADDRESS + BFieldElement::one()
+                    FIRST_DYNAMICALLY_ALLOCATED_ADDRESS
                 } else {
                     *e
                 }
             })
-            .or_insert_with(|| DYN_MALLOC_ADDRESS + BFieldElement::one());
+            .or_insert_with(|| FIRST_DYNAMICALLY_ALLOCATED_ADDRESS);
 
         let next_addr = *used_memory;
+        record_allocation(next_addr, size.value() as usize);
 
         stack.push(next_addr);
         *used_memory += size;
@@ -195,7 +202,7 @@ mod tests {
         let mut final_state_1 =
             DynMalloc.link_and_run_tasm_from_state_for_test(&mut empty_memory_state);
         assert_eq!(
-            DYN_MALLOC_ADDRESS + BFieldElement::one(),
+            FIRST_DYNAMICALLY_ALLOCATED_ADDRESS,
             final_state_1.final_stack.pop().unwrap()
         );
 
@@ -204,7 +211,7 @@ mod tests {
         let mut final_state_2 =
             DynMalloc.link_and_run_tasm_from_state_for_test(&mut non_empty_memory_state);
         assert_eq!(
-            DYN_MALLOC_ADDRESS + BFieldElement::new(11),
+            FIRST_DYNAMICALLY_ALLOCATED_ADDRESS + BFieldElement::new(10),
             final_state_2.final_stack.pop().unwrap()
         );
     }
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::dyn_malloc::{DYN_MALLOC_ADDRESS, FIRST_DYNAMICALLY_ALLOCATED_ADDRESS};
+use crate::rust_shadowing_helper_functions::dyn_malloc::record_allocation;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// The first page, *i.e.*, the first range of 2^32 addresses, that dynamically allocated
+/// memory must not reach in checked mode. The static allocator hands out memory downwards
+/// from the largest field element, which lies in the page after.
+const FIRST_STATIC_PAGE: u64 = (1 << 32) - 2;
+
+/// A checked mode of [`DynMalloc`](crate::memory::dyn_malloc::DynMalloc), sharing its
+/// state. Return a pointer to `size` free words, and crash the VM if
+///
+/// - `size` is not a u32,
+/// - the allocator's free memory, after the allocation, would start in the first page of
+///   memory, which is nondeterministically initialized, for example because the
+///   allocator's pointer wrapped around, or
+/// - the allocation would reach the pages used by the static allocator.
+///
+/// The last two conditions are checked by [`DynamicMemoryBoundCheck`], such that running
+/// out of dynamic memory is reported as a failure inside that snippet.
+#[derive(Clone, Debug)]
+pub struct DynMallocChecked;
+
+impl BasicSnippet for DynMallocChecked {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "size".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*addr".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_dyn_malloc_checked".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let bound_check = library.import(Box::new(DynamicMemoryBoundCheck));
+
+        triton_asm!(
+        // BEFORE: _ size
+        // AFTER:  _ *next_addr
+        {entrypoint}:
+            // Ensure that `size` is a u32
+            dup 0
+            split
+            pop 1
+            push 0
+            eq
+            assert                                     // _ size
+
+            push {DYN_MALLOC_ADDRESS}
+            read_mem 1
+            pop 1                                      // _ size *next_addr'
+
+            dup 0
+            push 0
+            eq
+            push {FIRST_DYNAMICALLY_ALLOCATED_ADDRESS}
+            mul
+            add                                        // _ size *next_addr
+
+            dup 0
+            swap 2
+            add                                        // _ *next_addr *next_next_addr
+
+            dup 0
+            call {bound_check}                         // _ *next_addr *next_next_addr
+
+            push {DYN_MALLOC_ADDRESS}
+            write_mem 1
+            pop 1                                      // _ *next_addr
+            return
+        )
+    }
+}
+
+impl Function for DynMallocChecked {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let size = stack.pop().unwrap();
+        assert!(size.value() <= u32::MAX as u64, "size must be a u32");
+
+        let next_addr = memory
+            .get(&DYN_MALLOC_ADDRESS)
+            .copied()
+            .filter(|addr| !addr.is_zero())
+            .unwrap_or(FIRST_DYNAMICALLY_ALLOCATED_ADDRESS);
+        let next_next_addr = next_addr + size;
+        let mut bound_check_stack = [empty_stack(), vec![next_next_addr]].concat();
+        DynamicMemoryBoundCheck.rust_shadow(&mut bound_check_stack, memory);
+        record_allocation(next_addr, size.value() as usize);
+
+        memory.insert(DYN_MALLOC_ADDRESS, next_next_addr);
+        stack.push(next_addr);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let next_addr = match rng.gen() {
+            true => BFieldElement::new(rng.gen_range(1 << 32..1 << 40)),
+            false => BFieldElement::zero(),
+        };
+
+        Self::initial_state(next_addr, rng.gen_range(0..1 << 20))
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let last_page_start = BFieldElement::new(FIRST_STATIC_PAGE << 32);
+        vec![
+            Self::initial_state(BFieldElement::zero(), 0),
+            Self::initial_state(BFieldElement::zero(), u32::MAX as u64),
+            Self::initial_state(last_page_start - BFieldElement::new(11), 10),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let last_page_start = BFieldElement::new(FIRST_STATIC_PAGE << 32);
+        [
+            Self::initial_state(BFieldElement::zero(), 1 << 32),
+            Self::initial_state(last_page_start - BFieldElement::new(10), 10),
+            Self::initial_state(BFieldElement::new(BFieldElement::MAX - 3), 10),
+            Self::initial_state(BFieldElement::new(7), 10),
+        ]
+        .into_iter()
+        .map(|state| (state, InstructionError::AssertionFailed))
+        .collect()
+    }
+}
+
+impl DynMallocChecked {
+    fn initial_state(next_addr: BFieldElement, size: u64) -> FunctionInitialState {
+        FunctionInitialState {
+            stack: [empty_stack(), vec![BFieldElement::new(size)]].concat(),
+            memory: HashMap::from([(DYN_MALLOC_ADDRESS, next_addr)]),
+        }
+    }
+}
+
+/// Crash the VM unless the given address lies in memory that [`DynMallocChecked`] may hand
+/// out: not in the first page of memory, and below the pages of the static allocator.
+#[derive(Clone, Debug)]
+pub struct DynamicMemoryBoundCheck;
+
+impl BasicSnippet for DynamicMemoryBoundCheck {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*addr".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_dyn_malloc_checked_bound_check".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        triton_asm!(
+        // BEFORE: _ *addr
+        // AFTER:  _
+        {entrypoint}:
+            split
+            pop 1                                      // _ page
+
+            // Ensure that the address does not lie in the first page
+            dup 0
+            push 0
+            eq
+            push 0
+            eq
+            assert                                     // _ page
+
+            // Ensure that the address lies below the static allocator's pages
+            push {FIRST_STATIC_PAGE}
+            swap 1
+            lt
+            assert                                     // _
+            return
+        )
+    }
+}
+
+impl Function for DynamicMemoryBoundCheck {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let page = stack.pop().unwrap().value() >> 32;
+        assert_ne!(0, page, "dynamic memory must not wrap into the first page");
+        assert!(page < FIRST_STATIC_PAGE, "dynamic memory is exhausted");
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let addr = rng.gen_range(1 << 32..FIRST_STATIC_PAGE << 32);
+        FunctionInitialState {
+            stack: [empty_stack(), vec![BFieldElement::new(addr)]].concat(),
+            memory: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use triton_vm::{NonDeterminism, Program};
+
+    use super::*;
+    use crate::execute_with_terminal_state_and_source_map;
+    use crate::linker::link_for_isolated_run_with_source_map;
+    use crate::test_helpers::assert_assertion_failure;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_malloc_checked_test() {
+        ShadowedFunction::new(DynMallocChecked).test();
+    }
+
+    #[test]
+    fn dynamic_memory_bound_check_test() {
+        ShadowedFunction::new(DynamicMemoryBoundCheck).test();
+    }
+
+    /// Run [`DynMallocChecked`] and return the error message if it fails.
+    fn failure_message(initial_state: FunctionInitialState) -> String {
+        let (code, source_map) =
+            link_for_isolated_run_with_source_map(Rc::new(RefCell::new(DynMallocChecked)), 0);
+        let result = execute_with_terminal_state_and_source_map(
+            &Program::new(&code),
+            &[],
+            &initial_state.stack,
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            None,
+            &source_map,
+        );
+        let message = format!("{:#}", result.as_ref().unwrap_err());
+        assert_assertion_failure(result);
+        message
+    }
+
+    #[test]
+    fn exhaustion_is_reported_inside_bound_check() {
+        let last_page_start = BFieldElement::new(FIRST_STATIC_PAGE << 32);
+        for next_addr in [
+            last_page_start - BFieldElement::new(5),
+            BFieldElement::new(BFieldElement::MAX - 5),
+        ] {
+            let state = DynMallocChecked::initial_state(next_addr, 10);
+            let message = failure_message(state);
+            assert!(message.contains("inside tasm_memory_dyn_malloc_checked_bound_check"));
+        }
+    }
+
+    #[test]
+    fn oversized_allocation_is_reported_inside_allocator() {
+        let state = DynMallocChecked::initial_state(BFieldElement::zero(), 1 << 32);
+        let message = failure_message(state);
+        assert!(message.contains("inside tasm_memory_dyn_malloc_checked at"));
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_malloc_checked_benchmark() {
+        ShadowedFunction::new(DynMallocChecked).bench();
+    }
+}
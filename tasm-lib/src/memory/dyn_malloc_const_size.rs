@@ -10,6 +10,7 @@ use crate::data_type::DataType;
 use crate::empty_stack;
 use crate::library::Library;
 use crate::memory::dyn_malloc::DYN_MALLOC_ADDRESS;
+use crate::rust_shadowing_helper_functions::dyn_malloc::record_allocation;
 use crate::traits::basic_snippet::BasicSnippet;
 use crate::traits::function::{Function, FunctionInitialState};
 
@@ -96,6 +97,7 @@ impl Function for DynMallocConstSize {
             .or_insert_with(|| CONST_SIZE_MALLOCS_FIRST_DYNAMICALLY_ALLOCATED_ADDRESS);
 
         let next_addr = *used_memory;
+        record_allocation(next_addr, TWO_POW_32 as usize);
 
         stack.push(next_addr);
         *used_memory += TWO_POW_32.into();
@@ -150,7 +152,7 @@ mod tests {
     use super::*;
     use crate::execute_with_terminal_state;
     use crate::linker::link_for_isolated_run;
-    use crate::test_helpers::tasm_final_state;
+    use crate::test_helpers::{assert_assertion_failure, tasm_final_state};
     use crate::traits::function::ShadowedFunction;
     use crate::traits::rust_shadow::RustShadow;
 
@@ -225,9 +227,10 @@ mod tests {
         );
 
         assert!(
-            rust_result.is_err() && tvm_result.is_err(),
-            "Test case: dyn malloc const size must fail when "
+            rust_result.is_err(),
+            "Test case: dyn malloc const size must fail when out of memory"
         );
+        assert_assertion_failure(tvm_result);
     }
 }
 
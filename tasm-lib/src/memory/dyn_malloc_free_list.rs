@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::dyn_free::memory_with_freed_blocks;
+use crate::memory::dyn_malloc::{DynMalloc, FREE_LIST_ADDRESS};
+use crate::rust_shadowing_helper_functions::dyn_malloc::record_allocation;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::deprecated_snippet::DeprecatedSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Return a pointer to `size` free words, like [`DynMalloc`], but reuse a block released
+/// through [`DynFree`](crate::memory::dyn_free::DynFree) if one of exactly the requested
+/// size is available. Only if none is, fresh memory is allocated through [`DynMalloc`], so
+/// both allocators can be used side by side.
+///
+/// Finding a freed block takes time linear in the number of freed blocks.
+#[derive(Clone, Debug)]
+pub struct DynMallocFreeList;
+
+impl BasicSnippet for DynMallocFreeList {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "size".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Bfe, "*addr".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_dyn_malloc_free_list".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let find_block = format!("{entrypoint}_find_block");
+        let allocate_new_block = format!("{entrypoint}_allocate_new_block");
+        let unlink_block = format!("{entrypoint}_unlink_block");
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+
+        triton_asm!(
+        // BEFORE: _ size
+        // AFTER:  _ *addr
+        {entrypoint}:
+            push {FREE_LIST_ADDRESS}
+            call {find_block}           // _ size *prev *block
+
+            push 1
+            dup 1
+            push 0
+            eq                          // _ size *prev *block 1 (*block == 0)
+            skiz
+                call {allocate_new_block}
+            skiz
+                call {unlink_block}
+            // _ size *prev *addr
+
+            swap 2
+            pop 2
+            return
+
+        // Walk the free list until a block of the requested size or the end of the list.
+        // The pointer to the next block lives in a block's first word, its size in the
+        // second. The list's head is treated as the next-pointer of an imaginary first block.
+        // INVARIANT: _ size *prev
+        {find_block}:
+            dup 0
+            read_mem 1
+            pop 1                       // _ size *prev *block
+            dup 0
+            push 0
+            eq
+            skiz return
+
+            dup 0
+            push 1
+            add
+            read_mem 1
+            pop 1                       // _ size *prev *block block_size
+            dup 3
+            eq
+            skiz return
+            // _ size *prev *block
+
+            swap 1
+            pop 1                       // _ size *block
+            recurse
+
+        // BEFORE: _ size *prev 0 1
+        // AFTER:  _ size *prev *addr 0
+        {allocate_new_block}:
+            pop 2
+            dup 1
+            call {dyn_malloc}
+            push 0
+            return
+
+        // BEFORE: _ size *prev *block
+        // AFTER:  _ size *prev *block
+        {unlink_block}:
+            dup 0
+            read_mem 1
+            pop 1                       // _ size *prev *block *next
+            dup 2
+            write_mem 1
+            pop 1                       // _ size *prev *block
+            return
+        )
+    }
+}
+
+impl Function for DynMallocFreeList {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let size = stack.pop().unwrap();
+        let read = |memory: &HashMap<BFieldElement, BFieldElement>, address: BFieldElement| {
+            memory.get(&address).copied().unwrap_or_default()
+        };
+
+        let mut prev = FREE_LIST_ADDRESS;
+        loop {
+            let block = read(memory, prev);
+            if block.is_zero() {
+                stack.push(size);
+                DynMalloc.rust_shadowing(stack, vec![], vec![], memory);
+                return;
+            }
+            if read(memory, block + BFieldElement::new(1)) == size {
+                let next = read(memory, block);
+                memory.insert(prev, next);
+                record_allocation(block, size.value() as usize);
+                stack.push(block);
+                return;
+            }
+            prev = block;
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let num_freed_blocks = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 10,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.gen_range(0..20),
+        };
+        let freed_block_sizes = (0..num_freed_blocks)
+            .map(|_| rng.gen_range(2..20))
+            .collect::<Vec<_>>();
+
+        // the worst case walks the entire free list without finding a block
+        let size = match bench_case {
+            Some(BenchmarkCase::CommonCase) => freed_block_sizes[0],
+            Some(BenchmarkCase::WorstCase) => 20,
+            None => match rng.gen() {
+                true => *freed_block_sizes.choose(&mut rng).unwrap_or(&0),
+                false => rng.gen_range(0..30),
+            },
+        };
+
+        Self::initial_state(&freed_block_sizes, size)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let freed_block_sizes = [4, 2, 7, 4];
+        let mut states = vec![
+            Self::initial_state(&[], 0),
+            Self::initial_state(&[], 5),
+            Self::initial_state(&[0, 1], 1),
+        ];
+        for size in [0, 1, 2, 3, 4, 7] {
+            states.push(Self::initial_state(&freed_block_sizes, size));
+        }
+
+        states
+    }
+}
+
+impl DynMallocFreeList {
+    fn initial_state(freed_block_sizes: &[usize], size: usize) -> FunctionInitialState {
+        let memory = memory_with_freed_blocks(freed_block_sizes);
+        let stack = [empty_stack(), vec![BFieldElement::new(size as u64)]].concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::NonDeterminism;
+
+    use super::*;
+    use crate::memory::dyn_malloc::FIRST_DYNAMICALLY_ALLOCATED_ADDRESS;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::{
+        dynamic_allocator_reusing_freed_memory, dynamic_free,
+    };
+    use crate::test_helpers::tasm_final_state;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_malloc_free_list_test() {
+        ShadowedFunction::new(DynMallocFreeList).test();
+    }
+
+    #[test]
+    fn free_list_cannot_be_supplied_nondeterministically() {
+        // a block in nondeterministically initialized memory, linked from the last word
+        // of that region
+        let fake_block = BFieldElement::new(1 << 20);
+        let size = BFieldElement::new(4);
+        let memory = HashMap::from([
+            (BFieldElement::new((1 << 32) - 1), fake_block),
+            (fake_block, BFieldElement::new(0)),
+            (fake_block + BFieldElement::new(1), size),
+        ]);
+        let stack = [empty_stack(), vec![size]].concat();
+
+        let mut rust_stack = stack.clone();
+        DynMallocFreeList.rust_shadow(&mut rust_stack, &mut memory.clone());
+        assert_eq!(
+            FIRST_DYNAMICALLY_ALLOCATED_ADDRESS,
+            rust_stack.pop().unwrap()
+        );
+
+        let snippet = ShadowedFunction::new(DynMallocFreeList);
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let mut tasm = tasm_final_state(&snippet, &stack, &[], nondeterminism, &None, 0);
+        assert_eq!(
+            FIRST_DYNAMICALLY_ALLOCATED_ADDRESS,
+            tasm.final_stack.pop().unwrap()
+        );
+    }
+
+    #[test]
+    fn freed_memory_is_reused() {
+        let mut memory = HashMap::new();
+        let first_block = dynamic_allocator_reusing_freed_memory(5, &mut memory);
+        let second_block = dynamic_allocator_reusing_freed_memory(3, &mut memory);
+        assert_eq!(FIRST_DYNAMICALLY_ALLOCATED_ADDRESS, first_block);
+
+        dynamic_free(first_block, 5, &mut memory);
+        dynamic_free(second_block, 3, &mut memory);
+        assert_eq!(
+            first_block,
+            dynamic_allocator_reusing_freed_memory(5, &mut memory)
+        );
+        assert_eq!(
+            second_block,
+            dynamic_allocator_reusing_freed_memory(3, &mut memory)
+        );
+
+        let fresh_block = dynamic_allocator_reusing_freed_memory(5, &mut memory);
+        assert_eq!(second_block + BFieldElement::new(3), fresh_block);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dyn_malloc_free_list_benchmark() {
+        ShadowedFunction::new(DynMallocFreeList).bench();
+    }
+}
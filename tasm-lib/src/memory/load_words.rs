@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::load_words_from_memory_pop_pointer;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Read `N` consecutive words, starting at the given pointer, onto the stack. The word at
+/// the pointer ends up on top, which is the order in which
+/// [`write_words_to_memory_pop_pointer`](crate::memory::write_words_to_memory_pop_pointer)
+/// expects them, and the order of a value's encoding in memory. To read a value of a known
+/// [`DataType`], see [`PushRamToStack`](crate::memory::push_ram_to_stack::PushRamToStack).
+///
+/// `N` must be at least 1 and at most the number of op stack registers, such that all
+/// loaded words remain accessible.
+#[derive(Clone, Debug)]
+pub struct LoadWords<const N: usize>;
+
+impl<const N: usize> BasicSnippet for LoadWords<N> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*first_word".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        (0..N)
+            .rev()
+            .map(|i| (DataType::Bfe, format!("word_{i}")))
+            .collect()
+    }
+
+    fn entrypoint(&self) -> String {
+        assert!(
+            0 < N && N <= NUM_OP_STACK_REGISTERS,
+            "Can only load between 1 and {NUM_OP_STACK_REGISTERS} words onto the stack"
+        );
+        format!("tasm_memory_load_words_{N}")
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let load_words = load_words_from_memory_pop_pointer(N);
+
+        triton_asm!(
+        // BEFORE: _ *first_word
+        // AFTER:  _ [word_{N-1}, ..., word_0]
+        {entrypoint}:
+            push {N - 1}
+            add                         // _ *last_word
+            {&load_words}
+            return
+        )
+    }
+}
+
+impl<const N: usize> Function for LoadWords<N> {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let first_word = stack.pop().unwrap();
+        for i in (0..N).rev() {
+            let address = first_word + BFieldElement::new(i as u64);
+            stack.push(memory.get(&address).copied().unwrap_or_default());
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        Self::initial_state(rng.gen(), &mut rng)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x4c; 32]);
+        let last_full_region = BFieldElement::new(BFieldElement::MAX - N as u64 + 1);
+        let uninitialized_memory = FunctionInitialState {
+            stack: [empty_stack(), vec![BFieldElement::new(1 << 32)]].concat(),
+            memory: HashMap::new(),
+        };
+
+        vec![
+            Self::initial_state(BFieldElement::new(0), &mut rng),
+            Self::initial_state(last_full_region, &mut rng),
+            uninitialized_memory,
+        ]
+    }
+}
+
+impl<const N: usize> LoadWords<N> {
+    fn initial_state(first_word: BFieldElement, rng: &mut StdRng) -> FunctionInitialState {
+        let memory = (0..N)
+            .map(|i| (first_word + BFieldElement::new(i as u64), rng.gen()))
+            .collect();
+        let stack = [empty_stack(), vec![first_word]].concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::push_ram_to_stack::PushRamToStack;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn load_words_test() {
+        ShadowedFunction::new(LoadWords::<1>).test();
+        ShadowedFunction::new(LoadWords::<2>).test();
+        ShadowedFunction::new(LoadWords::<5>).test();
+        ShadowedFunction::new(LoadWords::<7>).test();
+        ShadowedFunction::new(LoadWords::<10>).test();
+        ShadowedFunction::new(LoadWords::<15>).test();
+        ShadowedFunction::new(LoadWords::<16>).test();
+    }
+
+    #[test]
+    fn words_are_loaded_in_the_order_of_a_value_of_equal_size() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let initial_state = LoadWords::<5>::initial_state(rng.gen(), &mut rng);
+
+        let mut load_words_stack = initial_state.stack.clone();
+        let mut push_ram_to_stack_stack = initial_state.stack;
+        let mut memory = initial_state.memory;
+        LoadWords::<5>.rust_shadow(&mut load_words_stack, &mut memory);
+        let push_digest = PushRamToStack {
+            data_type: DataType::Digest,
+        };
+        push_digest.rust_shadow(&mut push_ram_to_stack_stack, &mut memory);
+
+        assert_eq!(push_ram_to_stack_stack, load_words_stack);
+    }
+
+    #[should_panic]
+    #[test]
+    fn loading_more_words_than_fit_on_the_op_stack_is_rejected() {
+        LoadWords::<17>.entrypoint();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn load_words_benchmark() {
+        ShadowedFunction::new(LoadWords::<16>).bench();
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::traits::{ModPowU32, PrimitiveRootOfUnity};
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// A table of precomputed constants. Snippets that need the table place it in statically
+/// allocated memory through [`static_address`](Self::static_address), where it is
+/// initialized before the program's entrypoint runs. Looking up an entry then costs a
+/// single memory read instead of recomputing it.
+///
+/// Tables are identified by their name: all snippets in one [`Library`] that use tables of
+/// the same name share one copy in memory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LookupTable {
+    pub name: String,
+    pub entries: Vec<BFieldElement>,
+}
+
+impl LookupTable {
+    /// # Panics
+    ///
+    /// Panics if the name cannot be used as part of a label.
+    pub fn new(name: &str, entries: Vec<BFieldElement>) -> Self {
+        assert!(
+            name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            "table name `{name}` must only contain alphanumeric characters and underscores"
+        );
+        Self {
+            name: name.to_owned(),
+            entries,
+        }
+    }
+
+    /// The powers ω^0, ω^1, …, ω^(n-1) of the primitive `n`th root of unity ω.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not a power of two or the field has no `n`th root of unity.
+    pub fn powers_of_primitive_root_of_unity(n: u32) -> Self {
+        assert!(n.is_power_of_two(), "order {n} must be a power of two");
+        let omega = BFieldElement::primitive_root_of_unity(n as u64).unwrap();
+        let entries = (0..n).map(|i| omega.mod_pow_u32(i)).collect();
+        Self::new(&format!("powers_of_omega_{n}"), entries)
+    }
+
+    /// The permutation of `0..2^log2_length` that reverses the lowest `log2_length` bits of
+    /// every index, as used in NTTs.
+    pub fn bit_reversal_permutation(log2_length: u32) -> Self {
+        let entries = (0..1_u64 << log2_length)
+            .map(|i| match log2_length {
+                0 => i,
+                _ => i.reverse_bits() >> (u64::BITS - log2_length),
+            })
+            .map(BFieldElement::new)
+            .collect();
+        Self::new(&format!("bit_reversal_{log2_length}"), entries)
+    }
+
+    /// ⌊log₂(i)⌋ for every `i` in `0..length`. The entry for 0 is 0.
+    pub fn floor_log2(length: u32) -> Self {
+        let entries = (0..length)
+            .map(|i| i.checked_ilog2().unwrap_or_default())
+            .map(BFieldElement::from)
+            .collect();
+        Self::new(&format!("floor_log2_{length}"), entries)
+    }
+
+    fn static_allocation_name(&self) -> String {
+        format!("lookup_table_{}", self.name)
+    }
+
+    /// The address of the table's first entry, statically allocating and initializing the
+    /// table in `library` unless that has happened already.
+    pub fn static_address(&self, library: &mut Library) -> BFieldElement {
+        let name = self.static_allocation_name();
+        library
+            .static_address(&name)
+            .unwrap_or_else(|| library.kmalloc_constant(&name, &self.entries))
+    }
+}
+
+/// Return the entry at the given index of a [`LookupTable`]. Crashes the VM if the index is
+/// out of bounds.
+#[derive(Clone, Debug)]
+pub struct TableLookup {
+    pub table: LookupTable,
+}
+
+impl BasicSnippet for TableLookup {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "index".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Bfe, "entry".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasm_memory_table_lookup_{}", self.table.name)
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let table_address = self.table.static_address(library);
+        let table_length = self.table.entries.len();
+
+        triton_asm!(
+        // BEFORE: _ index
+        // AFTER:  _ entry
+        {entrypoint}:
+            push {table_length}
+            dup 1
+            lt
+            assert                      // _ index
+
+            push {table_address}
+            add
+            read_mem 1
+            pop 1                       // _ entry
+            return
+        )
+    }
+}
+
+impl Function for TableLookup {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let index = stack.pop().unwrap().value() as usize;
+        assert!(index < self.table.entries.len(), "index out of bounds");
+        stack.push(self.table.entries[index]);
+
+        // mirror the initialization of static memory
+        let mut library = Library::new();
+        self.code(&mut library);
+        memory.extend(library.static_data_memory());
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        Self::initial_state(rng.gen_range(0..self.table.entries.len()))
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let last_index = self.table.entries.len() - 1;
+        [0, last_index]
+            .into_iter()
+            .dedup()
+            .map(Self::initial_state)
+            .collect()
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let table_length = self.table.entries.len();
+        vec![
+            (
+                Self::initial_state(table_length),
+                InstructionError::AssertionFailed,
+            ),
+            (
+                Self::initial_state(table_length + 1),
+                InstructionError::AssertionFailed,
+            ),
+        ]
+    }
+}
+
+impl TableLookup {
+    fn initial_state(index: usize) -> FunctionInitialState {
+        FunctionInitialState {
+            stack: [empty_stack(), vec![BFieldElement::new(index as u64)]].concat(),
+            memory: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::One;
+
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn table_lookup_test() {
+        for table in [
+            LookupTable::powers_of_primitive_root_of_unity(1),
+            LookupTable::powers_of_primitive_root_of_unity(256),
+            LookupTable::bit_reversal_permutation(0),
+            LookupTable::bit_reversal_permutation(5),
+            LookupTable::floor_log2(100),
+            LookupTable::new("custom", vec![BFieldElement::new(42)]),
+        ] {
+            ShadowedFunction::new(TableLookup { table }).test();
+        }
+    }
+
+    #[test]
+    fn precomputed_tables_are_correct() {
+        let powers_of_omega = LookupTable::powers_of_primitive_root_of_unity(8).entries;
+        let omega = powers_of_omega[1];
+        assert!(omega.mod_pow_u32(8).is_one());
+        assert!(!omega.mod_pow_u32(4).is_one());
+
+        let bit_reversal = LookupTable::bit_reversal_permutation(3).entries;
+        let bit_reversal = bit_reversal.iter().map(|i| i.value()).collect_vec();
+        assert_eq!(vec![0, 4, 2, 6, 1, 5, 3, 7], bit_reversal);
+
+        let floor_log2 = LookupTable::floor_log2(9).entries;
+        let floor_log2 = floor_log2.iter().map(|i| i.value()).collect_vec();
+        assert_eq!(vec![0, 0, 1, 1, 2, 2, 2, 2, 3], floor_log2);
+    }
+
+    #[test]
+    fn snippets_share_tables_of_the_same_name() {
+        let mut library = Library::new();
+        let table = LookupTable::floor_log2(10);
+        let address = table.static_address(&mut library);
+        library.import(Box::new(TableLookup { table }));
+
+        let shared_address = library.static_address("lookup_table_floor_log2_10");
+        assert_eq!(Some(address), shared_address);
+        assert_eq!(10, library.num_words_statically_allocated());
+    }
+
+    #[should_panic]
+    #[test]
+    fn table_names_must_be_label_friendly() {
+        LookupTable::new("not a label", vec![]);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn table_lookup_benchmark() {
+        let table = LookupTable::powers_of_primitive_root_of_unity(1 << 10);
+        ShadowedFunction::new(TableLookup { table }).bench();
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism};
+
+use crate::{
+    data_type::DataType,
+    empty_stack,
+    library::Library,
+    snippet_bencher::BenchmarkCase,
+    traits::{
+        accessor::{Accessor, AccessorInitialState},
+        basic_snippet::BasicSnippet,
+    },
+};
+
+/// Compares two memory regions of `num_words` words each, word by word, and returns whether
+/// they are equal.
+///
+/// If `report_first_difference` is set, additionally returns the offset of the first word
+/// in which the regions differ, or `num_words` if they are equal.
+#[derive(Clone, Debug)]
+pub struct MemCmp {
+    pub report_first_difference: bool,
+}
+
+impl BasicSnippet for MemCmp {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*lhs".to_owned()),
+            (DataType::VoidPointer, "*rhs".to_owned()),
+            (DataType::U32, "num_words".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        let mut outputs = vec![(DataType::Bool, "equal".to_owned())];
+        if self.report_first_difference {
+            outputs.push((DataType::U32, "first_difference".to_owned()));
+        }
+        outputs
+    }
+
+    fn entrypoint(&self) -> String {
+        match self.report_first_difference {
+            true => "tasm_memory_memcmp_with_first_difference".to_owned(),
+            false => "tasm_memory_memcmp".to_owned(),
+        }
+    }
+
+    fn input_objects(
+        &self,
+        initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(usize, u32)>> {
+        let [.., _, _, num_words] = initial_stack else {
+            return None;
+        };
+        let num_words = u32::try_from(num_words.value()).ok()?;
+        Some(vec![(0, num_words), (1, num_words)])
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let loop_label = format!("{entrypoint}_loop");
+
+        let clean_up = match self.report_first_difference {
+            true => triton_asm!(
+                // _ *lhs *rhs num_words offset
+                swap 1
+                dup 1
+                eq
+                // _ *lhs *rhs offset equal
+
+                swap 3
+                pop 1
+                swap 1
+                pop 1
+                // _ equal offset
+            ),
+            false => triton_asm!(
+                // _ *lhs *rhs num_words offset
+                eq
+                swap 2
+                pop 2
+                // _ equal
+            ),
+        };
+
+        triton_asm!(
+            // BEFORE: _ *lhs *rhs num_words
+            // AFTER:  _ equal [first_difference]
+            {entrypoint}:
+                push 0
+                // _ *lhs *rhs num_words 0
+
+                call {loop_label}
+                // _ *lhs *rhs num_words offset
+
+                {&clean_up}
+                return
+
+            // INVARIANT: _ *lhs *rhs num_words offset
+            {loop_label}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+                // _ *lhs *rhs num_words offset
+
+                dup 3
+                dup 1
+                add
+                read_mem 1
+                pop 1
+                // _ *lhs *rhs num_words offset lhs_word
+
+                dup 3
+                dup 2
+                add
+                read_mem 1
+                pop 1
+                // _ *lhs *rhs num_words offset lhs_word rhs_word
+
+                eq
+                push 0
+                eq
+                skiz return
+                // _ *lhs *rhs num_words offset
+
+                push 1
+                add
+                recurse
+        )
+    }
+}
+
+impl Accessor for MemCmp {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let num_words = stack.pop().unwrap().value();
+        let rhs_pointer = stack.pop().unwrap();
+        let lhs_pointer = stack.pop().unwrap();
+
+        let read = |address: BFieldElement| memory.get(&address).copied().unwrap_or_default();
+        let first_difference = (0..num_words)
+            .find(|&offset| {
+                let offset = BFieldElement::new(offset);
+                read(lhs_pointer + offset) != read(rhs_pointer + offset)
+            })
+            .unwrap_or(num_words);
+
+        stack.push(BFieldElement::new((first_difference == num_words) as u64));
+        if self.report_first_difference {
+            stack.push(BFieldElement::new(first_difference));
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> AccessorInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let num_words = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 32,
+            Some(BenchmarkCase::WorstCase) => 1000,
+            None => rng.gen_range(0..200),
+        };
+
+        // only differ in benchmarks, to compare all words
+        let difference = match bench_case {
+            Some(_) => None,
+            None => Some(rng.gen_range(0..=num_words)).filter(|&offset| offset < num_words),
+        };
+
+        let lhs_pointer = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let rhs_pointer = BFieldElement::new(rng.gen_range(1 << 30..1 << 31));
+        Self::initial_state(lhs_pointer, rhs_pointer, num_words, difference, &mut rng)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<AccessorInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x17; 32]);
+        let lhs_pointer = BFieldElement::new(100);
+        let rhs_pointer = BFieldElement::new(1000);
+
+        let mut states = vec![
+            Self::initial_state(lhs_pointer, rhs_pointer, 0, None, &mut rng),
+            Self::initial_state(lhs_pointer, lhs_pointer, 10, None, &mut rng),
+        ];
+        for num_words in [1, 2, 5, 17] {
+            for difference in [None, Some(0), Some(num_words - 1)] {
+                states.push(Self::initial_state(
+                    lhs_pointer,
+                    rhs_pointer,
+                    num_words,
+                    difference,
+                    &mut rng,
+                ));
+            }
+        }
+
+        states
+    }
+}
+
+impl MemCmp {
+    /// Two regions of `num_words` random words each, which are equal except in the word at
+    /// offset `difference`, if given.
+    fn initial_state(
+        lhs_pointer: BFieldElement,
+        rhs_pointer: BFieldElement,
+        num_words: u64,
+        difference: Option<u64>,
+        rng: &mut StdRng,
+    ) -> AccessorInitialState {
+        let mut memory = HashMap::new();
+        for offset in (0..num_words).map(BFieldElement::new) {
+            let word: BFieldElement = rng.gen();
+            memory.insert(lhs_pointer + offset, word);
+            memory.insert(rhs_pointer + offset, word);
+        }
+        if let Some(offset) = difference.map(BFieldElement::new) {
+            let mut word = memory[&(rhs_pointer + offset)];
+            word.increment();
+            memory.insert(rhs_pointer + offset, word);
+        }
+
+        let stack = [
+            empty_stack(),
+            vec![lhs_pointer, rhs_pointer, BFieldElement::new(num_words)],
+        ]
+        .concat();
+
+        AccessorInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::tasm_final_state;
+    use crate::traits::accessor::ShadowedAccessor;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn memcmp_test() {
+        for report_first_difference in [false, true] {
+            ShadowedAccessor::new(MemCmp {
+                report_first_difference,
+            })
+            .test();
+        }
+    }
+
+    #[test]
+    fn first_difference_is_reported() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let pointer = BFieldElement::new(0);
+        let other_pointer = BFieldElement::new(50);
+        let AccessorInitialState {
+            stack,
+            nondeterminism,
+        } = MemCmp::initial_state(pointer, other_pointer, 20, Some(13), &mut rng);
+
+        let snippet = MemCmp {
+            report_first_difference: true,
+        };
+        let expected = [BFieldElement::new(0), BFieldElement::new(13)];
+
+        let mut rust_stack = stack.clone();
+        snippet.rust_shadow(&mut rust_stack, &nondeterminism.ram, &nondeterminism);
+        assert_eq!(&expected, &rust_stack[rust_stack.len() - 2..]);
+
+        let shadowed = ShadowedAccessor::new(snippet);
+        let tasm = tasm_final_state(&shadowed, &stack, &[], nondeterminism, &None, 0);
+        let tasm_stack = tasm.final_stack;
+        assert_eq!(&expected, &tasm_stack[tasm_stack.len() - 2..]);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::accessor::ShadowedAccessor;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn memcmp_benchmark() {
+        ShadowedAccessor::new(MemCmp {
+            report_first_difference: false,
+        })
+        .bench();
+    }
+}
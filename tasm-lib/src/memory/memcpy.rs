@@ -322,10 +322,48 @@ mod tests {
 #[cfg(test)]
 mod benches {
     use super::*;
-    use crate::snippet_bencher::bench_and_write;
+    use crate::snippet_bencher::{
+        bench_and_write, write_benchmarks, BenchmarkCase, BenchmarkResult,
+    };
 
     #[test]
     fn memcpy_benchmark() {
         bench_and_write(MemCpy);
     }
+
+    #[test]
+    fn memcpy_benchmark_10_words() {
+        benchmark_copy_of_size(10, BenchmarkCase::CommonCase);
+    }
+
+    #[test]
+    fn memcpy_benchmark_1000_words() {
+        benchmark_copy_of_size(1000, BenchmarkCase::WorstCase);
+    }
+
+    /// Too large to run as often as the test suite runs. Run on demand, *e.g.*, through
+    /// `cargo test memcpy_benchmark_100_000_words -- --ignored`.
+    #[test]
+    #[ignore = "copies 100 000 words"]
+    fn memcpy_benchmark_100_000_words() {
+        benchmark_copy_of_size(100_000, BenchmarkCase::WorstCase);
+    }
+
+    /// Benchmark copying `num_words` words and write the result to its own file.
+    fn benchmark_copy_of_size(num_words: usize, case: BenchmarkCase) {
+        let mut execution_state = MemCpy::random_input_state(num_words);
+        let execution_result = MemCpy
+            .link_and_run_tasm_from_state_for_bench(&mut execution_state)
+            .unwrap();
+        let benchmark = BenchmarkResult {
+            name: format!("{}_{num_words}_words", MemCpy.entrypoint_name()),
+            clock_cycle_count: execution_result.cycle_count,
+            hash_table_height: execution_result.hash_table_height,
+            u32_table_height: execution_result.u32_table_height,
+            case,
+            allocation_statistics: Default::default(),
+            cycle_breakdown: None,
+        };
+        write_benchmarks(vec![benchmark]);
+    }
 }
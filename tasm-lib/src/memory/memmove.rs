@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
+
+use crate::{
+    data_type::DataType,
+    empty_stack,
+    library::Library,
+    snippet_bencher::BenchmarkCase,
+    traits::{
+        basic_snippet::BasicSnippet,
+        function::{Function, FunctionInitialState},
+    },
+};
+
+/// Copies `num_words` words from `*source` to `*destination`, where the two regions may
+/// overlap.
+///
+/// If the destination lies inside the source region, the words are copied back to front;
+/// otherwise front to back. Either way, every word is read before it is overwritten. Use
+/// [`MemCpy`](crate::memory::memcpy::MemCpy) if the regions are known to be disjoint.
+#[derive(Clone, Debug)]
+pub struct MemMove;
+
+impl BasicSnippet for MemMove {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*source".to_owned()),
+            (DataType::VoidPointer, "*destination".to_owned()),
+            (DataType::U32, "num_words".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_memmove".to_owned()
+    }
+
+    fn writable_memory_regions(
+        &self,
+        initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        let [.., _, destination, num_words] = initial_stack else {
+            return None;
+        };
+        let num_words = u32::try_from(num_words.value()).ok()?;
+        Some(vec![(*destination, num_words)])
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let copy_backward = format!("{entrypoint}_copy_backward");
+        let copy_backward_loop = format!("{entrypoint}_copy_backward_loop");
+        let copy_forward = format!("{entrypoint}_copy_forward");
+
+        triton_asm!(
+            // BEFORE: _ *source *destination num_words
+            // AFTER:  _
+            {entrypoint}:
+                dup 1
+                dup 3
+                push -1
+                mul
+                add
+                split
+                // _ *source *destination num_words hi lo
+                // where (hi, lo) = split(*destination - *source)
+
+                dup 2
+                swap 1
+                lt
+                // _ *source *destination num_words hi (lo < num_words)
+
+                swap 1
+                push 0
+                eq
+                mul
+                // _ *source *destination num_words destination_in_source
+
+                push 1
+                swap 1
+                // _ *source *destination num_words 1 destination_in_source
+
+                skiz
+                    call {copy_backward}
+                skiz
+                    call {copy_forward}
+                // _ *source *destination 0
+
+                pop 3
+                return
+
+            // BEFORE: _ *source *destination num_words 1
+            // AFTER:  _ *source *destination 0 0
+            {copy_backward}:
+                pop 1
+                call {copy_backward_loop}
+                push 0
+                return
+
+            // INVARIANT: _ *source *destination remaining_words
+            {copy_backward_loop}:
+                dup 0
+                push 0
+                eq
+                skiz return
+                // _ *source *destination remaining_words
+
+                push -1
+                add
+                // _ *source *destination (remaining_words - 1)
+
+                dup 2
+                dup 1
+                add
+                read_mem 1
+                pop 1
+                // _ *source *destination (remaining_words - 1) word
+
+                dup 2
+                dup 2
+                add
+                write_mem 1
+                pop 1
+                // _ *source *destination (remaining_words - 1)
+
+                recurse
+
+            // INVARIANT: _ *source *destination remaining_words
+            {copy_forward}:
+                dup 0
+                push 0
+                eq
+                skiz return
+                // _ *source *destination remaining_words
+
+                dup 2
+                read_mem 1
+                pop 1
+                // _ *source *destination remaining_words word
+
+                dup 2
+                write_mem 1
+                // _ *source *destination remaining_words (*destination + 1)
+
+                swap 2
+                pop 1
+                // _ *source (*destination + 1) remaining_words
+
+                swap 2
+                push 1
+                add
+                swap 2
+                // _ (*source + 1) (*destination + 1) remaining_words
+
+                push -1
+                add
+                recurse
+        )
+    }
+}
+
+impl Function for MemMove {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let num_words = stack.pop().unwrap().value();
+        let destination = stack.pop().unwrap();
+        let source = stack.pop().unwrap();
+
+        let words = (0..num_words)
+            .map(BFieldElement::new)
+            .map(|offset| memory.get(&(source + offset)).copied().unwrap_or_default())
+            .collect::<Vec<_>>();
+        for (offset, word) in (0..num_words).map(BFieldElement::new).zip(words) {
+            memory.insert(destination + offset, word);
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let num_words = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 17,
+            Some(BenchmarkCase::WorstCase) => 1000,
+            None => rng.gen_range(0..200),
+        };
+
+        let source = rng.gen_range(1 << 20..1 << 30);
+        let max_shift = num_words as i64 + 1;
+        let destination = match rng.gen() {
+            true => source + rng.gen_range(-max_shift..=max_shift),
+            false => rng.gen_range(0..1 << 30),
+        };
+
+        Self::initial_state(source, destination, num_words, &mut rng)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x91; 32]);
+        let source = 1000;
+        let num_words = 10;
+
+        let mut states = vec![Self::initial_state(source, source + 3, 0, &mut rng)];
+        for shift in [0, 1, -1, 5, -5, 9, -9, 10, -10, 11, -11] {
+            states.push(Self::initial_state(
+                source,
+                source + shift,
+                num_words,
+                &mut rng,
+            ));
+        }
+
+        states
+    }
+}
+
+impl MemMove {
+    fn initial_state(
+        source: i64,
+        destination: i64,
+        num_words: u64,
+        rng: &mut StdRng,
+    ) -> FunctionInitialState {
+        let source = BFieldElement::new(source as u64);
+        let destination = BFieldElement::new(destination as u64);
+        let memory = (0..num_words)
+            .map(|offset| (source + BFieldElement::new(offset), rng.gen()))
+            .collect();
+
+        let stack = [
+            empty_stack(),
+            vec![source, destination, BFieldElement::new(num_words)],
+        ]
+        .concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn memmove_test() {
+        ShadowedFunction::new(MemMove).test();
+    }
+
+    #[test]
+    fn overlapping_regions_are_moved_intact() {
+        for shift in [-3, -1, 1, 3] {
+            let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+            let FunctionInitialState {
+                mut stack,
+                mut memory,
+            } = MemMove::initial_state(100, 100 + shift, 5, &mut rng);
+            let words = (100..105)
+                .map(|address| memory[&BFieldElement::new(address)])
+                .collect::<Vec<_>>();
+
+            MemMove.rust_shadow(&mut stack, &mut memory);
+            let moved_words = (100 + shift..105 + shift)
+                .map(|address| memory[&BFieldElement::new(address as u64)])
+                .collect::<Vec<_>>();
+            assert_eq!(words, moved_words);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn memmove_benchmark() {
+        ShadowedFunction::new(MemMove).bench();
+    }
+}
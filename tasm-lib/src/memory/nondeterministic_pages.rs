@@ -0,0 +1,325 @@
+//! A convention for memory whose contents the prover supplies through
+//! [`NonDeterminism::ram`](triton_vm::NonDeterminism).
+//!
+//! Such memory lives in [pages](NONDETERMINISTIC_PAGE_SIZE) inside the nondeterministically
+//! initialized region $[0: 2^{32})$, which is disjoint from the memory handed out by
+//! [`DynMalloc`](crate::memory::dyn_malloc::DynMalloc). The host reserves pages through
+//! [`NondeterministicPages`] and tests check with [`assert_pages_unchanged`] that a program
+//! does not write to them. Programs can guard their own writes with
+//! [`AssertOutsideNondeterministicPages`].
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::encode_to_memory;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// The size of a page, in words. Every page starts at a multiple of the page size.
+pub const NONDETERMINISTIC_PAGE_SIZE: u64 = 1 << 20;
+
+/// The number of pages available, which together cover the nondeterministically
+/// initialized region.
+pub const NUM_NONDETERMINISTIC_PAGES: u64 = (1 << 32) / NONDETERMINISTIC_PAGE_SIZE;
+
+/// Reserves pages for objects whose encodings the prover supplies, in order, starting with
+/// the first page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NondeterministicPages {
+    num_reserved_pages: u64,
+}
+
+impl NondeterministicPages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve enough consecutive pages to hold `num_words` words, but at least one, and
+    /// return the address of the first reserved word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not enough pages are left.
+    pub fn reserve(&mut self, num_words: u64) -> BFieldElement {
+        let num_pages = num_words.div_ceil(NONDETERMINISTIC_PAGE_SIZE).max(1);
+        let first_page = self.num_reserved_pages;
+        assert!(
+            first_page + num_pages <= NUM_NONDETERMINISTIC_PAGES,
+            "cannot reserve {num_pages} more pages: {first_page} of \
+            {NUM_NONDETERMINISTIC_PAGES} pages are reserved already"
+        );
+
+        self.num_reserved_pages += num_pages;
+        BFieldElement::new(first_page * NONDETERMINISTIC_PAGE_SIZE)
+    }
+
+    /// Reserve pages for the given object, write its encoding to `ram`, and return the
+    /// pointer to it.
+    pub fn load<T: BFieldCodec>(
+        &mut self,
+        ram: &mut HashMap<BFieldElement, BFieldElement>,
+        object: T,
+    ) -> BFieldElement {
+        let address = self.reserve(object.encode().len() as u64);
+        encode_to_memory(ram, address, object);
+        address
+    }
+
+    /// The addresses of all reserved pages.
+    pub fn reserved_addresses(&self) -> Range<u64> {
+        0..self.num_reserved_pages * NONDETERMINISTIC_PAGE_SIZE
+    }
+
+    pub fn contains(&self, address: BFieldElement) -> bool {
+        self.reserved_addresses().contains(&address.value())
+    }
+}
+
+/// Assert that no word in any of the reserved pages differs between the two memory states.
+pub fn assert_pages_unchanged(
+    pages: &NondeterministicPages,
+    initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    final_memory: &HashMap<BFieldElement, BFieldElement>,
+) {
+    let mut changed_addresses = initial_memory
+        .keys()
+        .chain(final_memory.keys())
+        .filter(|&&address| pages.contains(address))
+        .filter(|&address| initial_memory.get(address) != final_memory.get(address))
+        .map(|address| address.value())
+        .collect::<Vec<_>>();
+    changed_addresses.sort_unstable();
+    changed_addresses.dedup();
+
+    assert!(
+        changed_addresses.is_empty(),
+        "nondeterministically initialized pages must not be written to. Changed addresses: {}",
+        changed_addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+/// Crash the VM if any word of the region of `size` words starting at `*region` could be
+/// part of a [nondeterministic page](self), *i.e.*, if it lies in the nondeterministically
+/// initialized region. Call this before writing to memory whose origin is not known.
+#[derive(Clone, Debug)]
+pub struct AssertOutsideNondeterministicPages;
+
+impl BasicSnippet for AssertOutsideNondeterministicPages {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*region".to_owned()),
+            (DataType::U32, "size".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_assert_outside_nondeterministic_pages".to_string()
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let check_region = format!("{entrypoint}_check_region");
+
+        triton_asm!(
+        // BEFORE: _ *region size
+        // AFTER:  _
+        {entrypoint}:
+            dup 0
+            push 0
+            eq
+            push 0
+            eq                          // _ *region size (size != 0)
+            skiz
+                call {check_region}
+            pop 2
+            return
+
+        // Both the first and the last word must lie outside of the first 2^32 words. Since
+        // the size is a u32, no word in between can lie there, unless the region wraps
+        // around, in which case the last word lies there.
+        // BEFORE: _ *region size
+        // AFTER:  _ *region size
+        {check_region}:
+            dup 1
+            split
+            pop 1
+            push 0
+            eq
+            push 0
+            eq
+            assert                      // _ *region size
+
+            dup 1
+            dup 1
+            add
+            push -1
+            add                         // _ *region size *last_word
+            split
+            pop 1
+            push 0
+            eq
+            push 0
+            eq
+            assert                      // _ *region size
+            return
+        )
+    }
+}
+
+impl Function for AssertOutsideNondeterministicPages {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let size = stack.pop().unwrap();
+        let region = stack.pop().unwrap();
+        if size.value() == 0 {
+            return;
+        }
+
+        let last_word = region + size - BFieldElement::new(1);
+        for address in [region, last_word] {
+            assert!(
+                address.value() >= 1 << 32,
+                "region must not overlap with nondeterministic pages"
+            );
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let region = rng.gen_range(1 << 32..1 << 40);
+        Self::initial_state(region, rng.gen())
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        vec![
+            Self::initial_state(1 << 32, 0),
+            Self::initial_state(1 << 32, u32::MAX),
+            Self::initial_state(0, 0),
+            Self::initial_state(BFieldElement::MAX, 1),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        [
+            Self::initial_state(0, 1),
+            Self::initial_state((1 << 32) - 1, 10),
+            Self::initial_state(BFieldElement::MAX, 2),
+            Self::initial_state(BFieldElement::MAX - 5, u32::MAX),
+        ]
+        .into_iter()
+        .map(|state| (state, InstructionError::AssertionFailed))
+        .collect()
+    }
+}
+
+impl AssertOutsideNondeterministicPages {
+    fn initial_state(region: u64, size: u32) -> FunctionInitialState {
+        let stack = [
+            empty_stack(),
+            vec![BFieldElement::new(region), BFieldElement::from(size)],
+        ]
+        .concat();
+
+        FunctionInitialState {
+            stack,
+            memory: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::dyn_malloc::FREE_LIST_ADDRESS;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn assert_outside_nondeterministic_pages_test() {
+        ShadowedFunction::new(AssertOutsideNondeterministicPages).test();
+    }
+
+    #[test]
+    fn pages_are_aligned_and_disjoint() {
+        let mut pages = NondeterministicPages::new();
+        let mut ram = HashMap::new();
+        let first = pages.load(&mut ram, vec![BFieldElement::new(1); 10]);
+        let second = pages.reserve(NONDETERMINISTIC_PAGE_SIZE + 1);
+        let third = pages.reserve(0);
+
+        assert_eq!(0, first.value());
+        assert_eq!(NONDETERMINISTIC_PAGE_SIZE, second.value());
+        assert_eq!(3 * NONDETERMINISTIC_PAGE_SIZE, third.value());
+        assert_eq!(
+            0..4 * NONDETERMINISTIC_PAGE_SIZE,
+            pages.reserved_addresses()
+        );
+        assert_eq!(11, ram.len());
+    }
+
+    #[test]
+    fn pages_never_contain_the_free_list() {
+        let mut pages = NondeterministicPages::new();
+        pages.reserve(NUM_NONDETERMINISTIC_PAGES * NONDETERMINISTIC_PAGE_SIZE);
+        assert!(!pages.contains(FREE_LIST_ADDRESS));
+    }
+
+    #[should_panic]
+    #[test]
+    fn reserving_too_many_pages_fails() {
+        let mut pages = NondeterministicPages::new();
+        pages.reserve(NUM_NONDETERMINISTIC_PAGES * NONDETERMINISTIC_PAGE_SIZE);
+        pages.reserve(1);
+    }
+
+    #[should_panic(expected = "must not be written to")]
+    #[test]
+    fn writes_to_reserved_pages_are_detected() {
+        let mut pages = NondeterministicPages::new();
+        let mut initial_memory = HashMap::new();
+        let object = pages.load(&mut initial_memory, vec![BFieldElement::new(1); 3]);
+
+        let mut final_memory = initial_memory.clone();
+        final_memory.insert(object + BFieldElement::new(2), BFieldElement::new(0));
+        assert_pages_unchanged(&pages, &initial_memory, &final_memory);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn assert_outside_nondeterministic_pages_benchmark() {
+        ShadowedFunction::new(AssertOutsideNondeterministicPages).bench();
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::dyn_malloc::{DynMalloc, DYN_MALLOC_ADDRESS};
+use crate::memory::memcpy::MemCpy;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::deprecated_snippet::DeprecatedSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Resize a dynamically allocated region of `old_size` words to `new_size` words and return
+/// the pointer to the resized region, which holds the region's old contents.
+///
+/// Shrinking keeps the region where it is. Growing allocates a new region through
+/// [`DynMalloc`] and copies the old contents over. If `grow_in_place` is set and the
+/// region ends where the dynamic allocator's free memory begins, the region is instead
+/// grown in place, saving the copy.
+#[derive(Clone, Debug)]
+pub struct Realloc {
+    pub grow_in_place: bool,
+}
+
+impl BasicSnippet for Realloc {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*old".to_owned()),
+            (DataType::U32, "old_size".to_owned()),
+            (DataType::U32, "new_size".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*new".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        match self.grow_in_place {
+            true => "tasm_memory_realloc_grow_in_place".to_string(),
+            false => "tasm_memory_realloc".to_string(),
+        }
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let keep = format!("{entrypoint}_keep");
+        let grow = format!("{entrypoint}_grow");
+        let grow_in_place = format!("{entrypoint}_grow_in_place");
+        let move_region = format!("{entrypoint}_move");
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+        let memcpy = library.import(Box::new(MemCpy));
+
+        let grow_subroutines = match self.grow_in_place {
+            true => triton_asm!(
+                // BEFORE: _ *old old_size new_size
+                // AFTER:  _ *old old_size new_size *new
+                {grow}:
+                    push 1
+                    push {DYN_MALLOC_ADDRESS}
+                    read_mem 1
+                    pop 1                       // _ *old old_size new_size 1 *free_memory
+                    dup 4
+                    dup 4
+                    add
+                    eq                          // _ *old old_size new_size 1 (*old + old_size == *free_memory)
+                    skiz
+                        call {grow_in_place}
+                    skiz
+                        call {move_region}
+                    return
+
+                // BEFORE: _ *old old_size new_size 1
+                // AFTER:  _ *old old_size new_size *old 0
+                {grow_in_place}:
+                    pop 1
+                    dup 0
+                    dup 2
+                    push -1
+                    mul
+                    add                         // _ *old old_size new_size (new_size - old_size)
+                    call {dyn_malloc}
+                    pop 1
+                    dup 2
+                    push 0
+                    return
+
+                // BEFORE: _ *old old_size new_size
+                // AFTER:  _ *old old_size new_size *new
+                {move_region}:
+                    dup 0
+                    call {dyn_malloc}           // _ *old old_size new_size *new
+                    dup 3
+                    dup 1
+                    dup 4                       // _ *old old_size new_size *new *old *new old_size
+                    call {memcpy}
+                    return
+            ),
+            false => triton_asm!(
+                // BEFORE: _ *old old_size new_size
+                // AFTER:  _ *old old_size new_size *new
+                {grow}:
+                    dup 0
+                    call {dyn_malloc}           // _ *old old_size new_size *new
+                    dup 3
+                    dup 1
+                    dup 4                       // _ *old old_size new_size *new *old *new old_size
+                    call {memcpy}
+                    return
+            ),
+        };
+
+        triton_asm!(
+        // BEFORE: _ *old old_size new_size
+        // AFTER:  _ *new
+        {entrypoint}:
+            push 1
+            dup 1
+            dup 3
+            lt
+            push 0
+            eq                          // _ *old old_size new_size 1 (new_size <= old_size)
+            skiz
+                call {keep}
+            skiz
+                call {grow}
+            // _ *old old_size new_size *new
+
+            swap 3
+            pop 3
+            return
+
+        // BEFORE: _ *old old_size new_size 1
+        // AFTER:  _ *old old_size new_size *old 0
+        {keep}:
+            pop 1
+            dup 2
+            push 0
+            return
+
+        {&grow_subroutines}
+        )
+    }
+}
+
+impl Function for Realloc {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let new_size = stack.pop().unwrap().value();
+        let old_size = stack.pop().unwrap().value();
+        let old = stack.pop().unwrap();
+
+        if new_size <= old_size {
+            stack.push(old);
+            return;
+        }
+
+        let free_memory = memory.get(&DYN_MALLOC_ADDRESS).copied().unwrap_or_default();
+        if self.grow_in_place && old + BFieldElement::new(old_size) == free_memory {
+            dynamic_allocator((new_size - old_size) as usize, memory);
+            stack.push(old);
+            return;
+        }
+
+        let new = dynamic_allocator(new_size as usize, memory);
+        let mut memcpy_stack =
+            [empty_stack(), vec![old, new, BFieldElement::new(old_size)]].concat();
+        MemCpy.rust_shadowing(&mut memcpy_stack, vec![], vec![], memory);
+        stack.push(new);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (old_size, new_size, at_frontier) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (10, 20, false),
+            Some(BenchmarkCase::WorstCase) => (1000, 2000, false),
+            None => {
+                let old_size = rng.gen_range(0..100);
+                (old_size, rng.gen_range(0..2 * old_size + 10), rng.gen())
+            }
+        };
+
+        Self::initial_state(old_size, new_size, at_frontier, &mut rng)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x3a; 32]);
+        let mut states = vec![];
+        for at_frontier in [false, true] {
+            for (old_size, new_size) in [(0, 0), (0, 5), (5, 0), (5, 4), (5, 5), (5, 6), (5, 17)] {
+                states.push(Self::initial_state(
+                    old_size,
+                    new_size,
+                    at_frontier,
+                    &mut rng,
+                ));
+            }
+        }
+
+        states
+    }
+}
+
+impl Realloc {
+    /// A region of `old_size` random words, allocated after some other region. If
+    /// `at_frontier` is not set, yet another region is allocated after it.
+    fn initial_state(
+        old_size: u32,
+        new_size: u32,
+        at_frontier: bool,
+        rng: &mut StdRng,
+    ) -> FunctionInitialState {
+        let mut memory = HashMap::new();
+        dynamic_allocator(rng.gen_range(0..100), &mut memory);
+        let old = dynamic_allocator(old_size as usize, &mut memory);
+        for i in 0..old_size {
+            memory.insert(old + BFieldElement::new(i as u64), rng.gen());
+        }
+        if !at_frontier {
+            dynamic_allocator(rng.gen_range(1..100), &mut memory);
+        }
+
+        let stack = [
+            empty_stack(),
+            vec![old, BFieldElement::new(old_size as u64), new_size.into()],
+        ]
+        .concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn realloc_test() {
+        for grow_in_place in [false, true] {
+            ShadowedFunction::new(Realloc { grow_in_place }).test();
+        }
+    }
+
+    #[test]
+    fn region_at_frontier_grows_in_place_only_if_enabled() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let initial_state = Realloc::initial_state(5, 8, true, &mut rng);
+        let old = initial_state.stack[initial_state.stack.len() - 3];
+
+        for grow_in_place in [false, true] {
+            let FunctionInitialState {
+                mut stack,
+                mut memory,
+            } = initial_state.clone();
+            Realloc { grow_in_place }.rust_shadow(&mut stack, &mut memory);
+            let new = stack.pop().unwrap();
+            assert_eq!(grow_in_place, old == new);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn realloc_benchmark() {
+        for grow_in_place in [false, true] {
+            ShadowedFunction::new(Realloc { grow_in_place }).bench();
+        }
+    }
+}
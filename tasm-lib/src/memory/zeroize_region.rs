@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Overwrite `num_words` words of memory, starting at `*region`, with zeros, *e.g.*, to
+/// clear secret material from memory once it is no longer needed.
+///
+/// The writes have no effect on the stack, which makes them look redundant to code
+/// transformations that only track the stack. The tests ensure that the
+/// [optimizer](crate::optimizer::optimize) keeps them.
+#[derive(Clone, Debug)]
+pub struct ZeroizeRegion;
+
+impl BasicSnippet for ZeroizeRegion {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*region".to_owned()),
+            (DataType::U32, "num_words".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_memory_zeroize_region".to_string()
+    }
+
+    fn writable_memory_regions(
+        &self,
+        initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        let [.., region, num_words] = initial_stack else {
+            return None;
+        };
+        let num_words = u32::try_from(num_words.value()).ok()?;
+        Some(vec![(*region, num_words)])
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let zeroize_chunks = format!("{entrypoint}_zeroize_chunks");
+        let zeroize_words = format!("{entrypoint}_zeroize_words");
+
+        triton_asm!(
+        // BEFORE: _ *region num_words
+        // AFTER:  _
+        {entrypoint}:
+            call {zeroize_chunks}       // _ *remainder (num_words % 5)
+            call {zeroize_words}        // _ *end 0
+            pop 2
+            return
+
+        // Zero 5 words at a time while at least 5 words remain.
+        // INVARIANT: _ *ptr num_words_left
+        {zeroize_chunks}:
+            push 5
+            dup 1
+            lt                          // _ *ptr num_words_left (num_words_left < 5)
+            skiz return
+
+            push 0
+            push 0
+            push 0
+            push 0
+            push 0
+            dup 6                       // _ *ptr num_words_left 0 0 0 0 0 *ptr
+            write_mem 5                 // _ *ptr num_words_left (*ptr + 5)
+            swap 2
+            pop 1
+            push -5
+            add                         // _ (*ptr + 5) (num_words_left - 5)
+            recurse
+
+        // INVARIANT: _ *ptr num_words_left
+        {zeroize_words}:
+            dup 0
+            push 0
+            eq
+            skiz return
+
+            push 0
+            dup 2                       // _ *ptr num_words_left 0 *ptr
+            write_mem 1                 // _ *ptr num_words_left (*ptr + 1)
+            swap 2
+            pop 1
+            push -1
+            add                         // _ (*ptr + 1) (num_words_left - 1)
+            recurse
+        )
+    }
+}
+
+impl Function for ZeroizeRegion {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let num_words = stack.pop().unwrap().value();
+        let region = stack.pop().unwrap();
+        for i in 0..num_words {
+            memory.insert(region + BFieldElement::new(i), BFieldElement::new(0));
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let num_words = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 100,
+            Some(BenchmarkCase::WorstCase) => 1000,
+            None => rng.gen_range(0..200),
+        };
+
+        Self::initial_state(rng.gen(), num_words, &mut rng)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x5e; 32]);
+        let mut states = vec![];
+        for num_words in [0, 1, 4, 5, 6, 9, 10, 11] {
+            states.push(Self::initial_state(
+                BFieldElement::new(0),
+                num_words,
+                &mut rng,
+            ));
+        }
+
+        states
+    }
+}
+
+impl ZeroizeRegion {
+    /// A region of random words, surrounded by more random words that must not be touched.
+    fn initial_state(
+        region: BFieldElement,
+        num_words: u32,
+        rng: &mut StdRng,
+    ) -> FunctionInitialState {
+        let memory = (0..num_words as u64 + 2)
+            .map(|i| {
+                (
+                    region + BFieldElement::new(i) - BFieldElement::new(1),
+                    rng.gen(),
+                )
+            })
+            .collect();
+        let stack = [empty_stack(), vec![region, num_words.into()]].concat();
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use triton_vm::instruction::AnInstruction;
+    use triton_vm::{NonDeterminism, Program};
+
+    use super::*;
+    use crate::execute_with_terminal_state;
+    use crate::linker::link_for_isolated_run;
+    use crate::optimizer::optimize;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn zeroize_region_test() {
+        ShadowedFunction::new(ZeroizeRegion).test();
+    }
+
+    #[test]
+    fn optimizer_keeps_all_writes() {
+        let count_writes = |code: &[LabelledInstruction]| {
+            code.iter()
+                .filter(|instruction| {
+                    matches!(
+                        instruction,
+                        LabelledInstruction::Instruction(AnInstruction::WriteMem(_))
+                    )
+                })
+                .count()
+        };
+        let code = link_for_isolated_run(Rc::new(RefCell::new(ZeroizeRegion)), 0);
+        let optimized_code = optimize(&code);
+        assert_eq!(count_writes(&code), count_writes(&optimized_code));
+
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let region = BFieldElement::new(1 << 33);
+        let num_words = 13;
+        let initial_state = ZeroizeRegion::initial_state(region, num_words, &mut rng);
+        let final_state = execute_with_terminal_state(
+            &Program::new(&optimized_code),
+            &[],
+            &initial_state.stack,
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            None,
+        )
+        .unwrap();
+
+        for i in 0..num_words as u64 {
+            let address = region + BFieldElement::new(i);
+            assert_eq!(BFieldElement::new(0), final_state.ram[&address]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn zeroize_region_benchmark() {
+        ShadowedFunction::new(ZeroizeRegion).bench();
+    }
+}
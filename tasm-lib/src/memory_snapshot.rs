@@ -0,0 +1,157 @@
+//! Snapshots of memory images and the differences between them, for inspecting what a
+//! snippet did to memory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+/// A copy of a memory image, ordered by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemorySnapshot {
+    words: BTreeMap<u64, BFieldElement>,
+}
+
+/// One changed word of memory: its address, its value before, and its value after. A
+/// value of `None` means the address was not initialized.
+pub type MemoryChange = (BFieldElement, Option<BFieldElement>, Option<BFieldElement>);
+
+/// The words in which two [memory snapshots](MemorySnapshot) differ, ordered by address.
+/// Displays the changes grouped into ranges of contiguous addresses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryDiff {
+    changes: Vec<MemoryChange>,
+}
+
+impl MemorySnapshot {
+    pub fn new(memory: &HashMap<BFieldElement, BFieldElement>) -> Self {
+        let words = memory
+            .iter()
+            .map(|(address, &word)| (address.value(), word))
+            .collect();
+        Self { words }
+    }
+
+    pub fn get(&self, address: BFieldElement) -> Option<BFieldElement> {
+        self.words.get(&address.value()).copied()
+    }
+
+    /// The changes that turn this snapshot into `after`.
+    pub fn diff(&self, after: &MemorySnapshot) -> MemoryDiff {
+        let changes = self
+            .words
+            .keys()
+            .chain(after.words.keys())
+            .copied()
+            .sorted()
+            .dedup()
+            .map(BFieldElement::new)
+            .map(|address| (address, self.get(address), after.get(address)))
+            .filter(|(_, before, after)| before != after)
+            .collect();
+        MemoryDiff { changes }
+    }
+}
+
+impl MemoryDiff {
+    pub fn changes(&self) -> &[MemoryChange] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The changes, split into runs of contiguous addresses.
+    pub fn contiguous_ranges(&self) -> Vec<&[MemoryChange]> {
+        let mut ranges = vec![];
+        let mut rest = &self.changes[..];
+        while !rest.is_empty() {
+            let range_length = rest
+                .windows(2)
+                .take_while(|pair| pair[0].0.value() + 1 == pair[1].0.value())
+                .count()
+                + 1;
+            let (range, remainder) = rest.split_at(range_length);
+            ranges.push(range);
+            rest = remainder;
+        }
+
+        ranges
+    }
+}
+
+impl Display for MemoryDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "(no changes)");
+        }
+
+        let format_word = |word: &Option<BFieldElement>| match word {
+            Some(word) => word.to_string(),
+            None => "(uninitialized)".to_owned(),
+        };
+        for range in self.contiguous_ranges() {
+            let (first_address, ..) = range[0];
+            let (last_address, ..) = range[range.len() - 1];
+            writeln!(
+                f,
+                "[{first_address}..={last_address}] ({} words):",
+                range.len()
+            )?;
+            for (address, before, after) in range {
+                let before = format_word(before);
+                let after = format_word(after);
+                writeln!(f, "  {address}: {before} -> {after}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(words: &[(u64, u64)]) -> HashMap<BFieldElement, BFieldElement> {
+        words
+            .iter()
+            .map(|&(address, word)| (BFieldElement::new(address), BFieldElement::new(word)))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lists_changed_added_and_removed_words() {
+        let before = MemorySnapshot::new(&memory(&[(1, 10), (2, 20), (3, 30)]));
+        let after = MemorySnapshot::new(&memory(&[(1, 10), (2, 21), (4, 40)]));
+
+        let bfe = BFieldElement::new;
+        let expected = vec![
+            (bfe(2), Some(bfe(20)), Some(bfe(21))),
+            (bfe(3), Some(bfe(30)), None),
+            (bfe(4), None, Some(bfe(40))),
+        ];
+        assert_eq!(expected, before.diff(&after).changes());
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn changes_are_grouped_into_contiguous_ranges() {
+        let before = MemorySnapshot::default();
+        let after = MemorySnapshot::new(&memory(&[(5, 1), (6, 2), (7, 3), (9, 4), (20, 5)]));
+        let diff = before.diff(&after);
+
+        let range_lengths = diff
+            .contiguous_ranges()
+            .iter()
+            .map(|r| r.len())
+            .collect_vec();
+        assert_eq!(vec![3, 1, 1], range_lengths);
+
+        let display = diff.to_string();
+        assert!(display.starts_with("[5..=7] (3 words):\n  5: (uninitialized) -> 1\n"));
+        assert!(display.contains("[20..=20] (1 words):"));
+    }
+}
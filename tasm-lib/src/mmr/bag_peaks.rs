@@ -2,19 +2,21 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use rand::rngs::StdRng;
-use rand::{random, Rng, SeedableRng};
+use rand::{Rng, SeedableRng};
 use triton_vm::instruction::LabelledInstruction;
 use triton_vm::{triton_asm, BFieldElement};
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
 use twenty_first::{shared_math::other::random_elements, util_types::shared::bag_peaks};
 
 use crate::data_type::DataType;
+use crate::initial_state::InitialStateBuilder;
+use crate::list::ListType;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::traits::basic_snippet::BasicSnippet;
 use crate::traits::function::{Function, FunctionInitialState};
 use crate::{
-    empty_stack,
     list::unsafeimplu32::{get::UnsafeGet, length::Length as UnsafeLength},
-    rust_shadowing_helper_functions, Digest, VmHasher, DIGEST_LENGTH,
+    Digest, VmHasher, DIGEST_LENGTH,
 };
 
 pub struct BagPeaks;
@@ -22,19 +24,11 @@ pub struct BagPeaks;
 impl BagPeaks {
     fn input_state(num_peaks: usize) -> FunctionInitialState {
         let peaks: Vec<Digest> = random_elements(num_peaks);
-        let address: BFieldElement = random();
-        let mut stack = empty_stack();
-        stack.push(address);
-        let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
-
-        // Insert list into memory
-        rust_shadowing_helper_functions::unsafe_list::unsafe_list_insert(
-            address,
-            peaks,
-            &mut memory,
-        );
-
-        FunctionInitialState { stack, memory }
+        let peaks = peaks.iter().map(|peak| peak.encode()).collect();
+
+        InitialStateBuilder::new()
+            .with_list(ListType::Unsafe, &DataType::Digest, peaks)
+            .build_function_state()
     }
 }
 
@@ -54,6 +48,26 @@ impl BasicSnippet for BagPeaks {
         vec![(DataType::Digest, "digest".to_owned())]
     }
 
+    fn corner_case_categories(&self) -> Vec<String> {
+        [
+            "no peaks",
+            "one peak",
+            "few peaks",
+            "maximal number of peaks",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    fn input_objects(
+        &self,
+        initial_stack: &[BFieldElement],
+        initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(usize, u32)>> {
+        let num_peaks = initial_memory.get(initial_stack.last()?)?.value() as usize;
+        Some(vec![(0, (1 + num_peaks * DIGEST_LENGTH) as u32)])
+    }
+
     fn code(&self, library: &mut crate::library::Library) -> Vec<LabelledInstruction> {
         let entrypoint = self.entrypoint();
 
@@ -237,13 +251,13 @@ impl Function for BagPeaks {
         }
     }
 
-    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, FunctionInitialState)> {
         vec![
-            Self::input_state(0),
-            Self::input_state(1),
-            Self::input_state(2),
-            Self::input_state(3),
-            Self::input_state(63),
+            ("no peaks".to_owned(), Self::input_state(0)),
+            ("one peak".to_owned(), Self::input_state(1)),
+            ("few peaks".to_owned(), Self::input_state(2)),
+            ("few peaks".to_owned(), Self::input_state(3)),
+            ("maximal number of peaks".to_owned(), Self::input_state(63)),
         ]
     }
 }
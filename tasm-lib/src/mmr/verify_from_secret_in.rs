@@ -25,7 +25,7 @@ use crate::list::unsafeimplu32::get::UnsafeGet;
 use crate::list::ListType;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::traits::basic_snippet::BasicSnippet;
-use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::traits::procedure::{Procedure, ProcedureInitialState, TokenCursor};
 use crate::{
     empty_stack, rust_shadowing_helper_functions, Digest, ExecutionState, VmHasher, DIGEST_LENGTH,
 };
@@ -274,12 +274,32 @@ impl BasicSnippet for MmrVerifyLeafMembershipFromSecretIn {
 
 impl Procedure for MmrVerifyLeafMembershipFromSecretIn {
     fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<crate::VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            &mut tokens,
+        )
+    }
+
+    fn rust_shadow_with_token_cursor(
         &self,
         stack: &mut Vec<BFieldElement>,
         memory: &mut HashMap<BFieldElement, BFieldElement>,
         nondeterminism: &NonDeterminism<BFieldElement>,
         _public_input: &[BFieldElement],
         _sponge_state: &mut Option<crate::VmHasherState>,
+        tokens: &mut TokenCursor,
     ) -> Vec<BFieldElement> {
         let mut leaf_digest = [BFieldElement::new(0); DIGEST_LENGTH];
         for elem in leaf_digest.iter_mut() {
@@ -305,14 +325,8 @@ impl Procedure for MmrVerifyLeafMembershipFromSecretIn {
             );
             peaks.push(digest);
         }
-        let leaf_index_hi: u32 = nondeterminism.individual_tokens[0]
-            .value()
-            .try_into()
-            .unwrap();
-        let leaf_index_lo: u32 = nondeterminism.individual_tokens[1]
-            .value()
-            .try_into()
-            .unwrap();
+        let leaf_index_hi: u32 = tokens.read().value().try_into().unwrap();
+        let leaf_index_lo: u32 = tokens.read().value().try_into().unwrap();
         let leaf_index: u64 = ((leaf_index_hi as u64) << 32) + leaf_index_lo as u64;
         let (mut mt_index, _peak_index) =
             leaf_index_to_mt_index_and_peak_index(leaf_index, leaf_count);
@@ -387,10 +401,12 @@ mod benches {
 
 #[cfg(test)]
 mod tests {
-    use crate::traits::rust_shadow::RustShadow;
     use crate::{
-        mmr::MAX_MMR_HEIGHT, test_helpers::test_rust_equivalence_given_complete_state,
-        traits::procedure::ShadowedProcedure, VmHasher,
+        mmr::MAX_MMR_HEIGHT,
+        test_executor::{run_snippet_tests_in_parallel, SnippetTest},
+        test_helpers::test_rust_equivalence_given_complete_state,
+        traits::procedure::ShadowedProcedure,
+        VmHasher,
     };
     use rand::thread_rng;
     use triton_vm::NonDeterminism;
@@ -403,16 +419,17 @@ mod tests {
 
     #[test]
     fn prop() {
+        let mut tests = vec![];
         for _ in 0..10 {
-            ShadowedProcedure::new(MmrVerifyLeafMembershipFromSecretIn {
-                list_type: ListType::Unsafe,
-            })
-            .test();
-            ShadowedProcedure::new(MmrVerifyLeafMembershipFromSecretIn {
-                list_type: ListType::Safe,
-            })
-            .test()
+            for list_type in [ListType::Unsafe, ListType::Safe] {
+                tests.push(SnippetTest::new(move || {
+                    ShadowedProcedure::new(MmrVerifyLeafMembershipFromSecretIn {
+                        list_type: list_type.clone(),
+                    })
+                }));
+            }
         }
+        run_snippet_tests_in_parallel(tests);
     }
 
     #[test]
@@ -0,0 +1,182 @@
+use triton_vm::instruction::{AnInstruction, LabelledInstruction};
+use triton_vm::op_stack::NumberOfWords;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+
+/// Remove obviously redundant instruction sequences from a linked program.
+///
+/// The following rewrites are applied until none of them matches anymore:
+/// - `swap k swap k` is removed,
+/// - `push 0 add` is removed,
+/// - `push a pop 1` is removed, and `push a pop n` becomes `pop n-1`,
+/// - `pop m pop n` becomes `pop m+n`, as long as `m+n` does not exceed 5.
+///
+/// Labels and breakpoints are never part of a rewritten sequence, so jump and call targets
+/// are preserved. Sequences directly following a `skiz` are left untouched, since `skiz`
+/// might skip only their first instruction.
+///
+/// Snippets can be linked into an optimized program through
+/// [`link_for_isolated_run_optimized`](crate::linker::link_for_isolated_run_optimized).
+pub fn optimize(program: &[LabelledInstruction]) -> Vec<LabelledInstruction> {
+    let mut optimized = Vec::with_capacity(program.len());
+    for instruction in program {
+        optimized.push(instruction.to_owned());
+        while rewrite_tail(&mut optimized) {}
+    }
+
+    optimized
+}
+
+/// Apply at most one rewrite rule to the last two instructions of `code`. Returns `true` if
+/// a rule was applied.
+fn rewrite_tail(code: &mut Vec<LabelledInstruction>) -> bool {
+    let len = code.len();
+    if len < 2 {
+        return false;
+    }
+
+    let preceded_by_skiz = len > 2
+        && matches!(
+            code[len - 3],
+            LabelledInstruction::Instruction(AnInstruction::Skiz)
+        );
+    if preceded_by_skiz {
+        return false;
+    }
+
+    let (LabelledInstruction::Instruction(penultimate), LabelledInstruction::Instruction(last)) =
+        (&code[len - 2], &code[len - 1])
+    else {
+        return false;
+    };
+
+    let replacement = match (penultimate, last) {
+        (AnInstruction::Swap(a), AnInstruction::Swap(b)) if a == b => vec![],
+        (AnInstruction::Push(zero), AnInstruction::Add) if *zero == BFieldElement::new(0) => {
+            vec![]
+        }
+        (AnInstruction::Push(_), AnInstruction::Pop(n)) => match usize::from(*n) - 1 {
+            0 => vec![],
+            remaining => vec![pop(remaining)],
+        },
+        (AnInstruction::Pop(m), AnInstruction::Pop(n)) => {
+            let total = usize::from(*m) + usize::from(*n);
+            match NumberOfWords::try_from(total) {
+                Ok(_) => vec![pop(total)],
+                Err(_) => return false,
+            }
+        }
+        _ => return false,
+    };
+
+    code.truncate(len - 2);
+    code.extend(replacement);
+    true
+}
+
+fn pop(num_words: usize) -> LabelledInstruction {
+    let num_words = NumberOfWords::try_from(num_words).unwrap();
+    LabelledInstruction::Instruction(AnInstruction::Pop(num_words))
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::program::Program;
+    use triton_vm::triton_asm;
+    use triton_vm::{NonDeterminism, PublicInput};
+
+    use super::*;
+
+    #[test]
+    fn redundant_sequences_are_removed() {
+        assert_eq!(
+            triton_asm!(push 1),
+            optimize(&triton_asm!(push 1 swap 2 swap 2))
+        );
+        assert_eq!(
+            triton_asm!(push 1),
+            optimize(&triton_asm!(push 1 push 0 add))
+        );
+        assert_eq!(
+            triton_asm!(push 1),
+            optimize(&triton_asm!(push 1 push 7 pop 1))
+        );
+        assert_eq!(triton_asm!(pop 2), optimize(&triton_asm!(push 7 pop 3)));
+        assert_eq!(triton_asm!(pop 5), optimize(&triton_asm!(pop 2 pop 3)));
+        assert_eq!(
+            triton_asm!(pop 5 pop 1),
+            optimize(&triton_asm!(pop 2 pop 3 pop 1))
+        );
+    }
+
+    #[test]
+    fn rewrites_cascade() {
+        assert_eq!(
+            triton_asm!(pop 4),
+            optimize(&triton_asm!(push 1 pop 1 swap 1 push 0 add swap 1 pop 3 push 8 pop 2))
+        );
+        assert_eq!(
+            Vec::<LabelledInstruction>::new(),
+            optimize(&triton_asm!(push 5 push 6 pop 2))
+        );
+    }
+
+    #[test]
+    fn labels_breakpoints_and_skiz_are_barriers() {
+        let code = triton_asm!(
+            swap 1
+            label:
+            swap 1
+            push 0
+            break
+            add
+            push 0
+            skiz
+            pop 1
+            pop 1
+            return
+        );
+        assert_eq!(code, optimize(&code));
+    }
+
+    #[test]
+    fn optimized_program_computes_the_same_output() {
+        let code = triton_asm!(
+            push 3
+            push 4
+            swap 1
+            swap 1
+            push 0
+            add
+            push 9
+            pop 1
+            call double
+            write_io 1
+            push 1
+            push 2
+            pop 1
+            pop 1
+            push 0
+            skiz
+            push 0
+            add
+            write_io 1
+            halt
+
+            double:
+                dup 0
+                push 0
+                add
+                add
+                return
+        );
+        let optimized = optimize(&code);
+        assert!(optimized.len() < code.len());
+
+        let run = |code: &[LabelledInstruction]| {
+            Program::new(code)
+                .run(PublicInput::new(vec![]), NonDeterminism::new(vec![]))
+                .unwrap()
+        };
+        assert_eq!(run(&code), run(&optimized));
+    }
+}
@@ -1,7 +1,13 @@
+pub mod deduplicate_indices;
 pub mod fri_verify;
 pub mod get_colinear_y;
 pub mod get_colinearity_check_x;
+pub mod hash_table_rows;
 pub mod merkle_verify;
 pub mod proof_stream;
+pub mod recursive_verification_harness;
 pub mod verify_authentication_paths_for_leaf_and_index_list;
+pub mod verify_leaf_openings;
+pub mod verify_out_of_domain_quotient;
+pub mod verify_table_rows;
 pub mod xfe_ntt;
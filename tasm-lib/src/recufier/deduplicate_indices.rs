@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Copy a list of indices into a new list, keeping only the first occurrence of every
+/// index, *e.g.*, to open every sampled row of a table only once. The order of the indices
+/// is preserved.
+///
+/// Runs in time quadratic in the number of indices.
+#[derive(Clone, Debug)]
+pub struct DeduplicateIndices {
+    pub list_type: ListType,
+}
+
+impl BasicSnippet for DeduplicateIndices {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(
+            DataType::List(Box::new(DataType::U32)),
+            "*indices".to_string(),
+        )]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(
+            DataType::List(Box::new(DataType::U32)),
+            "*unique_indices".to_string(),
+        )]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasm_recufier_deduplicate_indices_{}", self.list_type)
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let deduplicate = format!("{entrypoint}_deduplicate");
+        let push_index = format!("{entrypoint}_push_index");
+        let contains = format!("{entrypoint}_contains");
+        let contains_loop = format!("{entrypoint}_contains_loop");
+        let new_list = library.import(self.list_type.new_list_snippet(DataType::U32));
+        let length = library.import(self.list_type.length_snippet(DataType::U32));
+        let get = library.import(self.list_type.get_snippet(DataType::U32));
+        let push = library.import(self.list_type.push_snippet(DataType::U32));
+
+        triton_asm!(
+            // BEFORE: _ *indices
+            // AFTER:  _ *unique_indices
+            {entrypoint}:
+                dup 0
+                call {length}           // _ *indices num_indices
+                dup 0
+                call {new_list}         // _ *indices num_indices *unique_indices
+                push 0
+                call {deduplicate}      // _ *indices num_indices *unique_indices num_indices
+                pop 1
+                swap 2
+                pop 2                   // _ *unique_indices
+                return
+
+            // INVARIANT: _ *indices num_indices *unique_indices i
+            {deduplicate}:
+                dup 2
+                dup 1
+                eq
+                skiz return
+
+                dup 3
+                dup 1
+                call {get}              // _ *indices num_indices *unique_indices i index
+                dup 2
+                dup 1
+                call {contains}         // _ *indices num_indices *unique_indices i index is_duplicate
+                push 0
+                eq
+                skiz call {push_index}  // _ *indices num_indices *unique_indices i index
+                pop 1
+
+                push 1
+                add
+                recurse
+
+            // BEFORE: _ *unique_indices i index
+            // AFTER:  _ *unique_indices i index
+            {push_index}:
+                dup 2
+                dup 1
+                call {push}
+                return
+
+            // BEFORE: _ *unique_indices index
+            // AFTER:  _ (index ∈ unique_indices)
+            {contains}:
+                dup 1
+                call {length}           // _ *unique_indices index num_unique
+                push 0
+                call {contains_loop}    // _ *unique_indices index num_unique j
+                eq
+                push 0
+                eq                      // _ *unique_indices index (j != num_unique)
+                swap 2
+                pop 2
+                return
+
+            // Stops at the first j with unique_indices[j] == index, or at j == num_unique.
+            // INVARIANT: _ *unique_indices index num_unique j
+            {contains_loop}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 3
+                dup 1
+                call {get}              // _ *unique_indices index num_unique j unique_indices[j]
+                dup 3
+                eq
+                skiz return
+
+                push 1
+                add
+                recurse
+        )
+    }
+}
+
+impl Function for DeduplicateIndices {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let indices = stack.pop().unwrap();
+        let num_indices = match self.list_type {
+            ListType::Safe => safe_list::safe_list_get_length(indices, memory),
+            ListType::Unsafe => unsafe_list::unsafe_list_get_length(indices, memory),
+        };
+        let unique_indices = (0..num_indices)
+            .map(|i| self.list_type.rust_shadowing_get(indices, i, memory, 1)[0])
+            .unique()
+            .collect_vec();
+
+        let unique_indices_pointer =
+            dynamic_allocator(num_indices + self.list_type.metadata_size(), memory);
+        match self.list_type {
+            ListType::Safe => safe_list::safe_list_insert(
+                unique_indices_pointer,
+                num_indices as u32,
+                unique_indices,
+                memory,
+            ),
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_insert(unique_indices_pointer, unique_indices, memory)
+            }
+        }
+
+        stack.push(unique_indices_pointer);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (num_indices, upper_bound) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (40, 1 << 10),
+            Some(BenchmarkCase::WorstCase) => (80, 1 << 20),
+            None => {
+                let num_indices = rng.gen_range(0..40);
+                (num_indices, rng.gen_range(1..=num_indices + 1))
+            }
+        };
+        let indices = (0..num_indices)
+            .map(|_| rng.gen_range(0..upper_bound))
+            .collect_vec();
+        self.initial_state(indices)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        vec![
+            self.initial_state(vec![]),
+            self.initial_state(vec![7]),
+            self.initial_state(vec![7; 5]),
+            self.initial_state((0..10).collect()),
+            self.initial_state(vec![3, 1, 3, 0, 1, 3]),
+        ]
+    }
+}
+
+impl DeduplicateIndices {
+    fn initial_state(&self, indices: Vec<u32>) -> FunctionInitialState {
+        let indices_pointer = BFieldElement::new(1 << 20);
+        let mut memory = HashMap::new();
+        match self.list_type {
+            ListType::Safe => {
+                let capacity = indices.len() as u32;
+                safe_list::safe_list_insert(indices_pointer, capacity, indices, &mut memory)
+            }
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_insert(indices_pointer, indices, &mut memory)
+            }
+        }
+
+        FunctionInitialState {
+            stack: [empty_stack(), vec![indices_pointer]].concat(),
+            memory,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn deduplicate_indices_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            ShadowedFunction::new(DeduplicateIndices { list_type }).test();
+        }
+    }
+
+    #[test]
+    fn first_occurrences_are_kept_in_order() {
+        let snippet = DeduplicateIndices {
+            list_type: ListType::Unsafe,
+        };
+        let FunctionInitialState {
+            mut stack,
+            mut memory,
+        } = snippet.initial_state(vec![3, 1, 3, 0, 1, 3]);
+        snippet.rust_shadow(&mut stack, &mut memory);
+
+        let unique_indices = stack.pop().unwrap();
+        let num_unique_indices = unsafe_list::unsafe_list_get_length(unique_indices, &memory);
+        let unique_indices = (0..num_unique_indices)
+            .map(|i| unsafe_list::unsafe_list_get(unique_indices, i, &memory, 1)[0].value())
+            .collect_vec();
+        assert_eq!(vec![3, 1, 0], unique_indices);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn deduplicate_indices_benchmark() {
+        ShadowedFunction::new(DeduplicateIndices {
+            list_type: ListType::Unsafe,
+        })
+        .bench();
+    }
+}
@@ -47,6 +47,7 @@ use crate::memory::encode_to_memory;
 use crate::recufier::get_colinear_y::ColinearYXfe;
 use crate::recufier::get_colinearity_check_x::GetColinearityCheckX;
 use crate::recufier::proof_stream::dequeue::Dequeue;
+use crate::recufier::proof_stream::dequeue_next_as::{DequeueNextAs, ProofItemVariant};
 use crate::recufier::proof_stream::sample_scalars::SampleScalars;
 use crate::recufier::proof_stream::vm_proof_stream::VmProofStream;
 use crate::recufier::verify_authentication_paths_for_leaf_and_index_list::VerifyAuthenticationPathForLeafAndIndexList;
@@ -426,7 +427,12 @@ impl FriVerify {
         self.set_up_stack_and_non_determinism_using_digests(proof_stream, digests)
     }
 
-    fn extract_digests_required_for_proving(&self, proof_stream: &VmProofStream) -> Vec<Digest> {
+    /// The digests of the authentication paths that the verifier reads nondeterministically
+    /// when verifying the FRI proof at the front of the given proof stream.
+    pub(crate) fn extract_digests_required_for_proving(
+        &self,
+        proof_stream: &VmProofStream,
+    ) -> Vec<Digest> {
         let mut digests = vec![];
         self.inner_verify(&mut proof_stream.clone(), &mut digests)
             .unwrap();
@@ -516,6 +522,12 @@ impl BasicSnippet for FriVerify {
             data_type: DataType::Xfe,
         }));
         let proof_stream_dequeue = library.import(Box::new(Dequeue {}));
+        let dequeue_merkle_root = library.import(Box::new(DequeueNextAs {
+            proof_item: ProofItemVariant::MerkleRoot,
+        }));
+        let dequeue_fri_response = library.import(Box::new(DequeueNextAs {
+            proof_item: ProofItemVariant::FriResponse,
+        }));
         let proof_stream_sample_scalars = library.import(Box::new(SampleScalars {}));
         let dequeue_commit_phase = format!("{entrypoint}_dequeue_commit_phase_remainder");
         let convert_xfe_to_digest = format!("{entrypoint}_convert_xfe_to_digest");
@@ -664,9 +676,7 @@ impl BasicSnippet for FriVerify {
                 },
             }),
         }));
-        let proof_item_as_merkle_root = VmProofStream::proof_item_as_merkle_root_code();
         let proof_item_as_fri_codeword = VmProofStream::proof_item_as_fri_codeword_code();
-        let proof_item_as_fri_response = VmProofStream::proof_item_as_fri_response_code();
 
         triton_asm! {
             // INVARIANT:          _ *alphas current_tree_height *a_indices *a_elements *revealed_indices_and_leafs current_domain_length r half_domain_length *b_indices *b_elements *fri_verify current_tree_height-1 half_domain_length *c_indices *c_values i
@@ -790,9 +800,7 @@ impl BasicSnippet for FriVerify {
                 swap 1      // _ num_rounds-1 *alphas *roots
                 dup 6       // _ num_rounds-1 *alphas *roots *proof_stream
 
-                call {proof_stream_dequeue} // _ num_rounds-1 *alphas *roots *proof_stream *root_ev
-                {&proof_item_as_merkle_root}
-                                            // _ num_rounds-1 *alphas *roots *proof_stream *root
+                call {dequeue_merkle_root}  // _ num_rounds-1 *alphas *roots *proof_stream *root
                 swap 1 pop 1                // _ num_rounds-1 *alphas *roots *root
                 dup 1 swap 1                // _ num_rounds-1 *alphas *roots *roots *root
                 {&read_digest}              // _ num_rounds-1 *alphas *roots *roots [root]
@@ -859,9 +867,7 @@ impl BasicSnippet for FriVerify {
                 // dequeue first Merkle root
                 swap 1                      // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots
                 dup 5                       // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *proof_stream
-                call {proof_stream_dequeue} // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *proof_stream *root_ev
-                {&proof_item_as_merkle_root}
-                                            // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *proof_stream *root
+                call {dequeue_merkle_root}  // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *proof_stream *root
                 swap 1 pop 1                // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *root
                 dup 1 swap 1                // _ *proof_stream *fri_verify num_rounds last_round_max_degree *alphas *roots *roots *root
 
@@ -961,9 +967,7 @@ impl BasicSnippet for FriVerify {
 
                 // dequeue proof item as fri response
                 swap 2                      // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas tree_height *indices *proof_stream
-                call {proof_stream_dequeue} // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas tree_height *indices *proof_stream *proof_item
-                {&proof_item_as_fri_response}
-                                            // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas tree_height *indices *proof_stream *fri_response
+                call {dequeue_fri_response} // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas tree_height *indices *proof_stream *fri_response
                 swap 1 pop 1                // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas tree_height *indices *fri_response
 
                 // assert correct length of number of leafs
@@ -1060,9 +1064,7 @@ impl BasicSnippet for FriVerify {
 
                 // dequeue fri response and get "B" elements
                 dup 14                      // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas current_tree_height *a_indices *a_elements *revealed_indices_and_leafs current_domain_length r half_domain_length *b_indices *proof_stream
-                call {proof_stream_dequeue}
-                                            // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas current_tree_height *a_indices *a_elements *revealed_indices_and_leafs current_domain_length r half_domain_length *b_indices *proof_stream *fri_response_ev
-                {&proof_item_as_fri_response}
+                call {dequeue_fri_response}
                 hint fri_response: Pointer = stack[0]
                                             // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas current_tree_height *a_indices *a_elements *revealed_indices_and_leafs current_domain_length r half_domain_length *b_indices *proof_stream *fri_response
                 swap 1 pop 1                // _ *proof_stream *fri_verify num_rounds last_round_max_degree *last_codeword' *roots *alphas current_tree_height *a_indices *a_elements *revealed_indices_and_leafs current_domain_length r half_domain_length *b_indices *fri_response
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+
+use crate::data_type::DataType;
+use crate::hashing::hash_varlen::HashVarlen;
+use crate::library::Library;
+use crate::list::ListType;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+use crate::{empty_stack, Digest, VmHasher, DIGEST_LENGTH};
+
+/// Hash every revealed row of a table into the leaf of the table's Merkle tree, like Triton
+/// VM's verifier does. The rows are given as the encoding of a `Vec<Vec<T>>` for some
+/// statically sized `T`, *i.e.*, the payload of a `MasterBaseTableRows` or a
+/// `MasterExtTableRows` proof item, and each leaf is the [`hash_varlen`][hash] of the row's
+/// elements. The leaves are returned as a new list, in the order of the rows.
+///
+/// [hash]: AlgebraicHasher::hash_varlen
+#[derive(Clone, Debug)]
+pub struct HashTableRows {
+    pub list_type: ListType,
+}
+
+impl BasicSnippet for HashTableRows {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*rows".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(
+            DataType::List(Box::new(DataType::Digest)),
+            "*leaves".to_string(),
+        )]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasm_recufier_hash_table_rows_{}", self.list_type)
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let hash_rows = format!("{entrypoint}_hash_rows");
+        let new_list = library.import(self.list_type.new_list_snippet(DataType::Digest));
+        let push = library.import(self.list_type.push_snippet(DataType::Digest));
+        let hash_varlen = library.import(Box::new(HashVarlen));
+
+        triton_asm!(
+            // BEFORE: _ *rows
+            // AFTER:  _ *leaves
+            {entrypoint}:
+                read_mem 1
+                push 2
+                add                     // _ num_rows *row_0
+                dup 1
+                call {new_list}         // _ num_rows *row_0 *leaves
+                swap 2
+                swap 1                  // _ *leaves num_rows *row_0
+                call {hash_rows}        // _ *leaves 0 *end_of_rows
+                pop 2
+                return
+
+            // Every row is encoded as its size, its number of elements, and its elements.
+            // INVARIANT: _ *leaves num_rows_left *row
+            {hash_rows}:
+                dup 1
+                push 0
+                eq
+                skiz return
+
+                read_mem 1
+                push 3
+                add                     // _ *leaves num_rows_left row_size *row_elements
+                dup 1
+                push -1
+                add                     // _ *leaves num_rows_left row_size *row_elements num_words
+                dup 4
+                dup 2
+                dup 2
+                call {hash_varlen}      // _ *leaves num_rows_left row_size *row_elements num_words *leaves [leaf]
+                call {push}             // _ *leaves num_rows_left row_size *row_elements num_words
+                add                     // _ *leaves num_rows_left row_size *next_row
+                swap 1
+                pop 1
+                swap 1
+                push -1
+                add
+                swap 1                  // _ *leaves (num_rows_left - 1) *next_row
+                recurse
+        )
+    }
+}
+
+impl Function for HashTableRows {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let rows = stack.pop().unwrap();
+        let leaves = Self::leaves(&Self::read_rows(rows, memory));
+
+        let leaves_pointer = dynamic_allocator(
+            leaves.len() * DIGEST_LENGTH + self.list_type.metadata_size(),
+            memory,
+        );
+        match self.list_type {
+            ListType::Safe => {
+                let capacity = leaves.len() as u32;
+                safe_list::safe_list_insert(leaves_pointer, capacity, leaves, memory)
+            }
+            ListType::Unsafe => unsafe_list::unsafe_list_insert(leaves_pointer, leaves, memory),
+        }
+
+        stack.push(leaves_pointer);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (num_rows, num_columns) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (40, 100),
+            Some(BenchmarkCase::WorstCase) => (80, 400),
+            None => (rng.gen_range(0..20), rng.gen_range(0..30)),
+        };
+        let element_size = match rng.gen() {
+            true => 1,
+            false => 3,
+        };
+        let rows = (0..num_rows)
+            .map(|_| (0..num_columns * element_size).map(|_| rng.gen()).collect())
+            .collect_vec();
+        let rows_pointer = BFieldElement::new(rng.gen_range(0..1 << 20));
+
+        Self::initial_state(rows_pointer, &rows, element_size)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let rows_pointer = BFieldElement::new(1 << 20);
+        let single_row = vec![vec![BFieldElement::new(42); 3]];
+        let empty_rows = vec![vec![]; 3];
+        vec![
+            Self::initial_state(rows_pointer, &[], 1),
+            Self::initial_state(rows_pointer, &single_row, 1),
+            Self::initial_state(rows_pointer, &single_row, 3),
+            Self::initial_state(rows_pointer, &empty_rows, 1),
+        ]
+    }
+}
+
+impl HashTableRows {
+    /// The leaves of the rows, each of which is given as the words of its elements.
+    pub fn leaves(rows: &[Vec<BFieldElement>]) -> Vec<Digest> {
+        rows.iter().map(|row| VmHasher::hash_varlen(row)).collect()
+    }
+
+    /// Encode the rows, each of which is given as the words of its elements, like
+    /// `BFieldCodec` encodes `Vec<Vec<T>>` for a `T` of `element_size` words.
+    pub fn encode_rows(rows: &[Vec<BFieldElement>], element_size: usize) -> Vec<BFieldElement> {
+        let mut encoding = vec![BFieldElement::new(rows.len() as u64)];
+        for row in rows {
+            let num_elements = row.len() / element_size;
+            encoding.push(BFieldElement::new(row.len() as u64 + 1));
+            encoding.push(BFieldElement::new(num_elements as u64));
+            encoding.extend(row);
+        }
+        encoding
+    }
+
+    /// Read the rows encoded at `rows` from memory, each as the words of its elements.
+    fn read_rows(
+        rows: BFieldElement,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Vec<Vec<BFieldElement>> {
+        let read_word = |address| memory.get(&address).copied().unwrap_or_default();
+        let num_rows = read_word(rows).value();
+
+        let mut row = rows + BFieldElement::new(1);
+        let mut all_rows = vec![];
+        for _ in 0..num_rows {
+            let row_size = read_word(row).value();
+            let row_elements = row + BFieldElement::new(2);
+            let words = (0..row_size - 1)
+                .map(|i| read_word(row_elements + BFieldElement::new(i)))
+                .collect();
+            all_rows.push(words);
+            row += BFieldElement::new(row_size + 1);
+        }
+        all_rows
+    }
+
+    fn initial_state(
+        rows_pointer: BFieldElement,
+        rows: &[Vec<BFieldElement>],
+        element_size: usize,
+    ) -> FunctionInitialState {
+        let mut memory = HashMap::new();
+        for (i, word) in Self::encode_rows(rows, element_size)
+            .into_iter()
+            .enumerate()
+        {
+            memory.insert(rows_pointer + BFieldElement::new(i as u64), word);
+        }
+
+        FunctionInitialState {
+            stack: [empty_stack(), vec![rows_pointer]].concat(),
+            memory,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::bfield_codec::BFieldCodec;
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn hash_table_rows_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            ShadowedFunction::new(HashTableRows { list_type }).test();
+        }
+    }
+
+    #[test]
+    fn rows_are_encoded_like_base_and_extension_table_rows() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let base_rows: Vec<Vec<BFieldElement>> = (0..5)
+            .map(|_| (0..7).map(|_| rng.gen()).collect())
+            .collect();
+        assert_eq!(
+            base_rows.encode(),
+            HashTableRows::encode_rows(&base_rows, 1)
+        );
+
+        let ext_rows: Vec<Vec<XFieldElement>> = (0..5)
+            .map(|_| (0..7).map(|_| rng.gen()).collect())
+            .collect();
+        let ext_row_words = ext_rows
+            .iter()
+            .map(|row| row.iter().flat_map(|xfe| xfe.coefficients).collect_vec())
+            .collect_vec();
+        assert_eq!(
+            ext_rows.encode(),
+            HashTableRows::encode_rows(&ext_row_words, 3)
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn hash_table_rows_benchmark() {
+        ShadowedFunction::new(HashTableRows {
+            list_type: ListType::Unsafe,
+        })
+        .bench();
+    }
+}
@@ -11,7 +11,7 @@ use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
 use crate::data_type::DataType;
 use crate::library::Library;
 use crate::snippet_bencher::BenchmarkCase;
-use crate::traits::algorithm::{Algorithm, AlgorithmInitialState};
+use crate::traits::algorithm::{Algorithm, AlgorithmInitialState, DigestCursor};
 use crate::traits::basic_snippet::BasicSnippet;
 use crate::{empty_stack, Digest, VmHasher};
 
@@ -93,8 +93,19 @@ impl Algorithm for MerkleVerify {
     fn rust_shadow(
         &self,
         stack: &mut Vec<BFieldElement>,
-        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
         nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        self.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, &mut digests);
+    }
+
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        _memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        digests: &mut DigestCursor,
     ) {
         let pop_digest_from = |stack: &mut Vec<BFieldElement>| {
             Digest::new([
@@ -115,16 +126,14 @@ impl Algorithm for MerkleVerify {
         assert!(leaf_index < num_leaves);
 
         let mut node_digest = leaf;
-        let mut sibling_height = 0;
         let mut node_index = leaf_index + num_leaves;
         while node_index != 1 {
-            let sibling = nondeterminism.digests[sibling_height];
+            let sibling = digests.read();
             let node_is_left_sibling = node_index % 2 == 0;
             node_digest = match node_is_left_sibling {
                 true => VmHasher::hash_pair(node_digest, sibling),
                 false => VmHasher::hash_pair(sibling, node_digest),
             };
-            sibling_height += 1;
             node_index /= 2;
         }
         assert_eq!(node_digest, root);
@@ -192,6 +201,7 @@ mod tests {
 
     use crate::execute_with_terminal_state;
     use crate::linker::link_for_isolated_run;
+    use crate::test_helpers::{rust_final_state, test_rust_equivalence_given_complete_state};
     use crate::traits::algorithm::ShadowedAlgorithm;
     use crate::traits::rust_shadow::RustShadow;
 
@@ -203,6 +213,31 @@ mod tests {
         ShadowedAlgorithm::new(MerkleVerify).test()
     }
 
+    #[test]
+    fn surplus_digests_are_read_by_neither_rust_nor_tasm() {
+        let AlgorithmInitialState {
+            stack,
+            mut nondeterminism,
+        } = MerkleVerify.pseudorandom_initial_state(thread_rng().gen(), None);
+        let tree_height = nondeterminism.digests.len();
+        nondeterminism.digests.push(thread_rng().gen());
+
+        let shadowed = ShadowedAlgorithm::new(MerkleVerify);
+        let rust = rust_final_state(&shadowed, &stack, &[], &nondeterminism, &None);
+        assert_eq!(tree_height, rust.digests_read);
+
+        let tasm = test_rust_equivalence_given_complete_state(
+            &shadowed,
+            &stack,
+            &[],
+            &nondeterminism,
+            &None,
+            0,
+            None,
+        );
+        assert_eq!(tree_height, tasm.digests_read);
+    }
+
     #[test]
     fn negative_test() {
         let seed: [u8; 32] = thread_rng().gen();
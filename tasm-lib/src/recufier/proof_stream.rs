@@ -1,3 +1,5 @@
 pub mod dequeue;
+pub mod dequeue_next_as;
+pub mod instantiate_fiat_shamir_with_claim;
 pub mod sample_scalars;
 pub mod vm_proof_stream;
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use num_traits::Zero;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::proof_item::{FriResponse, ProofItem};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism};
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+use twenty_first::shared_math::x_field_element::XFieldElement;
+
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::memory::encode_to_memory;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, Digest, VmHasherState};
+
+use super::dequeue::Dequeue;
+use super::vm_proof_stream::VmProofStream;
+
+/// The variants of [`ProofItem`], without their associated data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProofItemVariant {
+    AuthenticationStructure,
+    MasterBaseTableRows,
+    MasterExtTableRows,
+    OutOfDomainBaseRow,
+    OutOfDomainExtRow,
+    OutOfDomainQuotientSegments,
+    MerkleRoot,
+    Log2PaddedHeight,
+    QuotientSegmentsElements,
+    FriCodeword,
+    FriResponse,
+}
+
+impl ProofItemVariant {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::AuthenticationStructure,
+            Self::MasterBaseTableRows,
+            Self::MasterExtTableRows,
+            Self::OutOfDomainBaseRow,
+            Self::OutOfDomainExtRow,
+            Self::OutOfDomainQuotientSegments,
+            Self::MerkleRoot,
+            Self::Log2PaddedHeight,
+            Self::QuotientSegmentsElements,
+            Self::FriCodeword,
+            Self::FriResponse,
+        ]
+    }
+
+    pub fn of(proof_item: &ProofItem) -> Self {
+        match proof_item {
+            ProofItem::AuthenticationStructure(_) => Self::AuthenticationStructure,
+            ProofItem::MasterBaseTableRows(_) => Self::MasterBaseTableRows,
+            ProofItem::MasterExtTableRows(_) => Self::MasterExtTableRows,
+            ProofItem::OutOfDomainBaseRow(_) => Self::OutOfDomainBaseRow,
+            ProofItem::OutOfDomainExtRow(_) => Self::OutOfDomainExtRow,
+            ProofItem::OutOfDomainQuotientSegments(_) => Self::OutOfDomainQuotientSegments,
+            ProofItem::MerkleRoot(_) => Self::MerkleRoot,
+            ProofItem::Log2PaddedHeight(_) => Self::Log2PaddedHeight,
+            ProofItem::QuotientSegmentsElements(_) => Self::QuotientSegmentsElements,
+            ProofItem::FriCodeword(_) => Self::FriCodeword,
+            ProofItem::FriResponse(_) => Self::FriResponse,
+        }
+    }
+
+    fn empty_item(&self) -> ProofItem {
+        match self {
+            Self::AuthenticationStructure => ProofItem::AuthenticationStructure(vec![]),
+            Self::MasterBaseTableRows => ProofItem::MasterBaseTableRows(vec![]),
+            Self::MasterExtTableRows => ProofItem::MasterExtTableRows(vec![]),
+            Self::OutOfDomainBaseRow => ProofItem::OutOfDomainBaseRow(vec![]),
+            Self::OutOfDomainExtRow => ProofItem::OutOfDomainExtRow(vec![]),
+            Self::OutOfDomainQuotientSegments => {
+                ProofItem::OutOfDomainQuotientSegments([XFieldElement::zero(); 4])
+            }
+            Self::MerkleRoot => ProofItem::MerkleRoot(Digest::default()),
+            Self::Log2PaddedHeight => ProofItem::Log2PaddedHeight(0),
+            Self::QuotientSegmentsElements => ProofItem::QuotientSegmentsElements(vec![]),
+            Self::FriCodeword => ProofItem::FriCodeword(vec![]),
+            Self::FriResponse => ProofItem::FriResponse(FriResponse {
+                auth_structure: vec![],
+                revealed_leaves: vec![],
+            }),
+        }
+    }
+
+    pub fn discriminant(&self) -> BFieldElement {
+        BFieldElement::new(self.empty_item().bfield_codec_discriminant() as u64)
+    }
+
+    /// Whether the associated data has a static size, in which case its encoding is not
+    /// prefixed with its length.
+    pub fn has_static_size(&self) -> bool {
+        matches!(
+            self,
+            Self::OutOfDomainQuotientSegments | Self::MerkleRoot | Self::Log2PaddedHeight
+        )
+    }
+
+    pub fn label_friendly_name(&self) -> &'static str {
+        match self {
+            Self::AuthenticationStructure => "authentication_structure",
+            Self::MasterBaseTableRows => "master_base_table_rows",
+            Self::MasterExtTableRows => "master_ext_table_rows",
+            Self::OutOfDomainBaseRow => "out_of_domain_base_row",
+            Self::OutOfDomainExtRow => "out_of_domain_ext_row",
+            Self::OutOfDomainQuotientSegments => "out_of_domain_quotient_segments",
+            Self::MerkleRoot => "merkle_root",
+            Self::Log2PaddedHeight => "log2_padded_height",
+            Self::QuotientSegmentsElements => "quotient_segments_elements",
+            Self::FriCodeword => "fri_codeword",
+            Self::FriResponse => "fri_response",
+        }
+    }
+}
+
+/// Dequeue the next item from the proof stream through [`Dequeue`], which also absorbs it
+/// into the sponge if the item is part of the Fiat-Shamir heuristic, and crash the VM
+/// unless the item is of the expected variant. Returns the pointer to the item's associated
+/// data, such that the data can be accessed through its type's
+/// [`TasmObject`](crate::structure::tasm_object::TasmObject) implementation.
+#[derive(Clone, Debug)]
+pub struct DequeueNextAs {
+    pub proof_item: ProofItemVariant,
+}
+
+impl BasicSnippet for DequeueNextAs {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*proof_stream".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*proof_stream".to_string()),
+            (DataType::VoidPointer, "*proof_item_payload".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_recufier_proof_stream_dequeue_next_as_{}",
+            self.proof_item.label_friendly_name()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let dequeue = library.import(Box::new(Dequeue));
+
+        // skip the discriminant and, for dynamically sized data, its length indicator
+        let payload_offset = match self.proof_item.has_static_size() {
+            true => 2,
+            false => 3,
+        };
+
+        triton_asm!(
+            // BEFORE: _ *proof_stream
+            // AFTER:  _ *proof_stream *proof_item_payload
+            {entrypoint}:
+                call {dequeue}          // _ *proof_stream *proof_item
+                read_mem 1              // _ *proof_stream discriminant (*proof_item - 1)
+                push {payload_offset}
+                add                     // _ *proof_stream discriminant *proof_item_payload
+                swap 1
+                push {self.proof_item.discriminant()}
+                eq
+                assert                  // _ *proof_stream *proof_item_payload
+                return
+        )
+    }
+}
+
+impl Procedure for DequeueNextAs {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        Dequeue.rust_shadow(stack, memory, nondeterminism, public_input, sponge_state);
+
+        let proof_item = stack.pop().unwrap();
+        let discriminant = memory.get(&proof_item).copied().unwrap_or_default();
+        assert_eq!(self.proof_item.discriminant(), discriminant);
+
+        let payload_offset = match self.proof_item.has_static_size() {
+            true => 1,
+            false => 2,
+        };
+        stack.push(proof_item + BFieldElement::new(payload_offset));
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        loop {
+            let proof_items = VmProofStream::pseudorandom_items_list(rng.gen());
+            let position = proof_items
+                .iter()
+                .position(|item| ProofItemVariant::of(item) == self.proof_item);
+            if let Some(position) = position {
+                return Self::initial_state(&proof_items, position, &mut rng);
+            }
+        }
+    }
+
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xde; 32]);
+        loop {
+            let proof_items = VmProofStream::pseudorandom_items_list(rng.gen());
+            let position = proof_items
+                .iter()
+                .position(|item| ProofItemVariant::of(item) != self.proof_item);
+            if let Some(position) = position {
+                let state = Self::initial_state(&proof_items, position, &mut rng);
+                return vec![(state, InstructionError::AssertionFailed)];
+            }
+        }
+    }
+}
+
+impl DequeueNextAs {
+    /// The proof stream of the given items, stored at a random address, where all items
+    /// before `position` have been dequeued already.
+    fn initial_state(
+        proof_items: &[ProofItem],
+        position: usize,
+        rng: &mut StdRng,
+    ) -> ProcedureInitialState {
+        let mut proof_stream = VmProofStream::new(proof_items);
+        for _ in 0..position {
+            proof_stream.dequeue().unwrap();
+        }
+
+        let address = BFieldElement::new(rng.gen_range(0..1 << 20));
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, address, proof_stream);
+
+        ProcedureInitialState {
+            stack: [empty_stack(), vec![address]].concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            sponge_state: Some(VmHasherState { state: rng.gen() }),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structure::tasm_object::TasmObject;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    use super::*;
+
+    #[test]
+    fn dequeue_next_as_test() {
+        for proof_item in ProofItemVariant::all() {
+            ShadowedProcedure::new(DequeueNextAs { proof_item }).test();
+        }
+    }
+
+    #[test]
+    fn variants_have_distinct_discriminants() {
+        let variants = ProofItemVariant::all();
+        for (i, variant) in variants.iter().enumerate() {
+            assert_eq!(*variant, ProofItemVariant::of(&variant.empty_item()));
+            for other in variants.iter().skip(i + 1) {
+                assert_ne!(variant.discriminant(), other.discriminant());
+            }
+        }
+    }
+
+    #[test]
+    fn payload_pointers_point_to_associated_data() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let merkle_root: Digest = rng.gen();
+        let fri_codeword: Vec<XFieldElement> = (0..32).map(|_| rng.gen()).collect();
+        let proof_items = vec![
+            ProofItem::MerkleRoot(merkle_root),
+            ProofItem::FriCodeword(fri_codeword.clone()),
+        ];
+        let initial_state = DequeueNextAs::initial_state(&proof_items, 0, &mut rng);
+
+        let mut stack = initial_state.stack;
+        let mut memory = initial_state.nondeterminism.ram.clone();
+        let mut sponge_state = initial_state.sponge_state;
+        let mut dequeue_next_as = |proof_item, stack: &mut Vec<_>, memory: &mut HashMap<_, _>| {
+            DequeueNextAs { proof_item }.rust_shadow(
+                stack,
+                memory,
+                &initial_state.nondeterminism,
+                &[],
+                &mut sponge_state,
+            );
+            stack.pop().unwrap()
+        };
+
+        let merkle_root_pointer =
+            dequeue_next_as(ProofItemVariant::MerkleRoot, &mut stack, &mut memory);
+        assert_eq!(
+            merkle_root,
+            *Digest::decode_from_memory(&memory, merkle_root_pointer).unwrap()
+        );
+
+        let fri_codeword_pointer =
+            dequeue_next_as(ProofItemVariant::FriCodeword, &mut stack, &mut memory);
+        assert_eq!(
+            fri_codeword,
+            *Vec::<XFieldElement>::decode_from_memory(&memory, fri_codeword_pointer).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn dequeue_next_as_benchmark() {
+        ShadowedProcedure::new(DequeueNextAs {
+            proof_item: ProofItemVariant::FriCodeword,
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{
+    instruction::LabelledInstruction, triton_asm, BFieldElement, Claim, NonDeterminism,
+};
+
+use crate::data_type::DataType;
+use crate::hashing::absorb::Absorb;
+use crate::library::Library;
+use crate::memory::encode_to_memory;
+use crate::recufier::proof_stream::vm_proof_stream::VmProofStream;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::structure::tasm_object::TasmObject;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::procedure::{Procedure, ProcedureInitialState};
+use crate::{empty_stack, VmHasherState};
+
+/// Reset the sponge and absorb the encoding of the [`Claim`] stored in memory, such that
+/// the sponge is in the state the prover's proof stream was in before the first proof
+/// item got enqueued. All challenges sampled afterwards, for example through
+/// [`SampleScalars`](super::sample_scalars::SampleScalars) or
+/// [`SampleIndices`](crate::hashing::sample_indices::SampleIndices), are then bound to
+/// the claim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstantiateFiatShamirWithClaim;
+
+impl BasicSnippet for InstantiateFiatShamirWithClaim {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*claim".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasm_recufier_proof_stream_instantiate_fiat_shamir_with_claim".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let absorb = library.import(Box::new(Absorb));
+        let claim_size = Claim::size_in_memory();
+
+        triton_asm!(
+            // BEFORE: _ *claim
+            // AFTER:  _
+            {entrypoint}:
+                sponge_init
+                dup 0               // _ *claim *claim
+                {&claim_size}       // _ *claim claim_size
+                call {absorb}       // _
+                return
+        )
+    }
+}
+
+impl Procedure for InstantiateFiatShamirWithClaim {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        _public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        let claim_pointer = stack.pop().unwrap();
+        let claim = *Claim::decode_from_memory(memory, claim_pointer).unwrap();
+
+        let mut proof_stream = VmProofStream::new(&[]);
+        proof_stream.alter_fiat_shamir_state_with(&claim);
+        *sponge_state = Some(proof_stream.sponge_state);
+
+        vec![]
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> ProcedureInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (input_length, output_length) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (10, 10),
+            Some(BenchmarkCase::WorstCase) => (100, 100),
+            None => (rng.gen_range(0..50), rng.gen_range(0..50)),
+        };
+        let claim = Claim {
+            program_digest: rng.gen(),
+            input: (0..input_length).map(|_| rng.gen()).collect(),
+            output: (0..output_length).map(|_| rng.gen()).collect(),
+        };
+        let claim_pointer = BFieldElement::new(rng.gen_range(0..(1 << 20)));
+
+        Self::initial_state(&claim, claim_pointer, rng.gen())
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+        let empty_claim = Claim {
+            program_digest: Default::default(),
+            input: vec![],
+            output: vec![],
+        };
+        vec![Self::initial_state(
+            &empty_claim,
+            BFieldElement::new(0),
+            VmHasherState {
+                state: [BFieldElement::new(1); 16],
+            },
+        )]
+    }
+}
+
+impl InstantiateFiatShamirWithClaim {
+    fn initial_state(
+        claim: &Claim,
+        claim_pointer: BFieldElement,
+        sponge_state: VmHasherState,
+    ) -> ProcedureInitialState {
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, claim_pointer, claim.clone());
+
+        ProcedureInitialState {
+            stack: [empty_stack(), vec![claim_pointer]].concat(),
+            nondeterminism: NonDeterminism::default().with_ram(memory),
+            public_input: vec![],
+            // any sponge state must be overwritten
+            sponge_state: Some(sponge_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triton_vm::proof_item::ProofItem;
+    use triton_vm::proof_stream::ProofStream;
+    use triton_vm::vm::VMState;
+    use triton_vm::{Program, PublicInput};
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+    use twenty_first::util_types::algebraic_hasher::{AlgebraicHasher, SpongeHasher};
+
+    use super::*;
+    use crate::hashing::sample_indices::SampleIndices;
+    use crate::list::ListType;
+    use crate::recufier::proof_stream::dequeue::Dequeue;
+    use crate::recufier::proof_stream::sample_scalars::SampleScalars;
+    use crate::test_helpers::tasm_final_state;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::VmHasher;
+
+    #[test]
+    fn test() {
+        ShadowedProcedure::new(InstantiateFiatShamirWithClaim).test();
+    }
+
+    /// The challenges derived in Triton VM must be identical to the challenges Triton VM's
+    /// own verifier derives from the same claim and proof items, in the same order.
+    #[test]
+    fn challenges_match_those_of_triton_vm_proof_stream() {
+        let mut rng: StdRng = SeedableRng::from_seed([0xc1; 32]);
+        let claim = Claim {
+            program_digest: rng.gen(),
+            input: (0..13).map(|_| rng.gen()).collect(),
+            output: (0..7).map(|_| rng.gen()).collect(),
+        };
+        let proof_items = vec![
+            ProofItem::MerkleRoot(rng.gen()),
+            ProofItem::OutOfDomainQuotientSegments(rng.gen()),
+            ProofItem::MerkleRoot(rng.gen()),
+        ];
+        let num_weights = 17;
+        let num_indices = 11;
+        let upper_bound = 1 << 10;
+
+        // Triton VM's proof stream
+        let mut proof_stream = ProofStream::<VmHasher>::new();
+        proof_stream.alter_fiat_shamir_state_with(&claim);
+        for item in proof_items
+            .iter()
+            .filter(|i| i.include_in_fiat_shamir_heuristic())
+        {
+            proof_stream.alter_fiat_shamir_state_with(item);
+        }
+        let weights = VmHasher::sample_scalars(&mut proof_stream.sponge_state, num_weights);
+        let indices =
+            VmHasher::sample_indices(&mut proof_stream.sponge_state, upper_bound, num_indices);
+
+        // the same transcript in Triton VM
+        let mut library = Library::new();
+        let instantiate_fiat_shamir = library.import(Box::new(InstantiateFiatShamirWithClaim));
+        let dequeue = library.import(Box::new(Dequeue));
+        let sample_scalars = library.import(Box::new(SampleScalars));
+        let sample_indices = library.import(Box::new(SampleIndices {
+            list_type: ListType::Unsafe,
+        }));
+        let dequeue_all_items = vec![triton_asm!(call {dequeue} pop 1); proof_items.len()].concat();
+        let library_code = library.all_imports();
+        let entrypoint = "tasm_recufier_proof_stream_instantiate_fiat_shamir_test_program";
+        let code = triton_asm!(
+            call {entrypoint}
+            halt
+
+            // BEFORE: _ *proof_stream *claim
+            // AFTER:  _ *weights *indices
+            {entrypoint}:
+                call {instantiate_fiat_shamir}
+                                        // _ *proof_stream
+                {&dequeue_all_items}    // _ *proof_stream
+                pop 1
+                push {num_weights}
+                call {sample_scalars}   // _ *weights
+                push {num_indices}
+                push {upper_bound}
+                call {sample_indices}   // _ *weights *indices
+                return
+
+            {&library_code}
+        );
+
+        let claim_pointer = BFieldElement::new(1 << 20);
+        let proof_stream_pointer = BFieldElement::new(1 << 25);
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, claim_pointer, claim);
+        encode_to_memory(
+            &mut memory,
+            proof_stream_pointer,
+            VmProofStream::new(&proof_items),
+        );
+
+        let program = Program::new(&code);
+        let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory);
+        let mut vm_state = VMState::new(&program, PublicInput::default(), nondeterminism);
+        vm_state.op_stack.stack =
+            [empty_stack(), vec![proof_stream_pointer, claim_pointer]].concat();
+        vm_state.run().unwrap();
+
+        let indices_pointer = vm_state.op_stack.stack.pop().unwrap();
+        let weights_pointer = vm_state.op_stack.stack.pop().unwrap();
+        let vm_indices = *Vec::<u32>::decode_from_memory(&vm_state.ram, indices_pointer).unwrap();
+        let vm_weights =
+            *Vec::<XFieldElement>::decode_from_memory(&vm_state.ram, weights_pointer).unwrap();
+
+        assert_eq!(weights, vm_weights);
+        assert_eq!(indices, vm_indices);
+        assert_eq!(Some(proof_stream.sponge_state.state), vm_state.sponge_state);
+    }
+
+    #[test]
+    fn instantiating_fiat_shamir_with_different_claims_gives_different_sponge_states() {
+        let mut rng: StdRng = SeedableRng::from_seed([0xc2; 32]);
+        let claim = Claim {
+            program_digest: rng.gen(),
+            input: vec![],
+            output: vec![BFieldElement::new(42)],
+        };
+        let mut other_claim = claim.clone();
+        other_claim.output[0].increment();
+
+        let sponge_state_after_claim = |claim: &Claim| {
+            let initial_state = InstantiateFiatShamirWithClaim::initial_state(
+                claim,
+                BFieldElement::new(1 << 20),
+                VmHasher::init(),
+            );
+            let tasm = tasm_final_state(
+                &ShadowedProcedure::new(InstantiateFiatShamirWithClaim),
+                &initial_state.stack,
+                &initial_state.public_input,
+                initial_state.nondeterminism,
+                &initial_state.sponge_state,
+                0,
+            );
+            tasm.final_sponge_state.unwrap().state
+        };
+        assert_ne!(
+            sponge_state_after_claim(&claim),
+            sponge_state_after_claim(&other_claim)
+        );
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    use super::*;
+    use crate::traits::procedure::ShadowedProcedure;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn bench() {
+        ShadowedProcedure::new(InstantiateFiatShamirWithClaim).bench();
+    }
+}
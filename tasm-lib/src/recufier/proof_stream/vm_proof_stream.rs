@@ -47,13 +47,13 @@ impl VmProofStream {
         self.word_index = (word_data_index + size) as u32;
 
         if item.include_in_fiat_shamir_heuristic() {
-            self.fiat_shamir(&item);
+            self.alter_fiat_shamir_state_with(&item);
         }
 
         Ok(Box::new(item))
     }
 
-    fn fiat_shamir<T: BFieldCodec>(&mut self, item: &T) {
+    pub fn alter_fiat_shamir_state_with<T: BFieldCodec>(&mut self, item: &T) {
         VmHasher::absorb_repeatedly(
             &mut self.sponge_state,
             Self::encode_and_pad_item(item).iter(),
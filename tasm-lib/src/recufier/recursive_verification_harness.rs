@@ -0,0 +1,363 @@
+//! End-to-end harness for recursive verification: prove a program with Triton VM on the
+//! host, load the claim and the proof into Triton VM's memory, and run an in-VM verifier
+//! on them.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::Result;
+use triton_vm::proof_item::ProofItem;
+use triton_vm::proof_stream::ProofStream;
+use triton_vm::vm::VMState;
+use triton_vm::{BFieldElement, Claim, NonDeterminism, Program, PublicInput, StarkParameters};
+
+use crate::linker::link_for_isolated_run;
+use crate::memory::encode_to_memory;
+use crate::recufier::proof_stream::vm_proof_stream::VmProofStream;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::{empty_stack, execute_with_terminal_state, VmHasher};
+
+/// The address at which the claim is stored.
+const CLAIM_ADDRESS: BFieldElement = BFieldElement::new(1 << 20);
+
+/// The address at which the proof stream is stored.
+const PROOF_STREAM_ADDRESS: BFieldElement = BFieldElement::new(1 << 25);
+
+/// A claim about some program's execution, together with the proof items of a proof for
+/// that claim.
+#[derive(Debug, Clone)]
+pub struct RecursiveVerificationTestCase {
+    pub claim: Claim,
+    pub proof_items: Vec<ProofItem>,
+}
+
+impl RecursiveVerificationTestCase {
+    /// Run the program on the given input, and prove the resulting claim.
+    pub fn prove(
+        program: &Program,
+        public_input: &[BFieldElement],
+        nondeterminism: NonDeterminism<BFieldElement>,
+    ) -> Result<Self> {
+        let input = PublicInput::new(public_input.to_vec());
+        let mut vm_state = VMState::new(program, input, nondeterminism.clone());
+        vm_state.run()?;
+
+        let claim = Claim {
+            program_digest: program.hash::<VmHasher>(),
+            input: public_input.to_vec(),
+            output: vm_state.public_output,
+        };
+        let proof = triton_vm::prove(StarkParameters::default(), &claim, program, nondeterminism)?;
+        let proof_items = ProofStream::<VmHasher>::try_from(&proof)?.items;
+
+        Ok(Self { claim, proof_items })
+    }
+
+    /// The stack `_ *claim *proof_stream` and the memory the two pointers point into.
+    pub fn initial_state(&self) -> (Vec<BFieldElement>, NonDeterminism<BFieldElement>) {
+        let mut memory = Default::default();
+        encode_to_memory(&mut memory, CLAIM_ADDRESS, self.claim.clone());
+        encode_to_memory(
+            &mut memory,
+            PROOF_STREAM_ADDRESS,
+            VmProofStream::new(&self.proof_items),
+        );
+
+        let stack = [empty_stack(), vec![CLAIM_ADDRESS, PROOF_STREAM_ADDRESS]].concat();
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        (stack, nondeterminism)
+    }
+
+    /// Run the given verifier on the claim and the proof. The verifier's signature must be
+    /// `_ *claim *proof_stream -> _`, and it must crash the VM if the proof is invalid.
+    pub fn verify_in_vm<S: BasicSnippet>(&self, verifier: S) -> Result<VMState> {
+        let code = link_for_isolated_run(Rc::new(RefCell::new(verifier)), 0);
+        let program = Program::new(&code);
+        let (stack, nondeterminism) = self.initial_state();
+
+        execute_with_terminal_state(&program, &[], &stack, &nondeterminism, None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::stark::Stark;
+    use triton_vm::table::challenges::Challenges;
+    use triton_vm::table::master_table::{num_quotients, NUM_BASE_COLUMNS, NUM_EXT_COLUMNS};
+    use triton_vm::triton_asm;
+
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::library::Library;
+    use crate::recufier::fri_verify::FriVerify;
+    use crate::recufier::proof_stream::dequeue_next_as::{DequeueNextAs, ProofItemVariant};
+    use crate::recufier::proof_stream::instantiate_fiat_shamir_with_claim::InstantiateFiatShamirWithClaim;
+    use crate::recufier::proof_stream::sample_scalars::SampleScalars;
+    use crate::test_helpers::assert_assertion_failure;
+    use crate::VmHasherState;
+
+    /// The part of the STARK verifier that exists in this crate: instantiate the Fiat-Shamir
+    /// heuristic with the claim, dequeue the padded height and the base table's Merkle
+    /// root, and sample the extension challenges.
+    #[derive(Debug, Clone, Copy)]
+    struct VerifyProofPrefix;
+
+    impl BasicSnippet for VerifyProofPrefix {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![
+                (DataType::VoidPointer, "*claim".to_string()),
+                (DataType::VoidPointer, "*proof_stream".to_string()),
+            ]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasm_recufier_recursive_verification_harness_verify_proof_prefix".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let entrypoint = self.entrypoint();
+            let instantiate_fiat_shamir = library.import(Box::new(InstantiateFiatShamirWithClaim));
+            let dequeue_log2_padded_height = library.import(Box::new(DequeueNextAs {
+                proof_item: ProofItemVariant::Log2PaddedHeight,
+            }));
+            let dequeue_merkle_root = library.import(Box::new(DequeueNextAs {
+                proof_item: ProofItemVariant::MerkleRoot,
+            }));
+            let sample_scalars = library.import(Box::new(SampleScalars));
+            let num_challenges = Challenges::num_challenges_to_sample();
+
+            triton_asm!(
+                // BEFORE: _ *claim *proof_stream
+                // AFTER:  _
+                {entrypoint}:
+                    swap 1
+                    call {instantiate_fiat_shamir}
+                                                        // _ *proof_stream
+                    call {dequeue_log2_padded_height}   // _ *proof_stream *log2_padded_height
+                    pop 1
+                    call {dequeue_merkle_root}          // _ *proof_stream *base_merkle_root
+                    pop 1
+                    push {num_challenges}
+                    call {sample_scalars}               // _ *proof_stream *challenges
+                    pop 2
+                    return
+            )
+        }
+    }
+
+    /// The address at which the parameters of FRI are stored.
+    const FRI_VERIFY_ADDRESS: BFieldElement = BFieldElement::new(1 << 21);
+
+    /// The number of quotient segments, as in Triton VM's out-of-domain quotient segments.
+    const NUM_QUOTIENT_SEGMENTS: usize = 4;
+
+    /// The number of codewords the DEEP codeword is combined from in Triton VM's verifier.
+    const NUM_DEEP_CODEWORD_COMPONENTS: usize = 3;
+
+    /// The number of weights Triton VM's verifier samples for the combination codeword.
+    const NUM_LINEAR_COMBINATION_WEIGHTS: usize =
+        NUM_BASE_COLUMNS + NUM_EXT_COLUMNS + NUM_QUOTIENT_SEGMENTS + NUM_DEEP_CODEWORD_COMPONENTS;
+
+    /// Go through the proof stream like Triton VM's STARK verifier does, up to and
+    /// including FRI: dequeue the commitments and the out-of-domain rows, sample all
+    /// challenges and weights, and verify the FRI proof with [`FriVerify`], which is
+    /// expected at [`FRI_VERIFY_ADDRESS`]. The out-of-domain evaluations, the combination
+    /// codeword, and the revealed table rows are not checked.
+    #[derive(Debug, Clone, Copy)]
+    struct VerifyProofThroughFri {
+        fri_verify: FriVerify,
+    }
+
+    impl BasicSnippet for VerifyProofThroughFri {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            VerifyProofPrefix.inputs()
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasm_recufier_recursive_verification_harness_verify_proof_through_fri".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let entrypoint = self.entrypoint();
+            let verify_proof_prefix = library.import(Box::new(VerifyProofPrefix));
+            let mut dequeue = |proof_item| library.import(Box::new(DequeueNextAs { proof_item }));
+            let dequeue_merkle_root = dequeue(ProofItemVariant::MerkleRoot);
+            let dequeue_ood_base_row = dequeue(ProofItemVariant::OutOfDomainBaseRow);
+            let dequeue_ood_ext_row = dequeue(ProofItemVariant::OutOfDomainExtRow);
+            let dequeue_ood_quotient_segments =
+                dequeue(ProofItemVariant::OutOfDomainQuotientSegments);
+            let sample_scalars = library.import(Box::new(SampleScalars));
+            let fri_verify = library.import(Box::new(self.fri_verify));
+            let num_quotient_weights = num_quotients();
+
+            triton_asm!(
+                // BEFORE: _ *claim *proof_stream
+                // AFTER:  _
+                {entrypoint}:
+                    dup 0
+                    swap 2
+                    swap 1
+                    call {verify_proof_prefix}          // _ *proof_stream
+                    call {dequeue_merkle_root}          // _ *proof_stream *ext_merkle_root
+                    pop 1
+                    push {num_quotient_weights}
+                    call {sample_scalars}               // _ *proof_stream *quotient_weights
+                    pop 1
+                    call {dequeue_merkle_root}          // _ *proof_stream *quotient_merkle_root
+                    pop 1
+                    push 1
+                    call {sample_scalars}               // _ *proof_stream *ood_point
+                    pop 1
+
+                    call {dequeue_ood_base_row}         // _ *proof_stream *ood_curr_base_row
+                    pop 1
+                    call {dequeue_ood_ext_row}          // _ *proof_stream *ood_curr_ext_row
+                    pop 1
+                    call {dequeue_ood_base_row}         // _ *proof_stream *ood_next_base_row
+                    pop 1
+                    call {dequeue_ood_ext_row}          // _ *proof_stream *ood_next_ext_row
+                    pop 1
+                    call {dequeue_ood_quotient_segments}
+                                                        // _ *proof_stream *ood_quotient_segments
+                    pop 1
+                    push {NUM_LINEAR_COMBINATION_WEIGHTS}
+                    call {sample_scalars}               // _ *proof_stream *weights
+                    pop 1
+
+                    push {FRI_VERIFY_ADDRESS}
+                    call {fri_verify}                   // _ *proof_stream *revealed_indices_and_elements
+                    pop 2
+                    return
+            )
+        }
+    }
+
+    fn test_case() -> RecursiveVerificationTestCase {
+        let program = Program::new(&triton_asm!(
+            read_io 1
+            dup 0
+            mul
+            write_io 1
+            halt
+        ));
+        let public_input = [BFieldElement::new(7)];
+        RecursiveVerificationTestCase::prove(&program, &public_input, NonDeterminism::default())
+            .unwrap()
+    }
+
+    /// The sponge state after the host-side verifier went through the same steps as
+    /// [`VerifyProofPrefix`].
+    fn host_sponge_state(claim: &Claim, proof_items: &[ProofItem]) -> VmHasherState {
+        let mut proof_stream = VmProofStream::new(proof_items);
+        proof_stream.alter_fiat_shamir_state_with(claim);
+        proof_stream.dequeue().unwrap();
+        proof_stream.dequeue().unwrap();
+        proof_stream.sample_scalars(Challenges::num_challenges_to_sample());
+        proof_stream.sponge_state
+    }
+
+    /// The proof stream after the host-side verifier went through the same steps as
+    /// [`VerifyProofThroughFri`] up to FRI, and the parameters of FRI.
+    fn host_proof_stream_at_fri(
+        claim: &Claim,
+        proof_items: &[ProofItem],
+    ) -> (VmProofStream, FriVerify) {
+        let mut proof_stream = VmProofStream::new(proof_items);
+        proof_stream.alter_fiat_shamir_state_with(claim);
+        let log_2_padded_height = proof_stream
+            .dequeue()
+            .unwrap()
+            .as_log2_padded_height()
+            .unwrap();
+        proof_stream.dequeue().unwrap();
+        proof_stream.sample_scalars(Challenges::num_challenges_to_sample());
+        proof_stream.dequeue().unwrap();
+        proof_stream.sample_scalars(num_quotients());
+        proof_stream.dequeue().unwrap();
+        proof_stream.sample_scalars(1);
+        for _ in 0..5 {
+            proof_stream.dequeue().unwrap();
+        }
+        proof_stream.sample_scalars(NUM_LINEAR_COMBINATION_WEIGHTS);
+
+        let parameters = StarkParameters::default();
+        let padded_height = 1 << log_2_padded_height;
+        let fri_domain = Stark::derive_fri(&parameters, padded_height).domain;
+        let fri_verify = FriVerify::new(
+            fri_domain.offset,
+            fri_domain.length as u32,
+            parameters.fri_expansion_factor as u32,
+            parameters.num_colinearity_checks as u32,
+        );
+        (proof_stream, fri_verify)
+    }
+
+    #[test]
+    fn honest_proof_is_accepted_and_transcript_matches_host() {
+        let test_case = test_case();
+        assert_eq!(vec![BFieldElement::new(49)], test_case.claim.output);
+
+        let vm_state = test_case.verify_in_vm(VerifyProofPrefix).unwrap();
+        let host_state = host_sponge_state(&test_case.claim, &test_case.proof_items);
+        assert_eq!(Some(host_state.state), vm_state.sponge_state);
+    }
+
+    #[test]
+    fn proof_with_missing_item_is_rejected() {
+        let mut test_case = test_case();
+        test_case.proof_items.remove(0);
+        assert_assertion_failure(test_case.verify_in_vm(VerifyProofPrefix));
+    }
+
+    #[test]
+    fn proof_with_reordered_items_is_rejected() {
+        let mut test_case = test_case();
+        test_case.proof_items.swap(0, 1);
+        assert_assertion_failure(test_case.verify_in_vm(VerifyProofPrefix));
+    }
+
+    #[test]
+    fn truncated_proof_is_rejected() {
+        let mut test_case = test_case();
+        test_case.proof_items.truncate(1);
+        assert!(test_case.verify_in_vm(VerifyProofPrefix).is_err());
+    }
+
+    #[test]
+    fn proof_for_different_claim_gives_different_transcript() {
+        let honest_test_case = test_case();
+        let honest_state =
+            host_sponge_state(&honest_test_case.claim, &honest_test_case.proof_items);
+
+        let mut test_case = honest_test_case;
+        test_case.claim.output[0].increment();
+        let vm_state = test_case.verify_in_vm(VerifyProofPrefix).unwrap();
+        assert_ne!(Some(honest_state.state), vm_state.sponge_state);
+    }
+
+    #[test]
+    fn honest_proof_is_accepted_through_fri() {
+        let test_case = test_case();
+        let (proof_stream, fri_verify) =
+            host_proof_stream_at_fri(&test_case.claim, &test_case.proof_items);
+        let digests = fri_verify.extract_digests_required_for_proving(&proof_stream);
+
+        let verifier = VerifyProofThroughFri { fri_verify };
+        let code = link_for_isolated_run(Rc::new(RefCell::new(verifier)), 0);
+        let (stack, mut nondeterminism) = test_case.initial_state();
+        encode_to_memory(&mut nondeterminism.ram, FRI_VERIFY_ADDRESS, fri_verify);
+        let nondeterminism = nondeterminism.with_digests(digests);
+
+        let program = Program::new(&code);
+        execute_with_terminal_state(&program, &[], &stack, &nondeterminism, None).unwrap();
+    }
+}
@@ -11,7 +11,7 @@ use twenty_first::util_types::{
 use crate::{
     data_type::DataType,
     traits::{
-        algorithm::{Algorithm, AlgorithmInitialState},
+        algorithm::{Algorithm, AlgorithmInitialState, DigestCursor},
         basic_snippet::BasicSnippet,
     },
 };
@@ -123,6 +123,17 @@ impl Algorithm for VerifyAuthenticationPathForLeafAndIndexList {
         stack: &mut Vec<BFieldElement>,
         memory: &mut HashMap<BFieldElement, BFieldElement>,
         nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        self.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, &mut digests);
+    }
+
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _nondeterminism: &NonDeterminism<BFieldElement>,
+        digests: &mut DigestCursor,
     ) {
         // read arguments from stack
         let height = stack.pop().unwrap().value() as usize;
@@ -147,15 +158,8 @@ impl Algorithm for VerifyAuthenticationPathForLeafAndIndexList {
         .unwrap();
 
         // iterate and verify
-        let mut digest_index = 0;
         for (index, leaf) in indices_and_leafs {
-            let authentication_path = (0..height)
-                .map(|_| {
-                    let node = nondeterminism.digests[digest_index];
-                    digest_index += 1;
-                    node
-                })
-                .collect_vec();
+            let authentication_path = (0..height).map(|_| digests.read()).collect_vec();
             assert!(MerkleTree::<VmHasher>::verify_authentication_structure(
                 root,
                 height,
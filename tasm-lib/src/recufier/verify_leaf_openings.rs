@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism};
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+use twenty_first::util_types::merkle_tree::{CpuParallel, MerkleTree};
+use twenty_first::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::list::ListType;
+use crate::recufier::merkle_verify::MerkleVerify;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::algorithm::{Algorithm, AlgorithmInitialState, DigestCursor};
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::{empty_stack, push_encodable, Digest, VmHasher, DIGEST_LENGTH};
+
+/// Verify that each of the `leaves` lives in the Merkle tree with the given root, at the
+/// corresponding leaf index, *e.g.*, to authenticate the revealed rows of a table or the
+/// revealed values of a FRI codeword at the sampled indices. Crashes the VM if the lists'
+/// lengths differ or if any opening is invalid.
+///
+/// The authentication paths are divined, one after the other, through [`MerkleVerify`].
+/// A proof contains only the authentication structure shared among all openings; use
+/// [`nondeterministic_digests`] to reduplicate the paths from it.
+#[derive(Clone, Debug)]
+pub struct VerifyLeafOpenings {
+    pub list_type: ListType,
+}
+
+/// The nondeterministic digests with which [`VerifyLeafOpenings`] verifies the openings
+/// of `leaves` at `leaf_indices`, reduplicated from the authentication structure shared
+/// among them.
+pub fn nondeterministic_digests(
+    tree_height: usize,
+    leaf_indices: &[u32],
+    leaves: &[Digest],
+    authentication_structure: &[Digest],
+) -> Result<Vec<Digest>> {
+    if leaf_indices.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let leaf_indices = leaf_indices.iter().map(|&i| i as usize).collect_vec();
+    let authentication_paths =
+        MerkleTree::<VmHasher>::authentication_paths_from_authentication_structure(
+            tree_height,
+            &leaf_indices,
+            leaves,
+            authentication_structure,
+        )?;
+    Ok(authentication_paths.concat())
+}
+
+impl BasicSnippet for VerifyLeafOpenings {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (
+                DataType::List(Box::new(DataType::U32)),
+                "*leaf_indices".to_string(),
+            ),
+            (
+                DataType::List(Box::new(DataType::Digest)),
+                "*leaves".to_string(),
+            ),
+            (DataType::Digest, "root".to_string()),
+            (DataType::U32, "tree_height".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasm_recufier_verify_leaf_openings_{}", self.list_type)
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let verify_openings = format!("{entrypoint}_verify_openings");
+        let indices_length = library.import(self.list_type.length_snippet(DataType::U32));
+        let leaves_length = library.import(self.list_type.length_snippet(DataType::Digest));
+        let get_index = library.import(self.list_type.get_snippet(DataType::U32));
+        let get_leaf = library.import(self.list_type.get_snippet(DataType::Digest));
+        let merkle_verify = library.import(Box::new(MerkleVerify));
+
+        triton_asm!(
+            // BEFORE: _ *leaf_indices *leaves [root] tree_height
+            // AFTER:  _
+            {entrypoint}:
+                dup 7
+                call {indices_length}   // _ *leaf_indices *leaves [root] tree_height num_openings
+                dup 7
+                call {leaves_length}    // _ *leaf_indices *leaves [root] tree_height num_openings num_leaves
+                dup 1
+                eq
+                assert                  // _ *leaf_indices *leaves [root] tree_height num_openings
+
+                push 0
+                call {verify_openings}  // _ *leaf_indices *leaves [root] tree_height num_openings num_openings
+                pop 5
+                pop 5
+                return
+
+            // INVARIANT: _ *leaf_indices *leaves [root] tree_height num_openings i
+            {verify_openings}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 7
+                dup 7
+                dup 7
+                dup 7
+                dup 7                   // _ *leaf_indices *leaves [root] tree_height num_openings i [root]
+                dup 14
+                dup 6
+                call {get_index}        // _ *leaf_indices *leaves [root] tree_height num_openings i [root] leaf_index
+                dup 14
+                dup 7
+                call {get_leaf}         // _ *leaf_indices *leaves [root] tree_height num_openings i [root] leaf_index [leaf]
+                dup 13
+                call {merkle_verify}    // _ *leaf_indices *leaves [root] tree_height num_openings i
+
+                push 1
+                add
+                recurse
+        )
+    }
+}
+
+impl Algorithm for VerifyLeafOpenings {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        self.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, &mut digests);
+    }
+
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        digests: &mut DigestCursor,
+    ) {
+        let tree_height = stack.pop().unwrap();
+        let root = (0..DIGEST_LENGTH)
+            .map(|_| stack.pop().unwrap())
+            .collect_vec();
+        let leaves = stack.pop().unwrap();
+        let leaf_indices = stack.pop().unwrap();
+
+        let list_length = |list| match self.list_type {
+            ListType::Safe => safe_list::safe_list_get_length(list, memory),
+            ListType::Unsafe => unsafe_list::unsafe_list_get_length(list, memory),
+        };
+        let num_openings = list_length(leaf_indices);
+        assert_eq!(num_openings, list_length(leaves));
+
+        for i in 0..num_openings {
+            let leaf_index = self
+                .list_type
+                .rust_shadowing_get(leaf_indices, i, memory, 1);
+            let leaf = self
+                .list_type
+                .rust_shadowing_get(leaves, i, memory, DIGEST_LENGTH);
+
+            stack.extend(root.iter().rev());
+            stack.push(leaf_index[0]);
+            stack.extend(leaf.into_iter().rev());
+            stack.push(tree_height);
+            MerkleVerify.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, digests);
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> AlgorithmInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (tree_height, num_openings) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (10, 40),
+            Some(BenchmarkCase::WorstCase) => (20, 80),
+            None => (rng.gen_range(0..12), rng.gen_range(0..20)),
+        };
+        self.initial_state(&mut rng, tree_height, num_openings)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<AlgorithmInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x1e; 32]);
+        vec![
+            self.initial_state(&mut rng, 0, 0),
+            self.initial_state(&mut rng, 0, 3),
+            self.initial_state(&mut rng, 5, 1),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(AlgorithmInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xfa; 32]);
+
+        let mut missing_leaf = self.initial_state(&mut rng, 5, 4);
+        let leaves = missing_leaf.stack[missing_leaf.stack.len() - 7];
+        let memory = &mut missing_leaf.nondeterminism.ram;
+        match self.list_type {
+            ListType::Safe => safe_list::safe_list_set_length(leaves, 3, memory),
+            ListType::Unsafe => unsafe_list::unsafe_list_set_length(leaves, 3, memory),
+        }
+
+        let mut index_out_of_range = self.initial_state(&mut rng, 5, 4);
+        let leaf_indices = index_out_of_range.stack[index_out_of_range.stack.len() - 8];
+        let out_of_range = vec![BFieldElement::new(1 << 5)];
+        self.list_type.rust_shadowing_set(
+            leaf_indices,
+            2,
+            out_of_range,
+            &mut index_out_of_range.nondeterminism.ram,
+        );
+
+        vec![
+            (missing_leaf, InstructionError::AssertionFailed),
+            (index_out_of_range, InstructionError::AssertionFailed),
+        ]
+    }
+}
+
+impl VerifyLeafOpenings {
+    fn initial_state(
+        &self,
+        rng: &mut StdRng,
+        tree_height: usize,
+        num_openings: usize,
+    ) -> AlgorithmInitialState {
+        let num_leaves = 1 << tree_height;
+        let all_leaves: Vec<Digest> = (0..num_leaves).map(|_| rng.gen()).collect();
+        let tree = <CpuParallel as MerkleTreeMaker<VmHasher>>::from_digests(&all_leaves);
+
+        let leaf_indices: Vec<u32> = (0..num_openings)
+            .map(|_| rng.gen_range(0..num_leaves as u32))
+            .collect();
+        let leaves = leaf_indices
+            .iter()
+            .map(|&i| all_leaves[i as usize])
+            .collect_vec();
+        let leaf_indices_usize = leaf_indices.iter().map(|&i| i as usize).collect_vec();
+        let authentication_structure = tree.get_authentication_structure(&leaf_indices_usize);
+        let digests = nondeterministic_digests(
+            tree_height,
+            &leaf_indices,
+            &leaves,
+            &authentication_structure,
+        )
+        .unwrap();
+
+        let mut memory = HashMap::new();
+        let leaf_indices_pointer = BFieldElement::new(rng.gen_range(0..1 << 20));
+        let leaves_pointer = BFieldElement::new(rng.gen_range(1 << 21..1 << 22));
+        self.insert_list(leaf_indices_pointer, leaf_indices, &mut memory);
+        self.insert_list(leaves_pointer, leaves, &mut memory);
+
+        let mut stack = empty_stack();
+        stack.push(leaf_indices_pointer);
+        stack.push(leaves_pointer);
+        push_encodable(&mut stack, &tree.get_root());
+        stack.push(BFieldElement::new(tree_height as u64));
+
+        AlgorithmInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default()
+                .with_digests(digests)
+                .with_ram(memory),
+        }
+    }
+
+    fn insert_list<T: BFieldCodec>(
+        &self,
+        list_pointer: BFieldElement,
+        elements: Vec<T>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        match self.list_type {
+            ListType::Safe => {
+                let capacity = elements.len() as u32;
+                safe_list::safe_list_insert(list_pointer, capacity, elements, memory)
+            }
+            ListType::Unsafe => unsafe_list::unsafe_list_insert(list_pointer, elements, memory),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::algorithm::ShadowedAlgorithm;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_leaf_openings_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            ShadowedAlgorithm::new(VerifyLeafOpenings { list_type }).test();
+        }
+    }
+
+    #[test]
+    fn reduplicated_paths_are_the_individual_authentication_paths() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let tree_height = 6;
+        let all_leaves: Vec<Digest> = (0..1 << tree_height).map(|_| rng.gen()).collect();
+        let tree = <CpuParallel as MerkleTreeMaker<VmHasher>>::from_digests(&all_leaves);
+
+        let leaf_indices = [3, 17, 18, 3, 60];
+        let leaves = leaf_indices.map(|i| all_leaves[i as usize]);
+        let authentication_structure =
+            tree.get_authentication_structure(&leaf_indices.map(|i| i as usize));
+        let digests = nondeterministic_digests(
+            tree_height,
+            &leaf_indices,
+            &leaves,
+            &authentication_structure,
+        )
+        .unwrap();
+
+        let individual_paths = leaf_indices
+            .iter()
+            .flat_map(|&i| tree.get_authentication_structure(&[i as usize]))
+            .collect_vec();
+        assert_eq!(individual_paths, digests);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::algorithm::ShadowedAlgorithm;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_leaf_openings_benchmark() {
+        ShadowedAlgorithm::new(VerifyLeafOpenings {
+            list_type: ListType::Unsafe,
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,580 @@
+use std::collections::HashMap;
+
+use num_traits::{One, Zero};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::x_field_element::{XFieldElement, EXTENSION_DEGREE};
+
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+use crate::{empty_stack, push_encodable};
+
+/// The number of segments the quotient codeword is split into, matching the length of
+/// [`ProofItem::OutOfDomainQuotientSegments`](triton_vm::proof_item::ProofItem).
+pub const NUM_QUOTIENT_SEGMENTS: usize = 4;
+
+/// Crash the VM unless the out-of-domain quotient segments are consistent with the
+/// constraints evaluated at the out-of-domain point `z`.
+///
+/// The constraint values and their weights are arrays of `XFieldElement`s, both holding
+/// the initial, consistency, transition, and terminal constraints, in that order. Each
+/// category's weighted sum is divided by its zerofier, where `n` is the padded height and
+/// `g` generates the trace domain:
+///
+/// | category    | zerofier                |
+/// |:------------|:------------------------|
+/// | initial     | `z - 1`                 |
+/// | consistency | `z^n - 1`               |
+/// | transition  | `(z^n - 1) / (z - g⁻¹)` |
+/// | terminal    | `z - g⁻¹`               |
+///
+/// The sum of these quotients must equal `Σ_i z^i·segment_i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyOutOfDomainQuotient {
+    pub num_initial_constraints: usize,
+    pub num_consistency_constraints: usize,
+    pub num_transition_constraints: usize,
+    pub num_terminal_constraints: usize,
+}
+
+impl VerifyOutOfDomainQuotient {
+    pub fn num_constraints(&self) -> usize {
+        self.num_initial_constraints
+            + self.num_consistency_constraints
+            + self.num_transition_constraints
+            + self.num_terminal_constraints
+    }
+
+    /// The offsets, in number of constraints, of the initial, consistency, transition, and
+    /// terminal constraints.
+    fn category_offsets(&self) -> [usize; 4] {
+        let consistency = self.num_initial_constraints;
+        let transition = consistency + self.num_consistency_constraints;
+        let terminal = transition + self.num_transition_constraints;
+        [0, consistency, transition, terminal]
+    }
+
+    /// The sum of the weighted constraints, each category divided by its zerofier.
+    pub fn quotient_value(
+        &self,
+        constraint_values: &[XFieldElement],
+        weights: &[XFieldElement],
+        out_of_domain_point: XFieldElement,
+        log2_padded_height: u32,
+        trace_domain_generator: BFieldElement,
+    ) -> XFieldElement {
+        let z = out_of_domain_point;
+        let z_pow_n = (0..log2_padded_height).fold(z, |power, _| power * power);
+        let one = XFieldElement::one();
+        let except_last_row = z - (BFieldElement::one() / trace_domain_generator).lift();
+
+        let initial_zerofier_inverse = one / (z - one);
+        let consistency_zerofier_inverse = one / (z_pow_n - one);
+        let transition_zerofier_inverse = except_last_row * consistency_zerofier_inverse;
+        let terminal_zerofier_inverse = one / except_last_row;
+        let zerofier_inverses = [
+            initial_zerofier_inverse,
+            consistency_zerofier_inverse,
+            transition_zerofier_inverse,
+            terminal_zerofier_inverse,
+        ];
+
+        let category_lengths = [
+            self.num_initial_constraints,
+            self.num_consistency_constraints,
+            self.num_transition_constraints,
+            self.num_terminal_constraints,
+        ];
+        let inner_product = |offset: usize, length: usize| {
+            constraint_values[offset..offset + length]
+                .iter()
+                .zip(&weights[offset..offset + length])
+                .map(|(&value, &weight)| value * weight)
+                .fold(XFieldElement::zero(), |acc, summand| acc + summand)
+        };
+
+        self.category_offsets()
+            .into_iter()
+            .zip(category_lengths)
+            .zip(zerofier_inverses)
+            .map(|((offset, length), zerofier_inverse)| {
+                inner_product(offset, length) * zerofier_inverse
+            })
+            .fold(XFieldElement::zero(), |acc, summand| acc + summand)
+    }
+
+    /// `Σ_i z^i·segment_i`
+    pub fn recombine_segments(
+        quotient_segments: &[XFieldElement; NUM_QUOTIENT_SEGMENTS],
+        out_of_domain_point: XFieldElement,
+    ) -> XFieldElement {
+        quotient_segments
+            .iter()
+            .rev()
+            .fold(XFieldElement::zero(), |acc, &segment| {
+                acc * out_of_domain_point + segment
+            })
+    }
+}
+
+impl BasicSnippet for VerifyOutOfDomainQuotient {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*quotient_segments".to_owned()),
+            (DataType::VoidPointer, "*weights".to_owned()),
+            (DataType::VoidPointer, "*constraint_values".to_owned()),
+            (DataType::Xfe, "out_of_domain_point".to_owned()),
+            (DataType::U32, "log2_padded_height".to_owned()),
+            (DataType::Bfe, "trace_domain_generator".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasm_recufier_verify_out_of_domain_quotient___{}_{}_{}_{}",
+            self.num_initial_constraints,
+            self.num_consistency_constraints,
+            self.num_transition_constraints,
+            self.num_terminal_constraints
+        )
+    }
+
+    fn writable_memory_regions(
+        &self,
+        _initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        Some(vec![])
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let inner_product = format!("{entrypoint}_inner_product");
+        let inner_product_loop = format!("{entrypoint}_inner_product_loop");
+        let square_repeatedly = format!("{entrypoint}_square_repeatedly");
+
+        let [initial_offset, consistency_offset, transition_offset, terminal_offset] = self
+            .category_offsets()
+            .map(|offset| offset * EXTENSION_DEGREE);
+
+        let xfe_size = EXTENSION_DEGREE;
+        let last_segment_offset = (NUM_QUOTIENT_SEGMENTS - 1) * xfe_size;
+        let horner_steps = (0..NUM_QUOTIENT_SEGMENTS - 1)
+            .rev()
+            .flat_map(|i| {
+                triton_asm!(
+                    dup 8
+                    dup 8
+                    dup 8
+                    xxmul                   // _ *qs *w *cv [z] [quotient] [v·z]
+                    dup 11
+                    push {i * xfe_size + xfe_size - 1}
+                    add
+                    read_mem {xfe_size}
+                    pop 1                   // _ *qs *w *cv [z] [quotient] [v·z] [segment_i]
+                    xxadd                   // _ *qs *w *cv [z] [quotient] [v']
+                )
+            })
+            .collect::<Vec<_>>();
+
+        triton_asm!(
+            // BEFORE: _ *quotient_segments *weights *constraint_values [z] log2_padded_height g
+            // AFTER:  _
+            {entrypoint}:
+                invert
+                push -1
+                mul                     // _ *qs *w *cv [z] k (-1/g)
+                dup 2
+                add                     // _ *qs *w *cv [z] k e_0
+                dup 4
+                dup 4
+                swap 1
+                swap 2                  // _ *qs *w *cv [z] k [e]
+                swap 1
+                swap 2
+                swap 3                  // _ *qs *w *cv [z] [e] k
+
+                dup 6
+                dup 6
+                dup 6
+                call {square_repeatedly}
+                                        // _ *qs *w *cv [z] [e] 0 [z^n]
+                swap 1
+                swap 2
+                swap 3
+                pop 1                   // _ *qs *w *cv [z] [e] [z^n]
+                push -1
+                add
+                xinvert                 // _ *qs *w *cv [z] [e] [1/(z^n - 1)]
+
+                // terminal constraints
+                dup 9
+                push {terminal_offset}
+                add
+                dup 11
+                push {terminal_offset}
+                add
+                push {self.num_terminal_constraints}
+                call {inner_product}    // _ *qs *w *cv [z] [e] [czi] [ip]
+                dup 8
+                dup 8
+                dup 8
+                xinvert
+                xxmul                   // _ *qs *w *cv [z] [e] [czi] [quotient]
+
+                // transition constraints
+                dup 12
+                push {transition_offset}
+                add
+                dup 14
+                push {transition_offset}
+                add
+                push {self.num_transition_constraints}
+                call {inner_product}    // _ *qs *w *cv [z] [e] [czi] [quotient] [ip]
+                dup 11
+                dup 11
+                dup 11
+                xxmul
+                dup 8
+                dup 8
+                dup 8
+                xxmul
+                xxadd                   // _ *qs *w *cv [z] [e] [czi] [quotient]
+
+                // consistency constraints
+                dup 12
+                push {consistency_offset}
+                add
+                dup 14
+                push {consistency_offset}
+                add
+                push {self.num_consistency_constraints}
+                call {inner_product}    // _ *qs *w *cv [z] [e] [czi] [quotient] [ip]
+                dup 8
+                dup 8
+                dup 8
+                xxmul
+                xxadd                   // _ *qs *w *cv [z] [e] [czi] [quotient]
+
+                // initial constraints
+                dup 12
+                push {initial_offset}
+                add
+                dup 14
+                push {initial_offset}
+                add
+                push {self.num_initial_constraints}
+                call {inner_product}    // _ *qs *w *cv [z] [e] [czi] [quotient] [ip]
+                dup 14
+                dup 14
+                dup 14
+                push -1
+                add
+                xinvert
+                xxmul
+                xxadd                   // _ *qs *w *cv [z] [e] [czi] [quotient]
+
+                swap 6
+                pop 1
+                swap 6
+                pop 1
+                swap 6
+                pop 1
+                pop 3                   // _ *qs *w *cv [z] [quotient]
+
+                // recombine segments with Horner's method
+                dup 8
+                push {last_segment_offset + xfe_size - 1}
+                add
+                read_mem {xfe_size}
+                pop 1                   // _ *qs *w *cv [z] [quotient] [segment_last]
+                {&horner_steps}         // _ *qs *w *cv [z] [quotient] [recombined]
+
+                dup 3
+                eq
+                assert
+                dup 3
+                eq
+                assert
+                dup 3
+                eq
+                assert                  // _ *qs *w *cv [z] [quotient]
+                pop 5
+                pop 4                   // _
+                return
+
+            // BEFORE: _ *a *b n
+            // AFTER:  _ [Σ a_i·b_i]
+            {inner_product}:
+                push 0
+                push 0
+                push 0
+                call {inner_product_loop}
+                                        // _ *a_end *b_end 0 [ip]
+                swap 3
+                pop 1
+                swap 3
+                pop 1
+                swap 3
+                pop 1                   // _ [ip]
+                return
+
+            // INVARIANT: _ *a *b num_elements_left [acc]
+            {inner_product_loop}:
+                dup 3
+                push 0
+                eq
+                skiz return
+
+                dup 5
+                push {xfe_size - 1}
+                add
+                read_mem {xfe_size}
+                pop 1                   // _ *a *b n [acc] [a_i]
+                dup 7
+                push {xfe_size - 1}
+                add
+                read_mem {xfe_size}
+                pop 1                   // _ *a *b n [acc] [a_i] [b_i]
+                xxmul
+                xxadd                   // _ *a *b n [acc']
+
+                swap 5
+                push {xfe_size}
+                add
+                swap 5
+                swap 4
+                push {xfe_size}
+                add
+                swap 4
+                swap 3
+                push -1
+                add
+                swap 3                  // _ (*a + 3) (*b + 3) (n - 1) [acc']
+                recurse
+
+            // INVARIANT: _ num_squarings_left [x]
+            {square_repeatedly}:
+                dup 3
+                push 0
+                eq
+                skiz return
+
+                dup 2
+                dup 2
+                dup 2
+                xxmul                   // _ k [x^2]
+                swap 3
+                push -1
+                add
+                swap 3                  // _ (k - 1) [x^2]
+                recurse
+        )
+    }
+}
+
+impl Function for VerifyOutOfDomainQuotient {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let trace_domain_generator = stack.pop().unwrap();
+        let log2_padded_height: u32 = stack.pop().unwrap().value().try_into().unwrap();
+        let c_0 = stack.pop().unwrap();
+        let c_1 = stack.pop().unwrap();
+        let c_2 = stack.pop().unwrap();
+        let out_of_domain_point = XFieldElement::new([c_0, c_1, c_2]);
+        let constraint_values = stack.pop().unwrap();
+        let weights = stack.pop().unwrap();
+        let quotient_segments = stack.pop().unwrap();
+
+        let num_constraints = self.num_constraints();
+        let constraint_values = read_xfes(memory, constraint_values, num_constraints);
+        let weights = read_xfes(memory, weights, num_constraints);
+        let quotient_segments = read_xfes(memory, quotient_segments, NUM_QUOTIENT_SEGMENTS);
+
+        let quotient_value = self.quotient_value(
+            &constraint_values,
+            &weights,
+            out_of_domain_point,
+            log2_padded_height,
+            trace_domain_generator,
+        );
+        let recombined =
+            Self::recombine_segments(&quotient_segments.try_into().unwrap(), out_of_domain_point);
+        assert_eq!(quotient_value, recombined);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let log2_padded_height = rng.gen_range(0..20);
+        self.initial_state(&mut rng, log2_padded_height, true)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x0d; 32]);
+        vec![
+            self.initial_state(&mut rng, 0, true),
+            self.initial_state(&mut rng, 1, true),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xf0; 32]);
+        vec![(
+            self.initial_state(&mut rng, 10, false),
+            InstructionError::AssertionFailed,
+        )]
+    }
+}
+
+impl VerifyOutOfDomainQuotient {
+    /// Random constraint values and weights, and quotient segments that are consistent
+    /// with them if and only if `consistent` is set.
+    fn initial_state(
+        &self,
+        rng: &mut StdRng,
+        log2_padded_height: u32,
+        consistent: bool,
+    ) -> FunctionInitialState {
+        let num_constraints = self.num_constraints();
+        let constraint_values: Vec<XFieldElement> =
+            (0..num_constraints).map(|_| rng.gen()).collect();
+        let weights: Vec<XFieldElement> = (0..num_constraints).map(|_| rng.gen()).collect();
+        let out_of_domain_point: XFieldElement = rng.gen();
+        let trace_domain_generator =
+            BFieldElement::primitive_root_of_unity(1 << log2_padded_height).unwrap();
+
+        let quotient_value = self.quotient_value(
+            &constraint_values,
+            &weights,
+            out_of_domain_point,
+            log2_padded_height,
+            trace_domain_generator,
+        );
+
+        // choose all but the last segment at random, then solve for the last one
+        let mut quotient_segments: [XFieldElement; NUM_QUOTIENT_SEGMENTS] = rng.gen();
+        quotient_segments[NUM_QUOTIENT_SEGMENTS - 1] = XFieldElement::zero();
+        let remainder =
+            quotient_value - Self::recombine_segments(&quotient_segments, out_of_domain_point);
+        let last_power = (1..NUM_QUOTIENT_SEGMENTS)
+            .fold(XFieldElement::one(), |power, _| power * out_of_domain_point);
+        quotient_segments[NUM_QUOTIENT_SEGMENTS - 1] = remainder / last_power;
+        if !consistent {
+            quotient_segments[0] += XFieldElement::one();
+        }
+
+        let constraint_values_pointer = BFieldElement::new(rng.gen_range(0..1 << 20));
+        let weights_pointer = BFieldElement::new(rng.gen_range(1 << 21..1 << 22));
+        let quotient_segments_pointer = BFieldElement::new(rng.gen_range(1 << 23..1 << 24));
+
+        let mut memory = HashMap::new();
+        write_xfes(&mut memory, constraint_values_pointer, &constraint_values);
+        write_xfes(&mut memory, weights_pointer, &weights);
+        write_xfes(&mut memory, quotient_segments_pointer, &quotient_segments);
+
+        let mut stack = empty_stack();
+        stack.push(quotient_segments_pointer);
+        stack.push(weights_pointer);
+        stack.push(constraint_values_pointer);
+        push_encodable(&mut stack, &out_of_domain_point);
+        stack.push(BFieldElement::new(log2_padded_height as u64));
+        stack.push(trace_domain_generator);
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+fn read_xfes(
+    memory: &HashMap<BFieldElement, BFieldElement>,
+    pointer: BFieldElement,
+    num_elements: usize,
+) -> Vec<XFieldElement> {
+    (0..num_elements * EXTENSION_DEGREE)
+        .map(|i| pointer + BFieldElement::new(i as u64))
+        .map(|address| memory.get(&address).copied().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .chunks(EXTENSION_DEGREE)
+        .map(|coefficients| XFieldElement::new(coefficients.try_into().unwrap()))
+        .collect()
+}
+
+fn write_xfes(
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+    pointer: BFieldElement,
+    elements: &[XFieldElement],
+) {
+    let words = elements.iter().flat_map(|element| element.coefficients);
+    for (i, word) in words.enumerate() {
+        memory.insert(pointer + BFieldElement::new(i as u64), word);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_out_of_domain_quotient_test() {
+        for (initial, consistency, transition, terminal) in
+            [(0, 0, 0, 0), (1, 1, 1, 1), (3, 0, 5, 2), (10, 20, 30, 4)]
+        {
+            ShadowedFunction::new(VerifyOutOfDomainQuotient {
+                num_initial_constraints: initial,
+                num_consistency_constraints: consistency,
+                num_transition_constraints: transition,
+                num_terminal_constraints: terminal,
+            })
+            .test();
+        }
+    }
+
+    #[test]
+    fn recombining_segments_evaluates_segment_polynomial() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let segments: [XFieldElement; NUM_QUOTIENT_SEGMENTS] = rng.gen();
+        let z: XFieldElement = rng.gen();
+        let expected =
+            segments[0] + z * segments[1] + z * z * segments[2] + z * z * z * segments[3];
+        assert_eq!(
+            expected,
+            VerifyOutOfDomainQuotient::recombine_segments(&segments, z)
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_out_of_domain_quotient_benchmark() {
+        ShadowedFunction::new(VerifyOutOfDomainQuotient {
+            num_initial_constraints: 80,
+            num_consistency_constraints: 80,
+            num_transition_constraints: 300,
+            num_terminal_constraints: 20,
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement, NonDeterminism};
+use twenty_first::util_types::merkle_tree::{CpuParallel, MerkleTree};
+use twenty_first::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+use crate::data_type::DataType;
+use crate::library::Library;
+use crate::list::ListType;
+use crate::recufier::hash_table_rows::HashTableRows;
+use crate::recufier::verify_leaf_openings::{nondeterministic_digests, VerifyLeafOpenings};
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::snippet_bencher::BenchmarkCase;
+use crate::traits::algorithm::{Algorithm, AlgorithmInitialState, DigestCursor};
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::function::Function;
+use crate::{empty_stack, push_encodable, Digest, VmHasher, DIGEST_LENGTH};
+
+/// Verify that the revealed rows of a committed table are the rows at the given indices,
+/// *i.e.*, that the leaf of the `i`th row lives in the table's Merkle tree at the `i`th
+/// index. The rows are hashed with [`HashTableRows`] and the openings are verified with
+/// [`VerifyLeafOpenings`], which crashes the VM if the number of rows and indices differ.
+///
+/// Calling this snippet for every committed table with the same list of indices ties the
+/// tables' revealed rows together: all of them are rows at the same indices.
+#[derive(Clone, Debug)]
+pub struct VerifyTableRows {
+    pub list_type: ListType,
+}
+
+impl BasicSnippet for VerifyTableRows {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (
+                DataType::List(Box::new(DataType::U32)),
+                "*row_indices".to_string(),
+            ),
+            (DataType::VoidPointer, "*rows".to_string()),
+            (DataType::Digest, "root".to_string()),
+            (DataType::U32, "tree_height".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasm_recufier_verify_table_rows_{}", self.list_type)
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let hash_table_rows = library.import(Box::new(HashTableRows {
+            list_type: self.list_type.clone(),
+        }));
+        let verify_leaf_openings = library.import(Box::new(VerifyLeafOpenings {
+            list_type: self.list_type.clone(),
+        }));
+
+        triton_asm!(
+            // BEFORE: _ *row_indices *rows [root] tree_height
+            // AFTER:  _
+            {entrypoint}:
+                dup 6
+                call {hash_table_rows}      // _ *row_indices *rows [root] tree_height *leaves
+                swap 7
+                pop 1                       // _ *row_indices *leaves [root] tree_height
+                call {verify_leaf_openings} // _
+                return
+        )
+    }
+}
+
+impl Algorithm for VerifyTableRows {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+    ) {
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        self.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, &mut digests);
+    }
+
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        digests: &mut DigestCursor,
+    ) {
+        let tree_height = stack.pop().unwrap();
+        let root = (0..DIGEST_LENGTH)
+            .map(|_| stack.pop().unwrap())
+            .collect_vec();
+        let rows = stack.pop().unwrap();
+
+        stack.push(rows);
+        let hash_table_rows = HashTableRows {
+            list_type: self.list_type.clone(),
+        };
+        hash_table_rows.rust_shadow(stack, memory);
+
+        stack.extend(root.into_iter().rev());
+        stack.push(tree_height);
+        let verify_leaf_openings = VerifyLeafOpenings {
+            list_type: self.list_type.clone(),
+        };
+        verify_leaf_openings.rust_shadow_with_digest_cursor(stack, memory, nondeterminism, digests);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> AlgorithmInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let (tree_height, num_rows, num_columns) = match bench_case {
+            Some(BenchmarkCase::CommonCase) => (8, 40, 100),
+            Some(BenchmarkCase::WorstCase) => (10, 80, 400),
+            None => (
+                rng.gen_range(0..10),
+                rng.gen_range(0..20),
+                rng.gen_range(1..30),
+            ),
+        };
+        let table = Table::new(&mut rng, tree_height, num_columns);
+        let row_indices = (0..num_rows)
+            .map(|_| rng.gen_range(0..1 << tree_height))
+            .collect_vec();
+        self.initial_state(&table, row_indices)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<AlgorithmInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0x7a; 32]);
+        let single_row_table = Table::new(&mut rng, 0, 5);
+        let table = Table::new(&mut rng, 4, 5);
+        vec![
+            self.initial_state(&single_row_table, vec![]),
+            self.initial_state(&single_row_table, vec![0, 0]),
+            self.initial_state(&table, vec![3, 15, 3, 0]),
+        ]
+    }
+
+    fn failing_initial_states(&self) -> Vec<(AlgorithmInitialState, InstructionError)> {
+        let mut rng: StdRng = SeedableRng::from_seed([0xfb; 32]);
+        let table = Table::new(&mut rng, 4, 5);
+
+        let mut missing_row = self.initial_state(&table, vec![1, 2, 3]);
+        let row_indices = missing_row.stack[missing_row.stack.len() - 8];
+        let memory = &mut missing_row.nondeterminism.ram;
+        match self.list_type {
+            ListType::Safe => safe_list::safe_list_set_length(row_indices, 4, memory),
+            ListType::Unsafe => unsafe_list::unsafe_list_set_length(row_indices, 4, memory),
+        }
+
+        vec![(missing_row, InstructionError::AssertionFailed)]
+    }
+}
+
+/// A table of random rows, committed to through a Merkle tree.
+struct Table {
+    tree_height: usize,
+    rows: Vec<Vec<BFieldElement>>,
+    tree: MerkleTree<VmHasher>,
+}
+
+impl Table {
+    fn new(rng: &mut StdRng, tree_height: usize, num_columns: usize) -> Self {
+        let rows = (0..1 << tree_height)
+            .map(|_| (0..num_columns).map(|_| rng.gen()).collect())
+            .collect_vec();
+        let leaves = HashTableRows::leaves(&rows);
+        let tree = <CpuParallel as MerkleTreeMaker<VmHasher>>::from_digests(&leaves);
+
+        Self {
+            tree_height,
+            rows,
+            tree,
+        }
+    }
+
+    fn root(&self) -> Digest {
+        self.tree.get_root()
+    }
+}
+
+impl VerifyTableRows {
+    fn initial_state(&self, table: &Table, row_indices: Vec<u32>) -> AlgorithmInitialState {
+        let revealed_rows = row_indices
+            .iter()
+            .map(|&i| table.rows[i as usize].clone())
+            .collect_vec();
+        let leaves = HashTableRows::leaves(&revealed_rows);
+        let row_indices_usize = row_indices.iter().map(|&i| i as usize).collect_vec();
+        let authentication_structure = table.tree.get_authentication_structure(&row_indices_usize);
+        let digests = nondeterministic_digests(
+            table.tree_height,
+            &row_indices,
+            &leaves,
+            &authentication_structure,
+        )
+        .unwrap();
+
+        let mut memory = HashMap::new();
+        let row_indices_pointer = BFieldElement::new(1 << 20);
+        let rows_pointer = BFieldElement::new(1 << 22);
+        match self.list_type {
+            ListType::Safe => {
+                let capacity = row_indices.len() as u32;
+                safe_list::safe_list_insert(row_indices_pointer, capacity, row_indices, &mut memory)
+            }
+            ListType::Unsafe => {
+                unsafe_list::unsafe_list_insert(row_indices_pointer, row_indices, &mut memory)
+            }
+        }
+        let encoded_rows = HashTableRows::encode_rows(&revealed_rows, 1);
+        for (i, word) in encoded_rows.into_iter().enumerate() {
+            memory.insert(rows_pointer + BFieldElement::new(i as u64), word);
+        }
+
+        let mut stack = empty_stack();
+        stack.push(row_indices_pointer);
+        stack.push(rows_pointer);
+        push_encodable(&mut stack, &table.root());
+        stack.push(BFieldElement::new(table.tree_height as u64));
+
+        AlgorithmInitialState {
+            stack,
+            nondeterminism: NonDeterminism::default()
+                .with_digests(digests)
+                .with_ram(memory),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::rust_shadow_and_tasm_fail;
+    use crate::traits::algorithm::ShadowedAlgorithm;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_table_rows_test() {
+        for list_type in [ListType::Safe, ListType::Unsafe] {
+            ShadowedAlgorithm::new(VerifyTableRows { list_type }).test();
+        }
+    }
+
+    #[test]
+    fn tampered_row_is_rejected() {
+        let mut rng: StdRng = SeedableRng::from_seed([0xfc; 32]);
+        let table = Table::new(&mut rng, 4, 5);
+        let snippet = VerifyTableRows {
+            list_type: ListType::Unsafe,
+        };
+        let mut initial_state = snippet.initial_state(&table, vec![1, 2, 3]);
+
+        // the first element of the second row, behind the number of rows and the first row
+        let rows = initial_state.stack[initial_state.stack.len() - 7];
+        let tampered_word = rows + BFieldElement::new(1 + 7 + 2);
+        initial_state
+            .nondeterminism
+            .ram
+            .get_mut(&tampered_word)
+            .unwrap()
+            .increment();
+
+        let both_fail = rust_shadow_and_tasm_fail(
+            &ShadowedAlgorithm::new(snippet),
+            &initial_state.stack,
+            &[],
+            &initial_state.nondeterminism,
+            &None,
+            0,
+        );
+        assert!(both_fail);
+    }
+
+    #[test]
+    fn rows_at_different_indices_are_rejected() {
+        let mut rng: StdRng = SeedableRng::from_seed([0xfd; 32]);
+        let table = Table::new(&mut rng, 4, 5);
+        let snippet = VerifyTableRows {
+            list_type: ListType::Unsafe,
+        };
+        let mut initial_state = snippet.initial_state(&table, vec![1, 2, 3]);
+
+        let row_indices = initial_state.stack[initial_state.stack.len() - 8];
+        let memory = &mut initial_state.nondeterminism.ram;
+        unsafe_list::unsafe_list_set(row_indices, 0, vec![BFieldElement::new(4)], memory);
+
+        let both_fail = rust_shadow_and_tasm_fail(
+            &ShadowedAlgorithm::new(snippet),
+            &initial_state.stack,
+            &[],
+            &initial_state.nondeterminism,
+            &None,
+            0,
+        );
+        assert!(both_fail);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::algorithm::ShadowedAlgorithm;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn verify_table_rows_benchmark() {
+        ShadowedAlgorithm::new(VerifyTableRows {
+            list_type: ListType::Unsafe,
+        })
+        .bench();
+    }
+}
@@ -1,11 +1,17 @@
+use std::cell::Cell;
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
 use twenty_first::shared_math::b_field_element::BFieldElement;
 
 use crate::{
     empty_stack,
-    memory::dyn_malloc::{DynMalloc, DYN_MALLOC_ADDRESS},
-    traits::deprecated_snippet::DeprecatedSnippet,
+    memory::{
+        dyn_free::DynFree,
+        dyn_malloc::{DynMalloc, DYN_MALLOC_ADDRESS},
+        dyn_malloc_free_list::DynMallocFreeList,
+    },
+    traits::{deprecated_snippet::DeprecatedSnippet, function::Function},
 };
 
 // TODO: DELETE ME, OR FIX ME
@@ -32,3 +38,115 @@ pub fn dynamic_allocator(
     DynMalloc.rust_shadowing(&mut init_stack, vec![], vec![], memory);
     init_stack.pop().unwrap()
 }
+
+/// Allocate `size_in_words` words, reusing a freed block of exactly that size if there is
+/// one. Mirrors [`DynMallocFreeList`].
+pub fn dynamic_allocator_reusing_freed_memory(
+    size_in_words: usize,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) -> BFieldElement {
+    let mut stack = [
+        empty_stack(),
+        vec![BFieldElement::new(size_in_words as u64)],
+    ]
+    .concat();
+    DynMallocFreeList.rust_shadow(&mut stack, memory);
+    stack.pop().unwrap()
+}
+
+/// Release the block of `size_in_words` words at `address` for reuse. Mirrors [`DynFree`].
+pub fn dynamic_free(
+    address: BFieldElement,
+    size_in_words: usize,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) {
+    let mut stack = [
+        empty_stack(),
+        vec![address, BFieldElement::new(size_in_words as u64)],
+    ]
+    .concat();
+    DynFree.rust_shadow(&mut stack, memory);
+}
+
+/// Statistics about the dynamic memory allocations that Rust shadows make while
+/// [collecting](with_allocation_statistics) them. Since the size of the RAM table is
+/// part of the cost of proving, this shows which snippets drive memory growth.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocationStatistics {
+    /// The number of allocations, including those that reuse freed memory.
+    pub num_allocations: usize,
+
+    /// The sum of the sizes of all allocations, in words.
+    pub total_words_allocated: usize,
+
+    /// The number of words from the lowest to the highest dynamically allocated address.
+    pub peak_dynamic_memory: usize,
+}
+
+/// The statistics collected so far, together with the range of allocated addresses, if
+/// statistics are being collected on this thread.
+#[derive(Debug, Clone, Copy, Default)]
+struct AllocationRecord {
+    statistics: AllocationStatistics,
+    allocated_range: Option<(u64, u64)>,
+}
+
+thread_local! {
+    static ALLOCATION_RECORD: Cell<Option<AllocationRecord>> = const { Cell::new(None) };
+}
+
+/// Run `f` and return its result together with the statistics of all dynamic allocations
+/// that Rust shadows make on this thread in the meantime.
+pub fn with_allocation_statistics<R>(f: impl FnOnce() -> R) -> (R, AllocationStatistics) {
+    let enclosing_record = ALLOCATION_RECORD.replace(Some(AllocationRecord::default()));
+    let result = f();
+    let record = ALLOCATION_RECORD.replace(enclosing_record).unwrap();
+
+    (result, record.statistics)
+}
+
+/// Record an allocation of `size_in_words` words at `address`, in case statistics are
+/// [being collected](with_allocation_statistics). Rust shadows of allocators call this.
+pub fn record_allocation(address: BFieldElement, size_in_words: usize) {
+    let Some(mut record) = ALLOCATION_RECORD.get() else {
+        return;
+    };
+
+    let start = address.value();
+    let end = start + size_in_words as u64;
+    let (lowest, highest) = record.allocated_range.unwrap_or((start, end));
+    let (lowest, highest) = (lowest.min(start), highest.max(end));
+    record.allocated_range = Some((lowest, highest));
+
+    let statistics = &mut record.statistics;
+    statistics.num_allocations += 1;
+    statistics.total_words_allocated += size_in_words;
+    statistics.peak_dynamic_memory = (highest - lowest) as usize;
+    ALLOCATION_RECORD.set(Some(record));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocations_are_only_recorded_while_collecting_statistics() {
+        let mut memory = HashMap::new();
+        dynamic_allocator(100, &mut memory);
+
+        let ((), statistics) = with_allocation_statistics(|| {
+            let block = dynamic_allocator(5, &mut memory);
+            dynamic_allocator(7, &mut memory);
+            dynamic_free(block, 5, &mut memory);
+            dynamic_allocator_reusing_freed_memory(5, &mut memory);
+        });
+        dynamic_allocator(100, &mut memory);
+
+        let expected_statistics = AllocationStatistics {
+            num_allocations: 3,
+            total_words_allocated: 17,
+            peak_dynamic_memory: 12,
+        };
+        assert_eq!(expected_statistics, statistics);
+    }
+}
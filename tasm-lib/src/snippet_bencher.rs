@@ -1,9 +1,18 @@
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_json::to_writer_pretty;
 use std::fs::{create_dir_all, File};
 use std::path::{Path, PathBuf};
 
+use triton_vm::{BFieldElement, NonDeterminism};
+
+use crate::linker::{cycle_breakdown, link_for_isolated_run_with_source_map, SnippetCycleCount};
+use crate::rust_shadowing_helper_functions::dyn_malloc::{
+    with_allocation_statistics, AllocationStatistics,
+};
 use crate::traits::deprecated_snippet::DeprecatedSnippet;
+use crate::traits::rust_shadow::RustShadow;
+use crate::VmHasherState;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BenchmarkResult {
@@ -12,15 +21,28 @@ pub struct BenchmarkResult {
     pub hash_table_height: usize,
     pub u32_table_height: usize,
     pub case: BenchmarkCase,
+
+    /// The dynamic allocations the Rust shadow makes, as a proxy for the snippet's memory
+    /// use. Absent from benchmarks stored before these statistics were collected.
+    #[serde(default)]
+    pub allocation_statistics: AllocationStatistics,
+
+    /// The clock cycles spent in each snippet of the linked program. Only recorded on
+    /// request, see [`ENV_VARIABLE_CYCLE_BREAKDOWN`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle_breakdown: Option<Vec<SnippetCycleCount>>,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// Setting this environment variable makes benchmarks of snippets additionally record how
+/// many clock cycles are spent in each imported snippet.
+pub const ENV_VARIABLE_CYCLE_BREAKDOWN: &str = "CYCLE_BREAKDOWN";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BenchmarkCase {
     CommonCase,
     WorstCase,
 }
 
-#[allow(dead_code)]
 pub fn benchmark_snippet_deprecated<T: DeprecatedSnippet>(snippet: T) -> Vec<BenchmarkResult> {
     let mut benchmarks = Vec::with_capacity(2);
 
@@ -28,6 +50,14 @@ pub fn benchmark_snippet_deprecated<T: DeprecatedSnippet>(snippet: T) -> Vec<Ben
         (BenchmarkCase::CommonCase, snippet.common_case_input_state()),
         (BenchmarkCase::WorstCase, snippet.worst_case_input_state()),
     ] {
+        let mut stack = execution_state.stack.clone();
+        let mut memory = execution_state.nondeterminism.ram.clone();
+        let std_in = execution_state.std_in.clone();
+        let secret_in = execution_state.nondeterminism.individual_tokens.clone();
+        let ((), allocation_statistics) = with_allocation_statistics(|| {
+            snippet.rust_shadowing(&mut stack, std_in, secret_in, &mut memory)
+        });
+
         let execution_result = snippet
             .link_and_run_tasm_from_state_for_bench(&mut execution_state)
             .unwrap();
@@ -37,6 +67,8 @@ pub fn benchmark_snippet_deprecated<T: DeprecatedSnippet>(snippet: T) -> Vec<Ben
             hash_table_height: execution_result.hash_table_height,
             u32_table_height: execution_result.u32_table_height,
             case,
+            allocation_statistics,
+            cycle_breakdown: None,
         };
         benchmarks.push(benchmark);
     }
@@ -67,3 +99,180 @@ pub fn write_benchmarks(benchmarks: Vec<BenchmarkResult>) {
 pub fn bench_and_write<T: DeprecatedSnippet>(snippet: T) {
     write_benchmarks(benchmark_snippet_deprecated(snippet));
 }
+
+/// Run the Rust shadow of the given snippet in the given initial state and return the
+/// statistics of the dynamic allocations it makes.
+pub fn rust_shadow_allocation_statistics<T: RustShadow + ?Sized>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+) -> AllocationStatistics {
+    let mut stack = stack.to_vec();
+    let mut memory = nondeterminism.ram.clone();
+    let mut sponge_state = sponge_state.clone();
+    let (_, allocation_statistics) = with_allocation_statistics(|| {
+        shadowed_snippet.rust_shadow_wrapper(
+            stdin,
+            nondeterminism,
+            &mut stack,
+            &mut memory,
+            &mut sponge_state,
+        )
+    });
+
+    allocation_statistics
+}
+
+/// If the environment variable [`ENV_VARIABLE_CYCLE_BREAKDOWN`] is set, return the
+/// [`snippet_cycle_breakdown`] of the given snippet in the given initial state. Return
+/// `None` otherwise.
+pub fn cycle_breakdown_if_requested<T: RustShadow + ?Sized>(
+    shadowed_snippet: &T,
+    words_statically_allocated: usize,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+) -> Option<Vec<SnippetCycleCount>> {
+    std::env::var(ENV_VARIABLE_CYCLE_BREAKDOWN).ok()?;
+    let breakdown = snippet_cycle_breakdown(
+        shadowed_snippet,
+        words_statically_allocated,
+        stack,
+        stdin,
+        nondeterminism,
+        sponge_state,
+    );
+
+    Some(breakdown)
+}
+
+/// Run the given snippet in the given initial state and return how many clock cycles are
+/// spent in it and in each snippet it imports.
+pub fn snippet_cycle_breakdown<T: RustShadow + ?Sized>(
+    shadowed_snippet: &T,
+    words_statically_allocated: usize,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+) -> Vec<SnippetCycleCount> {
+    let (code, source_map) =
+        link_for_isolated_run_with_source_map(shadowed_snippet.inner(), words_statically_allocated);
+    cycle_breakdown(
+        &code,
+        &source_map,
+        stack,
+        stdin.to_vec(),
+        nondeterminism.to_owned(),
+        sponge_state.to_owned(),
+    )
+    .unwrap()
+}
+
+/// The benchmark results stored in this crate for the snippet with the given name, if any.
+pub fn stored_benchmarks(name: &str) -> Option<Vec<BenchmarkResult>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("benchmarks")
+        .join(Path::new(name).with_extension("json"));
+    let file = File::open(path).ok()?;
+    let benchmarks = serde_json::from_reader(file).expect("stored benchmarks must be valid");
+    Some(benchmarks)
+}
+
+/// Re-run the benchmark of the given snippet and assert that, in every benchmark case, neither
+/// the clock cycle count nor any table height exceeds the [stored](stored_benchmarks) result
+/// by more than the relative `tolerance`. For example, a tolerance of `0.05` accepts an
+/// increase of up to 5%.
+pub fn assert_no_regression<T: RustShadow>(shadowed_snippet: &T, tolerance: f64) {
+    let name = shadowed_snippet.inner().borrow().entrypoint();
+    let Some(stored) = stored_benchmarks(&name) else {
+        panic!("No benchmark is stored for snippet `{name}`");
+    };
+
+    let mut regressions = vec![];
+    for benchmark in shadowed_snippet.benchmark() {
+        let case = benchmark.case;
+        let Some(reference) = stored.iter().find(|stored| stored.case == case) else {
+            panic!("No benchmark is stored for snippet `{name}` in case {case:?}");
+        };
+
+        for (metric, reference, measured) in [
+            (
+                "clock cycle count",
+                reference.clock_cycle_count,
+                benchmark.clock_cycle_count,
+            ),
+            (
+                "hash table height",
+                reference.hash_table_height,
+                benchmark.hash_table_height,
+            ),
+            (
+                "u32 table height",
+                reference.u32_table_height,
+                benchmark.u32_table_height,
+            ),
+        ] {
+            if measured as f64 > reference as f64 * (1.0 + tolerance) {
+                regressions.push(format!("{case:?}: {metric} {reference} -> {measured}"));
+            }
+        }
+    }
+
+    assert!(
+        regressions.is_empty(),
+        "Performance of snippet `{name}` regressed beyond tolerance {tolerance}:\n{}",
+        regressions.iter().join("\n")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arithmetic::u32::safepow::Safepow;
+    use crate::library::Library;
+    use crate::mmr::bag_peaks::BagPeaks;
+    use crate::traits::basic_snippet::BasicSnippet;
+    use crate::traits::closure::ShadowedClosure;
+    use crate::traits::function::{Function, FunctionInitialState, ShadowedFunction};
+
+    use super::*;
+
+    #[test]
+    fn stored_benchmarks_can_be_loaded() {
+        let benchmarks = stored_benchmarks("tasm_arithmetic_u32_safepow").unwrap();
+        assert_eq!(2, benchmarks.len());
+        assert!(stored_benchmarks("tasm_no_such_snippet").is_none());
+    }
+
+    #[test]
+    fn unchanged_snippet_does_not_regress() {
+        assert_no_regression(&ShadowedClosure::new(Safepow), 0.1);
+    }
+
+    #[test]
+    fn cycles_of_composite_snippet_are_broken_down_per_import() {
+        let FunctionInitialState { stack, memory } =
+            BagPeaks.pseudorandom_initial_state([0; 32], Some(BenchmarkCase::CommonCase));
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let shadowed = ShadowedFunction::new(BagPeaks);
+        let breakdown = snippet_cycle_breakdown(&shadowed, 1, &stack, &[], &nondeterminism, &None);
+
+        let mut library = Library::new();
+        BagPeaks.code(&mut library);
+        let imports = library.get_all_snippet_names();
+        assert!(!imports.is_empty());
+        for snippet in imports.into_iter().chain([BagPeaks.entrypoint()]) {
+            let count = breakdown.iter().find(|count| count.snippet == snippet);
+            assert!(count.is_some_and(|count| count.self_cycles > 0));
+        }
+    }
+
+    #[should_panic(expected = "Performance of snippet `tasm_arithmetic_u32_safepow` regressed")]
+    #[test]
+    fn regression_beyond_tolerance_is_detected() {
+        assert_no_regression(&ShadowedClosure::new(Safepow), -0.5);
+    }
+}
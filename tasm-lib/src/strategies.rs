@@ -0,0 +1,207 @@
+//! [Proptest](proptest) strategies for the initial states of snippets. Requires feature
+//! `proptest`.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use triton_vm::{BFieldElement, NonDeterminism};
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use crate::data_type::DataType;
+use crate::list::ListType;
+use crate::memory::encode_to_memory;
+use crate::rust_shadowing_helper_functions::{safe_list, unsafe_list};
+use crate::traits::function::FunctionInitialState;
+use crate::traits::procedure::ProcedureInitialState;
+use crate::{empty_stack, DIGEST_LENGTH};
+
+/// Addresses of objects placed in memory are drawn from this range, which lies in
+/// nondeterministically initialized memory.
+const OBJECT_ADDRESSES: Range<u64> = 0..1 << 31;
+
+pub fn arb_bfe() -> impl Strategy<Value = BFieldElement> {
+    (0..=BFieldElement::MAX).prop_map(BFieldElement::new)
+}
+
+fn arb_u32_words(num_words: usize) -> BoxedStrategy<Vec<BFieldElement>> {
+    vec(any::<u32>(), num_words)
+        .prop_map(|words| {
+            words
+                .into_iter()
+                .map(|word| BFieldElement::new(word as u64))
+                .collect()
+        })
+        .boxed()
+}
+
+/// A value of the given data type, as the words it occupies on the stack. Lists are
+/// represented by their pointer.
+pub fn arb_stack_value(data_type: &DataType) -> BoxedStrategy<Vec<BFieldElement>> {
+    match data_type {
+        DataType::Bool => any::<bool>()
+            .prop_map(|b| vec![BFieldElement::new(b as u64)])
+            .boxed(),
+        DataType::U32 => arb_u32_words(1),
+        DataType::U64 => arb_u32_words(2),
+        DataType::U128 => arb_u32_words(4),
+        DataType::Bfe | DataType::VoidPointer | DataType::List(_) => {
+            arb_bfe().prop_map(|bfe| vec![bfe]).boxed()
+        }
+        DataType::Xfe => vec(arb_bfe(), 3).boxed(),
+        DataType::Digest => vec(arb_bfe(), DIGEST_LENGTH).boxed(),
+        DataType::Tuple(data_types) => data_types
+            .iter()
+            .map(arb_stack_value)
+            .collect::<Vec<_>>()
+            .prop_map(|values| values.concat())
+            .boxed(),
+    }
+}
+
+/// An initial stack holding one value for each of the given data types, the first one
+/// deepest in the stack.
+pub fn arb_stack(data_types: &[DataType]) -> BoxedStrategy<Vec<BFieldElement>> {
+    data_types
+        .iter()
+        .map(arb_stack_value)
+        .collect::<Vec<_>>()
+        .prop_map(|values| [empty_stack(), values.concat()].concat())
+        .boxed()
+}
+
+/// A well-formed list of the given list type and element type in memory, together with its
+/// pointer. A safe list's capacity matches its length.
+pub fn arb_list_in_memory(
+    list_type: ListType,
+    element_type: DataType,
+    length: Range<usize>,
+) -> BoxedStrategy<(BFieldElement, HashMap<BFieldElement, BFieldElement>)> {
+    let element_size = element_type.stack_size();
+    (
+        OBJECT_ADDRESSES,
+        vec(arb_stack_value(&element_type), length),
+    )
+        .prop_map(move |(list_pointer, elements)| {
+            let list_pointer = BFieldElement::new(list_pointer);
+            let mut memory = HashMap::new();
+            match list_type {
+                ListType::Safe => {
+                    let capacity = elements.len() as u32;
+                    safe_list::safe_list_new(list_pointer, capacity, &mut memory);
+                    for element in elements {
+                        safe_list::safe_list_push(list_pointer, element, &mut memory, element_size);
+                    }
+                }
+                ListType::Unsafe => {
+                    unsafe_list::unsafe_list_new(list_pointer, &mut memory);
+                    for element in elements {
+                        unsafe_list::unsafe_list_push(
+                            list_pointer,
+                            element,
+                            &mut memory,
+                            element_size,
+                        );
+                    }
+                }
+            }
+            (list_pointer, memory)
+        })
+        .boxed()
+}
+
+/// An object drawn from the given strategy, encoded in memory, together with its pointer.
+pub fn arb_object_in_memory<T: BFieldCodec + std::fmt::Debug>(
+    object: impl Strategy<Value = T>,
+) -> impl Strategy<Value = (BFieldElement, HashMap<BFieldElement, BFieldElement>)> {
+    (OBJECT_ADDRESSES, object).prop_map(|(pointer, object)| {
+        let pointer = BFieldElement::new(pointer);
+        let mut memory = HashMap::new();
+        encode_to_memory(&mut memory, pointer, object);
+        (pointer, memory)
+    })
+}
+
+/// Initial states with arbitrary arguments of the given input types on the stack and empty
+/// memory.
+impl Arbitrary for FunctionInitialState {
+    type Parameters = Vec<DataType>;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(input_types: Self::Parameters) -> Self::Strategy {
+        arb_stack(&input_types)
+            .prop_map(|stack| FunctionInitialState {
+                stack,
+                memory: HashMap::new(),
+            })
+            .boxed()
+    }
+}
+
+/// Initial states with arbitrary arguments of the given input types on the stack, and
+/// empty memory, nondeterminism, and public input.
+impl Arbitrary for ProcedureInitialState {
+    type Parameters = Vec<DataType>;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(input_types: Self::Parameters) -> Self::Strategy {
+        arb_stack(&input_types)
+            .prop_map(|stack| ProcedureInitialState {
+                stack,
+                nondeterminism: NonDeterminism::default(),
+                public_input: vec![],
+                sponge_state: None,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    use crate::arithmetic::u64::lt_u64::LtStandardU64;
+    use crate::arithmetic::u64::wrapping_sub_u64::WrappingSub;
+    use crate::traits::closure::ShadowedClosure;
+    use crate::traits::deprecated_snippet::DeprecatedSnippetWrapper;
+    use crate::traits::function::ShadowedFunction;
+
+    use super::*;
+
+    #[test]
+    fn stack_values_have_declared_size() {
+        let data_type = DataType::Tuple(vec![DataType::U64, DataType::Digest, DataType::Xfe]);
+        let mut runner = TestRunner::default();
+        for _ in 0..10 {
+            let value = arb_stack_value(&data_type)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(data_type.stack_size(), value.len());
+        }
+    }
+
+    #[test]
+    fn generated_lists_are_well_formed() {
+        let element_type = DataType::Xfe;
+        let strategy = arb_list_in_memory(ListType::Unsafe, element_type.clone(), 0..20);
+        let mut runner = TestRunner::default();
+        for _ in 0..10 {
+            let (list_pointer, memory) = strategy.new_tree(&mut runner).unwrap().current();
+            let length = memory[&list_pointer].value() as usize;
+            assert_eq!(1 + length * element_type.stack_size(), memory.len());
+        }
+    }
+
+    #[test]
+    fn prop_test_closure() {
+        ShadowedClosure::new(WrappingSub).prop_test();
+    }
+
+    #[test]
+    fn prop_test_function() {
+        ShadowedFunction::new(DeprecatedSnippetWrapper::new(LtStandardU64)).prop_test();
+    }
+}
@@ -1,2 +1,6 @@
 pub mod auto_generated_tasm_object_implementations;
+pub mod copy_object;
+pub mod for_each_list_element;
+pub mod get_list_element;
 pub mod tasm_object;
+pub mod validate_list_encoding;
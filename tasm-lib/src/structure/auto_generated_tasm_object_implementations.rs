@@ -1,4 +1,4 @@
-use triton_vm::{proof_item::FriResponse, Digest};
+use triton_vm::{proof_item::FriResponse, BFieldElement, Claim, Digest};
 use twenty_first::{
     shared_math::{tip5::Tip5, x_field_element::XFieldElement},
     util_types::mmr::{mmr_accumulator::MmrAccumulator, mmr_membership_proof::MmrMembershipProof},
@@ -6,6 +6,8 @@ use twenty_first::{
 
 // Autogenerated `TasmObject` implementation of `MmrMembershipProof`. Generated on Dec 12, 2023. Head was: f4f39ae856c75fc75c17efe33d8109294621a01d
 impl ::tasm_lib::structure::tasm_object::TasmObject for MmrMembershipProof<Tip5> {
+    const FIELD_NAMES: &'static [&'static str] = &["leaf_index", "authentication_path"];
+
     fn get_field(field_name: &str) -> Vec<triton_vm::instruction::LabelledInstruction> {
         match field_name {
             "authentication_path" => {
@@ -222,36 +224,75 @@ impl ::tasm_lib::structure::tasm_object::TasmObject for MmrMembershipProof<Tip5>
             unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
         }
     }
-    fn decode_iter<Itr: Iterator<Item = triton_vm::BFieldElement>>(
+    fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+        [
+            [triton_vm::instruction::LabelledInstruction::Instruction(
+                triton_vm::instruction::AnInstruction::Dup(
+                    ::triton_vm::op_stack::OpStackElement::ST0,
+                ),
+            )]
+            .to_vec(),
+            Self::get_field_start_with_jump_distance("leaf_index"),
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Swap(
+                        ::triton_vm::op_stack::OpStackElement::ST1,
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Push(
+                        -twenty_first::shared_math::b_field_element::BFieldElement::new(1u64),
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Mul,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+            ]
+            .to_vec(),
+        ]
+        .concat()
+    }
+    #[allow(unused_assignments)]
+    fn decode_iter_with_limits<Itr: Iterator<Item = triton_vm::BFieldElement>>(
         iterator: &mut Itr,
+        limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
     ) -> ::std::result::Result<
         ::std::boxed::Box<Self>,
-        ::std::boxed::Box<dyn std::error::Error + ::core::marker::Send + ::core::marker::Sync>,
+        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError,
     > {
+        let mut decoded_size: usize = 0;
         let length: usize = if let Some(static_length) =
             <Vec<Digest> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
         {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("authentication_path", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let authentication_path: Vec<Digest> =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         let length: usize = if let Some(static_length) =
             <u64 as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
         {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("leaf_index", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let leaf_index: u64 =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         ::std::result::Result::Ok(::std::boxed::Box::new(Self::new(
             leaf_index,
             authentication_path,
@@ -263,6 +304,8 @@ impl ::tasm_lib::structure::tasm_object::TasmObject for MmrMembershipProof<Tip5>
 impl ::tasm_lib::structure::tasm_object::TasmObject
     for MmrAccumulator<twenty_first::shared_math::tip5::Tip5>
 {
+    const FIELD_NAMES: &'static [&'static str] = &["leaf_count", "peaks"];
+
     fn get_field(field_name: &str) -> Vec<triton_vm::instruction::LabelledInstruction> {
         match field_name {
             "peaks" => {
@@ -479,42 +522,83 @@ impl ::tasm_lib::structure::tasm_object::TasmObject
             unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
         }
     }
-    fn decode_iter<Itr: Iterator<Item = triton_vm::BFieldElement>>(
+    fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+        [
+            [triton_vm::instruction::LabelledInstruction::Instruction(
+                triton_vm::instruction::AnInstruction::Dup(
+                    ::triton_vm::op_stack::OpStackElement::ST0,
+                ),
+            )]
+            .to_vec(),
+            Self::get_field_start_with_jump_distance("leaf_count"),
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Swap(
+                        ::triton_vm::op_stack::OpStackElement::ST1,
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Push(
+                        -twenty_first::shared_math::b_field_element::BFieldElement::new(1u64),
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Mul,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+            ]
+            .to_vec(),
+        ]
+        .concat()
+    }
+    #[allow(unused_assignments)]
+    fn decode_iter_with_limits<Itr: Iterator<Item = triton_vm::BFieldElement>>(
         iterator: &mut Itr,
+        limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
     ) -> ::std::result::Result<
         ::std::boxed::Box<Self>,
-        ::std::boxed::Box<dyn std::error::Error + ::core::marker::Send + ::core::marker::Sync>,
+        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError,
     > {
+        let mut decoded_size: usize = 0;
         let length: usize = if let Some(static_length) =
             <Vec<Digest> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
         {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("peaks", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let peaks: Vec<Digest> =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         let length: usize = if let Some(static_length) =
             <u64 as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
         {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("leaf_count", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let leaf_count: u64 =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         ::std::result::Result::Ok(::std::boxed::Box::new(Self::init(peaks, leaf_count)))
     }
 }
 
 // Autogenerated `TasmObject` implementation of `FriResponse`. Generated on Dec 12, 2023. Head was: f4f39ae856c75fc75c17efe33d8109294621a01d
 impl ::tasm_lib::structure::tasm_object::TasmObject for FriResponse {
+    const FIELD_NAMES: &'static [&'static str] = &["auth_structure", "revealed_leaves"];
+
     fn get_field(field_name: &str) -> Vec<triton_vm::instruction::LabelledInstruction> {
         match field_name {
             "revealed_leaves" => {
@@ -623,37 +707,321 @@ impl ::tasm_lib::structure::tasm_object::TasmObject for FriResponse {
 
         }
     }
-    fn decode_iter<Itr: Iterator<Item = triton_vm::BFieldElement>>(
+    fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+        [
+            [triton_vm::instruction::LabelledInstruction::Instruction(
+                triton_vm::instruction::AnInstruction::Dup(
+                    ::triton_vm::op_stack::OpStackElement::ST0,
+                ),
+            )]
+            .to_vec(),
+            Self::get_field_start_with_jump_distance("auth_structure"),
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Swap(
+                        ::triton_vm::op_stack::OpStackElement::ST1,
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Push(
+                        -twenty_first::shared_math::b_field_element::BFieldElement::new(1u64),
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Mul,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+            ]
+            .to_vec(),
+        ]
+        .concat()
+    }
+    #[allow(unused_assignments)]
+    fn decode_iter_with_limits<Itr: Iterator<Item = triton_vm::BFieldElement>>(
         iterator: &mut Itr,
+        limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
     ) -> ::std::result::Result<
         ::std::boxed::Box<Self>,
-        ::std::boxed::Box<dyn std::error::Error + ::core::marker::Send + ::core::marker::Sync>,
+        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError,
     > {
+        let mut decoded_size: usize = 0;
         let length:usize = if let Some(static_length) =  <Vec<XFieldElement>as twenty_first::shared_math::bfield_codec::BFieldCodec> ::static_length(){
         static_length
       }else {
-        iterator.next().unwrap().value()as usize
+        decoded_size = limits.check_total_size(decoded_size, 1)?;
+        ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
       };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("revealed_leaves", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let revealed_leaves: Vec<XFieldElement> =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         let length: usize = if let Some(static_length) =
             <Vec<Digest> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
         {
             static_length
         } else {
-            iterator.next().unwrap().value() as usize
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
         };
-        let sequence = (0..length)
-            .map(|_| iterator.next().unwrap())
-            .collect::<Vec<_>>();
+        decoded_size = limits.check_field_size("auth_structure", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
         let auth_structure: Vec<Digest> =
-            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
         ::std::result::Result::Ok(::std::boxed::Box::new(Self {
             revealed_leaves,
             auth_structure,
         }))
     }
 }
+
+// Autogenerated `TasmObject` implementation of `Claim`. Generated on Dec 12, 2023. Head was: f4f39ae856c75fc75c17efe33d8109294621a01d
+impl ::tasm_lib::structure::tasm_object::TasmObject for Claim {
+    const FIELD_NAMES: &'static [&'static str] = &["program_digest", "input", "output"];
+    fn get_field(field_name: &str) -> Vec<triton_vm::instruction::LabelledInstruction> {
+        match field_name {
+            "output" => {
+                let current = {
+                    if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+                };
+                let getter = {
+                    if <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+                };
+                [current, getter].concat()
+            }
+            "input" => {
+                let current = {
+                    [Self::get_field_start_with_jump_distance("output"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),{
+if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+},].concat()
+                };
+                let getter = {
+                    if <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+                };
+                [current, getter].concat()
+            }
+            "program_digest" => {
+                let current = {
+                    [Self::get_field_start_with_jump_distance("input"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),{
+if let Some(size) = <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+},].concat()
+                };
+                let getter = {
+                    if <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Pop(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+                };
+                [current, getter].concat()
+            }
+            unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+        }
+    }
+    fn get_field_with_size(field_name: &str) -> Vec<triton_vm::instruction::LabelledInstruction> {
+        match field_name {
+            "output" => {
+                let current = {
+                    if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+                };
+                let getter_sizer = {
+                    if <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),].to_vec()
+}
+                };
+                [current, getter_sizer].concat()
+            }
+            "input" => {
+                let current = {
+                    [Self::get_field_start_with_jump_distance("output"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),{
+if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+},].concat()
+                };
+                let getter_sizer = {
+                    if <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),].to_vec()
+}
+                };
+                [current, getter_sizer].concat()
+            }
+            "program_digest" => {
+                let current = {
+                    [Self::get_field_start_with_jump_distance("input"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),{
+if let Some(size) = <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+},].concat()
+                };
+                let getter_sizer = {
+                    if <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length().is_some() {
+std::vec::Vec::<triton_vm::instruction::LabelledInstruction>::new()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(-twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(triton_vm::op_stack::OpStackElement::ST1)),].to_vec()
+}
+                };
+                [current, getter_sizer].concat()
+            }
+            unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+        }
+    }
+    fn get_field_start_with_jump_distance(
+        field_name: &str,
+    ) -> Vec<triton_vm::instruction::LabelledInstruction> {
+        match field_name {
+"output" => {
+if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+},
+"input" => {
+let prev = [Self::get_field_start_with_jump_distance("output"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),].concat();
+let jumper = {
+if let Some(size) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+};
+[prev,jumper].concat()
+},
+"program_digest" => {
+let prev = [Self::get_field_start_with_jump_distance("input"),[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add)].to_vec(),].concat();
+let jumper = {
+if let Some(size) = <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(size as u64)))].to_vec()
+} else {
+[triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::ReadMem(::triton_vm::op_stack::NumberOfWords::N1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Swap(::triton_vm::op_stack::OpStackElement::ST1)),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Push(twenty_first::shared_math::b_field_element::BFieldElement::new(1u64))),triton_vm::instruction::LabelledInstruction::Instruction(triton_vm::instruction::AnInstruction::Add),].to_vec()
+}
+};
+[prev,jumper].concat()
+},
+unknown_field_name => panic!("Cannot match on field name `{unknown_field_name}`."),
+}
+    }
+    fn size_in_memory() -> Vec<triton_vm::instruction::LabelledInstruction> {
+        [
+            [triton_vm::instruction::LabelledInstruction::Instruction(
+                triton_vm::instruction::AnInstruction::Dup(
+                    ::triton_vm::op_stack::OpStackElement::ST0,
+                ),
+            )]
+            .to_vec(),
+            Self::get_field_start_with_jump_distance("program_digest"),
+            [
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Swap(
+                        ::triton_vm::op_stack::OpStackElement::ST1,
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Push(
+                        -twenty_first::shared_math::b_field_element::BFieldElement::new(1u64),
+                    ),
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Mul,
+                ),
+                triton_vm::instruction::LabelledInstruction::Instruction(
+                    triton_vm::instruction::AnInstruction::Add,
+                ),
+            ]
+            .to_vec(),
+        ]
+        .concat()
+    }
+    #[allow(unused_assignments)]
+    fn decode_iter_with_limits<Itr: Iterator<Item = triton_vm::BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &::tasm_lib::structure::tasm_object::DecodingLimits,
+    ) -> ::std::result::Result<
+        ::std::boxed::Box<Self>,
+        ::tasm_lib::structure::tasm_object::TasmObjectDecodeError,
+    > {
+        let mut decoded_size: usize = 0;
+        let length: usize = if let Some(static_length) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+static_length
+} else {
+decoded_size = limits.check_total_size(decoded_size, 1)?;
+::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
+};
+        decoded_size = limits.check_field_size("output", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
+        let output: Vec<BFieldElement> =
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
+        let length: usize = if let Some(static_length) = <Vec<BFieldElement> as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length() {
+static_length
+} else {
+decoded_size = limits.check_total_size(decoded_size, 1)?;
+::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
+};
+        decoded_size = limits.check_field_size("input", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
+        let input: Vec<BFieldElement> =
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
+        let length: usize = if let Some(static_length) =
+            <Digest as twenty_first::shared_math::bfield_codec::BFieldCodec>::static_length()
+        {
+            static_length
+        } else {
+            decoded_size = limits.check_total_size(decoded_size, 1)?;
+            ::tasm_lib::structure::tasm_object::next_length_prefix(iterator)?
+        };
+        decoded_size = limits.check_field_size("program_digest", length, decoded_size)?;
+        let sequence = ::tasm_lib::structure::tasm_object::next_sequence(iterator, length)?;
+        let program_digest: Digest =
+            *twenty_first::shared_math::bfield_codec::BFieldCodec::decode(&sequence)
+                .map_err(::tasm_lib::structure::tasm_object::TasmObjectDecodeError::inner)?;
+        ::std::result::Result::Ok(::std::boxed::Box::new(Self {
+            output,
+            input,
+            program_digest,
+        }))
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::triton_asm;
+use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+
+use crate::data_type::DataType;
+use crate::empty_stack;
+use crate::library::Library;
+use crate::memory::dyn_malloc::DynMalloc;
+use crate::memory::encode_to_memory;
+use crate::memory::memcpy::MemCpy;
+use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+use crate::snippet_bencher::BenchmarkCase;
+use crate::structure::tasm_object::TasmObject;
+use crate::traits::basic_snippet::BasicSnippet;
+use crate::traits::deprecated_snippet::DeprecatedSnippet;
+use crate::traits::function::{Function, FunctionInitialState};
+
+/// Copy an object of type `T` that lives in memory, as `T`'s [`TasmObject`] implementation
+/// expects it, to another location, and return the pointer to the copy. The object's size
+/// is computed through [`TasmObject::size_in_memory`].
+///
+/// If `allocate_destination` is set, the copy is written to memory freshly allocated
+/// through [`DynMalloc`]. Otherwise, the destination is given as an input and must not
+/// overlap with the object.
+///
+/// The entrypoint contains `T`'s type name, made label-friendly.
+#[derive(Debug, Clone)]
+pub struct CopyObject<T> {
+    pub allocate_destination: bool,
+    _object: PhantomData<T>,
+}
+
+impl<T> CopyObject<T> {
+    pub fn new(allocate_destination: bool) -> Self {
+        Self {
+            allocate_destination,
+            _object: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject + BFieldCodec> CopyObject<T> {
+    fn object_size(
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        object: BFieldElement,
+    ) -> Option<usize> {
+        let decoded_object = T::decode_from_memory(memory, object).ok()?;
+        Some(decoded_object.encode().len())
+    }
+}
+
+impl<T: TasmObject + BFieldCodec> BasicSnippet for CopyObject<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let object = (DataType::VoidPointer, "*object".to_owned());
+        let destination = (DataType::VoidPointer, "*destination".to_owned());
+        match self.allocate_destination {
+            true => vec![object],
+            false => vec![object, destination],
+        }
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*copy".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        let type_name = std::any::type_name::<T>()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        match self.allocate_destination {
+            true => format!("tasm_structure_copy_object_to_fresh_allocation___{type_name}"),
+            false => format!("tasm_structure_copy_object___{type_name}"),
+        }
+    }
+
+    fn writable_memory_regions(
+        &self,
+        initial_stack: &[BFieldElement],
+        initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        if self.allocate_destination {
+            return Some(vec![]);
+        }
+
+        let [.., object, destination] = initial_stack else {
+            return None;
+        };
+        let size = Self::object_size(initial_memory, *object)?;
+        Some(vec![(*destination, size.try_into().ok()?)])
+    }
+
+    fn input_objects(
+        &self,
+        initial_stack: &[BFieldElement],
+        initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(usize, u32)>> {
+        let object_position = match self.allocate_destination {
+            true => 1,
+            false => 2,
+        };
+        let object = initial_stack.get(initial_stack.len().checked_sub(object_position)?)?;
+        let size = Self::object_size(initial_memory, *object)?;
+        Some(vec![(0, size.try_into().ok()?)])
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let size_in_memory = T::size_in_memory();
+        let memcpy = library.import(Box::new(MemCpy));
+
+        if self.allocate_destination {
+            let dyn_malloc = library.import(Box::new(DynMalloc));
+            return triton_asm!(
+            // BEFORE: _ *object
+            // AFTER:  _ *copy
+            {entrypoint}:
+                dup 0
+                {&size_in_memory}       // _ *object size
+                dup 0
+                call {dyn_malloc}       // _ *object size *copy
+                dup 2
+                dup 1
+                dup 3                   // _ *object size *copy *object *copy size
+                call {memcpy}           // _ *object size *copy
+                swap 2
+                pop 2
+                return
+            );
+        }
+
+        triton_asm!(
+        // BEFORE: _ *object *destination
+        // AFTER:  _ *copy
+        {entrypoint}:
+            dup 1
+            {&size_in_memory}           // _ *object *destination size
+            dup 2
+            dup 2
+            dup 2                       // _ *object *destination size *object *destination size
+            call {memcpy}               // _ *object *destination size
+            pop 1
+            swap 1
+            pop 1
+            return
+        )
+    }
+}
+
+impl<T> Function for CopyObject<T>
+where
+    T: TasmObject + BFieldCodec,
+    Standard: Distribution<T>,
+{
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let destination = match self.allocate_destination {
+            true => None,
+            false => stack.pop(),
+        };
+        let object = stack.pop().unwrap();
+        let size = Self::object_size(memory, object).unwrap();
+        let copy = destination.unwrap_or_else(|| dynamic_allocator(size, memory));
+
+        let mut memcpy_stack = [
+            empty_stack(),
+            vec![object, copy, BFieldElement::new(size as u64)],
+        ]
+        .concat();
+        MemCpy.rust_shadowing(&mut memcpy_stack, vec![], vec![], memory);
+        stack.push(copy);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        _bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let mut memory = HashMap::new();
+        let object = BFieldElement::new(rng.gen_range(0..1 << 30));
+        let object_end = encode_to_memory(&mut memory, object, rng.gen::<T>());
+
+        let mut stack = [empty_stack(), vec![object]].concat();
+        if !self.allocate_destination {
+            stack.push(object_end + BFieldElement::new(rng.gen_range(0..100)));
+        }
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::shared_math::x_field_element::XFieldElement;
+
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
+    struct Payload {
+        digests: Vec<Digest>,
+        element: XFieldElement,
+        words: Vec<u32>,
+    }
+
+    impl Distribution<Payload> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Payload {
+            let num_digests = rng.gen_range(0..10);
+            let num_words = rng.gen_range(0..20);
+            Payload {
+                digests: (0..num_digests).map(|_| rng.gen()).collect(),
+                element: rng.gen(),
+                words: (0..num_words).map(|_| rng.gen()).collect(),
+            }
+        }
+    }
+
+    #[test]
+    fn copy_object_test() {
+        for allocate_destination in [false, true] {
+            ShadowedFunction::new(CopyObject::<Payload>::new(allocate_destination)).test();
+            ShadowedFunction::new(CopyObject::<(Digest, XFieldElement)>::new(
+                allocate_destination,
+            ))
+            .test();
+        }
+    }
+
+    #[test]
+    fn copy_decodes_to_original_object() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let payload: Payload = rng.gen();
+        let mut memory = HashMap::new();
+        let object = BFieldElement::new(1 << 20);
+        encode_to_memory(&mut memory, object, payload.clone());
+
+        let mut stack = [empty_stack(), vec![object]].concat();
+        CopyObject::<Payload>::new(true).rust_shadow(&mut stack, &mut memory);
+        let copy = stack.pop().unwrap();
+
+        assert_ne!(object, copy);
+        assert_eq!(
+            payload,
+            *Payload::decode_from_memory(&memory, copy).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+    use crate::Digest;
+
+    #[test]
+    fn copy_object_benchmark() {
+        ShadowedFunction::new(CopyObject::<[Digest; 4]>::new(true)).bench();
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, parser::tokenize, triton_asm, BFieldElement};
+
+use crate::{
+    data_type::DataType,
+    empty_stack,
+    library::Library,
+    list::higher_order::inner_function::InnerFunction,
+    snippet_bencher::BenchmarkCase,
+    traits::{
+        basic_snippet::BasicSnippet,
+        function::{Function, FunctionInitialState},
+    },
+};
+
+/// Calls a given function on a pointer to every element of a `Vec<T>` that lives in
+/// memory as BFieldCodec encodes it, *i.e.*, as its length followed by its elements, where
+/// dynamically-sized elements are prefixed by their size.
+///
+/// The inner function has the stack signature `_ *element -> _`. Like for
+/// [`Map`](crate::list::higher_order::map::Map), it may access additional arguments that
+/// live below the list pointer; here, they are buried under two words of loop state.
+pub struct ForEachListElement {
+    /// The static size of the elements in number of words, or `None` if the elements
+    /// are dynamically sized.
+    pub element_size: Option<usize>,
+    pub f: InnerFunction,
+}
+
+impl BasicSnippet for ForEachListElement {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*list".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_size = match self.element_size {
+            Some(size) => format!("static_size_{size}"),
+            None => "dynamic_size".to_owned(),
+        };
+        format!(
+            "tasm_structure_for_each_list_element_{element_size}_{}",
+            self.f.entrypoint()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        // Declare the inner function entrypoint name and import inner function in case it's a snippet
+        let inner_function_name = match &self.f {
+            InnerFunction::RawCode(rc) => rc.entrypoint(),
+            InnerFunction::DeprecatedSnippet(sn) => {
+                let fn_body = sn.function_code(library);
+                let (_, instructions) = tokenize(&fn_body).unwrap();
+                let labelled_instructions =
+                    triton_vm::parser::to_labelled_instructions(&instructions);
+                library
+                    .explicit_import(&sn.entrypoint_name(), &labelled_instructions)
+                    .unwrap()
+            }
+            InnerFunction::NoFunctionBody(lnat) => lnat.label_name.to_owned(),
+            InnerFunction::BasicSnippet(bs) => {
+                let labelled_instructions = bs.code(library);
+                library
+                    .explicit_import(&bs.entrypoint(), &labelled_instructions)
+                    .unwrap()
+            }
+        };
+
+        // If function was supplied as raw instructions, we need to append the inner function to the function
+        // body. Otherwise, `library` handles the imports.
+        let maybe_inner_function_body_raw = match &self.f {
+            InnerFunction::RawCode(rc) => rc.function.iter().map(|x| x.to_string()).join("\n"),
+            InnerFunction::DeprecatedSnippet(_) => String::default(),
+            InnerFunction::NoFunctionBody(_) => String::default(),
+            InnerFunction::BasicSnippet(_) => String::default(),
+        };
+
+        let (element_pointer, next_element_start) = match self.element_size {
+            Some(size) => (
+                triton_asm!(dup 1),
+                triton_asm!(
+                    swap 1
+                    push {size}
+                    add
+                    swap 1
+                ),
+            ),
+            None => (
+                triton_asm!(dup 1 push 1 add),
+                triton_asm!(
+                    swap 1
+                    read_mem 1
+                    push 2
+                    add
+                    add
+                    swap 1
+                ),
+            ),
+        };
+
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER: _
+            {entrypoint}:
+                read_mem 1
+                push 2
+                add
+                swap 1
+                // _ *first_element_start length
+
+                call {main_loop}
+                // _ *end 0
+
+                pop 2
+                return
+
+            // INVARIANT: _ *element_start remaining
+            {main_loop}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                {&element_pointer}
+                // _ *element_start remaining *element
+
+                call {inner_function_name}
+                // _ *element_start remaining
+
+                {&next_element_start}
+                // _ *next_element_start remaining
+
+                push -1
+                add
+                recurse
+
+            {maybe_inner_function_body_raw}
+        )
+    }
+}
+
+impl Function for ForEachListElement {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let list_pointer = stack.pop().unwrap();
+        let length = memory[&list_pointer].value();
+
+        let mut element_start = list_pointer + BFieldElement::new(1);
+        for remaining in (1..=length).rev() {
+            let (element_pointer, element_size) = match self.element_size {
+                Some(size) => (element_start, size as u64),
+                None => (
+                    element_start + BFieldElement::new(1),
+                    memory[&element_start].value() + 1,
+                ),
+            };
+
+            stack.push(element_start);
+            stack.push(BFieldElement::new(remaining));
+            stack.push(element_pointer);
+            self.f.apply(stack, memory);
+            stack.pop();
+            stack.pop();
+
+            element_start += BFieldElement::new(element_size);
+        }
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let list_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 10,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.gen_range(0..50),
+        };
+
+        self.init_state(&mut rng, list_length)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0u8; 32]);
+        vec![self.init_state(&mut rng, 0), self.init_state(&mut rng, 1)]
+    }
+}
+
+impl ForEachListElement {
+    /// Initializes a list of the given length, preceded on the stack by a zero that inner
+    /// functions may use as an accumulator.
+    fn init_state(&self, rng: &mut StdRng, list_length: usize) -> FunctionInitialState {
+        let list_pointer = BFieldElement::new(rng.gen_range(0..(1 << 30)));
+
+        let mut encoding = vec![BFieldElement::new(list_length as u64)];
+        for _ in 0..list_length {
+            let element_size = match self.element_size {
+                Some(size) => size,
+                None => {
+                    let size = rng.gen_range(1..10);
+                    encoding.push(BFieldElement::new(size as u64));
+                    size
+                }
+            };
+            encoding.extend((0..element_size).map(|_| rng.gen::<BFieldElement>()));
+        }
+
+        let memory = encoding
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (list_pointer + BFieldElement::new(i as u64), word))
+            .collect();
+
+        let mut stack = empty_stack();
+        stack.push(BFieldElement::new(0));
+        stack.push(list_pointer);
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::higher_order::inner_function::RawCode;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    /// Adds the first word of every element to the accumulator below the list pointer.
+    pub(super) fn sum_first_words() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(
+                sum_first_words:
+                    // _ acc *element_start remaining *element
+                    read_mem 1
+                    pop 1
+                    dup 3
+                    add
+                    swap 3
+                    pop 1
+                    // _ acc' *element_start remaining
+                    return
+            ),
+            DataType::VoidPointer,
+            DataType::Tuple(vec![]),
+        ))
+    }
+
+    #[test]
+    fn for_each_list_element_static_size_test() {
+        for element_size in [1, 2, 5] {
+            ShadowedFunction::new(ForEachListElement {
+                element_size: Some(element_size),
+                f: sum_first_words(),
+            })
+            .test();
+        }
+    }
+
+    #[test]
+    fn for_each_list_element_dynamic_size_test() {
+        ShadowedFunction::new(ForEachListElement {
+            element_size: None,
+            f: sum_first_words(),
+        })
+        .test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::tests::sum_first_words;
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn for_each_list_element_static_size_bench() {
+        ShadowedFunction::new(ForEachListElement {
+            element_size: Some(5),
+            f: sum_first_words(),
+        })
+        .bench();
+    }
+
+    #[test]
+    fn for_each_list_element_dynamic_size_bench() {
+        ShadowedFunction::new(ForEachListElement {
+            element_size: None,
+            f: sum_first_words(),
+        })
+        .bench();
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
+
+use crate::{
+    data_type::DataType,
+    empty_stack,
+    library::Library,
+    snippet_bencher::BenchmarkCase,
+    traits::{
+        basic_snippet::BasicSnippet,
+        function::{Function, FunctionInitialState},
+    },
+};
+
+/// Returns a pointer to the element with the given index of a `Vec<T>` that lives in
+/// memory as BFieldCodec encodes it, *i.e.*, as its length followed by its elements.
+///
+/// If `T` has a statically known size, the element is found by multiplying the index
+/// with that size. Otherwise, every element is prefixed by its size, and the snippet
+/// walks over the preceding elements one by one.
+///
+/// The index is not checked against the length of the list.
+#[derive(Clone, Debug)]
+pub struct GetListElement {
+    /// The static size of the elements in number of words, or `None` if the elements
+    /// are dynamically sized.
+    pub element_size: Option<usize>,
+}
+
+impl BasicSnippet for GetListElement {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*list".to_owned()),
+            (DataType::U32, "index".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*element".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        match self.element_size {
+            Some(size) => format!("tasm_structure_get_list_element_static_size_{size}"),
+            None => "tasm_structure_get_list_element_dynamic_size".to_owned(),
+        }
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        let Some(element_size) = self.element_size else {
+            return triton_asm!(
+                // BEFORE: _ *list index
+                // AFTER: _ *element
+                {entrypoint}:
+                    swap 1
+                    push 1
+                    add
+                    swap 1
+                    // _ *element_size index
+
+                    call {entrypoint}_loop
+                    // _ *element_size 0
+
+                    pop 1
+                    push 1
+                    add
+                    // _ *element
+
+                    return
+
+                // INVARIANT: _ *element_size remaining
+                {entrypoint}_loop:
+                    dup 0
+                    push 0
+                    eq
+                    skiz return
+
+                    swap 1
+                    read_mem 1
+                    // _ remaining element_size (*element_size - 1)
+
+                    push 2
+                    add
+                    add
+                    // _ remaining *next_element_size
+
+                    swap 1
+                    push -1
+                    add
+                    // _ *next_element_size (remaining - 1)
+
+                    recurse
+            );
+        };
+
+        triton_asm!(
+            // BEFORE: _ *list index
+            // AFTER: _ *element
+            {entrypoint}:
+                push {element_size}
+                mul
+                // _ *list offset
+
+                push 1
+                add
+                add
+                // _ *element
+
+                return
+        )
+    }
+}
+
+impl Function for GetListElement {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let index = stack.pop().unwrap().value();
+        let list_pointer = stack.pop().unwrap();
+
+        let mut element_pointer = list_pointer + BFieldElement::new(1);
+        match self.element_size {
+            Some(size) => element_pointer += BFieldElement::new(index * size as u64),
+            None => {
+                for _ in 0..index {
+                    element_pointer += memory[&element_pointer] + BFieldElement::new(1);
+                }
+                element_pointer.increment();
+            }
+        }
+
+        stack.push(element_pointer);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let list_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 10,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.gen_range(1..50),
+        };
+        let index = match bench_case {
+            Some(_) => list_length - 1,
+            None => rng.gen_range(0..list_length),
+        };
+
+        self.init_state(&mut rng, list_length, index)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0u8; 32]);
+        vec![
+            self.init_state(&mut rng, 1, 0),
+            self.init_state(&mut rng, 2, 0),
+            self.init_state(&mut rng, 2, 1),
+        ]
+    }
+}
+
+impl GetListElement {
+    fn init_state(
+        &self,
+        rng: &mut StdRng,
+        list_length: usize,
+        index: usize,
+    ) -> FunctionInitialState {
+        let list_pointer = BFieldElement::new(rng.gen_range(0..(1 << 30)));
+
+        let mut encoding = vec![BFieldElement::new(list_length as u64)];
+        for _ in 0..list_length {
+            let element_size = match self.element_size {
+                Some(size) => size,
+                None => {
+                    let size = rng.gen_range(0..10);
+                    encoding.push(BFieldElement::new(size as u64));
+                    size
+                }
+            };
+            encoding.extend((0..element_size).map(|_| rng.gen::<BFieldElement>()));
+        }
+
+        let memory = encoding
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (list_pointer + BFieldElement::new(i as u64), word))
+            .collect();
+
+        let mut stack = empty_stack();
+        stack.push(list_pointer);
+        stack.push(BFieldElement::new(index as u64));
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn get_list_element_static_size_test() {
+        for element_size in [1, 2, 5] {
+            ShadowedFunction::new(GetListElement {
+                element_size: Some(element_size),
+            })
+            .test();
+        }
+    }
+
+    #[test]
+    fn get_list_element_dynamic_size_test() {
+        ShadowedFunction::new(GetListElement { element_size: None }).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn get_list_element_static_size_bench() {
+        ShadowedFunction::new(GetListElement {
+            element_size: Some(5),
+        })
+        .bench();
+    }
+
+    #[test]
+    fn get_list_element_dynamic_size_bench() {
+        ShadowedFunction::new(GetListElement { element_size: None }).bench();
+    }
+}
@@ -1,14 +1,19 @@
 use std::collections::HashMap;
 use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
 
 use itertools::Itertools;
 use num_traits::Zero;
-use triton_vm::{instruction::LabelledInstruction, BFieldElement};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
 use twenty_first::shared_math::bfield_codec::BFieldCodec;
 
+use crate::library::Library;
+use crate::structure::validate_list_encoding::ValidateListEncoding;
+
 pub use derive_tasm_object::TasmObject;
 
-type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+type Result<T> = std::result::Result<T, TasmObjectDecodeError>;
 
 /// TasmObject
 ///
@@ -16,8 +21,13 @@ type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
 /// assuming those methods live in memory as they are encoded with BFieldCodec.
 ///
 /// The arguments referring to fields are strings. For structs with unnamed fields, the
-/// nth field name is implicitly `field_n`.
+/// nth field name is implicitly `field_n`. For enums, fields are prefixed by the name
+/// of their variant, as in `Variant::field_n`.
 pub trait TasmObject {
+    /// The names of all fields that can be accessed, in order of declaration. Used to
+    /// validate field names at compile time; see `field!`.
+    const FIELD_NAMES: &'static [&'static str];
+
     /// Returns tasm code that returns a pointer the field of the object, assuming:
     ///  - that a pointer to the said object lives on top of the stack;
     ///  - said object has a type that implements the TasmObject trait;
@@ -41,6 +51,97 @@ pub trait TasmObject {
     /// See also: `get_field` if you just want the field without the size.
     fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction>;
 
+    /// Like `get_field_with_size`, but additionally takes the size of the memory region
+    /// that the object lives in, and crashes the VM unless the field lies entirely within
+    /// that region, *i.e.*, within `[*object, *object + region_size)`. Use this to access
+    /// objects in memory that is supplied by an untrusted party, like the prover.
+    ///
+    /// BEFORE: _ *object region_size
+    ///
+    /// AFTER: _ *field field_size
+    ///
+    /// The region size must be a u32. Fields stored by pointer live outside the object and
+    /// thus fail this check.
+    fn get_field_with_bounds_check(field_name: &str) -> Vec<LabelledInstruction> {
+        let get_field_with_size = Self::get_field_with_size(field_name);
+        triton_asm!(
+            // _ *object region_size
+            dup 1
+            {&get_field_with_size}
+            // _ *object region_size *field field_size
+
+            dup 0
+            split
+            pop 1
+            push 0
+            eq
+            assert
+            // _ *object region_size *field field_size
+
+            dup 1
+            dup 4
+            push -1
+            mul
+            add
+            // _ *object region_size *field field_size field_offset
+
+            dup 0
+            split
+            pop 1
+            push 0
+            eq
+            assert
+            // _ *object region_size *field field_size field_offset
+
+            dup 1
+            add
+            dup 3
+            push 1
+            add
+            swap 1
+            // _ *object region_size *field field_size (region_size + 1) field_end_offset
+
+            lt
+            assert
+            // _ *object region_size *field field_size
+
+            swap 2
+            pop 1
+            swap 2
+            pop 1
+            // _ *field field_size
+        )
+    }
+
+    /// Returns the ordinal of the field, *i.e.*, its position in the list of fields in
+    /// order of declaration as returned by [`fields`](TasmObject::fields). Ordinals are
+    /// stable: they depend only on the declaration of the type, not on its encoding.
+    ///
+    /// Panics if the type has no field with the given name.
+    fn get_field_ordinal(field_name: &str) -> usize {
+        Self::fields()
+            .iter()
+            .position(|(name, _)| name == field_name)
+            .unwrap_or_else(|| panic!("Cannot match on field name `{field_name}`."))
+    }
+
+    /// Like `get_field_with_size`, but keyed by the field's ordinal instead of its name;
+    /// see `get_field_ordinal`. Use this to iterate over all fields of an object.
+    ///
+    /// BEFORE: _ *object
+    ///
+    /// AFTER: _ *field field_size
+    fn get_field_with_size_by_ordinal(ordinal: usize) -> Vec<LabelledInstruction> {
+        let fields = Self::fields();
+        let Some((field_name, _)) = fields.get(ordinal) else {
+            panic!(
+                "Field ordinal {ordinal} is out of range; there are {} fields.",
+                fields.len()
+            );
+        };
+        Self::get_field_with_size(field_name)
+    }
+
     /// Returns tasm code that returns a pointer to the start of the field of the object,
     /// along with the jump distance to the next field. Note that:
     ///
@@ -55,17 +156,373 @@ pub trait TasmObject {
     /// `get_field` or `get_field_with_size` instead.
     fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction>;
 
+    /// Returns tasm code that returns a pointer to a nested field of the object, by
+    /// following the given field names through the nested types. Every type on the path
+    /// except the last one must implement TasmObject.
+    ///
+    /// BEFORE: _ *object
+    ///
+    /// AFTER: _ *nested_field
+    ///
+    /// The derive macro overrides this method to follow the path into the fields' types.
+    /// See also: `field_chain!`.
+    fn get_field_chain(field_names: &[&str]) -> Vec<LabelledInstruction> {
+        match field_names {
+            [] => vec![],
+            [field_name] => Self::get_field(field_name),
+            [field_name, ..] => panic!("Cannot access nested fields of field `{field_name}`."),
+        }
+    }
+
+    /// Returns tasm code that returns a pointer to the element with the given index of a
+    /// field of type `Vec<T>`, assuming that a pointer to the object and the index live on
+    /// top of the stack. The index is not checked against the length of the list.
+    ///
+    /// BEFORE: _ *object index
+    ///
+    /// AFTER: _ *element
+    ///
+    /// The derive macro overrides this method for all fields of type `Vec<T>`. See also:
+    /// [`GetListElement`](crate::structure::get_list_element::GetListElement).
+    fn get_list_element(field_name: &str, _library: &mut Library) -> Vec<LabelledInstruction> {
+        panic!("Field `{field_name}` is not a list.")
+    }
+
+    /// Returns the offsets of the fields relative to the start of the object, in the order
+    /// of [`FIELD_NAMES`](TasmObject::FIELD_NAMES), if all fields are statically sized.
+    /// Returns `None` otherwise.
+    ///
+    /// The derive macro implements this method for structs. If it returns offsets, the
+    /// field getters reduce to a single `push offset add`.
+    fn field_offsets() -> Option<Vec<usize>> {
+        None
+    }
+
+    /// Returns the names of all fields in order of declaration, like
+    /// [`FIELD_NAMES`](TasmObject::FIELD_NAMES), along with their static sizes in number of
+    /// BFieldElements, or `None` if a field is dynamically sized. Generic tooling, like
+    /// pretty-printers of memory, can use this to enumerate the layout of an object.
+    ///
+    /// The derive macro implements this method. The default implementation considers all
+    /// fields dynamically sized.
+    fn fields() -> Vec<(String, Option<usize>)> {
+        Self::FIELD_NAMES
+            .iter()
+            .map(|field_name| (field_name.to_string(), None))
+            .collect()
+    }
+
+    /// Returns tasm code that computes the size of the object in number of
+    /// BFieldElements, assuming that a pointer to the object lives on top of the stack.
+    /// The size is the sum of the static sizes of the fields and the size indicators of
+    /// the dynamically-sized fields, including those indicators themselves.
+    ///
+    /// BEFORE: _ *object
+    ///
+    /// AFTER: _ size
+    fn size_in_memory() -> Vec<LabelledInstruction>;
+
+    /// Returns tasm code that writes the encoding of an object to memory, given pointers
+    /// to its fields along with their sizes, as returned by `get_field_with_size`. The
+    /// fields are given in order of declaration. Dynamically-sized fields are prepended
+    /// with their size.
+    ///
+    /// BEFORE: _ [*field field_size]* *destination
+    ///
+    /// AFTER: _ *destination_end
+    ///
+    /// Here, `*destination_end` points to the first word after the written encoding. The
+    /// derive macro implements this method for structs; it is not available for enums.
+    fn encode_from_fields(_library: &mut Library) -> Vec<LabelledInstruction> {
+        panic!("Encoding objects of this type from their fields is not supported")
+    }
+
+    /// Returns tasm code that allocates memory for an object using
+    /// [`DynMalloc`](crate::memory::dyn_malloc::DynMalloc) and writes the object's encoding
+    /// there, given pointers to its fields along with their sizes, like for
+    /// `encode_from_fields`.
+    ///
+    /// BEFORE: _ [*field field_size]*
+    ///
+    /// AFTER: _ *object
+    ///
+    /// The encoding is written to the first free address before the memory is allocated,
+    /// which relies on the allocator handing out consecutive chunks of memory. This is
+    /// asserted. The derive macro implements this method for structs; it is not available
+    /// for enums.
+    fn build_in_memory(_library: &mut Library) -> Vec<LabelledInstruction> {
+        panic!("Building objects of this type in memory is not supported")
+    }
+
+    /// Returns tasm code that returns pointers to all fields of the object, along with the
+    /// sizes of the dynamically-sized fields, in a single pass over the object. Fields are
+    /// returned in order of storage, which is the reverse of the order of declaration.
+    /// Hence, the first declared field ends up on top of the stack.
+    ///
+    /// BEFORE: _ *object
+    ///
+    /// AFTER: _ [*field (field_size)]*
+    ///
+    /// The derive macro implements this method for structs; it is not available for enums.
+    fn destructure() -> Vec<LabelledInstruction> {
+        panic!("Destructuring objects of this type is not supported")
+    }
+
+    /// Returns tasm code that checks that the object is well-formed and fits within a
+    /// region of the given size, and returns the size of the object. Crashes the VM if
+    ///  - the size indicator of any dynamically-sized field is not a u32,
+    ///  - the size indicator of any nested object disagrees with that object's size, or
+    ///  - the object does not fit within the region.
+    ///
+    /// Programs that receive objects through nondeterministically-initialized memory
+    /// should run this check before relying on any field getter.
+    ///
+    /// BEFORE: _ *object region_size
+    ///
+    /// AFTER: _ size
+    ///
+    /// The derive macro implements this method for structs, and `Vec<T>` implements it
+    /// through [`ValidateListEncoding`]. `Option`, `Result`, arrays, and tuples implement it
+    /// if their values are statically sized. The default implementation panics, since an
+    /// unchecked encoding must not pass for a validated one.
+    fn validate_encoding(_library: &mut Library) -> Vec<LabelledInstruction> {
+        panic!(
+            "Validating the encoding of `{}` is not supported",
+            std::any::type_name::<Self>()
+        )
+    }
+
+    /// Returns tasm code that reads the discriminant of an enum, assuming that a pointer
+    /// to the enum lives on top of the stack. Only implemented for enums.
+    ///
+    /// BEFORE: _ *object
+    ///
+    /// AFTER: _ discriminant
+    ///
+    /// The fields of enum variants are accessed through `get_field` and friends, with
+    /// field names of the form `Variant::field`. Those getters do not check the
+    /// discriminant, so use this function first if the active variant is not known.
+    fn get_variant_discriminant() -> Vec<LabelledInstruction> {
+        panic!("Only enums have a discriminant")
+    }
+
     /// Given an iterator over `BFieldElement`s, decode it as a Self object.
-    fn decode_iter<Itr: Iterator<Item = BFieldElement>>(iterator: &mut Itr) -> Result<Box<Self>>;
+    fn decode_iter<Itr: Iterator<Item = BFieldElement>>(iterator: &mut Itr) -> Result<Box<Self>> {
+        Self::decode_iter_with_limits(iterator, &DecodingLimits::unlimited())
+    }
+
+    /// Given an iterator over `BFieldElement`s, decode it as a Self object, but return an
+    /// error as soon as the encoding claims a size that exceeds the given limits. Use
+    /// this to decode untrusted, *e.g.*, nondeterministically supplied, memory.
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>>;
 
     /// Given a memory object (as HashMap of BFE->BFE) and and address (BFE), decode the
     /// object located there.
     fn decode_from_memory(
         memory: &HashMap<BFieldElement, BFieldElement>,
         address: BFieldElement,
+    ) -> Result<Box<Self>> {
+        Self::decode_from_memory_with_limits(memory, address, &DecodingLimits::unlimited())
+    }
+
+    /// Like `decode_from_memory`, but checks the sizes claimed by the encoding against
+    /// the given limits before reading any further.
+    ///
+    /// The derive macro overrides this method for structs with fields that are stored by
+    /// pointer, since following those pointers requires access to all of memory.
+    fn decode_from_memory_with_limits(
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        address: BFieldElement,
+        limits: &DecodingLimits,
     ) -> Result<Box<Self>> {
         let mut iterator = MemoryIter::new(memory, address);
-        Self::decode_iter(&mut iterator)
+        Self::decode_iter_with_limits(&mut iterator, limits)
+    }
+}
+
+/// Upper bounds on the sizes, in number of BFieldElements, that a decoder accepts. The
+/// total size includes size indicators; the size of a field does not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodingLimits {
+    pub max_total_size: usize,
+
+    /// Limits on individual fields, by field name as in [`TasmObject::FIELD_NAMES`].
+    pub max_field_sizes: HashMap<String, usize>,
+}
+
+impl Default for DecodingLimits {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl DecodingLimits {
+    pub fn unlimited() -> Self {
+        Self::with_max_total_size(usize::MAX)
+    }
+
+    pub fn with_max_total_size(max_total_size: usize) -> Self {
+        Self {
+            max_total_size,
+            max_field_sizes: HashMap::new(),
+        }
+    }
+
+    pub fn with_max_field_size(mut self, field_name: &str, max_field_size: usize) -> Self {
+        self.max_field_sizes
+            .insert(field_name.to_owned(), max_field_size);
+        self
+    }
+
+    /// Returns the new total size after decoding `additional_size` more words, or an
+    /// error if that exceeds the limit.
+    pub fn check_total_size(&self, decoded_size: usize, additional_size: usize) -> Result<usize> {
+        match decoded_size.checked_add(additional_size) {
+            Some(total_size) if total_size <= self.max_total_size => Ok(total_size),
+            _ => Err(TasmObjectDecodeError::TotalSizeExceeded {
+                decoded_size,
+                additional_size,
+                max_total_size: self.max_total_size,
+            }),
+        }
+    }
+
+    /// Like `check_total_size`, but additionally checks the limit of the named field.
+    pub fn check_field_size(
+        &self,
+        field_name: &str,
+        field_size: usize,
+        decoded_size: usize,
+    ) -> Result<usize> {
+        if let Some(&max_field_size) = self.max_field_sizes.get(field_name) {
+            if field_size > max_field_size {
+                return Err(TasmObjectDecodeError::FieldSizeExceeded {
+                    field_name: field_name.to_owned(),
+                    field_size,
+                    max_field_size,
+                });
+            }
+        }
+        self.check_total_size(decoded_size, field_size)
+    }
+}
+
+/// The reasons why decoding a [`TasmObject`] can fail.
+#[derive(Debug)]
+pub enum TasmObjectDecodeError {
+    /// The encoding ended before the object was decoded completely.
+    Truncated,
+
+    /// A size indicator or the length of a list is not a u32.
+    InvalidLengthPrefix(BFieldElement),
+
+    /// The discriminant does not belong to any variant of the enum.
+    InvalidDiscriminant {
+        type_name: String,
+        discriminant: u64,
+    },
+
+    /// The encoding exceeds the total size allowed by the [`DecodingLimits`].
+    TotalSizeExceeded {
+        decoded_size: usize,
+        additional_size: usize,
+        max_total_size: usize,
+    },
+
+    /// A field exceeds its size allowed by the [`DecodingLimits`].
+    FieldSizeExceeded {
+        field_name: String,
+        field_size: usize,
+        max_field_size: usize,
+    },
+
+    /// The type cannot be decoded from its encoding alone.
+    Unsupported(String),
+
+    /// Decoding a field or an element failed.
+    Inner(Box<dyn Error + Send + Sync>),
+}
+
+impl TasmObjectDecodeError {
+    /// Wraps the error of a field's or an element's decoder.
+    pub fn inner(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self::Inner(error.into())
+    }
+}
+
+impl Display for TasmObjectDecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "The encoding ended prematurely."),
+            Self::InvalidLengthPrefix(length) => {
+                write!(f, "Length indicator {length} is not a u32.")
+            }
+            Self::InvalidDiscriminant {
+                type_name,
+                discriminant,
+            } => write!(f, "Invalid discriminant {discriminant} for `{type_name}`."),
+            Self::TotalSizeExceeded {
+                decoded_size,
+                additional_size,
+                max_total_size,
+            } => write!(
+                f,
+                "Decoding {additional_size} more words after {decoded_size} words exceeds \
+                the maximum total size of {max_total_size}."
+            ),
+            Self::FieldSizeExceeded {
+                field_name,
+                field_size,
+                max_field_size,
+            } => write!(
+                f,
+                "Field `{field_name}` has size {field_size}, exceeding its maximum size \
+                of {max_field_size}."
+            ),
+            Self::Unsupported(reason) => write!(f, "{reason}"),
+            Self::Inner(error) => write!(f, "Decoding failed: {error}"),
+        }
+    }
+}
+
+impl Error for TasmObjectDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Inner(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the next word of the encoding, or an error if there is none.
+pub fn next_word<Itr: Iterator<Item = BFieldElement>>(iterator: &mut Itr) -> Result<BFieldElement> {
+    iterator.next().ok_or(TasmObjectDecodeError::Truncated)
+}
+
+/// Returns the next word of the encoding as a size indicator or list length, which must be
+/// a u32.
+pub fn next_length_prefix<Itr: Iterator<Item = BFieldElement>>(
+    iterator: &mut Itr,
+) -> Result<usize> {
+    let length = next_word(iterator)?;
+    match u32::try_from(length.value()) {
+        Ok(length) => Ok(length as usize),
+        Err(_) => Err(TasmObjectDecodeError::InvalidLengthPrefix(length)),
+    }
+}
+
+/// Returns the next `length` words of the encoding, or an error if there are fewer.
+pub fn next_sequence<Itr: Iterator<Item = BFieldElement>>(
+    iterator: &mut Itr,
+    length: usize,
+) -> Result<Vec<BFieldElement>> {
+    let sequence = iterator.take(length).collect_vec();
+    match sequence.len() == length {
+        true => Ok(sequence),
+        false => Err(TasmObjectDecodeError::Truncated),
     }
 }
 
@@ -78,10 +535,21 @@ pub fn decode_from_memory_with_size<T: BFieldCodec>(
         .map(|i| address + BFieldElement::new(i as u64))
         .map(|b| memory.get(&b).copied().unwrap_or(BFieldElement::new(0)))
         .collect_vec();
-    T::decode(&sequence).map_err(|e| e.into())
+    T::decode(&sequence).map_err(TasmObjectDecodeError::inner)
 }
 
 impl<T: BFieldCodec> TasmObject for Vec<T> {
+    const FIELD_NAMES: &'static [&'static str] = &[];
+
+    fn validate_encoding(library: &mut Library) -> Vec<LabelledInstruction> {
+        let validate_list_encoding = library.import(Box::new(ValidateListEncoding {
+            element_size: T::static_length(),
+        }));
+        triton_asm!(call {
+            validate_list_encoding
+        })
+    }
+
     fn get_field(_field_name: &str) -> Vec<LabelledInstruction> {
         panic!("`Vec` does not have fields; cannot access them")
     }
@@ -94,334 +562,2853 @@ impl<T: BFieldCodec> TasmObject for Vec<T> {
         panic!("`Vec` does not have fields; cannot access them")
     }
 
-    fn decode_iter<Itr: Iterator<Item = BFieldElement>>(iterator: &mut Itr) -> Result<Box<Self>> {
-        let length = iterator.next().unwrap().value() as usize;
+    fn size_in_memory() -> Vec<LabelledInstruction> {
+        let Some(element_size) = T::static_length() else {
+            panic!("The size of a `Vec` with dynamically-sized elements requires a loop")
+        };
+        triton_asm!(
+            // _ *vec
+            read_mem 1
+            pop 1
+            // _ length
+
+            push {element_size}
+            mul
+            push 1
+            add
+            // _ size
+        )
+    }
+
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        let mut decoded_size = limits.check_total_size(0, 1)?;
+        let length = next_length_prefix(iterator)?;
+        if let Some(static_size) = T::static_length() {
+            let elements_size = length.checked_mul(static_size).unwrap_or(usize::MAX);
+            limits.check_total_size(decoded_size, elements_size)?;
+        }
+
         let mut vector = vec![];
         for _ in 0..length {
             let sequence_length = if let Some(static_size) = T::static_length() {
                 static_size
             } else {
-                iterator.next().unwrap().value() as usize
+                decoded_size = limits.check_total_size(decoded_size, 1)?;
+                next_length_prefix(iterator)?
             };
-            let sequence = (0..sequence_length)
-                .map(|_| iterator.next().unwrap())
-                .collect_vec();
-            let object = *T::decode(&sequence).map_err(|e| e.into())?;
+            decoded_size = limits.check_total_size(decoded_size, sequence_length)?;
+            let sequence = next_sequence(iterator, sequence_length)?;
+            let object = *T::decode(&sequence).map_err(TasmObjectDecodeError::inner)?;
             vector.push(object);
         }
         Ok(Box::new(vector))
     }
 }
 
-/// Convenience struct for converting between string literals and field name identifiers.
-pub trait TasmObjectFieldName {
-    fn tasm_object_field_name(&self) -> String;
-}
+/// `Option<T>` is encoded as its discriminant, 0 for `None` and 1 for `Some`, followed by
+/// the encoding of the value if there is one. Since the value is not prepended by its
+/// size, sizes are only known, and decoding only possible, if `T` has a static size.
+impl<T: BFieldCodec> TasmObject for Option<T> {
+    const FIELD_NAMES: &'static [&'static str] = &["Some::field_0"];
 
-impl TasmObjectFieldName for &str {
-    fn tasm_object_field_name(&self) -> String {
-        self.to_string()
+    fn fields() -> Vec<(String, Option<usize>)> {
+        vec![("Some::field_0".to_owned(), T::static_length())]
     }
-}
 
-impl TasmObjectFieldName for i32 {
-    fn tasm_object_field_name(&self) -> String {
-        format!("field_{}", self)
+    fn get_field(field_name: &str) -> Vec<LabelledInstruction> {
+        assert_eq!(
+            "Some::field_0", field_name,
+            "`Option` has no field `{field_name}`"
+        );
+        triton_asm!(push 1 add)
     }
-}
 
-/// Convenience macro, so that we don't have to write
-/// ```ignore
-/// let field_f = <StructWithNamedFields as TasmObject>::get_field!("f");
-/// let field_0 = <StructWithUnnamedFields as TasmObject>::get_field!("field_0");
-/// ```
-/// but instead
-/// ```ignore
-/// let field_f = field!(StructWithNamedFields::f);
-/// let field_0 = field!(StructWithUnnamedFields::0);
-/// ```
-/// .
-///
-/// **Limitations** The type descriptor cannot have generic type arguments. To get around
-/// this, define a new type via `type Custom = Generic<T>` and use that instead.
-#[macro_export]
-macro_rules! field {
-    { $o : ident :: $e : ident } => {
-        <$o as $crate::structure::tasm_object::TasmObject>
-            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+    fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction> {
+        let size = option_value_size::<T>();
+        [Self::get_field(field_name), triton_asm!(push { size })].concat()
+    }
+
+    fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction> {
+        Self::get_field_with_size(field_name)
+    }
+
+    fn size_in_memory() -> Vec<LabelledInstruction> {
+        let size = option_value_size::<T>();
+        triton_asm!(
+            // _ *option
+            read_mem 1
+            pop 1
+            // _ discriminant
+
+            push {size}
+            mul
+            push 1
+            add
+            // _ size
         )
-    };
-    { $o : ident :: $e : expr } => {
-        <$o as $crate::structure::tasm_object::TasmObject>
-            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+    }
+
+    fn validate_encoding(_library: &mut Library) -> Vec<LabelledInstruction> {
+        let size = option_value_size::<T>();
+        triton_asm!(
+            // _ *option region_size
+            swap 1
+            read_mem 1
+            pop 1
+            // _ region_size discriminant
+
+            {&assert_is_bit()}
+            push {size}
+            mul
+            push 1
+            add
+            // _ region_size size
+
+            {&assert_size_fits_in_region()}
         )
-    };
-}
+    }
 
-/// Convenience macro, so that we don't have to write
-/// ```ignore
-/// let field_f = <StructWithNamedFields as TasmObject>::get_field_with_size!("f");
-/// let field_0 = <StructWithUnnamedFields as TasmObject>::get_field_with_size!("field_0");
-/// ```
-/// but instead
-/// ```ignore
-/// let field_f = field_with_size!(StructWithNamedFields::f);
-/// let field_0 = field_with_size!(StructWithUnnamedFields::0);
-/// ```
-/// and for numbered fields.
-///
-/// **Limitations** The type descriptor cannot have generic type arguments. To get around
-/// this, define a new type via `type Custom = Generic<T>` and use that instead.
-#[macro_export]
-macro_rules! field_with_size {
-    { $o : ident :: $e : ident } => {
-        <$o as $crate::structure::tasm_object::TasmObject>
-            ::get_field_with_size(
-                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
-            )
-    };
-    { $o : ident :: $e : expr } => {
-        <$o as $crate::structure::tasm_object::TasmObject>
-            ::get_field_with_size(
-                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
-            )
-    };
+    fn get_variant_discriminant() -> Vec<LabelledInstruction> {
+        triton_asm!(read_mem 1 pop 1)
+    }
+
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        let decoded_size = limits.check_total_size(0, 1)?;
+        match next_word(iterator)?.value() {
+            0 => Ok(Box::new(None)),
+            1 => {
+                let Some(size) = T::static_length() else {
+                    return Err(TasmObjectDecodeError::Unsupported(
+                        "Cannot decode `Option` of dynamically-sized type.".to_owned(),
+                    ));
+                };
+                limits.check_field_size("Some::field_0", size, decoded_size)?;
+                let sequence = next_sequence(iterator, size)?;
+                let value = *T::decode(&sequence).map_err(TasmObjectDecodeError::inner)?;
+                Ok(Box::new(Some(value)))
+            }
+            discriminant => Err(TasmObjectDecodeError::InvalidDiscriminant {
+                type_name: "Option".to_owned(),
+                discriminant,
+            }),
+        }
+    }
 }
 
-/// Turns a memory, represented as a `HashMap` from `BFieldElement`s to `BFieldElement`s,
-/// along with a starting address, into an iterator over `BFieldElement`s.
-pub struct MemoryIter<'a> {
-    memory: &'a HashMap<BFieldElement, BFieldElement>,
-    address: BFieldElement,
+fn option_value_size<T: BFieldCodec>() -> usize {
+    T::static_length().expect("The size of the value of an `Option` is only known if it is static")
 }
 
-impl<'a> MemoryIter<'a> {
-    pub fn new(memory: &'a HashMap<BFieldElement, BFieldElement>, address: BFieldElement) -> Self {
-        Self { memory, address }
+/// `Result<T, E>` is encoded like a derived enum with variants `Ok(T)` and `Err(E)`: its
+/// discriminant, 0 for `Ok` and 1 for `Err`, followed by the value, which is prepended by
+/// its size if that size is not static.
+impl<T: BFieldCodec, E: BFieldCodec> TasmObject for std::result::Result<T, E> {
+    const FIELD_NAMES: &'static [&'static str] = &["Ok::field_0", "Err::field_0"];
+
+    fn fields() -> Vec<(String, Option<usize>)> {
+        vec![
+            ("Ok::field_0".to_owned(), T::static_length()),
+            ("Err::field_0".to_owned(), E::static_length()),
+        ]
     }
-}
 
-impl<'a> Iterator for MemoryIter<'a> {
-    type Item = BFieldElement;
+    fn get_field(field_name: &str) -> Vec<LabelledInstruction> {
+        let value_offset = match result_value_static_length::<T, E>(field_name) {
+            Some(_) => 1,
+            None => 2,
+        };
+        triton_asm!(push {value_offset} add)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let element = self
-            .memory
-            .get(&self.address)
-            .copied()
-            .unwrap_or(BFieldElement::zero());
-        self.address.increment();
-        Some(element)
+    fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction> {
+        match result_value_static_length::<T, E>(field_name) {
+            Some(size) => triton_asm!(push 1 add push {size}),
+            None => triton_asm!(
+                // _ *result
+                push 1
+                add
+                read_mem 1
+                // _ size (*result)
+
+                push 2
+                add
+                swap 1
+                // _ *value size
+            ),
+        }
     }
-}
 
-#[cfg(test)]
-mod test {
-    use std::collections::HashMap;
+    fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction> {
+        match result_value_static_length::<T, E>(field_name) {
+            Some(size) => triton_asm!(push 1 add push {size}),
+            None => triton_asm!(
+                // _ *result
+                push 1
+                add
+                read_mem 1
+                // _ size (*value_start - 1)
+
+                push 1
+                add
+                swap 1
+                push 1
+                add
+                // _ *value_start jump_distance
+            ),
+        }
+    }
 
-    use arbitrary::{Arbitrary, Unstructured};
-    use itertools::Itertools;
-    use rand::RngCore;
-    use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
-    use triton_vm::instruction::LabelledInstruction;
-    use triton_vm::{proof_item::FriResponse, triton_asm, BFieldElement, NonDeterminism};
-    use twenty_first::shared_math::{bfield_codec::BFieldCodec, x_field_element::XFieldElement};
+    fn size_in_memory() -> Vec<LabelledInstruction> {
+        let ok_jump_distance = Self::get_field_start_with_jump_distance("Ok::field_0");
+        let err_jump_distance = Self::get_field_start_with_jump_distance("Err::field_0");
+        triton_asm!(
+            // _ *result
+            dup 0
+            {&ok_jump_distance}
+            swap 1
+            pop 1
+            dup 1
+            read_mem 1
+            pop 1
+            push 0
+            eq
+            mul
+            // _ *result (ok_jump_distance·[discriminant == 0])
+
+            dup 1
+            {&err_jump_distance}
+            swap 1
+            pop 1
+            dup 2
+            read_mem 1
+            pop 1
+            push 1
+            eq
+            mul
+            add
+            // _ *result jump_distance
+
+            swap 1
+            pop 1
+            push 1
+            add
+            // _ size
+        )
+    }
 
-    use crate::data_type::DataType;
-    use crate::memory::encode_to_memory;
-    use crate::{
-        empty_stack, execute_with_terminal_state, library::Library,
-        list::unsafeimplu32::length::Length, structure::tasm_object::TasmObject, Digest,
-    };
+    fn validate_encoding(_library: &mut Library) -> Vec<LabelledInstruction> {
+        let (Some(ok_size), Some(err_size)) = (T::static_length(), E::static_length()) else {
+            panic!("Validating the encoding of a `Result` requires statically-sized values")
+        };
+        triton_asm!(
+            // _ *result region_size
+            swap 1
+            read_mem 1
+            pop 1
+            // _ region_size discriminant
+
+            {&assert_is_bit()}
+            dup 0
+            push {err_size}
+            mul
+            swap 1
+            push -1
+            mul
+            push 1
+            add
+            push {ok_size}
+            mul
+            // _ region_size (err_size·discriminant) (ok_size·(1 - discriminant))
+
+            add
+            push 1
+            add
+            // _ region_size size
+
+            {&assert_size_fits_in_region()}
+        )
+    }
 
-    #[test]
-    fn test_load_and_decode_from_memory() {
-        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec)]
-        enum InnerEnum {
-            Cow(u32),
-            Horse(u128, u128),
-            Pig(XFieldElement),
-            Sheep([BFieldElement; 13]),
-        }
+    fn get_variant_discriminant() -> Vec<LabelledInstruction> {
+        triton_asm!(read_mem 1 pop 1)
+    }
+
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        let mut decoded_size = limits.check_total_size(0, 1)?;
+        let discriminant = next_word(iterator)?.value();
+        let field_name = match discriminant {
+            0 => "Ok::field_0",
+            1 => "Err::field_0",
+            _ => {
+                return Err(TasmObjectDecodeError::InvalidDiscriminant {
+                    type_name: "Result".to_owned(),
+                    discriminant,
+                })
+            }
+        };
+
+        let size = match result_value_static_length::<T, E>(field_name) {
+            Some(size) => size,
+            None => {
+                decoded_size = limits.check_total_size(decoded_size, 1)?;
+                next_length_prefix(iterator)?
+            }
+        };
+        limits.check_field_size(field_name, size, decoded_size)?;
+        let sequence = next_sequence(iterator, size)?;
+        let result = match discriminant {
+            0 => Ok(*T::decode(&sequence).map_err(TasmObjectDecodeError::inner)?),
+            _ => Err(*E::decode(&sequence).map_err(TasmObjectDecodeError::inner)?),
+        };
+        Ok(Box::new(result))
+    }
+}
+
+fn result_value_static_length<T: BFieldCodec, E: BFieldCodec>(field_name: &str) -> Option<usize> {
+    match field_name {
+        "Ok::field_0" => T::static_length(),
+        "Err::field_0" => E::static_length(),
+        unknown_field_name => panic!("`Result` has no field `{unknown_field_name}`"),
+    }
+}
+
+/// `Box<T>` lives in memory exactly like `T` does.
+impl<T: TasmObject> TasmObject for Box<T> {
+    const FIELD_NAMES: &'static [&'static str] = T::FIELD_NAMES;
+
+    fn get_field(field_name: &str) -> Vec<LabelledInstruction> {
+        T::get_field(field_name)
+    }
+
+    fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction> {
+        T::get_field_with_size(field_name)
+    }
+
+    fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction> {
+        T::get_field_start_with_jump_distance(field_name)
+    }
+
+    fn get_field_chain(field_names: &[&str]) -> Vec<LabelledInstruction> {
+        T::get_field_chain(field_names)
+    }
+
+    fn get_list_element(field_name: &str, library: &mut Library) -> Vec<LabelledInstruction> {
+        T::get_list_element(field_name, library)
+    }
+
+    fn field_offsets() -> Option<Vec<usize>> {
+        T::field_offsets()
+    }
+
+    fn fields() -> Vec<(String, Option<usize>)> {
+        T::fields()
+    }
+
+    fn size_in_memory() -> Vec<LabelledInstruction> {
+        T::size_in_memory()
+    }
+
+    fn encode_from_fields(library: &mut Library) -> Vec<LabelledInstruction> {
+        T::encode_from_fields(library)
+    }
+
+    fn build_in_memory(library: &mut Library) -> Vec<LabelledInstruction> {
+        T::build_in_memory(library)
+    }
+
+    fn destructure() -> Vec<LabelledInstruction> {
+        T::destructure()
+    }
+
+    fn validate_encoding(library: &mut Library) -> Vec<LabelledInstruction> {
+        T::validate_encoding(library)
+    }
+
+    fn get_variant_discriminant() -> Vec<LabelledInstruction> {
+        T::get_variant_discriminant()
+    }
+
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        Ok(Box::new(T::decode_iter_with_limits(iterator, limits)?))
+    }
+
+    fn decode_from_memory_with_limits(
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        address: BFieldElement,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        Ok(Box::new(T::decode_from_memory_with_limits(
+            memory, address, limits,
+        )?))
+    }
+}
+
+/// Accesses a field that is stored by pointer, *i.e.*, as the address of the field's
+/// encoding instead of the encoding itself. This allows several objects to share one
+/// large object without duplicating it. The derive macro uses this type like a custom
+/// module for fields marked `#[tasm_object(by_pointer)]`.
+///
+/// The getters dereference the pointer, such that `get_field` returns a pointer to the
+/// field's encoding, wherever that lives.
+pub struct StoredByPointer<T>(PhantomData<T>);
+
+impl<T: TasmObject> StoredByPointer<T> {
+    /// The pointer takes up one word.
+    pub fn static_length() -> Option<usize> {
+        Some(1)
+    }
+
+    /// BEFORE: _ *field_start
+    ///
+    /// AFTER: _ *field_start 1
+    pub fn jumper() -> Vec<LabelledInstruction> {
+        triton_asm!(push 1)
+    }
+
+    /// BEFORE: _ *field_start 1
+    ///
+    /// AFTER: _ *field
+    pub fn getter() -> Vec<LabelledInstruction> {
+        triton_asm!(
+            pop 1
+            read_mem 1
+            pop 1
+        )
+    }
+
+    /// BEFORE: _ *field_start 1
+    ///
+    /// AFTER: _ *field field_size
+    pub fn sizer() -> Vec<LabelledInstruction> {
+        let size_in_memory = T::size_in_memory();
+        triton_asm!(
+            {&Self::getter()}
+            dup 0
+            {&size_in_memory}
+        )
+    }
+
+    /// Decodes the field that the pointer in the given sequence points to.
+    pub fn decode_from_memory_with_limits(
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        sequence: &[BFieldElement],
+        limits: &DecodingLimits,
+    ) -> Result<Box<T>> {
+        let [pointer] = sequence else {
+            return Err(TasmObjectDecodeError::Truncated);
+        };
+        T::decode_from_memory_with_limits(memory, *pointer, limits)
+    }
+}
+
+/// Arrays are encoded as their elements, in order, where every element is prepended by
+/// its size if that size is not static. The elements are named `field_0`, `field_1`, and
+/// so on. Since these names depend on `N`, they are not listed in `FIELD_NAMES`.
+impl<T: BFieldCodec, const N: usize> TasmObject for [T; N] {
+    const FIELD_NAMES: &'static [&'static str] = &[];
+
+    fn fields() -> Vec<(String, Option<usize>)> {
+        (0..N)
+            .map(|i| (format!("field_{i}"), T::static_length()))
+            .collect()
+    }
+
+    fn get_field(field_name: &str) -> Vec<LabelledInstruction> {
+        let index = field_index(field_name, N, "array");
+        get_field_in_sequence(&[T::static_length(); N], index)
+    }
+
+    fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction> {
+        let index = field_index(field_name, N, "array");
+        get_field_with_size_in_sequence(&[T::static_length(); N], index)
+    }
+
+    fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction> {
+        let index = field_index(field_name, N, "array");
+        get_field_start_with_jump_distance_in_sequence(&[T::static_length(); N], index)
+    }
+
+    fn size_in_memory() -> Vec<LabelledInstruction> {
+        size_of_sequence(&[T::static_length(); N])
+    }
+
+    fn validate_encoding(_library: &mut Library) -> Vec<LabelledInstruction> {
+        let Some(element_size) = T::static_length() else {
+            panic!("Validating the encoding of an array requires statically-sized elements")
+        };
+        validate_statically_sized_encoding(N * element_size)
+    }
+
+    fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+        iterator: &mut Itr,
+        limits: &DecodingLimits,
+    ) -> Result<Box<Self>> {
+        let mut decoded_size = 0;
+        let mut elements = Vec::with_capacity(N);
+        for i in 0..N {
+            let field_name = format!("field_{i}");
+            let element =
+                decode_field_in_sequence::<T, _>(iterator, limits, &field_name, &mut decoded_size)?;
+            elements.push(element);
+        }
+        let Ok(array) = elements.try_into() else {
+            unreachable!("exactly {N} elements were decoded")
+        };
+        Ok(Box::new(array))
+    }
+}
+
+/// Tuples are encoded like tuple structs, except that the fields are stored in order of
+/// declaration: every field is prepended by its size if that size is not static.
+macro_rules! impl_tasm_object_for_tuple {
+    ($($type_parameter:ident $index:tt),+) => {
+        impl<$($type_parameter: BFieldCodec),+> TasmObject for ($($type_parameter,)+) {
+            const FIELD_NAMES: &'static [&'static str] =
+                &[$(concat!("field_", stringify!($index))),+];
+
+            fn fields() -> Vec<(String, Option<usize>)> {
+                vec![$(
+                    (concat!("field_", stringify!($index)).to_owned(), $type_parameter::static_length())
+                ),+]
+            }
+
+            fn get_field(field_name: &str) -> Vec<LabelledInstruction> {
+                let index = field_index(field_name, Self::FIELD_NAMES.len(), "tuple");
+                get_field_in_sequence(&[$($type_parameter::static_length()),+], index)
+            }
+
+            fn get_field_with_size(field_name: &str) -> Vec<LabelledInstruction> {
+                let index = field_index(field_name, Self::FIELD_NAMES.len(), "tuple");
+                get_field_with_size_in_sequence(&[$($type_parameter::static_length()),+], index)
+            }
+
+            fn get_field_start_with_jump_distance(field_name: &str) -> Vec<LabelledInstruction> {
+                let index = field_index(field_name, Self::FIELD_NAMES.len(), "tuple");
+                get_field_start_with_jump_distance_in_sequence(
+                    &[$($type_parameter::static_length()),+],
+                    index,
+                )
+            }
+
+            fn size_in_memory() -> Vec<LabelledInstruction> {
+                size_of_sequence(&[$($type_parameter::static_length()),+])
+            }
+
+            fn validate_encoding(_library: &mut Library) -> Vec<LabelledInstruction> {
+                let Some(field_sizes) = [$($type_parameter::static_length()),+]
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    panic!("Validating the encoding of a tuple requires statically-sized fields")
+                };
+                validate_statically_sized_encoding(field_sizes.into_iter().sum())
+            }
+
+            fn decode_iter_with_limits<Itr: Iterator<Item = BFieldElement>>(
+                iterator: &mut Itr,
+                limits: &DecodingLimits,
+            ) -> Result<Box<Self>> {
+                let mut decoded_size = 0;
+                Ok(Box::new(($(
+                    decode_field_in_sequence::<$type_parameter, _>(
+                        iterator,
+                        limits,
+                        concat!("field_", stringify!($index)),
+                        &mut decoded_size,
+                    )?,
+                )+)))
+            }
+        }
+    };
+}
+
+impl_tasm_object_for_tuple!(A 0, B 1);
+impl_tasm_object_for_tuple!(A 0, B 1, C 2);
+
+/// Parses field names of the form `field_i` of arrays and tuples.
+fn field_index(field_name: &str, num_fields: usize, type_name: &str) -> usize {
+    field_name
+        .strip_prefix("field_")
+        .and_then(|index| index.parse().ok())
+        .filter(|&index| index < num_fields)
+        .unwrap_or_else(|| panic!("This {type_name} has no field `{field_name}`."))
+}
+
+/// Returns tasm code that jumps over the first `index` fields of a sequence of fields
+/// with the given static sizes, where dynamically-sized fields are prepended by their
+/// size.
+///
+/// BEFORE: _ *first_field_start
+///
+/// AFTER: _ *field_start
+fn jump_over_fields_in_sequence(
+    field_sizes: &[Option<usize>],
+    index: usize,
+) -> Vec<LabelledInstruction> {
+    let mut code = vec![];
+    for field_size in &field_sizes[..index] {
+        let jump = match field_size {
+            Some(size) => triton_asm!(push {size} add),
+            None => triton_asm!(read_mem 1 push 2 add add),
+        };
+        code.extend(jump);
+    }
+    code
+}
+
+fn get_field_in_sequence(field_sizes: &[Option<usize>], index: usize) -> Vec<LabelledInstruction> {
+    let field_start = jump_over_fields_in_sequence(field_sizes, index);
+    match field_sizes[index] {
+        Some(_) => field_start,
+        None => [field_start, triton_asm!(push 1 add)].concat(),
+    }
+}
+
+fn get_field_with_size_in_sequence(
+    field_sizes: &[Option<usize>],
+    index: usize,
+) -> Vec<LabelledInstruction> {
+    let field_start = jump_over_fields_in_sequence(field_sizes, index);
+    let size = match field_sizes[index] {
+        Some(size) => triton_asm!(push { size }),
+        None => triton_asm!(
+            // _ *field_start
+            read_mem 1
+            push 2
+            add
+            swap 1
+            // _ *field field_size
+        ),
+    };
+    [field_start, size].concat()
+}
+
+fn get_field_start_with_jump_distance_in_sequence(
+    field_sizes: &[Option<usize>],
+    index: usize,
+) -> Vec<LabelledInstruction> {
+    let field_start = jump_over_fields_in_sequence(field_sizes, index);
+    let jump_distance = match field_sizes[index] {
+        Some(size) => triton_asm!(push { size }),
+        None => triton_asm!(
+            // _ *field_start
+            read_mem 1
+            push 1
+            add
+            swap 1
+            push 1
+            add
+            // _ *field_start jump_distance
+        ),
+    };
+    [field_start, jump_distance].concat()
+}
+
+fn size_of_sequence(field_sizes: &[Option<usize>]) -> Vec<LabelledInstruction> {
+    let end = jump_over_fields_in_sequence(field_sizes, field_sizes.len());
+    triton_asm!(
+        // _ *object
+        dup 0
+        {&end}
+        // _ *object *end
+
+        swap 1
+        push -1
+        mul
+        add
+        // _ size
+    )
+}
+
+/// Returns tasm code that validates the encoding of an object of the given static size.
+///
+/// BEFORE: _ *object region_size
+///
+/// AFTER: _ size
+fn validate_statically_sized_encoding(size: usize) -> Vec<LabelledInstruction> {
+    triton_asm!(
+        swap 1
+        pop 1
+        push {size}
+        {&assert_size_fits_in_region()}
+    )
+}
+
+/// BEFORE: _ value
+///
+/// AFTER: _ value
+fn assert_is_bit() -> Vec<LabelledInstruction> {
+    triton_asm!(
+        dup 0
+        dup 1
+        push -1
+        add
+        mul
+        push 0
+        eq
+        assert
+    )
+}
+
+/// BEFORE: _ region_size size
+///
+/// AFTER: _ size
+fn assert_size_fits_in_region() -> Vec<LabelledInstruction> {
+    triton_asm!(
+        dup 0
+        swap 2
+        push 1
+        add
+        swap 1
+        lt
+        assert
+    )
+}
+
+/// Decodes the next field of a sequence of fields, where dynamically-sized fields are
+/// prepended by their size, and keeps track of the number of decoded words.
+fn decode_field_in_sequence<T: BFieldCodec, Itr: Iterator<Item = BFieldElement>>(
+    iterator: &mut Itr,
+    limits: &DecodingLimits,
+    field_name: &str,
+    decoded_size: &mut usize,
+) -> Result<T> {
+    let size = match T::static_length() {
+        Some(size) => size,
+        None => {
+            *decoded_size = limits.check_total_size(*decoded_size, 1)?;
+            next_length_prefix(iterator)?
+        }
+    };
+    *decoded_size = limits.check_field_size(field_name, size, *decoded_size)?;
+    let sequence = next_sequence(iterator, size)?;
+    Ok(*T::decode(&sequence).map_err(TasmObjectDecodeError::inner)?)
+}
+
+/// Convenience struct for converting between string literals and field name identifiers.
+pub trait TasmObjectFieldName {
+    fn tasm_object_field_name(&self) -> String;
+}
+
+impl TasmObjectFieldName for &str {
+    fn tasm_object_field_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TasmObjectFieldName for i32 {
+    fn tasm_object_field_name(&self) -> String {
+        format!("field_{}", self)
+    }
+}
+
+/// Convenience macro, so that we don't have to write
+/// ```ignore
+/// let field_f = <StructWithNamedFields as TasmObject>::get_field!("f");
+/// let field_0 = <StructWithUnnamedFields as TasmObject>::get_field!("field_0");
+/// ```
+/// but instead
+/// ```ignore
+/// let field_f = field!(StructWithNamedFields::f);
+/// let field_0 = field!(StructWithUnnamedFields::0);
+/// ```
+/// .
+///
+/// Types with generic type arguments can be given either with or without turbofish:
+/// ```ignore
+/// let field_inner = field!(Wrapper::<Digest>::inner);
+/// let field_inner = field!(Wrapper<Digest>::inner);
+/// ```
+///
+/// Field names given as identifiers or literals are checked against
+/// [`TasmObject::FIELD_NAMES`] at compile time, so a typo results in a build error. This
+/// requires the type to be concrete, *i.e.*, not a generic type parameter.
+#[macro_export]
+macro_rules! field {
+    { @assert_declared $t : ty , $e : tt } => {
+        const _: () = assert!(
+            $crate::structure::tasm_object::field_name_is_declared(
+                <$t as $crate::structure::tasm_object::TasmObject>::FIELD_NAMES,
+                stringify!($e),
+            ),
+            concat!("`", stringify!($t), "` has no field `", stringify!($e), "`."),
+        );
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : ident } => {
+        $crate::field!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : literal } => {
+        $crate::field!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : expr } => {
+        $crate::field!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident < $( $g : ty ),+ > :: $e : ident } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+        )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : literal } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+        )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : expr } => {
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+        )
+    };
+    { $o : ident :: $e : ident } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+        )
+    }};
+    { $o : ident :: $e : literal } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+        )
+    }};
+    { $o : ident :: $e : expr } => {
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field(& $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+        )
+    };
+}
+
+/// Convenience macro, so that we don't have to write
+/// ```ignore
+/// let field_f = <StructWithNamedFields as TasmObject>::get_field_with_size!("f");
+/// let field_0 = <StructWithUnnamedFields as TasmObject>::get_field_with_size!("field_0");
+/// ```
+/// but instead
+/// ```ignore
+/// let field_f = field_with_size!(StructWithNamedFields::f);
+/// let field_0 = field_with_size!(StructWithUnnamedFields::0);
+/// ```
+/// and for numbered fields.
+///
+/// Types with generic type arguments and compile-time validation of field names are
+/// supported like in [`field!`].
+#[macro_export]
+macro_rules! field_with_size {
+    { $o : ident :: < $( $g : ty ),+ > :: $e : ident } => {
+        $crate::field_with_size!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : literal } => {
+        $crate::field_with_size!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : expr } => {
+        $crate::field_with_size!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident < $( $g : ty ),+ > :: $e : ident } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+            )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : literal } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : expr } => {
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    };
+    { $o : ident :: $e : ident } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+            )
+    }};
+    { $o : ident :: $e : literal } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    }};
+    { $o : ident :: $e : expr } => {
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_size(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    };
+}
+
+/// Convenience macro, so that we don't have to write
+/// ```ignore
+/// let field_f = <StructWithNamedFields as TasmObject>::get_field_with_bounds_check!("f");
+/// ```
+/// but instead
+/// ```ignore
+/// let field_f = field_with_bounds_check!(StructWithNamedFields::f);
+/// ```
+/// Numbered fields, types with generic type arguments, and compile-time validation of
+/// field names are supported like in [`field!`].
+#[macro_export]
+macro_rules! field_with_bounds_check {
+    { $o : ident :: < $( $g : ty ),+ > :: $e : ident } => {
+        $crate::field_with_bounds_check!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : literal } => {
+        $crate::field_with_bounds_check!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > :: $e : expr } => {
+        $crate::field_with_bounds_check!($o < $( $g ),+ > :: $e)
+    };
+    { $o : ident < $( $g : ty ),+ > :: $e : ident } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+            )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : literal } => {{
+        $crate::field!(@assert_declared $o < $( $g ),+ >, $e);
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    }};
+    { $o : ident < $( $g : ty ),+ > :: $e : expr } => {
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    };
+    { $o : ident :: $e : ident } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&stringify!($e))
+            )
+    }};
+    { $o : ident :: $e : literal } => {{
+        $crate::field!(@assert_declared $o, $e);
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    }};
+    { $o : ident :: $e : expr } => {
+        <$o as $crate::structure::tasm_object::TasmObject>
+            ::get_field_with_bounds_check(
+                & $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+            )
+    };
+}
+
+/// Determines whether a field name, given as a token, occurs in the list of declared field
+/// names. A token consisting of digits only, like `0`, refers to the numbered field
+/// `field_0`, and string literals are stripped of their quotes.
+///
+/// This function is `const` such that macros like `field!` can reject unknown field names
+/// at compile time.
+pub const fn field_name_is_declared(field_names: &[&str], field_name_token: &str) -> bool {
+    const fn bytes_eq(a: &[u8], a_offset: usize, b: &[u8], b_offset: usize, len: usize) -> bool {
+        if a.len() < a_offset + len || b.len() < b_offset + len {
+            return false;
+        }
+        let mut i = 0;
+        while i < len {
+            if a[a_offset + i] != b[b_offset + i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    const NUMBERED_FIELD_PREFIX: &[u8] = b"field_";
+
+    let token = field_name_token.as_bytes();
+    let token_is_number = {
+        let mut is_number = !token.is_empty();
+        let mut i = 0;
+        while i < token.len() {
+            is_number &= token[i].is_ascii_digit();
+            i += 1;
+        }
+        is_number
+    };
+    let token_is_string_literal =
+        token.len() >= 2 && token[0] == b'"' && token[token.len() - 1] == b'"';
+
+    let mut i = 0;
+    while i < field_names.len() {
+        let field_name = field_names[i].as_bytes();
+        let is_match = if token_is_number {
+            let prefix_len = NUMBERED_FIELD_PREFIX.len();
+            field_name.len() == prefix_len + token.len()
+                && bytes_eq(field_name, 0, NUMBERED_FIELD_PREFIX, 0, prefix_len)
+                && bytes_eq(field_name, prefix_len, token, 0, token.len())
+        } else if token_is_string_literal {
+            field_name.len() == token.len() - 2
+                && bytes_eq(field_name, 0, token, 1, field_name.len())
+        } else {
+            field_name.len() == token.len() && bytes_eq(field_name, 0, token, 0, token.len())
+        };
+        if is_match {
+            return true;
+        }
+        i += 1;
+    }
+
+    false
+}
+
+/// Convenience macro for accessing nested fields, so that we don't have to write
+/// ```ignore
+/// let field_c = [field!(Outer::a), field!(Middle::b), field!(Inner::c)].concat();
+/// ```
+/// but instead
+/// ```ignore
+/// let field_c = field_chain!(Outer::a::b::c);
+/// ```
+/// Numbered fields and types with generic type arguments are supported like in [`field!`].
+#[macro_export]
+macro_rules! field_chain {
+    { @field_name $e : ident } => {
+        stringify!($e).to_string()
+    };
+    { @field_name $e : literal } => {
+        $crate::structure::tasm_object::TasmObjectFieldName::tasm_object_field_name(&$e)
+    };
+    { $o : ident :: < $( $g : ty ),+ > $( :: $e : tt )+ } => {
+        $crate::field_chain!($o < $( $g ),+ > $( :: $e )+)
+    };
+    { $o : ident < $( $g : ty ),+ > $( :: $e : tt )+ } => {{
+        let field_names = [ $( $crate::field_chain!(@field_name $e) ),+ ];
+        <$o < $( $g ),+ > as $crate::structure::tasm_object::TasmObject>::get_field_chain(
+            &field_names.iter().map(|name| name.as_str()).collect::<Vec<_>>()
+        )
+    }};
+    { $o : ident $( :: $e : tt )+ } => {{
+        let field_names = [ $( $crate::field_chain!(@field_name $e) ),+ ];
+        <$o as $crate::structure::tasm_object::TasmObject>::get_field_chain(
+            &field_names.iter().map(|name| name.as_str()).collect::<Vec<_>>()
+        )
+    }};
+}
+
+/// Used by the derive macro to follow a chain of field names into the type of a field,
+/// which may or may not implement [`TasmObject`]. Method resolution picks
+/// [`FieldChainViaTasmObject`] if it does and falls back on [`FieldChainFallback`] if it
+/// doesn't.
+#[doc(hidden)]
+pub struct FieldChainProbe<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait FieldChainViaTasmObject {
+    fn get_field_chain_probe(&self, field_names: &[&str]) -> Vec<LabelledInstruction>;
+}
+
+impl<T: TasmObject> FieldChainViaTasmObject for FieldChainProbe<T> {
+    fn get_field_chain_probe(&self, field_names: &[&str]) -> Vec<LabelledInstruction> {
+        T::get_field_chain(field_names)
+    }
+}
+
+#[doc(hidden)]
+pub trait FieldChainFallback {
+    fn get_field_chain_probe(&self, field_names: &[&str]) -> Vec<LabelledInstruction>;
+}
+
+impl<T> FieldChainFallback for &FieldChainProbe<T> {
+    fn get_field_chain_probe(&self, field_names: &[&str]) -> Vec<LabelledInstruction> {
+        panic!(
+            "Type `{}` does not implement `TasmObject`; cannot access its field `{}`.",
+            std::any::type_name::<T>(),
+            field_names[0]
+        )
+    }
+}
+
+/// Used by the derive macro to validate the encoding of a field, whose type may or may not
+/// implement [`TasmObject`]. Method resolution picks [`ValidateEncodingViaTasmObject`] if
+/// it does and falls back on [`ValidateEncodingFallback`] if it doesn't. The fallback
+/// panics, like the default implementation of [`TasmObject::validate_encoding`].
+#[doc(hidden)]
+pub struct ValidateEncodingProbe<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait ValidateEncodingViaTasmObject {
+    fn validate_encoding_probe(&self, library: &mut Library) -> Vec<LabelledInstruction>;
+}
+
+impl<T: TasmObject> ValidateEncodingViaTasmObject for ValidateEncodingProbe<T> {
+    fn validate_encoding_probe(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        T::validate_encoding(library)
+    }
+}
+
+#[doc(hidden)]
+pub trait ValidateEncodingFallback {
+    fn validate_encoding_probe(&self, library: &mut Library) -> Vec<LabelledInstruction>;
+}
+
+impl<T> ValidateEncodingFallback for &ValidateEncodingProbe<T> {
+    fn validate_encoding_probe(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        panic!(
+            "Type `{}` does not implement `TasmObject`; cannot validate its encoding.",
+            std::any::type_name::<T>()
+        )
+    }
+}
+
+/// Turns a memory, represented as a `HashMap` from `BFieldElement`s to `BFieldElement`s,
+/// along with a starting address, into an iterator over `BFieldElement`s.
+pub struct MemoryIter<'a> {
+    memory: &'a HashMap<BFieldElement, BFieldElement>,
+    address: BFieldElement,
+}
+
+impl<'a> MemoryIter<'a> {
+    pub fn new(memory: &'a HashMap<BFieldElement, BFieldElement>, address: BFieldElement) -> Self {
+        Self { memory, address }
+    }
+}
+
+impl<'a> Iterator for MemoryIter<'a> {
+    type Item = BFieldElement;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self
+            .memory
+            .get(&self.address)
+            .copied()
+            .unwrap_or(BFieldElement::zero());
+        self.address.increment();
+        Some(element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use arbitrary::{Arbitrary, Unstructured};
+    use itertools::Itertools;
+    use rand::RngCore;
+    use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::{proof_item::FriResponse, triton_asm, BFieldElement, Claim, NonDeterminism};
+    use twenty_first::shared_math::{bfield_codec::BFieldCodec, x_field_element::XFieldElement};
+
+    use crate::data_type::DataType;
+    use crate::list::higher_order::inner_function::{InnerFunction, RawCode};
+    use crate::memory::dyn_malloc::{DYN_MALLOC_ADDRESS, FIRST_DYNAMICALLY_ALLOCATED_ADDRESS};
+    use crate::memory::encode_to_memory;
+    use crate::{
+        empty_stack, execute_with_terminal_state,
+        library::Library,
+        list::unsafeimplu32::length::Length,
+        structure::tasm_object::{
+            field_name_is_declared, DecodingLimits, TasmObject, TasmObjectDecodeError,
+        },
+        Digest,
+    };
+
+    #[test]
+    fn test_load_and_decode_from_memory() {
+        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
+        enum InnerEnum {
+            Cow(u32),
+            Horse(u128, u128),
+            Pig(XFieldElement),
+            Sheep([BFieldElement; 13]),
+        }
 
         #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
         struct InnerStruct(XFieldElement, u32);
 
-        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
-        struct OuterStruct {
-            o: InnerEnum,
-            a: Vec<Option<bool>>,
-            b: InnerStruct,
-            p: InnerEnum,
-            c: BFieldElement,
-            l: InnerEnum,
+        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject)]
+        struct OuterStruct {
+            o: InnerEnum,
+            a: Vec<Option<bool>>,
+            b: InnerStruct,
+            p: InnerEnum,
+            c: BFieldElement,
+            l: InnerEnum,
+        }
+
+        fn pseudorandom_object(seed: [u8; 32]) -> OuterStruct {
+            let mut rng: StdRng = SeedableRng::from_seed(seed);
+            let a = (0..19)
+                .map(|_| if rng.gen() { Some(rng.gen()) } else { None })
+                .collect_vec();
+            let b0: XFieldElement = rng.gen();
+            let b1: u32 = rng.gen();
+            let c: BFieldElement = rng.gen();
+
+            OuterStruct {
+                o: InnerEnum::Pig(XFieldElement::new_const(443u64.into())),
+                a,
+                b: InnerStruct(b0, b1),
+                p: InnerEnum::Cow(999),
+                c,
+                l: InnerEnum::Horse(1 << 99, 1 << 108),
+            }
+        }
+
+        let mut rng = thread_rng();
+        let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+
+        let object = pseudorandom_object(rng.gen());
+        let address = rng.gen();
+        encode_to_memory(&mut memory, address, object.clone());
+        let object_again: OuterStruct = *OuterStruct::decode_from_memory(&memory, address).unwrap();
+        assert_eq!(object, object_again);
+
+        let inner_enum = InnerEnum::Horse(1 << 99, 1 << 108);
+        encode_to_memory(&mut memory, address, inner_enum.clone());
+        let inner_enum_again: InnerEnum = *InnerEnum::decode_from_memory(&memory, address).unwrap();
+        assert_eq!(inner_enum, inner_enum_again);
+    }
+
+    /// Test derivation of field getters and manual derivations of the `field!` macro
+    mod derive_tests {
+        use triton_vm::Program;
+
+        use super::*;
+
+        #[test]
+        fn load_and_decode_struct_with_named_fields_from_memory() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct NamedFields {
+                a: Digest,
+                b: BFieldElement,
+                c: u128,
+                d: Vec<Digest>,
+                e: XFieldElement,
+                f: Vec<u32>,
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let random_object = NamedFields::arbitrary(&mut unstructured).unwrap();
+            let random_address: u64 = thread_rng().gen_range(0..(1 << 30));
+            let address = random_address.into();
+            let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+
+            encode_to_memory(&mut memory, address, random_object.clone());
+            let object_again: NamedFields =
+                *NamedFields::decode_from_memory(&memory, address).unwrap();
+            assert_eq!(random_object, object_again);
+
+            let mut library = Library::new();
+            let length_d = library.import(Box::new(Length {
+                data_type: DataType::Digest,
+            }));
+            let length_f = library.import(Box::new(Length {
+                data_type: DataType::U32,
+            }));
+            let code = triton_asm! {
+                    // _ *obj
+                    dup 0 {&field!(NamedFields::d)}
+
+                    // _ *obj *d
+                    swap 1
+                   {&field!(NamedFields::f)}
+                    // _ *d *f
+
+                    call {length_f}
+                    // _ *d f_length
+
+                    swap 1
+                    call {length_d}
+                    // _ f_length d_length
+            };
+
+            let mut stack = get_final_stack(&random_object, library, code);
+            let extracted_d_length = stack.pop().unwrap().value() as usize;
+            let extracted_f_length = stack.pop().unwrap().value() as usize;
+
+            assert_eq!(random_object.d.len(), extracted_d_length);
+            assert_eq!(random_object.f.len(), extracted_f_length);
+        }
+
+        #[test]
+        fn load_and_decode_tuple_struct_containing_enums_from_memory() {
+            #[derive(BFieldCodec, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            enum MyEnum {
+                A(u64, Digest),
+                B,
+                C,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct TupleStruct(
+                Vec<XFieldElement>,
+                MyEnum,
+                u32,
+                Vec<Digest>,
+                Digest,
+                Vec<BFieldElement>,
+                Digest,
+            );
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let random_object = TupleStruct::arbitrary(&mut unstructured).unwrap();
+            let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+            let random_address: u64 = thread_rng().gen_range(0..(1 << 30));
+            let address = random_address.into();
+
+            encode_to_memory(&mut memory, address, random_object.clone());
+            let object_again: TupleStruct =
+                *TupleStruct::decode_from_memory(&memory, address).unwrap();
+            assert_eq!(random_object, object_again);
+
+            // code snippet to access object's fields
+            let mut library = Library::new();
+            let length_digests = library.import(Box::new(Length {
+                data_type: DataType::Digest,
+            }));
+            let length_bfes = library.import(Box::new(Length {
+                data_type: DataType::Bfe,
+            }));
+            let length_xfes = library.import(Box::new(Length {
+                data_type: DataType::Xfe,
+            }));
+            let code = triton_asm! {
+                // _ *obj
+
+                dup 0
+                {&field!(TupleStruct::3)} // _ *obj *digests
+                swap 1                    // _ *digests *obj
+
+                dup 0
+                {&field!(TupleStruct::5)} // _ *digests *obj *bfes
+                swap 1                    // _ *digests *bfes *obj
+
+                {&field!(TupleStruct::0)} // _ *digests *bfes *xfes
+                call {length_xfes}     // _ *digests *bfes xfe_count
+                swap 2                 // _ xfe_count *bfes *digests
+                call {length_digests}  // _ xfe_count *bfes digest_count
+                swap 1
+                call {length_bfes}     // _ xfe_count digest_count bfe_count
+            };
+
+            // extract list lengths
+            let mut stack = get_final_stack(&random_object, library, code);
+            let extracted_bfe_count = stack.pop().unwrap().value() as usize;
+            let extracted_digest_count = stack.pop().unwrap().value() as usize;
+            let extracted_xfe_count = stack.pop().unwrap().value() as usize;
+
+            // assert correct lengths
+            assert_eq!(random_object.3.len(), extracted_digest_count);
+            assert_eq!(random_object.5.len(), extracted_bfe_count);
+            assert_eq!(random_object.0.len(), extracted_xfe_count);
+        }
+
+        #[test]
+        fn load_and_decode_enum_from_memory() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            enum MyEnum {
+                A,
+                B(u64, Vec<Digest>),
+                C(Vec<XFieldElement>, u32, Vec<BFieldElement>),
+            }
+
+            let mut rng = thread_rng();
+            let xfes = (0..17).map(|_| rng.gen::<XFieldElement>()).collect_vec();
+            let bfes = (0..23).map(|_| rng.gen::<BFieldElement>()).collect_vec();
+            let random_object = MyEnum::C(xfes, rng.gen(), bfes);
+
+            for object in [
+                MyEnum::A,
+                MyEnum::B(rng.gen(), vec![]),
+                random_object.clone(),
+            ] {
+                let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+                let address = rng.gen_range(0..(1u64 << 30)).into();
+                encode_to_memory(&mut memory, address, object.clone());
+                let object_again: MyEnum = *MyEnum::decode_from_memory(&memory, address).unwrap();
+                assert_eq!(object, object_again);
+            }
+
+            // code snippet to access the discriminant and the variant's fields
+            let mut library = Library::new();
+            let length_xfes = library.import(Box::new(Length {
+                data_type: DataType::Xfe,
+            }));
+            let length_bfes = library.import(Box::new(Length {
+                data_type: DataType::Bfe,
+            }));
+            let code = triton_asm! {
+                // _ *obj
+                dup 0
+                {&MyEnum::get_variant_discriminant()} // _ *obj discriminant
+                swap 1                                // _ discriminant *obj
+
+                dup 0
+                {&MyEnum::get_field("C::field_0")}    // _ discriminant *obj *xfes
+                call {length_xfes}                    // _ discriminant *obj xfe_count
+                swap 1                                // _ discriminant xfe_count *obj
+
+                {&MyEnum::get_field("C::field_2")}    // _ discriminant xfe_count *bfes
+                call {length_bfes}                    // _ discriminant xfe_count bfe_count
+            };
+
+            let mut stack = get_final_stack(&random_object, library, code);
+            let extracted_bfe_count = stack.pop().unwrap().value() as usize;
+            let extracted_xfe_count = stack.pop().unwrap().value() as usize;
+            let extracted_discriminant = stack.pop().unwrap().value();
+
+            let MyEnum::C(xfes, _, bfes) = random_object else {
+                unreachable!()
+            };
+            assert_eq!(2, extracted_discriminant);
+            assert_eq!(xfes.len(), extracted_xfe_count);
+            assert_eq!(bfes.len(), extracted_bfe_count);
+        }
+
+        #[test]
+        fn access_fields_of_generic_struct() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Wrapper<T: BFieldCodec> {
+                inner: T,
+                list: Vec<Digest>,
+            }
+
+            let mut rng = thread_rng();
+            let inner = (0..11).map(|_| rng.gen::<XFieldElement>()).collect_vec();
+            let list = (0..7).map(|_| rng.gen::<Digest>()).collect_vec();
+            let object = Wrapper { inner, list };
+
+            let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+            let address = rng.gen_range(0..(1u64 << 30)).into();
+            encode_to_memory(&mut memory, address, object.clone());
+            let object_again: Wrapper<Vec<XFieldElement>> =
+                *Wrapper::decode_from_memory(&memory, address).unwrap();
+            assert_eq!(object, object_again);
+
+            let mut library = Library::new();
+            let length_xfes = library.import(Box::new(Length {
+                data_type: DataType::Xfe,
+            }));
+            let length_digests = library.import(Box::new(Length {
+                data_type: DataType::Digest,
+            }));
+            let code = triton_asm! {
+                // _ *obj
+                dup 0
+                {&field!(Wrapper::<Vec<XFieldElement>>::inner)} // _ *obj *inner
+                call {length_xfes}                             // _ *obj inner_length
+                swap 1                                         // _ inner_length *obj
+                {&field!(Wrapper<Vec<XFieldElement>>::list)}   // _ inner_length *list
+                call {length_digests}                          // _ inner_length list_length
+            };
+
+            let mut stack = get_final_stack(&object, library, code);
+            let extracted_list_length = stack.pop().unwrap().value() as usize;
+            let extracted_inner_length = stack.pop().unwrap().value() as usize;
+
+            assert_eq!(object.inner.len(), extracted_inner_length);
+            assert_eq!(object.list.len(), extracted_list_length);
+        }
+
+        #[test]
+        fn access_fields_of_const_generic_struct() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Chunk<const N: usize> {
+                data: [u32; N],
+                tag: u64,
+                rest: Vec<[XFieldElement; N]>,
+            }
+
+            fn check<const N: usize>(object: Chunk<N>) {
+                let expected_fields = vec![
+                    ("data".to_owned(), Some(N)),
+                    ("tag".to_owned(), Some(2)),
+                    ("rest".to_owned(), None),
+                ];
+                assert_eq!(expected_fields, Chunk::<N>::fields());
+
+                let code = triton_asm!(
+                    // _ *object
+                    dup 0 {&Chunk::<N>::get_field("data")}
+                    // _ *object *data
+                    dup 1 {&Chunk::<N>::get_field_with_size("rest")}
+                    // _ *object *data *rest rest_size
+                );
+                let stack = get_final_stack(&object, Library::new(), code);
+                let [object_pointer, data_pointer, rest_pointer, rest_size] =
+                    stack[stack.len() - 4..]
+                else {
+                    panic!("stack must have at least four elements");
+                };
+
+                // fields are stored in reverse order: rest, tag, data
+                let rest_length = object.rest.encode().len() as u64;
+                assert_eq!(object_pointer + BFieldElement::new(1), rest_pointer);
+                assert_eq!(rest_length, rest_size.value());
+                let data_offset = 1 + rest_length + 2;
+                assert_eq!(
+                    object_pointer + BFieldElement::new(data_offset),
+                    data_pointer
+                );
+
+                let decoded = Chunk::<N>::decode_iter(&mut object.encode().into_iter());
+                assert_eq!(object, *decoded.unwrap());
+            }
+
+            let xfe = XFieldElement::new_const(BFieldElement::new(7));
+            check(Chunk {
+                data: [1, 2, 3],
+                tag: 1 << 40,
+                rest: vec![[xfe; 3]; 2],
+            });
+            check(Chunk {
+                data: [],
+                tag: 5,
+                rest: vec![[]; 4],
+            });
+        }
+
+        #[test]
+        fn access_nested_fields_through_field_chain() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Inner {
+                a: Vec<u32>,
+                b: Vec<XFieldElement>,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Middle(Vec<Digest>, Inner, u64);
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Outer {
+                x: Vec<BFieldElement>,
+                middle: Middle,
+                y: Digest,
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let random_object = Outer::arbitrary(&mut unstructured).unwrap();
+
+            assert_eq!(
+                [field!(Outer::middle), field!(Middle::1), field!(Inner::b)].concat(),
+                field_chain!(Outer::middle::1::b)
+            );
+
+            let mut library = Library::new();
+            let length_u32s = library.import(Box::new(Length {
+                data_type: DataType::U32,
+            }));
+            let length_xfes = library.import(Box::new(Length {
+                data_type: DataType::Xfe,
+            }));
+            let code = triton_asm! {
+                // _ *outer
+                dup 0
+                {&field_chain!(Outer::middle::1::a)} // _ *outer *a
+                call {length_u32s}                   // _ *outer a_length
+                swap 1                               // _ a_length *outer
+                {&field_chain!(Outer::middle::1::b)} // _ a_length *b
+                call {length_xfes}                   // _ a_length b_length
+            };
+
+            let mut stack = get_final_stack(&random_object, library, code);
+            let extracted_b_length = stack.pop().unwrap().value() as usize;
+            let extracted_a_length = stack.pop().unwrap().value() as usize;
+
+            assert_eq!(random_object.middle.1.a.len(), extracted_a_length);
+            assert_eq!(random_object.middle.1.b.len(), extracted_b_length);
+        }
+
+        #[should_panic]
+        #[test]
+        fn field_chain_into_field_without_subfields_panics() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Shallow {
+                a: Digest,
+            }
+
+            field_chain!(Shallow::a::b);
+        }
+
+        #[test]
+        fn access_elements_of_list_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithLists {
+                a: Vec<Vec<u32>>,
+                b: Digest,
+                c: Vec<XFieldElement>,
+            }
+
+            let mut rng = thread_rng();
+            let object = WithLists {
+                a: (0..10)
+                    .map(|_| (0..rng.gen_range(0..10)).map(|_| rng.gen()).collect_vec())
+                    .collect_vec(),
+                b: rng.gen(),
+                c: (0..10).map(|_| rng.gen()).collect_vec(),
+            };
+            let index_a = rng.gen_range(0..10);
+            let index_c = rng.gen_range(0..10);
+
+            let mut library = Library::new();
+            let code = triton_asm! {
+                // _ *obj
+                dup 0 push {index_a}
+                {&WithLists::get_list_element("a", &mut library)}
+                // _ *obj *a[index_a]
+
+                read_mem 1 pop 1
+                // _ *obj a[index_a].len()
+
+                swap 1 push {index_c}
+                {&WithLists::get_list_element("c", &mut library)}
+                // _ a[index_a].len() *c[index_c]
+
+                read_mem 1 pop 1
+                // _ a[index_a].len() c[index_c].coefficients[0]
+            };
+
+            let mut stack = get_final_stack(&object, library, code);
+            let extracted_coefficient = stack.pop().unwrap();
+            let extracted_length = stack.pop().unwrap().value() as usize;
+
+            assert_eq!(object.a[index_a].len(), extracted_length);
+            assert_eq!(object.c[index_c].coefficients[0], extracted_coefficient);
+        }
+
+        #[should_panic]
+        #[test]
+        fn get_list_element_of_field_that_is_not_a_list_panics() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithList {
+                a: Vec<u32>,
+                b: Digest,
+            }
+
+            WithList::get_list_element("b", &mut Library::new());
+        }
+
+        #[test]
+        fn size_in_memory_agrees_with_encoding_length() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            enum Animal {
+                Cow(u32),
+                Pig { name: Vec<u32>, weight: u64 },
+                Sheep,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Farm {
+                name: Vec<u32>,
+                location: Digest,
+                animals: Vec<Animal>,
+                favorite: Animal,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Pair(Vec<XFieldElement>, u128);
+
+            fn size_in_memory<T: BFieldCodec + TasmObject + Clone>(object: &T) -> usize {
+                let code = T::size_in_memory();
+                let mut stack = get_final_stack(object, Library::new(), code);
+                stack.pop().unwrap().value() as usize
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            for _ in 0..10 {
+                let farm = Farm::arbitrary(&mut unstructured).unwrap();
+                assert_eq!(farm.encode().len(), size_in_memory(&farm));
+                assert_eq!(farm.favorite.encode().len(), size_in_memory(&farm.favorite));
+
+                let pair = Pair::arbitrary(&mut unstructured).unwrap();
+                assert_eq!(pair.encode().len(), size_in_memory(&pair));
+                assert_eq!(pair.0.encode().len(), size_in_memory(&pair.0));
+            }
+
+            for animal in [Animal::Cow(7), Animal::Sheep] {
+                assert_eq!(animal.encode().len(), size_in_memory(&animal));
+            }
+        }
+
+        #[test]
+        fn decoding_with_limits_rejects_oversized_objects() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithLists {
+                a: Vec<u32>,
+                b: Digest,
+                c: Vec<Vec<XFieldElement>>,
+            }
+
+            let object = WithLists {
+                a: vec![1, 2, 3],
+                b: Digest::default(),
+                c: vec![vec![XFieldElement::new_const(BFieldElement::new(5))]; 4],
+            };
+            let size = object.encode().len();
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, object.clone());
+
+            let exact_limits = DecodingLimits::with_max_total_size(size);
+            let decoded =
+                WithLists::decode_from_memory_with_limits(&memory, address, &exact_limits);
+            assert_eq!(object, *decoded.unwrap());
+
+            let too_small_limits = DecodingLimits::with_max_total_size(size - 1);
+            assert!(
+                WithLists::decode_from_memory_with_limits(&memory, address, &too_small_limits)
+                    .is_err()
+            );
+
+            let field_limits = DecodingLimits::unlimited().with_max_field_size("a", 2);
+            assert!(
+                WithLists::decode_from_memory_with_limits(&memory, address, &field_limits).is_err()
+            );
+
+            // corrupt the size indicator of the field stored first
+            memory.insert(address, BFieldElement::new(1 << 60));
+            let limits = DecodingLimits::with_max_total_size(1 << 20);
+            assert!(WithLists::decode_from_memory_with_limits(&memory, address, &limits).is_err());
+        }
+
+        #[test]
+        fn decoding_malformed_encodings_returns_typed_errors() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithLists {
+                a: Vec<u32>,
+                b: Digest,
+                c: Vec<Vec<XFieldElement>>,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            enum Choice {
+                Left(u64),
+                Right,
+            }
+
+            let object = WithLists {
+                a: vec![1, 2, 3],
+                b: Digest::default(),
+                c: vec![vec![XFieldElement::new_const(BFieldElement::new(5))]; 4],
+            };
+            let encoding = object.encode();
+            let decoded = WithLists::decode_iter(&mut encoding.clone().into_iter());
+            assert_eq!(object, *decoded.unwrap());
+
+            let truncated = encoding[..encoding.len() - 1].to_vec();
+            let decoded = WithLists::decode_iter(&mut truncated.into_iter());
+            assert!(matches!(decoded, Err(TasmObjectDecodeError::Truncated)));
+
+            // the size indicator of the field stored first
+            let mut invalid_prefix = encoding.clone();
+            invalid_prefix[0] = BFieldElement::new(1 << 60);
+            let decoded = WithLists::decode_iter(&mut invalid_prefix.into_iter());
+            assert!(matches!(
+                decoded,
+                Err(TasmObjectDecodeError::InvalidLengthPrefix(_))
+            ));
+
+            // the last element of field `a`, which is stored last
+            let mut invalid_element = encoding.clone();
+            *invalid_element.last_mut().unwrap() = BFieldElement::new(1 << 40);
+            let decoded = WithLists::decode_iter(&mut invalid_element.into_iter());
+            assert!(matches!(decoded, Err(TasmObjectDecodeError::Inner(_))));
+
+            let choice = Choice::Left(1 << 40);
+            let decoded = Choice::decode_iter(&mut choice.encode().into_iter());
+            assert_eq!(choice, *decoded.unwrap());
+            assert_eq!(
+                Choice::Right,
+                *Choice::decode_iter(&mut Choice::Right.encode().into_iter()).unwrap()
+            );
+
+            let invalid_discriminant = vec![BFieldElement::new(2)];
+            let decoded = Choice::decode_iter(&mut invalid_discriminant.into_iter());
+            assert!(matches!(
+                decoded,
+                Err(TasmObjectDecodeError::InvalidDiscriminant {
+                    discriminant: 2,
+                    ..
+                })
+            ));
+
+            let decoded = Choice::decode_iter(&mut Vec::<BFieldElement>::new().into_iter());
+            assert!(matches!(decoded, Err(TasmObjectDecodeError::Truncated)));
+        }
+
+        #[test]
+        fn encode_struct_from_its_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct Source {
+                a: Vec<u32>,
+                b: Digest,
+                c: Vec<Vec<XFieldElement>>,
+                d: u64,
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let object = Source::arbitrary(&mut unstructured).unwrap();
+
+            // encode the object a second time, right after the first encoding
+            let size = object.encode().len();
+            let mut library = Library::new();
+            let encode = Source::encode_from_fields(&mut library);
+            let code = triton_asm! {
+                // _ *obj
+                dup 0 {&field_with_size!(Source::a)}
+                dup 2 {&field_with_size!(Source::b)}
+                dup 4 {&field_with_size!(Source::c)}
+                dup 6 {&field_with_size!(Source::d)}
+                // _ *obj [*field field_size; 4]
+
+                dup 8 push {size} add
+                // _ *obj [*field field_size; 4] *copy
+
+                {&encode}
+                // _ *obj *copy_end
+            };
+
+            let (mut stack, memory) = get_final_stack_and_memory(&object, library, code);
+            let copy_end = stack.pop().unwrap();
+            let object_pointer = stack.pop().unwrap();
+            let copy = object_pointer + BFieldElement::new(size as u64);
+            assert_eq!(copy + BFieldElement::new(size as u64), copy_end);
+
+            let object_again = *Source::decode_from_memory(&memory, copy).unwrap();
+            assert_eq!(object, object_again);
+        }
+
+        #[test]
+        fn access_and_decode_optional_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithOption {
+                a: Option<Digest>,
+                b: u32,
+            }
+
+            let digest: Digest = thread_rng().gen();
+            for a in [Some(digest), None] {
+                let object = WithOption { a, b: 7 };
+                let address = BFieldElement::new(1 << 20);
+                let mut memory = HashMap::new();
+                encode_to_memory(&mut memory, address, object.clone());
+                let object_again = *WithOption::decode_from_memory(&memory, address).unwrap();
+                assert_eq!(object, object_again);
+
+                let code = triton_asm! {
+                    // _ *obj
+                    dup 0 {&field!(WithOption::a)}
+                    {&Option::<Digest>::get_variant_discriminant()}
+                    // _ *obj discriminant
+
+                    swap 1 {&field!(WithOption::a)}
+                    {&Option::<Digest>::size_in_memory()}
+                    // _ discriminant size
+                };
+                let mut stack = get_final_stack(&object, Library::new(), code);
+                let size = stack.pop().unwrap().value() as usize;
+                let discriminant = stack.pop().unwrap().value();
+                assert_eq!(object.a.encode().len(), size);
+                assert_eq!(u64::from(object.a.is_some()), discriminant);
+            }
+
+            let chain = field_chain!(WithOption::a::"Some::field_0");
+            let expected = [field!(WithOption::a), triton_asm!(push 1 add)].concat();
+            assert_eq!(expected, chain);
+        }
+
+        #[test]
+        fn access_and_decode_results() {
+            type R = std::result::Result<Vec<u32>, u64>;
+
+            let ok_encoding = [0, 4, 3, 10, 11, 12].map(BFieldElement::new);
+            let err_encoding = [1, 17, 0].map(BFieldElement::new);
+
+            let mut memory = HashMap::new();
+            let ok_address = BFieldElement::new(1 << 20);
+            let err_address = encode_to_memory(&mut memory, ok_address, ok_encoding);
+            encode_to_memory(&mut memory, err_address, err_encoding);
+            assert_eq!(
+                Ok(vec![10, 11, 12]),
+                *R::decode_from_memory(&memory, ok_address).unwrap()
+            );
+            assert_eq!(
+                Err(17),
+                *R::decode_from_memory(&memory, err_address).unwrap()
+            );
+
+            let code = triton_asm! {
+                // _ *ok
+                dup 0 dup 0 {&R::size_in_memory()}
+                swap 1 {&R::get_field_with_size("Ok::field_0")}
+                // _ *ok size *value value_size
+            };
+            let mut stack = get_final_stack(&ok_encoding, Library::new(), code);
+            assert_eq!(4, stack.pop().unwrap().value());
+            let value_pointer = stack.pop().unwrap();
+            assert_eq!(6, stack.pop().unwrap().value());
+            let ok_pointer = stack.pop().unwrap();
+            assert_eq!(ok_pointer + BFieldElement::new(2), value_pointer);
+
+            let code = triton_asm! {
+                // _ *err
+                dup 0 {&R::size_in_memory()}
+                swap 1 {&R::get_field_with_size("Err::field_0")}
+                // _ size *value value_size
+            };
+            let mut stack = get_final_stack(&err_encoding, Library::new(), code);
+            assert_eq!(2, stack.pop().unwrap().value());
+            stack.pop();
+            assert_eq!(3, stack.pop().unwrap().value());
+        }
+
+        #[test]
+        fn access_elements_of_arrays_and_tuples() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct WithArrays {
+                a: [Digest; 3],
+                b: (Vec<u32>, XFieldElement),
+                c: [Vec<u32>; 4],
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let object = WithArrays::arbitrary(&mut unstructured).unwrap();
+
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, object.clone());
+            let object_again = *WithArrays::decode_from_memory(&memory, address).unwrap();
+            assert_eq!(object, object_again);
+
+            let mut library = Library::new();
+            let length = library.import(Box::new(Length {
+                data_type: DataType::U32,
+            }));
+            let code = triton_asm! {
+                // _ *obj
+                dup 0 {&field_chain!(WithArrays::c::2)}
+                call {length}
+                // _ *obj c[2].len()
+
+                swap 1 {&field_chain!(WithArrays::b::0)}
+                call {length}
+                // _ c[2].len() b.0.len()
+            };
+            let mut stack = get_final_stack(&object, library, code);
+            assert_eq!(object.b.0.len(), stack.pop().unwrap().value() as usize);
+            assert_eq!(object.c[2].len(), stack.pop().unwrap().value() as usize);
+
+            for (field, element) in object.c.iter().enumerate() {
+                let code = triton_asm! {
+                    // _ *obj
+                    {&field!(WithArrays::c)}
+                    {&<[Vec<u32>; 4]>::get_field_with_size(&format!("field_{field}"))}
+                    swap 1 pop 1
+                    // _ element_size
+                };
+                let mut stack = get_final_stack(&object, Library::new(), code);
+                assert_eq!(
+                    element.encode().len(),
+                    stack.pop().unwrap().value() as usize
+                );
+            }
+
+            let code = triton_asm! {
+                // _ *obj
+                dup 0 {&field!(WithArrays::a)}
+                {&<[Digest; 3]>::size_in_memory()}
+                swap 1 {&field!(WithArrays::b)}
+                {&<(Vec<u32>, XFieldElement)>::size_in_memory()}
+                // _ a_size b_size
+            };
+            let mut stack = get_final_stack(&object, Library::new(), code);
+            assert_eq!(
+                object.b.encode().len(),
+                stack.pop().unwrap().value() as usize
+            );
+            assert_eq!(
+                object.a.encode().len(),
+                stack.pop().unwrap().value() as usize
+            );
+        }
+
+        #[test]
+        fn decode_tuples_from_memory() {
+            let mut rng = thread_rng();
+            let pair: (Vec<XFieldElement>, u64) = ((0..5).map(|_| rng.gen()).collect(), rng.gen());
+            let triple: (u32, Vec<Digest>, Vec<u32>) = (
+                rng.gen(),
+                (0..3).map(|_| rng.gen()).collect(),
+                (0..7).map(|_| rng.gen()).collect(),
+            );
+
+            let mut memory = HashMap::new();
+            let pair_address = BFieldElement::new(1 << 20);
+            let triple_address = encode_to_memory(&mut memory, pair_address, pair.clone());
+            encode_to_memory(&mut memory, triple_address, triple.clone());
+
+            let pair_again = <(Vec<XFieldElement>, u64)>::decode_from_memory(&memory, pair_address);
+            assert_eq!(pair, *pair_again.unwrap());
+            let triple_again =
+                <(u32, Vec<Digest>, Vec<u32>)>::decode_from_memory(&memory, triple_address);
+            assert_eq!(triple, *triple_again.unwrap());
+        }
+
+        #[test]
+        fn destructure_struct_into_all_its_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
+            struct ManyFields {
+                a: Vec<u32>,
+                b: u64,
+                c: Vec<Digest>,
+                d: Digest,
+            }
+
+            let mut randomness = [0u8; 100000];
+            thread_rng().fill_bytes(&mut randomness);
+            let mut unstructured = Unstructured::new(&randomness);
+            let object = ManyFields::arbitrary(&mut unstructured).unwrap();
+
+            let code = triton_asm! {
+                // _ *obj
+                dup 0 {&ManyFields::destructure()}
+                // _ *obj *d *c c_size *b *a a_size
+            };
+            let mut stack = get_final_stack(&object, Library::new(), code);
+            let a_size = stack.pop().unwrap();
+            let a = stack.pop().unwrap();
+            let b = stack.pop().unwrap();
+            let c_size = stack.pop().unwrap();
+            let c = stack.pop().unwrap();
+            let d = stack.pop().unwrap();
+            let obj = stack.pop().unwrap();
+
+            let c_length = object.c.encode().len() as u64;
+            let a_length = object.a.encode().len() as u64;
+            assert_eq!(obj, d);
+            assert_eq!(obj + BFieldElement::new(6), c);
+            assert_eq!(c_length, c_size.value());
+            assert_eq!(c + BFieldElement::new(c_length), b);
+            assert_eq!(b + BFieldElement::new(3), a);
+            assert_eq!(a_length, a_size.value());
+        }
+
+        #[test]
+        fn declared_field_names_are_listed_in_declaration_order() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Named {
+                a: Digest,
+                b: Vec<u32>,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Unnamed(u64, Vec<Digest>);
+
+            assert_eq!(&["a", "b"], Named::FIELD_NAMES);
+            assert_eq!(&["field_0", "field_1"], Unnamed::FIELD_NAMES);
+
+            assert!(field_name_is_declared(Named::FIELD_NAMES, "a"));
+            assert!(field_name_is_declared(Named::FIELD_NAMES, "\"b\""));
+            assert!(!field_name_is_declared(Named::FIELD_NAMES, "c"));
+            assert!(field_name_is_declared(Unnamed::FIELD_NAMES, "1"));
+            assert!(!field_name_is_declared(Unnamed::FIELD_NAMES, "10"));
+        }
+
+        #[test]
+        fn fields_list_names_and_static_sizes_in_declaration_order() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Named {
+                a: Digest,
+                b: Vec<u32>,
+                c: (u64, XFieldElement),
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            enum Either {
+                Left(u32),
+                Right { list: Vec<u64>, digest: Digest },
+            }
+
+            let expected_fields = vec![
+                ("a".to_owned(), Some(5)),
+                ("b".to_owned(), None),
+                ("c".to_owned(), Some(5)),
+            ];
+            assert_eq!(expected_fields, Named::fields());
+
+            let expected_fields = vec![
+                ("Left::field_0".to_owned(), Some(1)),
+                ("Right::list".to_owned(), None),
+                ("Right::digest".to_owned(), Some(5)),
+            ];
+            assert_eq!(expected_fields, Either::fields());
+
+            let names = Named::fields().into_iter().map(|(name, _)| name);
+            assert!(names.eq(Named::FIELD_NAMES.iter().map(|name| name.to_string())));
+
+            let expected_fields = vec![
+                ("field_0".to_owned(), None),
+                ("field_1".to_owned(), Some(2)),
+            ];
+            assert_eq!(expected_fields, <(Vec<u32>, u64)>::fields());
+            assert_eq!(Named::fields(), Box::<Named>::fields());
+            assert!(Vec::<u32>::fields().is_empty());
+        }
+
+        #[test]
+        fn access_fields_by_their_ordinals() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Named {
+                a: Digest,
+                b: Vec<u32>,
+                c: u64,
+            }
+
+            assert_eq!(0, Named::get_field_ordinal("a"));
+            assert_eq!(2, Named::get_field_ordinal("c"));
+            assert_eq!(1, <[Vec<u32>; 3]>::get_field_ordinal("field_1"));
+
+            let object = Named {
+                a: Digest::default(),
+                b: vec![1, 2, 3],
+                c: 1 << 40,
+            };
+            let field_count = Named::fields().len();
+            let mut code = vec![];
+            for ordinal in 0..field_count {
+                // _ *object [*field field_size]^ordinal
+                let object_depth = 2 * ordinal;
+                code.extend(triton_asm!(dup { object_depth }));
+                code.extend(Named::get_field_with_size_by_ordinal(ordinal));
+            }
+            let stack = get_final_stack(&object, Library::new(), code);
+            let fields = &stack[stack.len() - 2 * field_count..];
+
+            let expected_sizes = [
+                object.a.encode().len(),
+                object.b.encode().len(),
+                object.c.encode().len(),
+            ];
+            for (ordinal, pointer_and_size) in fields.chunks(2).enumerate() {
+                let expected_code = Named::get_field_with_size(Named::FIELD_NAMES[ordinal]);
+                assert_eq!(
+                    expected_code,
+                    Named::get_field_with_size_by_ordinal(ordinal)
+                );
+                assert_eq!(expected_sizes[ordinal] as u64, pointer_and_size[1].value());
+            }
         }
 
-        fn pseudorandom_object(seed: [u8; 32]) -> OuterStruct {
-            let mut rng: StdRng = SeedableRng::from_seed(seed);
-            let a = (0..19)
-                .map(|_| if rng.gen() { Some(rng.gen()) } else { None })
-                .collect_vec();
-            let b0: XFieldElement = rng.gen();
-            let b1: u32 = rng.gen();
-            let c: BFieldElement = rng.gen();
+        #[test]
+        fn access_renamed_fields_by_their_new_names() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Renamed {
+                #[tasm_object(rename = "type")]
+                kind: u64,
+                #[tasm_object(rename = "digests")]
+                list: Vec<Digest>,
+            }
 
-            OuterStruct {
-                o: InnerEnum::Pig(XFieldElement::new_const(443u64.into())),
-                a,
-                b: InnerStruct(b0, b1),
-                p: InnerEnum::Cow(999),
-                c,
-                l: InnerEnum::Horse(1 << 99, 1 << 108),
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Original {
+                kind: u64,
+                list: Vec<Digest>,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            enum RenamedEnum {
+                Pair(#[tasm_object(rename = "left")] u64, u32),
             }
+
+            assert_eq!(&["type", "digests"], Renamed::FIELD_NAMES);
+            assert_eq!(&["Pair::left", "Pair::field_1"], RenamedEnum::FIELD_NAMES);
+            assert!(!field_name_is_declared(Renamed::FIELD_NAMES, "kind"));
+
+            assert_eq!(field!(Original::kind), field!(Renamed::"type"));
+            assert_eq!(
+                field_with_size!(Original::list),
+                field_with_size!(Renamed::digests)
+            );
+
+            let object = Renamed {
+                kind: 1 << 40,
+                list: vec![Digest::default(); 2],
+            };
+            let encoding = object.encode();
+            let decoded = Renamed::decode_iter(&mut encoding.into_iter());
+            assert_eq!(object, *decoded.unwrap());
+            let pair = RenamedEnum::Pair(5, 6);
+            let decoded = RenamedEnum::decode_iter(&mut pair.encode().into_iter());
+            assert_eq!(pair, *decoded.unwrap());
         }
 
-        let mut rng = thread_rng();
-        let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+        #[test]
+        fn derive_for_unit_structs_and_zero_sized_fields() {
+            #[derive(TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Unit;
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Plain {
+                a: u64,
+                b: Vec<u32>,
+            }
 
-        let object = pseudorandom_object(rng.gen());
-        let address = rng.gen();
-        encode_to_memory(&mut memory, address, object.clone());
-        let object_again: OuterStruct = *OuterStruct::decode_from_memory(&memory, address).unwrap();
-        assert_eq!(object, object_again);
-    }
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithMarker<H> {
+                a: u64,
+                #[bfield_codec(ignore)]
+                marker: std::marker::PhantomData<H>,
+                b: Vec<u32>,
+            }
 
-    /// Test derivation of field getters and manual derivations of the `field!` macro
-    mod derive_tests {
-        use triton_vm::Program;
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithEmptyArrays {
+                a: [Digest; 0],
+                b: u64,
+                c: [u32; 0],
+            }
 
-        use super::*;
+            assert!(Unit::FIELD_NAMES.is_empty());
+            let decoded = Unit::decode_iter(&mut Vec::<BFieldElement>::new().into_iter());
+            assert_eq!(Unit, *decoded.unwrap());
+
+            assert_eq!(&["a", "b"], WithMarker::<Digest>::FIELD_NAMES);
+            assert_eq!(field!(Plain::a), field!(WithMarker::<Digest>::a));
+            assert_eq!(field!(Plain::b), field!(WithMarker::<Digest>::b));
+            let object = WithMarker::<Digest> {
+                a: 1 << 40,
+                marker: std::marker::PhantomData,
+                b: vec![1, 2, 3],
+            };
+            let decoded = WithMarker::<Digest>::decode_iter(&mut object.encode().into_iter());
+            assert_eq!(object, *decoded.unwrap());
+
+            let object = WithEmptyArrays {
+                a: [],
+                b: 1 << 40,
+                c: [],
+            };
+            let size = object.encode().len();
+            let code = triton_asm!(
+                // _ *object
+                dup 0 {&field!(WithEmptyArrays::a)}
+                // _ *object *a
+                dup 1 {&field!(WithEmptyArrays::b)}
+                // _ *object *a *b
+                dup 2 {&WithEmptyArrays::size_in_memory()}
+                // _ *object *a *b size
+            );
+            let stack = get_final_stack(&object, Library::new(), code);
+            let [object_pointer, a_pointer, b_pointer, size_in_memory] = stack[stack.len() - 4..]
+            else {
+                panic!("stack must have at least four elements");
+            };
+            // fields are stored in reverse order: c, b, a
+            assert_eq!(object_pointer, b_pointer);
+            assert_eq!(object_pointer + BFieldElement::new(2), a_pointer);
+            assert_eq!(size as u64, size_in_memory.value());
+            let decoded = WithEmptyArrays::decode_iter(&mut object.encode().into_iter());
+            assert_eq!(object, *decoded.unwrap());
+        }
 
         #[test]
-        fn load_and_decode_struct_with_named_fields_from_memory() {
-            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
-            struct NamedFields {
+        fn access_and_decode_field_with_custom_module() {
+            /// Accesses a list like the derive macro does, but crashes the VM when the
+            /// list exceeds a maximum size.
+            mod bounded_list {
+                use super::*;
+
+                pub const MAX_SIZE: usize = 10;
+
+                pub fn static_length() -> Option<usize> {
+                    None
+                }
+
+                pub fn jumper() -> Vec<LabelledInstruction> {
+                    triton_asm!(read_mem 1 push 1 add swap 1 push 1 add)
+                }
+
+                pub fn getter() -> Vec<LabelledInstruction> {
+                    triton_asm!(pop 1 push 1 add)
+                }
+
+                pub fn sizer() -> Vec<LabelledInstruction> {
+                    let max_size_plus_one = MAX_SIZE + 1;
+                    triton_asm!(
+                        push -1 add swap 1 push 1 add swap 1
+                        push {max_size_plus_one} dup 1 lt assert
+                    )
+                }
+
+                pub fn decode(
+                    sequence: &[BFieldElement],
+                ) -> Result<Box<Vec<u32>>, Box<dyn std::error::Error + Send + Sync>>
+                {
+                    if sequence.len() > MAX_SIZE {
+                        return Err("list exceeds maximum size".into());
+                    }
+                    Vec::<u32>::decode(sequence)
+                }
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithBoundedList {
                 a: Digest,
-                b: BFieldElement,
-                c: u128,
-                d: Vec<Digest>,
-                e: XFieldElement,
-                f: Vec<u32>,
+                #[tasm_object(with = "bounded_list")]
+                b: Vec<u32>,
+                c: u64,
             }
 
-            let mut randomness = [0u8; 100000];
-            thread_rng().fill_bytes(&mut randomness);
-            let mut unstructured = Unstructured::new(&randomness);
-            let random_object = NamedFields::arbitrary(&mut unstructured).unwrap();
-            let random_address: u64 = thread_rng().gen_range(0..(1 << 30));
-            let address = random_address.into();
-            let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
+            let object = WithBoundedList {
+                a: Digest::default(),
+                b: vec![1, 2, 3],
+                c: 1 << 40,
+            };
+            let code = triton_asm!(
+                // _ *object
+                dup 0 {&field_with_size!(WithBoundedList::b)}
+                // _ *object *b b_size
+                swap 2 {&field!(WithBoundedList::a)}
+                // _ b_size *b *a
+            );
+            let stack = get_final_stack(&object, Library::new(), code);
+            let [b_size, b_pointer, a_pointer] = stack[stack.len() - 3..] else {
+                panic!("stack must have at least three elements");
+            };
+            let b_length = object.b.encode().len() as u64;
+            assert_eq!(b_length, b_size.value());
+            assert_eq!(b_pointer + BFieldElement::new(b_length), a_pointer);
+
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, object.clone());
+            let decoded = WithBoundedList::decode_from_memory(&memory, address);
+            assert_eq!(object, *decoded.unwrap());
+
+            let oversized_object = WithBoundedList {
+                b: vec![0; bounded_list::MAX_SIZE],
+                ..object
+            };
+            encode_to_memory(&mut memory, address, oversized_object);
+            assert!(WithBoundedList::decode_from_memory(&memory, address).is_err());
+        }
 
-            encode_to_memory(&mut memory, address, random_object.clone());
-            let object_again: NamedFields =
-                *NamedFields::decode_from_memory(&memory, address).unwrap();
-            assert_eq!(random_object, object_again);
+        #[test]
+        fn statically_sized_structs_have_constant_field_offsets() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct StaticallySized {
+                a: Digest,
+                b: u64,
+                c: XFieldElement,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct DynamicallySized {
+                a: Digest,
+                b: Vec<u64>,
+            }
+
+            assert_eq!(Some(vec![5, 3, 0]), StaticallySized::field_offsets());
+            assert_eq!(None, DynamicallySized::field_offsets());
+            assert_eq!(2, field!(StaticallySized::a).len());
+            assert_eq!(3, field_with_size!(StaticallySized::a).len());
+
+            let object = StaticallySized {
+                a: Digest::default(),
+                b: 1 << 50,
+                c: XFieldElement::new_const(BFieldElement::new(7)),
+            };
+            let code = triton_asm!(
+                // _ *object
+                dup 0 {&field!(StaticallySized::a)}
+                // _ *object *a
+                dup 1 {&field_with_size!(StaticallySized::b)}
+                // _ *object *a *b b_size
+                dup 3 {&StaticallySized::get_field_start_with_jump_distance("c")}
+                // _ *object *a *b b_size *c c_jump_distance
+            );
+            let stack = get_final_stack(&object, Library::new(), code);
+            let [object_pointer, a, b, b_size, c, c_jump_distance] = stack[stack.len() - 6..]
+            else {
+                panic!("stack must have at least six elements");
+            };
+            assert_eq!(object_pointer + BFieldElement::new(5), a);
+            assert_eq!(object_pointer + BFieldElement::new(3), b);
+            assert_eq!(2, b_size.value());
+            assert_eq!(object_pointer, c);
+            assert_eq!(3, c_jump_distance.value());
+        }
+
+        #[test]
+        fn validate_encoding_of_well_formed_and_malformed_objects() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Inner {
+                a: Vec<u32>,
+                b: u64,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Outer {
+                x: Digest,
+                inner: Inner,
+                list: Vec<Vec<u32>>,
+            }
+
+            let object = Outer {
+                x: Digest::default(),
+                inner: Inner {
+                    a: vec![1, 2, 3],
+                    b: 5,
+                },
+                list: vec![vec![1], vec![], vec![2, 3]],
+            };
+            let size = object.encode().len();
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, object);
+
+            let validate = |memory: &HashMap<BFieldElement, BFieldElement>, region_size: usize| {
+                let mut library = Library::new();
+                let validate_encoding = Outer::validate_encoding(&mut library);
+                let library_code = library.all_imports();
+                let program = Program::new(&triton_asm!(
+                    push {address}
+                    push {region_size}
+                    {&validate_encoding}
+                    halt
+
+                    {&library_code}
+                ));
+                let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory.clone());
+                execute_with_terminal_state(&program, &[], &empty_stack(), &nondeterminism, None)
+                    .map(|final_state| final_state.op_stack.stack.last().unwrap().value())
+            };
+
+            assert_eq!(size as u64, validate(&memory, size).unwrap());
+            assert_eq!(size as u64, validate(&memory, size + 10).unwrap());
+            assert!(validate(&memory, size - 1).is_err());
+
+            // fields are stored in reverse order: list, inner, x
+            let list_size_pointer = address;
+            let list_size = memory[&list_size_pointer];
+            let inner_size_pointer = address + list_size + BFieldElement::new(1);
+            let inner_size = memory[&inner_size_pointer];
+
+            let mut inconsistent_memory = memory.clone();
+            inconsistent_memory.insert(inner_size_pointer, inner_size + BFieldElement::new(1));
+            assert!(validate(&inconsistent_memory, size + 10).is_err());
+
+            let mut oversized_memory = memory.clone();
+            oversized_memory.insert(list_size_pointer, BFieldElement::new(1 << 40));
+            assert!(validate(&oversized_memory, size + 10).is_err());
+        }
+
+        /// Validate the given encoding as that of a `T`.
+        fn validate_encoding_of<T: TasmObject>(
+            encoding: &[BFieldElement],
+            region_size: usize,
+        ) -> anyhow::Result<u64> {
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, encoding.to_vec());
+            let encoding_start = address + BFieldElement::new(1);
 
             let mut library = Library::new();
-            let length_d = library.import(Box::new(Length {
-                data_type: DataType::Digest,
-            }));
-            let length_f = library.import(Box::new(Length {
-                data_type: DataType::U32,
-            }));
-            let code = triton_asm! {
-                    // _ *obj
-                    dup 0 {&field!(NamedFields::d)}
+            let validate_encoding = T::validate_encoding(&mut library);
+            let program = Program::new(&triton_asm!(
+                push {encoding_start}
+                push {region_size}
+                {&validate_encoding}
+                halt
+            ));
+            let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory);
+            execute_with_terminal_state(&program, &[], &empty_stack(), &nondeterminism, None)
+                .map(|final_state| final_state.op_stack.stack.last().unwrap().value())
+        }
 
-                    // _ *obj *d
-                    swap 1
-                   {&field!(NamedFields::f)}
-                    // _ *d *f
+        #[test]
+        fn validate_encoding_of_statically_sized_values() {
+            type Pair = (Digest, XFieldElement);
+            let pair = (
+                Digest::default(),
+                XFieldElement::new_const(BFieldElement::new(7)),
+            );
+            let pair_encoding = pair.encode();
+            assert_eq!(8, validate_encoding_of::<Pair>(&pair_encoding, 8).unwrap());
+            assert_eq!(8, validate_encoding_of::<Pair>(&pair_encoding, 9).unwrap());
+            assert!(validate_encoding_of::<Pair>(&pair_encoding, 7).is_err());
+
+            type Array = [XFieldElement; 5];
+            let array_encoding = [pair.1; 5].encode();
+            assert_eq!(
+                15,
+                validate_encoding_of::<Array>(&array_encoding, 15).unwrap()
+            );
+            assert!(validate_encoding_of::<Array>(&array_encoding, 14).is_err());
+
+            type O = Option<Digest>;
+            let none_encoding = None::<Digest>.encode();
+            let some_encoding = Some(pair.0).encode();
+            assert_eq!(1, validate_encoding_of::<O>(&none_encoding, 1).unwrap());
+            assert_eq!(6, validate_encoding_of::<O>(&some_encoding, 6).unwrap());
+            assert!(validate_encoding_of::<O>(&some_encoding, 5).is_err());
+
+            type R = std::result::Result<u64, XFieldElement>;
+            let ok_encoding = [0, 17, 0].map(BFieldElement::new);
+            let err_encoding = [1, 7, 0, 0].map(BFieldElement::new);
+            assert_eq!(3, validate_encoding_of::<R>(&ok_encoding, 3).unwrap());
+            assert_eq!(4, validate_encoding_of::<R>(&err_encoding, 4).unwrap());
+            assert!(validate_encoding_of::<R>(&err_encoding, 3).is_err());
+        }
 
-                    call {length_f}
-                    // _ *d f_length
+        #[test]
+        fn validate_encoding_rejects_invalid_discriminants() {
+            let some_encoding = [2, 1, 2, 3, 4, 5].map(BFieldElement::new);
+            assert!(validate_encoding_of::<Option<Digest>>(&some_encoding, 100).is_err());
 
-                    swap 1
-                    call {length_d}
-                    // _ f_length d_length
+            type R = std::result::Result<u64, XFieldElement>;
+            let ok_encoding = [2, 17, 0].map(BFieldElement::new);
+            assert!(validate_encoding_of::<R>(&ok_encoding, 100).is_err());
+        }
+
+        #[test]
+        #[should_panic(expected = "statically-sized elements")]
+        fn validating_array_of_dynamically_sized_elements_is_unsupported() {
+            <[Vec<u64>; 2]>::validate_encoding(&mut Library::new());
+        }
+
+        #[test]
+        #[should_panic(expected = "is not supported")]
+        fn validating_type_without_check_is_unsupported() {
+            FriResponse::validate_encoding(&mut Library::new());
+        }
+
+        #[test]
+        fn bounds_checked_field_getters_reject_fields_outside_region() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithList {
+                a: Digest,
+                b: Vec<u64>,
+            }
+
+            let object = WithList {
+                a: Digest::default(),
+                b: vec![1, 2, 3],
+            };
+            let size = object.encode().len();
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, address, object.clone());
+
+            let get_field = |memory: &HashMap<BFieldElement, BFieldElement>,
+                             field: Vec<LabelledInstruction>,
+                             region_size: usize| {
+                let program = Program::new(&triton_asm!(
+                    push {address}
+                    push {region_size}
+                    {&field}
+                    halt
+                ));
+                let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory.clone());
+                execute_with_terminal_state(&program, &[], &empty_stack(), &nondeterminism, None)
+                    .map(|final_state| {
+                        let stack = final_state.op_stack.stack;
+                        let [field_pointer, field_size] = stack[stack.len() - 2..] else {
+                            panic!("stack must have at least two elements");
+                        };
+                        (field_pointer, field_size.value() as usize)
+                    })
             };
 
-            let mut stack = get_final_stack(&random_object, library, code);
-            let extracted_d_length = stack.pop().unwrap().value() as usize;
-            let extracted_f_length = stack.pop().unwrap().value() as usize;
+            // fields are stored in reverse order: b, a
+            let b_size = object.b.encode().len();
+            let a_pointer = address + BFieldElement::new(b_size as u64 + 1);
+            let b_pointer = address + BFieldElement::new(1);
+            let a = field_with_bounds_check!(WithList::a);
+            let b = field_with_bounds_check!(WithList::b);
+            assert_eq!((a_pointer, 5), get_field(&memory, a.clone(), size).unwrap());
+            assert_eq!(
+                (b_pointer, b_size),
+                get_field(&memory, b.clone(), size).unwrap()
+            );
+            assert!(get_field(&memory, a, size - 1).is_err());
+            assert_eq!(
+                (b_pointer, b_size),
+                get_field(&memory, b.clone(), b_size + 1).unwrap()
+            );
+            assert!(get_field(&memory, b.clone(), b_size).is_err());
 
-            assert_eq!(random_object.d.len(), extracted_d_length);
-            assert_eq!(random_object.f.len(), extracted_f_length);
+            // a size indicator claiming more than the region
+            let mut oversized_memory = memory.clone();
+            oversized_memory.insert(address, BFieldElement::new(size as u64));
+            assert!(get_field(&oversized_memory, b.clone(), size).is_err());
+
+            // a size indicator that is not a u32
+            let mut malformed_memory = memory;
+            malformed_memory.insert(address, -BFieldElement::new(1));
+            assert!(get_field(&malformed_memory, b, size).is_err());
         }
 
         #[test]
-        fn load_and_decode_tuple_struct_containing_enums_from_memory() {
-            #[derive(BFieldCodec, PartialEq, Eq, Clone, Debug, Arbitrary)]
-            enum MyEnum {
-                A(u64, Digest),
-                B,
-                C,
+        fn overwrite_statically_sized_fields_with_setters() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithCounter {
+                #[tasm_object(setter)]
+                digest: Digest,
+                list: Vec<u32>,
+                #[tasm_object(setter)]
+                counter: u64,
             }
 
-            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug, Arbitrary)]
-            struct TupleStruct(
-                Vec<XFieldElement>,
-                MyEnum,
-                u32,
-                Vec<Digest>,
-                Digest,
-                Vec<BFieldElement>,
-                Digest,
+            let object = WithCounter {
+                digest: Digest::default(),
+                list: vec![1, 2, 3],
+                counter: 41,
+            };
+            let new_counter = 1u64 << 40;
+            let new_digest = Digest::new([1, 2, 3, 4, 5].map(BFieldElement::new));
+
+            let push_encoding = |value: Vec<BFieldElement>| {
+                value
+                    .into_iter()
+                    .rev()
+                    .flat_map(|word| triton_asm!(push { word }))
+                    .collect_vec()
+            };
+            let code = triton_asm!(
+                // _ *object
+                {&push_encoding(new_counter.encode())}
+                dup 2
+                {&WithCounter::set_counter()}
+                // _ *object
+                {&push_encoding(new_digest.encode())}
+                dup 5
+                {&WithCounter::set_digest()}
+                // _ *object
             );
+            let (stack, memory) = get_final_stack_and_memory(&object, Library::new(), code);
+            let address = *stack.last().unwrap();
+            let object_again = *WithCounter::decode_from_memory(&memory, address).unwrap();
+
+            let expected_object = WithCounter {
+                digest: new_digest,
+                counter: new_counter,
+                ..object
+            };
+            assert_eq!(expected_object, object_again);
+        }
 
-            let mut randomness = [0u8; 100000];
-            thread_rng().fill_bytes(&mut randomness);
-            let mut unstructured = Unstructured::new(&randomness);
-            let random_object = TupleStruct::arbitrary(&mut unstructured).unwrap();
-            let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
-            let random_address: u64 = thread_rng().gen_range(0..(1 << 30));
-            let address = random_address.into();
+        #[test]
+        fn iterate_over_elements_of_list_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithLists {
+                #[tasm_object(for_each)]
+                elements: Vec<BFieldElement>,
+                digest: Digest,
+                #[tasm_object(for_each)]
+                lists: Vec<Vec<BFieldElement>>,
+            }
 
-            encode_to_memory(&mut memory, address, random_object.clone());
-            let object_again: TupleStruct =
-                *TupleStruct::decode_from_memory(&memory, address).unwrap();
-            assert_eq!(random_object, object_again);
+            // adds the first word of every element to the accumulator below the object
+            let sum_first_words = || {
+                InnerFunction::RawCode(RawCode::new(
+                    triton_asm!(
+                        sum_first_words:
+                            // _ acc *element_start remaining *element
+                            read_mem 1
+                            pop 1
+                            dup 3
+                            add
+                            swap 3
+                            pop 1
+                            // _ acc' *element_start remaining
+                            return
+                    ),
+                    DataType::VoidPointer,
+                    DataType::Tuple(vec![]),
+                ))
+            };
+
+            let object = WithLists {
+                elements: vec![BFieldElement::new(3), BFieldElement::new(4)],
+                digest: Digest::default(),
+                lists: vec![
+                    vec![BFieldElement::new(1); 5],
+                    vec![BFieldElement::new(2); 7],
+                ],
+            };
 
-            // code snippet to access object's fields
             let mut library = Library::new();
-            let length_digests = library.import(Box::new(Length {
-                data_type: DataType::Digest,
-            }));
-            let length_bfes = library.import(Box::new(Length {
-                data_type: DataType::Bfe,
-            }));
-            let length_xfes = library.import(Box::new(Length {
-                data_type: DataType::Xfe,
-            }));
-            let code = triton_asm! {
-                // _ *obj
+            let for_each_element = WithLists::for_each_elements(sum_first_words(), &mut library);
+            let code = triton_asm!(
+                // _ *object
+                push 0
+                swap 1
+                // _ 0 *object
+                {&for_each_element}
+                // _ (3 + 4)
+            );
+            let stack = get_final_stack(&object, library, code);
+            assert_eq!(7, stack.last().unwrap().value());
 
-                dup 0
-                {&field!(TupleStruct::3)} // _ *obj *digests
-                swap 1                    // _ *digests *obj
+            // the first word of every inner list is its length
+            let mut library = Library::new();
+            let for_each_list = WithLists::for_each_lists(sum_first_words(), &mut library);
+            let code = triton_asm!(push 0 swap 1 {&for_each_list});
+            let stack = get_final_stack(&object, library, code);
+            assert_eq!(5 + 7, stack.last().unwrap().value());
+        }
 
-                dup 0
-                {&field!(TupleStruct::5)} // _ *digests *obj *bfes
-                swap 1                    // _ *digests *bfes *obj
+        #[test]
+        fn build_struct_in_memory_from_its_fields() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Built {
+                a: Vec<u32>,
+                b: Digest,
+                c: Vec<XFieldElement>,
+                d: u64,
+            }
 
-                {&field!(TupleStruct::0)} // _ *digests *bfes *xfes
-                call {length_xfes}     // _ *digests *bfes xfe_count
-                swap 2                 // _ xfe_count *bfes *digests
-                call {length_digests}  // _ xfe_count *bfes digest_count
-                swap 1
-                call {length_bfes}     // _ xfe_count digest_count bfe_count
+            let object = Built {
+                a: vec![1, 2, 3],
+                b: Digest::default(),
+                c: vec![XFieldElement::new_const(BFieldElement::new(9)); 2],
+                d: 1 << 60,
             };
 
-            // extract list lengths
-            let mut stack = get_final_stack(&random_object, library, code);
-            let extracted_bfe_count = stack.pop().unwrap().value() as usize;
-            let extracted_digest_count = stack.pop().unwrap().value() as usize;
-            let extracted_xfe_count = stack.pop().unwrap().value() as usize;
+            let mut library = Library::new();
+            let build_in_memory = Built::build_in_memory(&mut library);
+            let code = triton_asm!(
+                // _ *source
+                dup 0 {&field_with_size!(Built::a)}
+                dup 2 {&field_with_size!(Built::b)}
+                dup 4 {&field_with_size!(Built::c)}
+                dup 6 {&field_with_size!(Built::d)}
+                // _ *source [*field field_size]*
+                {&build_in_memory}
+                // _ *source *object
+            );
+            let (stack, memory) = get_final_stack_and_memory(&object, library, code);
+            let object_pointer = *stack.last().unwrap();
+            assert_eq!(FIRST_DYNAMICALLY_ALLOCATED_ADDRESS, object_pointer);
 
-            // assert correct lengths
-            assert_eq!(random_object.3.len(), extracted_digest_count);
-            assert_eq!(random_object.5.len(), extracted_bfe_count);
-            assert_eq!(random_object.0.len(), extracted_xfe_count);
+            let built_object = *Built::decode_from_memory(&memory, object_pointer).unwrap();
+            assert_eq!(object, built_object);
+
+            let dyn_malloc_state = memory[&DYN_MALLOC_ADDRESS];
+            let size = object.encode().len() as u64;
+            assert_eq!(object_pointer + BFieldElement::new(size), dyn_malloc_state);
+        }
+
+        #[test]
+        fn access_and_decode_fields_stored_by_pointer() {
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct Shared {
+                digests: Vec<Digest>,
+                id: u64,
+            }
+
+            #[derive(BFieldCodec, TasmObject, PartialEq, Eq, Clone, Debug)]
+            struct WithPointer {
+                a: u64,
+                #[tasm_object(by_pointer)]
+                shared: Shared,
+                c: Vec<u32>,
+            }
+
+            let shared = Shared {
+                digests: vec![Digest::default(); 3],
+                id: 1 << 40,
+            };
+            let object = WithPointer {
+                a: 7,
+                shared: shared.clone(),
+                c: vec![1, 2, 3, 4],
+            };
+
+            // fields are stored in reverse order, and `shared` only as its address
+            let shared_address = BFieldElement::new(1 << 31);
+            let c_encoding = object.c.encode();
+            let object_encoding = [
+                vec![BFieldElement::new(c_encoding.len() as u64)],
+                c_encoding.clone(),
+                vec![shared_address],
+                object.a.encode(),
+            ]
+            .concat();
+            let address = BFieldElement::new(1 << 20);
+            let mut memory = HashMap::new();
+            encode_to_memory(&mut memory, shared_address, shared.clone());
+            for (i, word) in object_encoding.iter().enumerate() {
+                memory.insert(address + BFieldElement::new(i as u64), *word);
+            }
+
+            let code = triton_asm!(
+                // _ *object
+                dup 0 {&field_with_size!(WithPointer::shared)}
+                // _ *object *shared shared_size
+                dup 2 {&field_chain!(WithPointer::shared::id)}
+                // _ *object *shared shared_size *id
+                dup 3 {&field!(WithPointer::a)}
+                // _ *object *shared shared_size *id *a
+                dup 4 {&<Box<WithPointer> as TasmObject>::size_in_memory()}
+                // _ *object *shared shared_size *id *a size
+            );
+            let (stack, _) = run_on_object_in_memory(memory.clone(), address, Library::new(), code);
+            let [shared_pointer, shared_size, id_pointer, a_pointer, size] =
+                stack[stack.len() - 5..]
+            else {
+                panic!("stack must have at least five elements");
+            };
+            assert_eq!(shared_address, shared_pointer);
+            assert_eq!(shared.encode().len() as u64, shared_size.value());
+            assert_eq!(shared_address, id_pointer);
+            let a_offset = c_encoding.len() as u64 + 2;
+            assert_eq!(address + BFieldElement::new(a_offset), a_pointer);
+            assert_eq!(object_encoding.len() as u64, size.value());
+
+            let decoded = WithPointer::decode_from_memory(&memory, address);
+            assert_eq!(object, *decoded.unwrap());
+            let decoded = Box::<WithPointer>::decode_from_memory(&memory, address);
+            assert_eq!(object, **decoded.unwrap());
+
+            let decoded = WithPointer::decode_iter(&mut object_encoding.into_iter());
+            assert!(matches!(
+                decoded,
+                Err(TasmObjectDecodeError::Unsupported(_))
+            ));
         }
 
         #[test]
@@ -476,18 +3463,76 @@ mod test {
             assert_eq!(num_leafs, extracted_xfes_length);
         }
 
+        #[test]
+        fn test_claim() {
+            let mut rng = thread_rng();
+            let input_length = 13;
+            let output_length = 7;
+            let claim = Claim {
+                program_digest: rng.gen(),
+                input: (0..input_length).map(|_| rng.gen()).collect_vec(),
+                output: (0..output_length).map(|_| rng.gen()).collect_vec(),
+            };
+
+            let code = triton_asm! {
+                // _ *claim
+                dup 0 {&field!(Claim::program_digest)}
+                // _ *claim *program_digest
+                dup 1 {&field_with_size!(Claim::input)}
+                // _ *claim *program_digest *input input_size
+                dup 3 {&field!(Claim::output)}
+                // _ *claim *program_digest *input input_size *output
+                dup 4 {&Claim::size_in_memory()}
+                // _ *claim *program_digest *input input_size *output size
+            };
+            let (stack, memory) = get_final_stack_and_memory(&claim, Library::new(), code);
+            let [claim_pointer, program_digest, input, input_size, output, size] =
+                stack[stack.len() - 6..]
+            else {
+                panic!("stack must have at least six elements");
+            };
+
+            // fields are stored in reverse order, dynamically sized ones with a length prefix
+            let offset = |pointer: BFieldElement| (pointer - claim_pointer).value() as usize;
+            assert_eq!(1, offset(output));
+            assert_eq!(output_length + 3, offset(input));
+            assert_eq!(input_length + output_length + 4, offset(program_digest));
+            assert_eq!(claim.input.encode().len() as u64, input_size.value());
+            assert_eq!(claim.encode().len() as u64, size.value());
+
+            let decoded = Claim::decode_from_memory(&memory, claim_pointer).unwrap();
+            assert_eq!(claim, *decoded);
+        }
+
         /// Helper function for testing field getters. Only returns the final stack.
         fn get_final_stack<T: BFieldCodec + Clone>(
             obj: &T,
             library: Library,
             code: Vec<LabelledInstruction>,
         ) -> Vec<BFieldElement> {
+            get_final_stack_and_memory(obj, library, code).0
+        }
+
+        fn get_final_stack_and_memory<T: BFieldCodec + Clone>(
+            obj: &T,
+            library: Library,
+            code: Vec<LabelledInstruction>,
+        ) -> (Vec<BFieldElement>, HashMap<BFieldElement, BFieldElement>) {
             // initialize memory and stack
             let mut memory: HashMap<BFieldElement, BFieldElement> = HashMap::new();
             let random_address: u64 = thread_rng().gen_range(0..(1 << 30));
             let address = random_address.into();
 
             encode_to_memory(&mut memory, address, obj.to_owned());
+            run_on_object_in_memory(memory, address, library, code)
+        }
+
+        fn run_on_object_in_memory(
+            memory: HashMap<BFieldElement, BFieldElement>,
+            address: BFieldElement,
+            library: Library,
+            code: Vec<LabelledInstruction>,
+        ) -> (Vec<BFieldElement>, HashMap<BFieldElement, BFieldElement>) {
             let stack = [empty_stack(), vec![address]].concat();
 
             // link by hand
@@ -508,7 +3553,7 @@ mod test {
             let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory);
             let final_state =
                 execute_with_terminal_state(&program, &[], &stack, &nondeterminism, None).unwrap();
-            final_state.op_stack.stack
+            (final_state.op_stack.stack, final_state.ram)
         }
     }
 }
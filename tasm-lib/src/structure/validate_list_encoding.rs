@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use triton_vm::{instruction::LabelledInstruction, triton_asm, BFieldElement};
+
+use crate::{
+    data_type::DataType,
+    empty_stack,
+    library::Library,
+    snippet_bencher::BenchmarkCase,
+    traits::{
+        basic_snippet::BasicSnippet,
+        function::{Function, FunctionInitialState},
+    },
+};
+
+/// Checks that a `Vec<T>`, living in memory as BFieldCodec encodes it, is well-formed and
+/// fits within a region of the given size, and returns its size. Crashes the VM otherwise.
+///
+/// The length of the list must be a u32. If `T` is dynamically sized, every element is
+/// prefixed by its size, which must be a u32 as well. The elements themselves are not
+/// inspected.
+#[derive(Clone, Debug)]
+pub struct ValidateListEncoding {
+    /// The static size of the elements in number of words, or `None` if the elements
+    /// are dynamically sized.
+    pub element_size: Option<usize>,
+}
+
+impl BasicSnippet for ValidateListEncoding {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*list".to_owned()),
+            (DataType::U32, "region_size".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "size".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        match self.element_size {
+            Some(size) => format!("tasm_structure_validate_list_encoding_static_size_{size}"),
+            None => "tasm_structure_validate_list_encoding_dynamic_size".to_owned(),
+        }
+    }
+
+    fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+
+        let assert_fits_in_region = triton_asm!(
+            // _ region_size size
+            dup 0
+            swap 2
+            push 1
+            add
+            swap 1
+            lt
+            assert
+            // _ size
+        );
+
+        let Some(element_size) = self.element_size else {
+            return triton_asm!(
+                // BEFORE: _ *list region_size
+                // AFTER: _ size
+                {entrypoint}:
+                    swap 1
+                    dup 0
+                    read_mem 1
+                    push 2
+                    add
+                    swap 1
+                    // _ region_size *list *first_element_size length
+
+                    dup 0
+                    split
+                    pop 1
+                    push 0
+                    eq
+                    assert
+                    // _ region_size *list *first_element_size length
+
+                    // every element takes at least one word, which bounds the loop
+                    dup 3
+                    dup 1
+                    lt
+                    assert
+
+                    call {entrypoint}_loop
+                    // _ region_size *list *end 0
+
+                    pop 1
+                    swap 1
+                    push -1
+                    mul
+                    add
+                    // _ region_size size
+
+                    {&assert_fits_in_region}
+                    return
+
+                // INVARIANT: _ region_size *list *element_size remaining
+                {entrypoint}_loop:
+                    dup 0
+                    push 0
+                    eq
+                    skiz return
+
+                    swap 1
+                    read_mem 1
+                    push 1
+                    add
+                    // _ region_size *list remaining element_size *element_size
+
+                    dup 1
+                    split
+                    pop 1
+                    push 0
+                    eq
+                    assert
+
+                    add
+                    push 1
+                    add
+                    // _ region_size *list remaining *next_element_size
+
+                    swap 1
+                    push -1
+                    add
+                    // _ region_size *list *next_element_size (remaining - 1)
+
+                    recurse
+            );
+        };
+
+        triton_asm!(
+            // BEFORE: _ *list region_size
+            // AFTER: _ size
+            {entrypoint}:
+                swap 1
+                read_mem 1
+                pop 1
+                // _ region_size length
+
+                dup 0
+                split
+                pop 1
+                push 0
+                eq
+                assert
+
+                push {element_size}
+                mul
+                push 1
+                add
+                // _ region_size size
+
+                {&assert_fits_in_region}
+                return
+        )
+    }
+}
+
+impl Function for ValidateListEncoding {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let region_size = stack.pop().unwrap().value();
+        let list_pointer = stack.pop().unwrap();
+
+        let length = memory[&list_pointer].value();
+        assert!(length <= u32::MAX as u64);
+
+        let size = match self.element_size {
+            Some(element_size) => length * element_size as u64 + 1,
+            None => {
+                assert!(length < region_size);
+                let mut element_size_pointer = list_pointer + BFieldElement::new(1);
+                for _ in 0..length {
+                    let element_size = memory[&element_size_pointer].value();
+                    assert!(element_size <= u32::MAX as u64);
+                    element_size_pointer += BFieldElement::new(element_size + 1);
+                }
+                (element_size_pointer - list_pointer).value()
+            }
+        };
+        assert!(size <= region_size);
+
+        stack.push(BFieldElement::new(size));
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let list_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 10,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.gen_range(0..50),
+        };
+        let slack = rng.gen_range(0..10);
+
+        self.init_state(&mut rng, list_length, slack)
+    }
+
+    fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+        let mut rng: StdRng = SeedableRng::from_seed([0u8; 32]);
+        vec![
+            self.init_state(&mut rng, 0, 0),
+            self.init_state(&mut rng, 1, 0),
+            self.init_state(&mut rng, 2, 1),
+        ]
+    }
+}
+
+impl ValidateListEncoding {
+    /// Initializes a well-formed list of the given length in a region that is `slack` words
+    /// larger than the list.
+    fn init_state(
+        &self,
+        rng: &mut StdRng,
+        list_length: usize,
+        slack: usize,
+    ) -> FunctionInitialState {
+        let list_pointer = BFieldElement::new(rng.gen_range(0..(1 << 30)));
+
+        let mut encoding = vec![BFieldElement::new(list_length as u64)];
+        for _ in 0..list_length {
+            let element_size = match self.element_size {
+                Some(size) => size,
+                None => {
+                    let size = rng.gen_range(0..10);
+                    encoding.push(BFieldElement::new(size as u64));
+                    size
+                }
+            };
+            encoding.extend((0..element_size).map(|_| rng.gen::<BFieldElement>()));
+        }
+        let region_size = encoding.len() + slack;
+
+        let memory = encoding
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| (list_pointer + BFieldElement::new(i as u64), word))
+            .collect();
+
+        let mut stack = empty_stack();
+        stack.push(list_pointer);
+        stack.push(BFieldElement::new(region_size as u64));
+
+        FunctionInitialState { stack, memory }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn validate_list_encoding_static_size_test() {
+        for element_size in [1, 2, 5] {
+            ShadowedFunction::new(ValidateListEncoding {
+                element_size: Some(element_size),
+            })
+            .test();
+        }
+    }
+
+    #[test]
+    fn validate_list_encoding_dynamic_size_test() {
+        ShadowedFunction::new(ValidateListEncoding { element_size: None }).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::traits::function::ShadowedFunction;
+    use crate::traits::rust_shadow::RustShadow;
+
+    #[test]
+    fn validate_list_encoding_static_size_bench() {
+        ShadowedFunction::new(ValidateListEncoding {
+            element_size: Some(5),
+        })
+        .bench();
+    }
+
+    #[test]
+    fn validate_list_encoding_dynamic_size_bench() {
+        ShadowedFunction::new(ValidateListEncoding { element_size: None }).bench();
+    }
+}
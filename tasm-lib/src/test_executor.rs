@@ -0,0 +1,108 @@
+//! Run many snippet tests concurrently, for example all instantiations of a generic
+//! snippet.
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use crate::traits::rust_shadow::RustShadow;
+
+/// The [test](RustShadow::test) of a shadowed snippet, ready to be run on any thread.
+///
+/// Shadowed snippets cannot be shared across threads, which is why the snippet is only
+/// constructed on the thread that runs its test.
+pub struct SnippetTest {
+    name: String,
+    run: Box<dyn Fn() + Send + Sync>,
+}
+
+impl SnippetTest {
+    /// The test of the shadowed snippet returned by `shadowed_snippet`, named after the
+    /// snippet's entrypoint.
+    pub fn new<S, F>(shadowed_snippet: F) -> Self
+    where
+        S: RustShadow,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        let name = shadowed_snippet().inner().borrow().entrypoint();
+        let run = Box::new(move || shadowed_snippet().test());
+        Self { name, run }
+    }
+}
+
+/// Run all tests on rayon's thread pool. Failing tests don't stop the others; once all
+/// tests are done, panics with a report of every failure.
+pub fn run_snippet_tests_in_parallel(tests: Vec<SnippetTest>) {
+    let num_tests = tests.len();
+    let failures = tests
+        .into_par_iter()
+        .filter_map(|test| {
+            let result = catch_unwind(AssertUnwindSafe(|| (test.run)()));
+            result
+                .err()
+                .map(|panic| (test.name, panic_message(panic.as_ref())))
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "ran {num_tests} snippet tests: {} passed, {} failed",
+        num_tests - failures.len(),
+        failures.len()
+    );
+    if failures.is_empty() {
+        return;
+    }
+
+    let report = failures
+        .iter()
+        .map(|(name, message)| format!("`{name}`: {message}"))
+        .join("\n\n");
+    panic!(
+        "{} of {num_tests} snippet tests failed:\n\n{report}",
+        failures.len()
+    );
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = panic.downcast_ref::<String>() {
+        return message.to_owned();
+    }
+
+    "(no panic message)".to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arithmetic::u32::safepow::Safepow;
+    use crate::arithmetic::u64::wrapping_sub_u64::WrappingSub;
+    use crate::traits::closure::ShadowedClosure;
+
+    use super::*;
+
+    #[test]
+    fn passing_tests_pass() {
+        run_snippet_tests_in_parallel(vec![
+            SnippetTest::new(|| ShadowedClosure::new(WrappingSub)),
+            SnippetTest::new(|| ShadowedClosure::new(Safepow)),
+        ]);
+    }
+
+    #[should_panic(expected = "1 of 3 snippet tests failed:\n\n`failing`: deliberate failure")]
+    #[test]
+    fn failures_are_reported_after_all_tests_ran() {
+        let failing_test = SnippetTest {
+            name: "failing".to_owned(),
+            run: Box::new(|| panic!("deliberate failure")),
+        };
+        run_snippet_tests_in_parallel(vec![
+            SnippetTest::new(|| ShadowedClosure::new(WrappingSub)),
+            failing_test,
+            SnippetTest::new(|| ShadowedClosure::new(Safepow)),
+        ]);
+    }
+}
@@ -1,18 +1,30 @@
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::fs::create_dir_all;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
 
 use itertools::Itertools;
-use triton_vm::instruction::LabelledInstruction;
-use triton_vm::{triton_asm, NonDeterminism};
+use rand::{thread_rng, Rng};
+use triton_vm::error::{InstructionError, VMError};
+use triton_vm::{NonDeterminism, Program};
 use twenty_first::shared_math::b_field_element::BFieldElement;
+use twenty_first::shared_math::bfield_codec::BFieldCodec;
+use twenty_first::shared_math::x_field_element::XFieldElement;
 
-use crate::dyn_malloc::DYN_MALLOC_ADDRESS;
-use crate::library::Library;
+use crate::data_type::DataType;
+use crate::dyn_malloc::{
+    DYN_MALLOC_ADDRESS, FIRST_DYNAMICALLY_ALLOCATED_ADDRESS, FREE_LIST_ADDRESS,
+};
+use crate::library::{format_tasm, Library};
+use crate::linker::link_for_isolated_run_with_source_map;
+use crate::memory_snapshot::MemorySnapshot;
 use crate::traits::basic_snippet::BasicSnippet;
 use crate::traits::deprecated_snippet::DeprecatedSnippet;
 use crate::traits::rust_shadow::RustShadow;
 use crate::{
-    execute_test, exported_snippets, ExecutionState, VmHasherState, VmOutputState, DIGEST_LENGTH,
+    execute_test_with_source_map, execute_with_terminal_state_and_source_map, exported_snippets,
+    Digest, ExecutionState, VmHasherState, VmOutputState, DIGEST_LENGTH,
 };
 
 #[allow(dead_code)]
@@ -82,29 +94,6 @@ pub fn test_rust_equivalence_given_input_values_deprecated<T: DeprecatedSnippet>
     )
 }
 
-fn link_for_isolated_run_deprecated<T: DeprecatedSnippet>(
-    snippet_struct: &T,
-    words_statically_allocated: u32,
-) -> Vec<LabelledInstruction> {
-    let mut snippet_state = Library::with_preallocated_memory(words_statically_allocated);
-    let entrypoint = snippet_struct.entrypoint();
-    let mut function_body = snippet_struct.function_code(&mut snippet_state);
-    function_body.push('\n');
-    let library_code = snippet_state.all_imports();
-
-    // The TASM code is always run through a function call, so the 1st instruction
-    // is a call to the function in question.
-    let code = triton_asm!(
-        call {entrypoint}
-        halt
-
-        {function_body}
-        {&library_code}
-    );
-
-    code
-}
-
 pub fn link_and_run_tasm_for_test_deprecated<T: DeprecatedSnippet>(
     snippet_struct: &T,
     stack: &mut Vec<BFieldElement>,
@@ -129,16 +118,18 @@ pub fn link_and_run_tasm_for_test_deprecated<T: DeprecatedSnippet>(
         "Declared stack diff must match type indicators"
     );
 
-    let code = link_for_isolated_run_deprecated(snippet_struct, words_statically_allocated);
+    let (code, source_map) =
+        snippet_struct.link_for_isolated_run_with_source_map(Some(words_statically_allocated));
     let nondeterminism = NonDeterminism::<BFieldElement>::new(secret_in).with_ram(memory.clone());
 
-    execute_test(
+    execute_test_with_source_map(
         &code,
         stack,
         snippet_struct.stack_diff(),
         std_in,
         nondeterminism,
         None,
+        &source_map,
     )
 }
 
@@ -289,7 +280,7 @@ pub fn rust_final_state<T: RustShadow>(
     let mut rust_sponge = sponge_state.clone();
 
     // run rust shadow
-    let output = shadowed_snippet.rust_shadow_wrapper(
+    let (output, consumption) = shadowed_snippet.rust_shadow_wrapper_counting_nondeterminism(
         stdin,
         nondeterminism,
         &mut rust_stack,
@@ -302,6 +293,8 @@ pub fn rust_final_state<T: RustShadow>(
         final_stack: rust_stack,
         final_ram: rust_memory,
         final_sponge_state: rust_sponge,
+        individual_tokens_read: consumption.individual_tokens_read,
+        digests_read: consumption.digests_read,
     }
 }
 
@@ -361,56 +354,321 @@ pub(crate) fn verify_memory_equivalence(
         return;
     }
 
-    fn format_hash_map_iterator<K, V>(map: impl Iterator<Item = (K, V)>) -> String
-    where
-        u64: From<K>,
-        K: Copy + Display,
-        V: Display,
-    {
-        map.sorted_by_key(|(k, _)| u64::from(*k))
-            .map(|(k, v)| format!("({k} => {v})"))
-            .join(", ")
-    }
-
-    let in_a_and_different_in_b = a_memory
-        .iter()
-        .filter(|(k, &v)| b_memory.get(k).map(|&b| b != v).unwrap_or(true));
-    let in_b_and_different_in_a = b_memory
-        .iter()
-        .filter(|(k, &v)| a_memory.get(k).map(|&b| b != v).unwrap_or(true));
-
-    let in_a_and_different_in_b = format_hash_map_iterator(in_a_and_different_in_b);
-    let in_b_and_different_in_a = format_hash_map_iterator(in_b_and_different_in_a);
-
+    let diff = MemorySnapshot::new(&a_memory).diff(&MemorySnapshot::new(&b_memory));
     panic!(
         "Memory for both implementations must match after execution.\n\n\
-        In B, different in A: {in_b_and_different_in_a}\n\n\
-        In A, different in B: {in_a_and_different_in_b}"
+        Changes from A to B:\n{diff}"
     );
 }
 
+/// The number of elements by which the snippet grows the stack, as implied by the sizes of
+/// its declared [inputs](BasicSnippet::inputs) and [outputs](BasicSnippet::outputs).
+pub fn stack_growth_from_signature(snippet: &dyn BasicSnippet) -> isize {
+    let size_of = |arguments: Vec<(DataType, String)>| -> isize {
+        arguments
+            .iter()
+            .map(|(data_type, _)| data_type.stack_size() as isize)
+            .sum()
+    };
+
+    size_of(snippet.outputs()) - size_of(snippet.inputs())
+}
+
+/// Verify that the observed change in stack height matches the snippet's declared signature.
+/// The expectation is derived from [`BasicSnippet::inputs`] and [`BasicSnippet::outputs`]
+/// alone, so a snippet whose signature is wrong gets caught even if its Rust shadow agrees
+/// with its tasm code. Additionally verify that [`BasicSnippet::stack_diff`], if overridden,
+/// agrees with the signature.
 pub fn verify_stack_growth<T: RustShadow>(
     shadowed_snippet: &T,
     initial_stack: &[BFieldElement],
     final_stack: &[BFieldElement],
 ) {
+    let snippet = shadowed_snippet.inner();
+    let snippet = snippet.borrow();
+    let entrypoint = snippet.entrypoint();
+    let expected_stack_growth = stack_growth_from_signature(&*snippet);
+
+    assert_eq!(
+        expected_stack_growth,
+        snippet.stack_diff(),
+        "Declared stack difference of `{entrypoint}` must agree with its signature.\n\
+        inputs:  {}\n\
+        outputs: {}",
+        snippet.inputs().iter().map(|(_, name)| name).join(", "),
+        snippet.outputs().iter().map(|(_, name)| name).join(", "),
+    );
+
     let observed_stack_growth: isize = final_stack.len() as isize - initial_stack.len() as isize;
-    let expected_stack_growth: isize = shadowed_snippet.inner().borrow().stack_diff();
     assert_eq!(
         expected_stack_growth,
         observed_stack_growth,
-        "Stack must pop and push expected number of elements. Got input: {}\nGot output: {}",
+        "`{entrypoint}` must pop and push the number of elements declared by its signature. \
+        Got input: {}\nGot output: {}",
         initial_stack.iter().map(|x| x.to_string()).join(","),
         final_stack.iter().map(|x| x.to_string()).join(",")
     );
 }
 
+/// Verify that the snippet only wrote to memory it is allowed to write to, namely:
+/// - the state of the dynamic memory allocator,
+/// - memory it allocated dynamically during execution,
+/// - statically allocated memory, and
+/// - the regions it declares [writable](BasicSnippet::writable_memory_regions).
+///
+/// Snippets that do not declare any writable regions are not checked.
+pub fn verify_memory_isolation<T: RustShadow>(
+    shadowed_snippet: &T,
+    initial_stack: &[BFieldElement],
+    initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    final_memory: &HashMap<BFieldElement, BFieldElement>,
+    words_statically_allocated: u32,
+) {
+    let snippet = shadowed_snippet.inner();
+    let snippet = snippet.borrow();
+    let Some(writable_regions) = snippet.writable_memory_regions(initial_stack, initial_memory)
+    else {
+        return;
+    };
+
+    let next_free_address = |memory: &HashMap<BFieldElement, BFieldElement>| {
+        match memory.get(&DYN_MALLOC_ADDRESS).copied().unwrap_or_default() {
+            address if address == BFieldElement::new(0) => FIRST_DYNAMICALLY_ALLOCATED_ADDRESS,
+            address => address,
+        }
+        .value()
+    };
+    let dynamically_allocated = next_free_address(initial_memory)..next_free_address(final_memory);
+
+    let mut library = Library::with_preallocated_memory(words_statically_allocated);
+    snippet.code(&mut library);
+    let static_memory_layout = library.static_memory_layout();
+
+    let is_writable = |address: BFieldElement| {
+        address == DYN_MALLOC_ADDRESS
+            || address == FREE_LIST_ADDRESS
+            || dynamically_allocated.contains(&address.value())
+            || static_memory_layout.is_reserved(address)
+            || writable_regions.iter().any(|&(first_address, num_words)| {
+                let offset = (address - first_address).value();
+                offset < num_words as u64
+            })
+    };
+
+    let illegal_writes = final_memory
+        .iter()
+        .filter(|&(address, value)| initial_memory.get(address) != Some(value))
+        .filter(|&(&address, _)| !is_writable(address))
+        .map(|(&address, _)| address)
+        .sorted_by_key(|address| address.value())
+        .collect_vec();
+    assert!(
+        illegal_writes.is_empty(),
+        "`{}` must only write to allocated or declared memory. Illegal writes to: {}",
+        snippet.entrypoint(),
+        illegal_writes.iter().join(", ")
+    );
+}
+
+/// Verify that the Rust shadow and the tasm code read the same amount of nondeterminism.
+/// Rust shadows that do not count what they read through a
+/// [`TokenCursor`](crate::traits::procedure::TokenCursor) or a
+/// [`DigestCursor`](crate::traits::algorithm::DigestCursor) are taken to read all of it.
+pub fn verify_nondeterminism_consumption(rust: &VmOutputState, tasm: &VmOutputState) {
+    assert_eq!(
+        rust.individual_tokens_read, tasm.individual_tokens_read,
+        "Rust shadowing and VM must read the same number of nondeterministic individual tokens"
+    );
+    assert_eq!(
+        rust.digests_read, tasm.digests_read,
+        "Rust shadowing and VM must read the same number of nondeterministic digests"
+    );
+}
+
+/// Report which of the snippet's declared corner-case categories are exercised by the given
+/// categorized initial states. Panics if a declared category is not exercised, or if an
+/// initial state belongs to an undeclared category.
+pub fn verify_corner_case_coverage<S>(
+    snippet: &dyn BasicSnippet,
+    categorized_corner_cases: &[(String, S)],
+) {
+    let declared_categories = snippet.corner_case_categories();
+    let counts = categorized_corner_cases
+        .iter()
+        .map(|(category, _)| category)
+        .counts();
+    if declared_categories.is_empty() && counts.is_empty() {
+        return;
+    }
+
+    println!("corner-case coverage of {}:", snippet.entrypoint());
+    for category in declared_categories.iter() {
+        let count = counts.get(category).copied().unwrap_or_default();
+        println!("  {category}: {count} initial state(s)");
+    }
+
+    let undeclared = counts
+        .keys()
+        .copied()
+        .filter(|&category| !declared_categories.contains(category))
+        .sorted()
+        .join(", ");
+    assert!(
+        undeclared.is_empty(),
+        "Corner cases of `{}` must belong to declared categories. Undeclared: {undeclared}",
+        snippet.entrypoint()
+    );
+
+    let unexercised = declared_categories
+        .iter()
+        .filter(|category| !counts.contains_key(category))
+        .join(", ");
+    assert!(
+        unexercised.is_empty(),
+        "Every declared corner-case category of `{}` must be exercised. Missing: {unexercised}",
+        snippet.entrypoint()
+    );
+}
+
+/// Verify that the Rust shadow and Triton VM wrote the same values to standard output, where
+/// the output consists of values of the given data types, in order. Reports mismatches per
+/// decoded value rather than per word.
+pub fn verify_typed_output_equivalence(
+    data_types: &[DataType],
+    rust_output: &[BFieldElement],
+    tasm_output: &[BFieldElement],
+) {
+    if rust_output == tasm_output {
+        return;
+    }
+
+    let rust_values = split_into_typed_values(data_types, rust_output);
+    let tasm_values = split_into_typed_values(data_types, tasm_output);
+    let mismatches = data_types
+        .iter()
+        .zip_eq(rust_values.iter().zip_eq(tasm_values.iter()))
+        .enumerate()
+        .filter(|(_, (_, (rust_value, tasm_value)))| rust_value != tasm_value)
+        .map(|(i, (data_type, (rust_value, tasm_value)))| {
+            let format = |words: &Option<&[BFieldElement]>| match words {
+                Some(words) => format_typed_value(data_type, words),
+                None => "<missing>".to_owned(),
+            };
+            format!(
+                "value {i} ({}):\n  Rust: {}\n  VM:   {}",
+                data_type.label_friendly_name(),
+                format(rust_value),
+                format(tasm_value)
+            )
+        })
+        .join("\n");
+
+    panic!(
+        "Rust shadowing and VM std out must agree.\n{mismatches}\n\
+        Rust output: {}\nVM output:   {}",
+        rust_output.iter().join(","),
+        tasm_output.iter().join(",")
+    );
+}
+
+/// Assert that the public output written by Triton VM consists of exactly the declared
+/// values, each a valid value of its type, and that the Rust shadow wrote the same values.
+/// Every declared entry is a type and the number of consecutive values of that type.
+pub fn verify_public_output(
+    declared_values: &[(DataType, usize)],
+    rust_output: &[BFieldElement],
+    tasm_output: &[BFieldElement],
+) {
+    let data_types = declared_values
+        .iter()
+        .flat_map(|(data_type, count)| vec![data_type.clone(); *count])
+        .collect_vec();
+    let declared_length: usize = data_types.iter().map(|t| t.stack_size()).sum();
+    assert_eq!(
+        declared_length,
+        tasm_output.len(),
+        "VM std out must consist of exactly the {} declared values",
+        data_types.len()
+    );
+
+    // values are written to std out in the order of their encoding, the reverse of their
+    // order on the stack
+    for (i, (data_type, value)) in data_types
+        .iter()
+        .zip_eq(split_into_typed_values(&data_types, tasm_output))
+        .enumerate()
+    {
+        let value = value.unwrap().iter().rev().copied().collect_vec();
+        assert!(
+            data_type.is_valid_stack_value(&value),
+            "VM std out value {i} must be a valid {}, but is [{}]",
+            data_type.label_friendly_name(),
+            value.iter().rev().join(", ")
+        );
+    }
+
+    verify_typed_output_equivalence(&data_types, rust_output, tasm_output);
+}
+
+/// Split the output into chunks holding one value of the respective data type each. A
+/// chunk is `None` if the output is too short to hold it.
+fn split_into_typed_values<'a>(
+    data_types: &[DataType],
+    output: &'a [BFieldElement],
+) -> Vec<Option<&'a [BFieldElement]>> {
+    let mut offset = 0;
+    data_types
+        .iter()
+        .map(|data_type| {
+            let end = offset + data_type.stack_size();
+            let chunk = output.get(offset..end);
+            offset = end;
+            chunk
+        })
+        .collect()
+}
+
+/// Render the encoding of a value of the given data type in human-readable form, falling
+/// back to the raw words if they are not a valid encoding.
+fn format_typed_value(data_type: &DataType, words: &[BFieldElement]) -> String {
+    let raw = || format!("[{}]", words.iter().join(", "));
+    match data_type {
+        DataType::Bool => match words[0].value() {
+            0 => "false".to_owned(),
+            1 => "true".to_owned(),
+            _ => raw(),
+        },
+        DataType::U32 => u32::decode(words).map_or_else(|_| raw(), |v| v.to_string()),
+        DataType::U64 => u64::decode(words).map_or_else(|_| raw(), |v| v.to_string()),
+        DataType::U128 => u128::decode(words).map_or_else(|_| raw(), |v| v.to_string()),
+        DataType::Bfe | DataType::VoidPointer | DataType::List(_) => words[0].to_string(),
+        DataType::Xfe => XFieldElement::decode(words).map_or_else(|_| raw(), |v| v.to_string()),
+        DataType::Digest => Digest::decode(words).map_or_else(|_| raw(), |v| v.to_string()),
+        DataType::Tuple(data_types) => {
+            let elements = split_into_typed_values(data_types, words)
+                .into_iter()
+                .zip_eq(data_types)
+                .map(|(element, data_type)| format_typed_value(data_type, element.unwrap()))
+                .join(", ");
+            format!("({elements})")
+        }
+    }
+}
+
 pub fn verify_sponge_equivalence(a: &Option<VmHasherState>, b: &Option<VmHasherState>) {
-    match (a, b) {
-        (Some(state_a), Some(state_b)) => assert_eq!(state_a.state, state_b.state),
-        (None, None) => (),
-        _ => panic!("{a:?} != {b:?}"),
+    let format = |sponge_state: &Option<VmHasherState>| match sponge_state {
+        Some(sponge_state) => format!("[{}]", sponge_state.state.iter().join(", ")),
+        None => "uninitialized".to_owned(),
     };
+    let a_state = a.as_ref().map(|sponge_state| sponge_state.state);
+    let b_state = b.as_ref().map(|sponge_state| sponge_state.state);
+    assert_eq!(
+        a_state,
+        b_state,
+        "A sponge state must match B sponge state\n\nA: {}\n\nB: {}",
+        format(a),
+        format(b)
+    );
 }
 
 #[allow(dead_code)]
@@ -426,6 +684,12 @@ pub fn test_rust_equivalence_given_complete_state<T: RustShadow>(
     expected_final_stack: Option<&[BFieldElement]>,
 ) -> VmOutputState {
     let init_stack = stack.to_vec();
+    let snippet = shadowed_snippet.inner();
+    let entrypoint = snippet.borrow().entrypoint();
+    if cfg!(debug_assertions) {
+        let inputs = snippet.borrow().inputs();
+        verify_stack_values_match_types(&inputs, stack, "Input", &entrypoint);
+    }
 
     let rust = rust_final_state(shadowed_snippet, stack, stdin, nondeterminism, sponge_state);
 
@@ -438,6 +702,10 @@ pub fn test_rust_equivalence_given_complete_state<T: RustShadow>(
         sponge_state,
         words_statically_allocated,
     );
+    if cfg!(debug_assertions) {
+        let outputs = snippet.borrow().outputs();
+        verify_stack_values_match_types(&outputs, &tasm.final_stack, "Output", &entrypoint);
+    }
 
     assert_eq!(
         rust.output, tasm.output,
@@ -449,11 +717,333 @@ pub fn test_rust_equivalence_given_complete_state<T: RustShadow>(
         verify_stack_equivalence(expected, &rust.final_stack);
     }
     verify_memory_equivalence(&rust.final_ram, &tasm.final_ram);
+    verify_nondeterminism_consumption(&rust, &tasm);
+    verify_memory_isolation(
+        shadowed_snippet,
+        &init_stack,
+        &nondeterminism.ram,
+        &tasm.final_ram,
+        words_statically_allocated,
+    );
     verify_stack_growth(shadowed_snippet, &init_stack, &tasm.final_stack);
 
+    // Snippets started from a given sponge state are part of a larger transcript, so their
+    // Rust shadows must keep track of the sponge. Other snippets may use the sponge
+    // internally without shadowing it.
+    if sponge_state.is_some() {
+        verify_sponge_equivalence(&rust.final_sponge_state, &tasm.final_sponge_state);
+    }
+
+    verify_relocation_invariance(
+        shadowed_snippet,
+        stack,
+        stdin,
+        nondeterminism,
+        sponge_state,
+        words_statically_allocated,
+        &tasm,
+    );
+
     tasm
 }
 
+/// Assert that the values on top of the stack are valid values of the declared data types,
+/// for example, that values declared as `u32` fit in 32 bits. The last declared value is
+/// expected on top of the stack. Panics with the name of the first offending value, using
+/// `kind`, for example "Input" or "Output", to describe it.
+pub fn verify_stack_values_match_types(
+    declared_values: &[(DataType, String)],
+    stack: &[BFieldElement],
+    kind: &str,
+    entrypoint: &str,
+) {
+    let mut value_end = stack.len();
+    for (data_type, name) in declared_values.iter().rev() {
+        let value_start = value_end
+            .checked_sub(data_type.stack_size())
+            .unwrap_or_else(|| panic!("{kind} `{name}` of `{entrypoint}` must be on the stack"));
+        let value = &stack[value_start..value_end];
+        assert!(
+            data_type.is_valid_stack_value(value),
+            "{kind} `{name}` of `{entrypoint}` must be a valid {}, but is [{}]",
+            data_type.label_friendly_name(),
+            value.iter().join(", ")
+        );
+        value_end = value_start;
+    }
+}
+
+/// Number of junk words placed directly before and after every relocated input object.
+const RELOCATION_JUNK_MARGIN: u64 = 8;
+
+/// Number of random memory layouts that snippets declaring their input objects are re-run
+/// with.
+const NUM_RELOCATIONS: usize = 3;
+
+/// Re-run the snippet with the [input objects](BasicSnippet::input_objects) it declares
+/// moved to random addresses, their old locations overwritten, and junk placed around their
+/// new locations. Assert that the Rust shadow and Triton VM agree on every layout, and that
+/// the snippet's non-pointer outputs and the final contents of the input objects are the
+/// same as in the original layout, whose final state is `original`.
+pub fn verify_relocation_invariance<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+    words_statically_allocated: u32,
+    original: &VmOutputState,
+) {
+    let snippet = shadowed_snippet.inner();
+    let snippet = snippet.borrow();
+    let Some(input_objects) = snippet.input_objects(stack, &nondeterminism.ram) else {
+        return;
+    };
+    let entrypoint = snippet.entrypoint();
+
+    // stack index of every input's deepest word
+    let input_sizes = snippet
+        .inputs()
+        .iter()
+        .map(|(t, _)| t.stack_size())
+        .collect_vec();
+    let input_positions = (0..input_sizes.len())
+        .map(|i| stack.len() - input_sizes[i..].iter().sum::<usize>())
+        .collect_vec();
+
+    let mut rng = thread_rng();
+    for _ in 0..NUM_RELOCATIONS {
+        let mut relocated_stack = stack.to_vec();
+        let mut relocated_nondeterminism = nondeterminism.clone();
+        let memory = &mut relocated_nondeterminism.ram;
+
+        let old_pointers = input_objects
+            .iter()
+            .map(|&(input_index, _)| stack[input_positions[input_index]])
+            .collect_vec();
+        for (&old_pointer, &(_, size)) in old_pointers.iter().zip_eq(input_objects.iter()) {
+            for offset in 0..size as u64 {
+                memory.insert(old_pointer + BFieldElement::new(offset), rng.gen());
+            }
+        }
+
+        let mut relocations = vec![];
+        for (&old_pointer, &(input_index, size)) in old_pointers.iter().zip_eq(input_objects.iter())
+        {
+            let size = size as u64;
+            let new_pointer = loop {
+                let candidate = rng.gen_range(RELOCATION_JUNK_MARGIN..1 << 31);
+                let mut region =
+                    candidate - RELOCATION_JUNK_MARGIN..candidate + size + RELOCATION_JUNK_MARGIN;
+                if region.all(|address| !memory.contains_key(&BFieldElement::new(address))) {
+                    break candidate;
+                }
+            };
+
+            for offset in 1..=RELOCATION_JUNK_MARGIN {
+                memory.insert(BFieldElement::new(new_pointer - offset), rng.gen());
+                memory.insert(
+                    BFieldElement::new(new_pointer + size - 1 + offset),
+                    rng.gen(),
+                );
+            }
+            for offset in 0..size {
+                let old_address = old_pointer + BFieldElement::new(offset);
+                let word = nondeterminism.ram.get(&old_address).copied();
+                memory.insert(
+                    BFieldElement::new(new_pointer + offset),
+                    word.unwrap_or_default(),
+                );
+            }
+
+            let new_pointer = BFieldElement::new(new_pointer);
+            relocated_stack[input_positions[input_index]] = new_pointer;
+            relocations.push((old_pointer, new_pointer, size));
+        }
+
+        let rust = rust_final_state(
+            shadowed_snippet,
+            &relocated_stack,
+            stdin,
+            &relocated_nondeterminism,
+            sponge_state,
+        );
+        let tasm = tasm_final_state(
+            shadowed_snippet,
+            &relocated_stack,
+            stdin,
+            relocated_nondeterminism,
+            sponge_state,
+            words_statically_allocated,
+        );
+        verify_stack_equivalence(&rust.final_stack, &tasm.final_stack);
+        verify_memory_equivalence(&rust.final_ram, &tasm.final_ram);
+
+        let mut output_end = tasm.final_stack.len();
+        let mut original_output_end = original.final_stack.len();
+        for (data_type, name) in snippet.outputs().into_iter().rev() {
+            let size = data_type.stack_size();
+            let output = &tasm.final_stack[output_end - size..output_end];
+            let original_output =
+                &original.final_stack[original_output_end - size..original_output_end];
+            output_end -= size;
+            original_output_end -= size;
+            if matches!(data_type, DataType::VoidPointer | DataType::List(_)) {
+                continue;
+            }
+            assert_eq!(
+                original_output, output,
+                "Output `{name}` of `{entrypoint}` must not depend on where its input objects \
+                are placed in memory"
+            );
+        }
+
+        for (old_pointer, new_pointer, size) in relocations {
+            for offset in 0..size {
+                let offset = BFieldElement::new(offset);
+                let read = |memory: &HashMap<BFieldElement, BFieldElement>, address| {
+                    memory.get(&address).copied().unwrap_or_default()
+                };
+                assert_eq!(
+                    read(&original.final_ram, old_pointer + offset),
+                    read(&tasm.final_ram, new_pointer + offset),
+                    "Final contents of the input objects of `{entrypoint}` must not depend on \
+                    where they are placed in memory"
+                );
+            }
+        }
+    }
+}
+
+/// Assert that both the Rust shadow and the tasm code fail on the given initial state: the
+/// Rust shadow must panic, and Triton VM must halt with the `expected_error`.
+pub fn test_rust_tasm_equivalence_expect_failure<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+    words_statically_allocated: u32,
+    expected_error: InstructionError,
+) {
+    let entrypoint = shadowed_snippet.inner().borrow().entrypoint();
+
+    let rust_result = catch_unwind(AssertUnwindSafe(|| {
+        rust_final_state(shadowed_snippet, stack, stdin, nondeterminism, sponge_state)
+    }));
+    assert!(
+        rust_result.is_err(),
+        "Rust shadow of `{entrypoint}` must panic on failing initial state"
+    );
+
+    let error = tasm_failure(
+        shadowed_snippet,
+        stack,
+        stdin,
+        nondeterminism,
+        sponge_state,
+        words_statically_allocated,
+    );
+    assert_eq!(
+        expected_error, error,
+        "Triton VM must fail with expected error when executing `{entrypoint}`"
+    );
+}
+
+/// Returns whether the Rust shadow panics on the given initial state, in which case Triton
+/// VM is asserted to fail as well. If it returns `false`, the caller is responsible for
+/// comparing the final states.
+pub fn rust_shadow_and_tasm_fail<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+    words_statically_allocated: u32,
+) -> bool {
+    let rust_result = catch_unwind(AssertUnwindSafe(|| {
+        rust_final_state(shadowed_snippet, stack, stdin, nondeterminism, sponge_state)
+    }));
+    if rust_result.is_ok() {
+        return false;
+    }
+
+    tasm_failure(
+        shadowed_snippet,
+        stack,
+        stdin,
+        nondeterminism,
+        sponge_state,
+        words_statically_allocated,
+    );
+    true
+}
+
+/// Run the snippet in Triton VM and return the error it fails with. Panics if execution
+/// succeeds.
+fn tasm_failure<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism<BFieldElement>,
+    sponge_state: &Option<VmHasherState>,
+    words_statically_allocated: u32,
+) -> InstructionError {
+    let entrypoint = shadowed_snippet.inner().borrow().entrypoint();
+    let (code, source_map) = link_for_isolated_run_with_source_map(
+        shadowed_snippet.inner(),
+        words_statically_allocated as usize,
+    );
+    let program = Program::new(&code);
+    let execution_result = execute_with_terminal_state_and_source_map(
+        &program,
+        stdin,
+        stack,
+        nondeterminism,
+        sponge_state.to_owned(),
+        &source_map,
+    );
+    if execution_result.is_ok() {
+        panic!("Triton VM must fail when executing `{entrypoint}` on failing initial state");
+    }
+
+    vm_error(execution_result)
+}
+
+/// The error with which Triton VM halted in the given execution. Panics if execution
+/// succeeded, or failed for any other reason than a VM error.
+fn vm_error<T>(execution_result: anyhow::Result<T>) -> InstructionError {
+    let Err(err) = execution_result else {
+        panic!("Triton VM execution must fail");
+    };
+    let Some(vm_error) = err.downcast_ref::<VMError>() else {
+        panic!("Execution must fail with a VM error. Got: {err}");
+    };
+
+    vm_error.source
+}
+
+/// Assert that Triton VM execution failed with the given error. Unlike a test marked
+/// `#[should_panic]`, this rejects failures with any other cause.
+pub fn assert_vm_error<T>(execution_result: anyhow::Result<T>, expected_error: InstructionError) {
+    assert_eq!(
+        expected_error,
+        vm_error(execution_result),
+        "Triton VM must fail with the expected error"
+    );
+}
+
+/// Assert that Triton VM execution failed because an `assert` instruction failed.
+pub fn assert_assertion_failure<T>(execution_result: anyhow::Result<T>) {
+    assert_vm_error(execution_result, InstructionError::AssertionFailed);
+}
+
+/// Assert that Triton VM execution failed because an instruction accessed more elements than
+/// the op stack holds.
+pub fn assert_op_stack_underflow<T>(execution_result: anyhow::Result<T>) {
+    assert_vm_error(execution_result, InstructionError::OpStackTooShallow);
+}
+
 pub fn link_and_run_tasm_for_test<T: RustShadow>(
     snippet_struct: &T,
     stack: &mut Vec<BFieldElement>,
@@ -462,44 +1052,22 @@ pub fn link_and_run_tasm_for_test<T: RustShadow>(
     maybe_sponge_state: Option<VmHasherState>,
     words_statically_allocated: u32,
 ) -> VmOutputState {
-    let code = link_for_isolated_run(snippet_struct, words_statically_allocated);
+    let (code, source_map) = link_for_isolated_run_with_source_map(
+        snippet_struct.inner(),
+        words_statically_allocated as usize,
+    );
 
-    execute_test(
+    execute_test_with_source_map(
         &code,
         stack,
         snippet_struct.inner().borrow().stack_diff(),
         std_in,
         nondeterminism,
         maybe_sponge_state,
+        &source_map,
     )
 }
 
-fn link_for_isolated_run<T: RustShadow>(
-    snippet_struct: &T,
-    words_statically_allocated: u32,
-) -> Vec<LabelledInstruction> {
-    println!(
-        "linking with preallocated memory ... \
-        number of statically allocated words: {words_statically_allocated}"
-    );
-    let mut library = Library::with_preallocated_memory(words_statically_allocated);
-    let entrypoint = snippet_struct.inner().borrow().entrypoint();
-    let function_body = snippet_struct.inner().borrow().code(&mut library);
-    let library_code = library.all_imports();
-
-    // The TASM code is always run through a function call, so the 1st instruction is a call to the
-    // function in question.
-    let code = triton_asm!(
-        call {entrypoint}
-        halt
-
-        {&function_body}
-        {&library_code}
-    );
-
-    code
-}
-
 #[allow(dead_code)]
 pub fn test_rust_equivalence_given_execution_state<T: BasicSnippet + RustShadow>(
     snippet_struct: &T,
@@ -517,15 +1085,90 @@ pub fn test_rust_equivalence_given_execution_state<T: BasicSnippet + RustShadow>
     )
 }
 
+/// Setting this environment variable makes [`assert_snippet_code_snapshot`] overwrite
+/// snapshots instead of comparing against them.
+pub const ENV_VARIABLE_BLESS_SNAPSHOTS: &str = "BLESS_SNAPSHOTS";
+
+/// Render the code of a snippet and all its imports in a canonical text form: one
+/// instruction per line, labels unindented, and imports sorted alphabetically after the
+/// snippet's own code.
+pub fn snippet_code_snapshot<T: BasicSnippet + ?Sized>(snippet: &T) -> String {
+    let mut library = Library::new();
+    let function_body = snippet.code(&mut library);
+
+    [function_body]
+        .into_iter()
+        .chain(library.all_external_dependencies())
+        .map(|code| format_tasm(&code))
+        .join("\n")
+}
+
+/// Assert that the code a snippet emits matches the golden file at `path`.
+///
+/// If the environment variable [`ENV_VARIABLE_BLESS_SNAPSHOTS`] is set, the golden file is
+/// (over)written instead.
+pub fn assert_snippet_code_snapshot<T: BasicSnippet + ?Sized>(snippet: &T, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let snapshot = snippet_code_snapshot(snippet);
+
+    if std::env::var(ENV_VARIABLE_BLESS_SNAPSHOTS).is_ok() {
+        if let Some(directory) = path.parent() {
+            create_dir_all(directory).expect("snapshot directory should be creatable");
+        }
+        std::fs::write(path, snapshot).expect("snapshot should be writable");
+        return;
+    }
+
+    let Ok(golden) = std::fs::read_to_string(path) else {
+        panic!(
+            "cannot read snapshot {}; set {ENV_VARIABLE_BLESS_SNAPSHOTS} to create it",
+            path.display()
+        );
+    };
+    if golden == snapshot {
+        return;
+    }
+
+    let golden_lines = golden.lines().collect_vec();
+    let snapshot_lines = snapshot.lines().collect_vec();
+    let num_lines = golden_lines.len().max(snapshot_lines.len());
+    let first_mismatch = (0..num_lines)
+        .find(|&i| golden_lines.get(i) != snapshot_lines.get(i))
+        .unwrap_or(num_lines);
+    panic!(
+        "code of snippet `{}` differs from snapshot {} starting at line {}.\n\
+        expected: {}\n\
+        actual:   {}\n\
+        Set {ENV_VARIABLE_BLESS_SNAPSHOTS} to accept the new code.",
+        snippet.entrypoint(),
+        path.display(),
+        first_mismatch + 1,
+        golden_lines
+            .get(first_mismatch)
+            .unwrap_or(&"<end of snapshot>"),
+        snapshot_lines
+            .get(first_mismatch)
+            .unwrap_or(&"<end of code>"),
+    );
+}
+
 #[cfg(test)]
 mod test {
     use rand::random;
-    use triton_vm::{BFieldElement, NonDeterminism};
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::vm::VMState;
+    use triton_vm::{triton_asm, BFieldElement, NonDeterminism};
     use twenty_first::shared_math::tip5::DIGEST_LENGTH;
 
-    use crate::{empty_stack, hashing::hash_varlen::HashVarlen};
+    use crate::arithmetic::u64::wrapping_sub_u64::WrappingSub;
+    use crate::library::DummyTestSnippetA;
+    use crate::mmr::bag_peaks::BagPeaks;
+    use crate::snippet_bencher::BenchmarkCase;
+    use crate::traits::closure::{Closure, ShadowedClosure};
+    use crate::traits::function::{Function, FunctionInitialState, ShadowedFunction};
+    use crate::{empty_stack, execute_with_terminal_state, hashing::hash_varlen::HashVarlen};
 
-    use super::test_rust_equivalence_given_complete_state_deprecated;
+    use super::*;
 
     /// TIP6 sets the bottom of the stack to the program hash. While testing Snippets,
     /// which are not standalone programs and therefore do not come with a well defined
@@ -553,4 +1196,281 @@ mod test {
             None,
         );
     }
+
+    #[test]
+    fn snippet_code_matches_snapshot() {
+        assert_snippet_code_snapshot(&DummyTestSnippetA, "snapshots/tasm_a_dummy_test_value.tasm");
+    }
+
+    #[should_panic]
+    #[test]
+    fn snippet_code_differing_from_snapshot_is_detected() {
+        let path = std::env::temp_dir().join("tasm_lib_outdated_snapshot.tasm");
+        std::fs::write(&path, "tasm_a_dummy_test_value:\n    return\n").unwrap();
+        assert_snippet_code_snapshot(&DummyTestSnippetA, path);
+    }
+
+    #[test]
+    fn stack_growth_is_derived_from_signature() {
+        assert_eq!(-2, stack_growth_from_signature(&WrappingSub));
+
+        let initial_stack = [empty_stack(), vec![BFieldElement::new(0); 4]].concat();
+        let final_stack = [empty_stack(), vec![BFieldElement::new(0); 2]].concat();
+        verify_stack_growth(
+            &ShadowedClosure::new(WrappingSub),
+            &initial_stack,
+            &final_stack,
+        );
+    }
+
+    #[should_panic(expected = "declared by its signature")]
+    #[test]
+    fn stack_growth_deviating_from_signature_is_detected() {
+        let initial_stack = [empty_stack(), vec![BFieldElement::new(0); 4]].concat();
+        let final_stack = [empty_stack(), vec![BFieldElement::new(0); 3]].concat();
+        verify_stack_growth(
+            &ShadowedClosure::new(WrappingSub),
+            &initial_stack,
+            &final_stack,
+        );
+    }
+
+    #[test]
+    fn equal_typed_output_is_accepted() {
+        let output = (1u64 << 40).encode();
+        verify_typed_output_equivalence(&[DataType::U64], &output, &output);
+    }
+
+    #[should_panic(expected = "value 1 (u64):\n  Rust: 1099511627776\n  VM:   1099511627777")]
+    #[test]
+    fn typed_output_mismatch_is_reported_per_value() {
+        let output_types = [DataType::Bool, DataType::U64];
+        let rust_output = [vec![BFieldElement::new(1)], (1u64 << 40).encode()].concat();
+        let tasm_output = [vec![BFieldElement::new(1)], ((1u64 << 40) + 1).encode()].concat();
+        verify_typed_output_equivalence(&output_types, &rust_output, &tasm_output);
+    }
+
+    #[test]
+    fn declared_corner_case_categories_are_covered() {
+        let categorized_corner_cases = BagPeaks.categorized_corner_case_initial_states();
+        verify_corner_case_coverage(&BagPeaks, &categorized_corner_cases);
+    }
+
+    #[should_panic(expected = "Missing: maximal number of peaks")]
+    #[test]
+    fn unexercised_corner_case_category_is_detected() {
+        let mut categorized_corner_cases = BagPeaks.categorized_corner_case_initial_states();
+        categorized_corner_cases.retain(|(category, _)| category != "maximal number of peaks");
+        verify_corner_case_coverage(&BagPeaks, &categorized_corner_cases);
+    }
+
+    #[should_panic(expected = "Undeclared: two peaks")]
+    #[test]
+    fn undeclared_corner_case_category_is_detected() {
+        let categorized_corner_cases = vec![("two peaks".to_owned(), ())];
+        verify_corner_case_coverage(&BagPeaks, &categorized_corner_cases);
+    }
+
+    #[test]
+    fn equal_sponge_states_are_accepted() {
+        let sponge_state = VmHasherState { state: random() };
+        verify_sponge_equivalence(&Some(sponge_state.clone()), &Some(sponge_state));
+        verify_sponge_equivalence(&None, &None);
+    }
+
+    #[should_panic(expected = "A sponge state must match B sponge state")]
+    #[test]
+    fn diverging_sponge_states_are_detected() {
+        let sponge_state = VmHasherState { state: random() };
+        let mut diverged_sponge_state = sponge_state.clone();
+        diverged_sponge_state.state[0].increment();
+        verify_sponge_equivalence(&Some(sponge_state), &Some(diverged_sponge_state));
+    }
+
+    /// Returns the pointer to its input object instead of anything stored in the object.
+    struct LeakPointer;
+
+    impl BasicSnippet for LeakPointer {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::VoidPointer, "*object".to_owned())]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Bfe, "address".to_owned())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasm_test_leak_pointer".to_owned()
+        }
+
+        fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+            let entrypoint = self.entrypoint();
+            triton_asm!({entrypoint}: return)
+        }
+
+        fn input_objects(
+            &self,
+            _initial_stack: &[BFieldElement],
+            _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+        ) -> Option<Vec<(usize, u32)>> {
+            Some(vec![(0, 1)])
+        }
+    }
+
+    impl Closure for LeakPointer {
+        fn rust_shadow(&self, _stack: &mut Vec<BFieldElement>) {}
+
+        fn pseudorandom_initial_state(
+            &self,
+            _seed: [u8; 32],
+            _bench_case: Option<BenchmarkCase>,
+        ) -> Vec<BFieldElement> {
+            [empty_stack(), vec![BFieldElement::new(100)]].concat()
+        }
+    }
+
+    #[test]
+    fn relocated_input_objects_yield_same_results() {
+        let shadowed = ShadowedFunction::new(BagPeaks);
+        let FunctionInitialState { stack, memory } =
+            BagPeaks.pseudorandom_initial_state(random(), None);
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let original = tasm_final_state(&shadowed, &stack, &[], nondeterminism.clone(), &None, 0);
+        verify_relocation_invariance(&shadowed, &stack, &[], &nondeterminism, &None, 0, &original);
+    }
+
+    #[should_panic(expected = "must not depend on where its input objects are placed in memory")]
+    #[test]
+    fn address_dependent_output_is_detected() {
+        ShadowedClosure::new(LeakPointer).test();
+    }
+
+    /// Fails its only assertion, which is placed at offset 2 of its code.
+    struct FailAssertion;
+
+    impl BasicSnippet for FailAssertion {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasm_test_fail_assertion".to_owned()
+        }
+
+        fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+            let entrypoint = self.entrypoint();
+            triton_asm!({entrypoint}: push 0 assert return)
+        }
+    }
+
+    impl Closure for FailAssertion {
+        fn rust_shadow(&self, _stack: &mut Vec<BFieldElement>) {}
+
+        fn pseudorandom_initial_state(
+            &self,
+            _seed: [u8; 32],
+            _bench_case: Option<BenchmarkCase>,
+        ) -> Vec<BFieldElement> {
+            empty_stack()
+        }
+    }
+
+    #[should_panic(expected = "inside tasm_test_fail_assertion at relative offset 2")]
+    #[test]
+    fn failures_are_attributed_to_the_failing_snippet() {
+        let shadowed = ShadowedClosure::new(FailAssertion);
+        let mut stack = empty_stack();
+        let nondeterminism = NonDeterminism::default();
+        link_and_run_tasm_for_test(&shadowed, &mut stack, vec![], nondeterminism, None, 0);
+    }
+
+    #[test]
+    fn output_of_declared_shape_is_accepted() {
+        let declared = [(DataType::U64, 2), (DataType::Bool, 1)];
+        let output = [
+            (1u64 << 40).encode(),
+            5u64.encode(),
+            vec![BFieldElement::new(1)],
+        ]
+        .concat();
+        verify_public_output(&declared, &output, &output);
+    }
+
+    #[should_panic(expected = "VM std out must consist of exactly the 3 declared values")]
+    #[test]
+    fn surplus_output_is_detected() {
+        let declared = [(DataType::U64, 2), (DataType::Bool, 1)];
+        let output = [7u64.encode(), 5u64.encode(), vec![BFieldElement::new(1); 2]].concat();
+        verify_public_output(&declared, &output, &output);
+    }
+
+    #[should_panic(expected = "VM std out value 2 must be a valid bool")]
+    #[test]
+    fn ill_typed_output_is_detected() {
+        let declared = [(DataType::U64, 2), (DataType::Bool, 1)];
+        let output = [7u64.encode(), 5u64.encode(), vec![BFieldElement::new(2)]].concat();
+        verify_public_output(&declared, &output, &output);
+    }
+
+    fn signature_values() -> Vec<(DataType, String)> {
+        vec![
+            (DataType::Bool, "flag".to_owned()),
+            (
+                DataType::Tuple(vec![DataType::U32, DataType::Bfe]),
+                "pair".to_owned(),
+            ),
+            (DataType::U64, "amount".to_owned()),
+        ]
+    }
+
+    #[test]
+    fn values_matching_declared_types_are_accepted() {
+        let stack = [1, u32::MAX as u64, u64::MAX - 1, 0, 7]
+            .map(BFieldElement::new)
+            .to_vec();
+        let stack = [empty_stack(), stack].concat();
+        verify_stack_values_match_types(&signature_values(), &stack, "Input", "tasm_test");
+    }
+
+    #[should_panic(expected = "Input `pair` of `tasm_test` must be a valid tuple_Lu32___bfeR")]
+    #[test]
+    fn value_not_matching_declared_type_is_reported_by_name() {
+        let stack = [1, 1 << 32, 0, 0, 7].map(BFieldElement::new).to_vec();
+        let stack = [empty_stack(), stack].concat();
+        verify_stack_values_match_types(&signature_values(), &stack, "Input", "tasm_test");
+    }
+
+    #[should_panic(expected = "Output `flag` of `tasm_test` must be a valid bool")]
+    #[test]
+    fn non_boolean_flag_is_detected() {
+        let stack = [2, 0, 0, 0, 7].map(BFieldElement::new).to_vec();
+        let stack = [empty_stack(), stack].concat();
+        verify_stack_values_match_types(&signature_values(), &stack, "Output", "tasm_test");
+    }
+
+    fn execute_popping_too_much() -> anyhow::Result<VMState> {
+        let program = Program::new(&triton_asm!(pop 5 pop 5 pop 5 pop 5 halt));
+        execute_with_terminal_state(
+            &program,
+            &[],
+            &empty_stack(),
+            &NonDeterminism::default(),
+            None,
+        )
+    }
+
+    #[test]
+    fn op_stack_underflow_is_recognized() {
+        assert_op_stack_underflow(execute_popping_too_much());
+    }
+
+    #[should_panic(expected = "Triton VM must fail with the expected error")]
+    #[test]
+    fn failure_with_different_cause_is_rejected() {
+        assert_assertion_failure(execute_popping_too_much());
+    }
 }
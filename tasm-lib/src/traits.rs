@@ -1,8 +1,10 @@
+pub mod accessor;
 pub mod algorithm;
 pub mod basic_snippet;
 pub mod closure;
 pub mod compiled_program;
 pub mod deprecated_snippet;
 pub mod function;
+pub mod known_cycle_count;
 pub mod procedure;
 pub mod rust_shadow;
@@ -0,0 +1,380 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use triton_vm::error::InstructionError;
+use triton_vm::{BFieldElement, NonDeterminism};
+
+use super::{basic_snippet::BasicSnippet, rust_shadow::RustShadow};
+use crate::{
+    linker::{execute_bench, link_for_isolated_run},
+    snippet_bencher::{
+        cycle_breakdown_if_requested, rust_shadow_allocation_statistics, BenchmarkCase,
+        BenchmarkResult,
+    },
+    test_helpers::{
+        test_rust_equivalence_given_complete_state, test_rust_tasm_equivalence_expect_failure,
+        verify_corner_case_coverage,
+    },
+    VmHasherState,
+};
+
+/// An Accessor is a piece of tasm code that can read memory and take nondeterministic
+/// input, but is guaranteed not to write to memory. It cannot read from standard in or
+/// write to standard out.
+///
+/// The test harness asserts that memory is bit-identical before and after execution,
+/// including the state of the dynamic memory allocator.
+///
+/// See also: [closure], [function], [algorithm], [procedure]
+///
+/// [closure]: crate::traits::closure::Closure
+/// [function]: crate::traits::function::Function
+/// [algorithm]: crate::traits::algorithm::Algorithm
+/// [procedure]: crate::traits::procedure::Procedure
+pub trait Accessor: BasicSnippet {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+    );
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> AccessorInitialState;
+
+    fn corner_case_initial_states(&self) -> Vec<AccessorInitialState> {
+        vec![]
+    }
+
+    /// Corner cases together with the category they exercise, which must be one of the
+    /// [declared categories](BasicSnippet::corner_case_categories).
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, AccessorInitialState)> {
+        vec![]
+    }
+
+    /// Initial states on which the snippet must fail, together with the error with which
+    /// Triton VM must halt. The test harness asserts that the Rust shadow panics on each of
+    /// them as well.
+    fn failing_initial_states(&self) -> Vec<(AccessorInitialState, InstructionError)> {
+        vec![]
+    }
+
+    /// An initial state derived from fuzzer-provided bytes. By default, the bytes seed
+    /// [`pseudorandom_initial_state`](Self::pseudorandom_initial_state). Override this to
+    /// let the fuzzer control the structure of the state, for example with the helpers in
+    /// [`fuzzing`](crate::fuzzing).
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<AccessorInitialState> {
+        Ok(self.pseudorandom_initial_state(u.arbitrary()?, None))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AccessorInitialState {
+    pub stack: Vec<BFieldElement>,
+    pub nondeterminism: NonDeterminism<BFieldElement>,
+}
+
+pub struct ShadowedAccessor<T: Accessor + 'static> {
+    accessor: Rc<RefCell<T>>,
+}
+
+impl<T: Accessor + 'static> ShadowedAccessor<T> {
+    pub fn new(accessor: T) -> Self {
+        Self {
+            accessor: Rc::new(RefCell::new(accessor)),
+        }
+    }
+
+    /// Test the accessor on an initial state derived from fuzzer-provided bytes, for use in
+    /// fuzz targets. Bytes that don't suffice to derive a state are ignored. States on which
+    /// the Rust shadow panics are accepted if Triton VM fails on them, too.
+    #[cfg(feature = "fuzz")]
+    pub fn fuzz(&self, data: &[u8]) {
+        let mut unstructured = arbitrary::Unstructured::new(data);
+        let Ok(state) = self
+            .accessor
+            .borrow()
+            .arbitrary_initial_state(&mut unstructured)
+        else {
+            return;
+        };
+        if crate::test_helpers::rust_shadow_and_tasm_fail(
+            self,
+            &state.stack,
+            &[],
+            &state.nondeterminism,
+            &None,
+            0,
+        ) {
+            return;
+        }
+        self.test_initial_state(state);
+    }
+
+    fn test_initial_state(&self, initial_state: AccessorInitialState) {
+        let AccessorInitialState {
+            stack,
+            nondeterminism,
+        } = initial_state;
+
+        let stdin = vec![];
+        let tasm = test_rust_equivalence_given_complete_state(
+            self,
+            &stack,
+            &stdin,
+            &nondeterminism,
+            &None,
+            0,
+            None,
+        );
+
+        assert_eq!(
+            nondeterminism.ram,
+            tasm.final_ram,
+            "Accessor {} must leave memory unchanged",
+            self.accessor.borrow().entrypoint()
+        );
+    }
+}
+
+impl<T> RustShadow for ShadowedAccessor<T>
+where
+    T: Accessor + 'static,
+{
+    fn rust_shadow_wrapper(
+        &self,
+        _stdin: &[BFieldElement],
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> Vec<BFieldElement> {
+        self.accessor
+            .borrow()
+            .rust_shadow(stack, memory, nondeterminism);
+        vec![]
+    }
+
+    fn test(&self) {
+        let categorized_corner_cases = self
+            .accessor
+            .borrow()
+            .categorized_corner_case_initial_states();
+        let corner_cases = self
+            .accessor
+            .borrow()
+            .corner_case_initial_states()
+            .into_iter()
+            .chain(
+                categorized_corner_cases
+                    .iter()
+                    .map(|(_, state)| state.clone()),
+            );
+        for (i, corner_case) in corner_cases.enumerate() {
+            println!(
+                "testing {} corner case number {i}",
+                self.accessor.borrow().entrypoint(),
+            );
+            self.test_initial_state(corner_case);
+        }
+        verify_corner_case_coverage(&*self.accessor.borrow(), &categorized_corner_cases);
+
+        let num_states = 10;
+        let seed = [
+            0x3c, 0x18, 0x9e, 0x71, 0x05, 0xd2, 0x4a, 0x8b, 0xe0, 0x67, 0x13, 0xfa, 0x5d, 0x2c,
+            0x91, 0x48, 0xb6, 0x0f, 0x7e, 0xa3, 0x24, 0xc9, 0x58, 0x1d, 0x6a, 0xe5, 0x32, 0x8f,
+            0x07, 0xbc, 0x43, 0xd0,
+        ];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        for _ in 0..num_states {
+            let seed: [u8; 32] = rng.gen();
+            println!(
+                "testing {} common case with seed: {:#4x?}",
+                self.accessor.borrow().entrypoint(),
+                seed
+            );
+            let initial_state = self
+                .accessor
+                .borrow()
+                .pseudorandom_initial_state(seed, None);
+            self.test_initial_state(initial_state);
+        }
+
+        for (i, (state, expected_error)) in self
+            .accessor
+            .borrow()
+            .failing_initial_states()
+            .into_iter()
+            .enumerate()
+        {
+            println!(
+                "testing {} failing case number {i}",
+                self.accessor.borrow().entrypoint(),
+            );
+            test_rust_tasm_equivalence_expect_failure(
+                self,
+                &state.stack,
+                &[],
+                &state.nondeterminism,
+                &None,
+                0,
+                expected_error,
+            );
+        }
+    }
+
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
+        let mut rng: StdRng = SeedableRng::from_seed(
+            hex::decode("a4c3f1e2b7d8091a2b3c4d5e6f708192a4c3f1e2b7d8091a2b3c4d5e6f708192")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let mut benchmarks = Vec::with_capacity(2);
+
+        for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
+            let AccessorInitialState {
+                stack,
+                nondeterminism,
+            } = self
+                .accessor
+                .borrow()
+                .pseudorandom_initial_state(rng.gen(), Some(bench_case));
+            let program = link_for_isolated_run(self.accessor.clone(), 1);
+            let allocation_statistics =
+                rust_shadow_allocation_statistics(self, &stack, &[], &nondeterminism, &None);
+            let cycle_breakdown =
+                cycle_breakdown_if_requested(self, 1, &stack, &[], &nondeterminism, &None);
+            let execution_result = execute_bench(&program, &stack, vec![], nondeterminism, None);
+            let benchmark = BenchmarkResult {
+                name: self.accessor.borrow().entrypoint(),
+                clock_cycle_count: execution_result.cycle_count,
+                hash_table_height: execution_result.hash_table_height,
+                u32_table_height: execution_result.u32_table_height,
+                case: bench_case,
+                allocation_statistics,
+                cycle_breakdown,
+            };
+            benchmarks.push(benchmark);
+        }
+
+        benchmarks
+    }
+
+    fn inner(&self) -> Rc<RefCell<dyn BasicSnippet>> {
+        self.accessor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::triton_asm;
+
+    use crate::data_type::DataType;
+    use crate::empty_stack;
+    use crate::library::Library;
+    use crate::memory::dyn_malloc::DYN_MALLOC_ADDRESS;
+
+    use super::*;
+
+    /// Reads the word at the given address. If `touch_allocator` is set, the snippet
+    /// additionally overwrites the dynamic allocator's state, which is invisible to the Rust
+    /// shadow's memory comparison but not to the accessor harness.
+    struct ReadWord {
+        touch_allocator: bool,
+    }
+
+    impl BasicSnippet for ReadWord {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::VoidPointer, "*address".to_owned())]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Bfe, "value".to_owned())]
+        }
+
+        fn entrypoint(&self) -> String {
+            match self.touch_allocator {
+                true => "tasm_test_read_word_touch_allocator".to_owned(),
+                false => "tasm_test_read_word".to_owned(),
+            }
+        }
+
+        fn code(&self, _library: &mut Library) -> Vec<LabelledInstruction> {
+            let entrypoint = self.entrypoint();
+            let touch_allocator = match self.touch_allocator {
+                true => triton_asm!(
+                    push 2
+                    push {DYN_MALLOC_ADDRESS}
+                    write_mem 1
+                    pop 1
+                ),
+                false => vec![],
+            };
+
+            triton_asm!(
+                // BEFORE: _ *address
+                // AFTER:  _ value
+                {entrypoint}:
+                    {&touch_allocator}
+                    read_mem 1
+                    pop 1
+                    return
+            )
+        }
+    }
+
+    impl Accessor for ReadWord {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &HashMap<BFieldElement, BFieldElement>,
+            _nondeterminism: &NonDeterminism<BFieldElement>,
+        ) {
+            let address = stack.pop().unwrap();
+            stack.push(memory.get(&address).copied().unwrap_or_default());
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            _bench_case: Option<BenchmarkCase>,
+        ) -> AccessorInitialState {
+            let mut rng: StdRng = SeedableRng::from_seed(seed);
+            let address = BFieldElement::new(rng.gen_range(0..1 << 30));
+            let memory = HashMap::from([
+                (address, rng.gen()),
+                (DYN_MALLOC_ADDRESS, BFieldElement::new(1)),
+            ]);
+
+            AccessorInitialState {
+                stack: [empty_stack(), vec![address]].concat(),
+                nondeterminism: NonDeterminism::default().with_ram(memory),
+            }
+        }
+    }
+
+    #[test]
+    fn read_word_test() {
+        ShadowedAccessor::new(ReadWord {
+            touch_allocator: false,
+        })
+        .test();
+    }
+
+    #[test]
+    #[should_panic(expected = "must leave memory unchanged")]
+    fn writing_accessor_is_caught() {
+        ShadowedAccessor::new(ReadWord {
+            touch_allocator: true,
+        })
+        .test();
+    }
+}
@@ -1,25 +1,36 @@
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use triton_vm::error::InstructionError;
 use triton_vm::{BFieldElement, NonDeterminism};
 use twenty_first::shared_math::bfield_codec::BFieldCodec;
 
-use super::{basic_snippet::BasicSnippet, rust_shadow::RustShadow};
+use super::{
+    basic_snippet::BasicSnippet,
+    rust_shadow::{NondeterminismConsumption, RustShadow},
+};
 use crate::{
     linker::{execute_bench, link_for_isolated_run},
-    snippet_bencher::{write_benchmarks, BenchmarkCase, BenchmarkResult},
-    test_helpers::test_rust_equivalence_given_complete_state,
-    VmHasherState,
+    snippet_bencher::{
+        cycle_breakdown_if_requested, rust_shadow_allocation_statistics, BenchmarkCase,
+        BenchmarkResult,
+    },
+    test_helpers::{
+        test_rust_equivalence_given_complete_state, test_rust_tasm_equivalence_expect_failure,
+        verify_corner_case_coverage,
+    },
+    Digest, VmHasherState,
 };
 
 /// An Algorithm is a piece of tasm code that can modify memory even at addresses below
 /// the dynamic memory allocator, and can take nondeterministic input. It cannot read from
 /// standard in or write to standard out.
 ///
-/// See also: [closure], [function], [procedure]
+/// See also: [accessor], [closure], [function], [procedure]
 ///
 /// [closure]: crate::traits::closure::Closure
 /// [function]: crate::traits::function::Function
 /// [procedure]: crate::traits::procedure::Procedure
+/// [accessor]: crate::traits::accessor::Accessor
 pub trait Algorithm: BasicSnippet {
     fn rust_shadow(
         &self,
@@ -28,6 +39,23 @@ pub trait Algorithm: BasicSnippet {
         nondeterminism: &NonDeterminism<BFieldElement>,
     );
 
+    /// Like [`rust_shadow`](Self::rust_shadow), but reads the nondeterministic digests
+    /// through a cursor, in the order in which the tasm code divines them. Override this if
+    /// the snippet divines digests, and let `rust_shadow` defer to it. The test harness then
+    /// checks that the Rust shadow and the tasm code read the same number of digests.
+    ///
+    /// By default, defers to `rust_shadow`, which is taken to read all digests.
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        digests: &mut DigestCursor,
+    ) {
+        self.rust_shadow(stack, memory, nondeterminism);
+        digests.skip_remaining();
+    }
+
     /// Take a object about which something is being proven in order to extract out the
     /// right nondeterminism. Update the mutably referenced non-determism argument.
     ///
@@ -62,6 +90,69 @@ pub trait Algorithm: BasicSnippet {
     fn corner_case_initial_states(&self) -> Vec<AlgorithmInitialState> {
         vec![]
     }
+
+    /// Corner cases together with the category they exercise, which must be one of the
+    /// [declared categories](BasicSnippet::corner_case_categories).
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, AlgorithmInitialState)> {
+        vec![]
+    }
+
+    /// Initial states on which the snippet must fail, together with the error with which
+    /// Triton VM must halt. The test harness asserts that the Rust shadow panics on each of
+    /// them as well.
+    fn failing_initial_states(&self) -> Vec<(AlgorithmInitialState, InstructionError)> {
+        vec![]
+    }
+
+    /// An initial state derived from fuzzer-provided bytes. By default, the bytes seed
+    /// [`pseudorandom_initial_state`](Self::pseudorandom_initial_state). Override this to
+    /// let the fuzzer control the structure of the state, for example with the helpers in
+    /// [`fuzzing`](crate::fuzzing).
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<AlgorithmInitialState> {
+        Ok(self.pseudorandom_initial_state(u.arbitrary()?, None))
+    }
+}
+
+/// Sequential access to the nondeterministic digests, mirroring how Triton VM's
+/// `divine_sibling` consumes them.
+#[derive(Debug, Clone)]
+pub struct DigestCursor<'a> {
+    digests: &'a [Digest],
+    num_read: usize,
+}
+
+impl<'a> DigestCursor<'a> {
+    pub fn new(digests: &'a [Digest]) -> Self {
+        Self {
+            digests,
+            num_read: 0,
+        }
+    }
+
+    /// Read the next digest. Panics if all digests have been read, in which case Triton VM
+    /// would crash.
+    pub fn read(&mut self) -> Digest {
+        let Some(&digest) = self.digests.get(self.num_read) else {
+            panic!(
+                "Cannot read nondeterministic digest: all {} digests were read already",
+                self.digests.len()
+            );
+        };
+        self.num_read += 1;
+        digest
+    }
+
+    pub fn num_read(&self) -> usize {
+        self.num_read
+    }
+
+    pub fn skip_remaining(&mut self) {
+        self.num_read = self.digests.len();
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -80,6 +171,40 @@ impl<T: Algorithm + 'static> ShadowedAlgorithm<T> {
             algorithm: Rc::new(RefCell::new(algorithm)),
         }
     }
+
+    /// Test the algorithm on an initial state derived from fuzzer-provided bytes, for use in
+    /// fuzz targets. Bytes that don't suffice to derive a state are ignored. States on which
+    /// the Rust shadow panics are accepted if Triton VM fails on them, too.
+    #[cfg(feature = "fuzz")]
+    pub fn fuzz(&self, data: &[u8]) {
+        let mut unstructured = arbitrary::Unstructured::new(data);
+        let Ok(state) = self
+            .algorithm
+            .borrow()
+            .arbitrary_initial_state(&mut unstructured)
+        else {
+            return;
+        };
+        if crate::test_helpers::rust_shadow_and_tasm_fail(
+            self,
+            &state.stack,
+            &[],
+            &state.nondeterminism,
+            &None,
+            0,
+        ) {
+            return;
+        }
+        test_rust_equivalence_given_complete_state(
+            self,
+            &state.stack,
+            &[],
+            &state.nondeterminism,
+            &None,
+            0,
+            None,
+        );
+    }
 }
 
 impl<T> RustShadow for ShadowedAlgorithm<T>
@@ -100,14 +225,44 @@ where
         vec![]
     }
 
+    fn rust_shadow_wrapper_counting_nondeterminism(
+        &self,
+        _stdin: &[BFieldElement],
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        _sponge_state: &mut Option<VmHasherState>,
+    ) -> (Vec<BFieldElement>, NondeterminismConsumption) {
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        self.algorithm.borrow().rust_shadow_with_digest_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            &mut digests,
+        );
+        let consumption = NondeterminismConsumption {
+            individual_tokens_read: nondeterminism.individual_tokens.len(),
+            digests_read: digests.num_read(),
+        };
+        (vec![], consumption)
+    }
+
     fn test(&self) {
-        for (i, corner_case) in self
+        let categorized_corner_cases = self
+            .algorithm
+            .borrow()
+            .categorized_corner_case_initial_states();
+        let corner_cases = self
             .algorithm
             .borrow()
             .corner_case_initial_states()
             .into_iter()
-            .enumerate()
-        {
+            .chain(
+                categorized_corner_cases
+                    .iter()
+                    .map(|(_, state)| state.clone()),
+            );
+        for (i, corner_case) in corner_cases.enumerate() {
             println!(
                 "testing {} corner case number {i}",
                 self.algorithm.borrow().entrypoint(),
@@ -124,6 +279,7 @@ where
                 None,
             );
         }
+        verify_corner_case_coverage(&*self.algorithm.borrow(), &categorized_corner_cases);
 
         let num_states = 10;
         let seed = [
@@ -158,9 +314,31 @@ where
                 None,
             );
         }
+
+        for (i, (state, expected_error)) in self
+            .algorithm
+            .borrow()
+            .failing_initial_states()
+            .into_iter()
+            .enumerate()
+        {
+            println!(
+                "testing {} failing case number {i}",
+                self.algorithm.borrow().entrypoint(),
+            );
+            test_rust_tasm_equivalence_expect_failure(
+                self,
+                &state.stack,
+                &[],
+                &state.nondeterminism,
+                &None,
+                0,
+                expected_error,
+            );
+        }
     }
 
-    fn bench(&self) {
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
         let mut rng: StdRng = SeedableRng::from_seed(
             hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
                 .unwrap()
@@ -178,6 +356,10 @@ where
                 .borrow()
                 .pseudorandom_initial_state(rng.gen(), Some(bench_case));
             let program = link_for_isolated_run(self.algorithm.clone(), 1);
+            let allocation_statistics =
+                rust_shadow_allocation_statistics(self, &stack, &[], &nondeterminism, &None);
+            let cycle_breakdown =
+                cycle_breakdown_if_requested(self, 1, &stack, &[], &nondeterminism, &None);
             let execution_result = execute_bench(&program, &stack, vec![], nondeterminism, None);
             let benchmark = BenchmarkResult {
                 name: self.algorithm.borrow().entrypoint(),
@@ -185,14 +367,42 @@ where
                 hash_table_height: execution_result.hash_table_height,
                 u32_table_height: execution_result.u32_table_height,
                 case: bench_case,
+                allocation_statistics,
+                cycle_breakdown,
             };
             benchmarks.push(benchmark);
         }
 
-        write_benchmarks(benchmarks);
+        benchmarks
     }
 
     fn inner(&self) -> Rc<RefCell<dyn BasicSnippet>> {
         self.algorithm.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_cursor_reads_digests_in_order() {
+        let digests: [Digest; 3] = rand::random();
+        let mut cursor = DigestCursor::new(&digests);
+        assert_eq!(digests[0], cursor.read());
+        assert_eq!(digests[1], cursor.read());
+        assert_eq!(2, cursor.num_read());
+
+        cursor.skip_remaining();
+        assert_eq!(3, cursor.num_read());
+    }
+
+    #[should_panic(expected = "all 1 digests were read already")]
+    #[test]
+    fn reading_past_the_last_digest_panics() {
+        let digests = [Digest::default()];
+        let mut cursor = DigestCursor::new(&digests);
+        cursor.read();
+        cursor.read();
+    }
+}
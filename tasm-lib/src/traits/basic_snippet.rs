@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use triton_vm::instruction::LabelledInstruction;
+use triton_vm::BFieldElement;
 
 use crate::{data_type::DataType, library::Library};
 
@@ -18,4 +21,38 @@ pub trait BasicSnippet {
         }
         diff
     }
+
+    /// The memory regions, as pairs of first address and size in words, that the snippet may
+    /// write to when started in the given initial state, in addition to memory it allocates
+    /// itself. The test harness fails if the snippet writes anywhere else. Returns `None` if
+    /// the snippet does not restrict its writes, in which case no such check is performed.
+    fn writable_memory_regions(
+        &self,
+        _initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(BFieldElement, u32)>> {
+        None
+    }
+
+    /// The inputs, by index into [`inputs`](Self::inputs), that point to objects in memory,
+    /// together with the size in words of the object in the given initial state. If declared,
+    /// the test harness re-runs the snippet with these objects moved to random addresses and
+    /// surrounded by junk, and asserts that the results are unaffected. Returns `None` if
+    /// the snippet does not declare its input objects, in which case no such check is
+    /// performed.
+    fn input_objects(
+        &self,
+        _initial_stack: &[BFieldElement],
+        _initial_memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<Vec<(usize, u32)>> {
+        None
+    }
+
+    /// Named categories of corner cases, such as "zero input" or "empty list", that the
+    /// snippet's tests must cover. The test harness reports how many categorized corner
+    /// cases exercise each category, and fails if a declared category is not exercised at
+    /// all.
+    fn corner_case_categories(&self) -> Vec<String> {
+        vec![]
+    }
 }
@@ -1,12 +1,16 @@
 use std::{cell::RefCell, rc::Rc};
 
 use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
 use triton_vm::{BFieldElement, NonDeterminism};
 
 use crate::{
     linker::{execute_bench, link_for_isolated_run},
-    snippet_bencher::{write_benchmarks, BenchmarkCase, BenchmarkResult},
-    test_helpers::test_rust_equivalence_given_complete_state,
+    snippet_bencher::{cycle_breakdown_if_requested, BenchmarkCase, BenchmarkResult},
+    test_helpers::{
+        test_rust_equivalence_given_complete_state, test_rust_tasm_equivalence_expect_failure,
+        verify_corner_case_coverage,
+    },
     VmHasherState,
 };
 
@@ -15,11 +19,12 @@ use super::{basic_snippet::BasicSnippet, rust_shadow::RustShadow};
 /// A Closure is a piece of tasm code that modifies the top of the stack without access to
 /// memory or nondeterminism or standard input/output.
 ///
-/// See also: [function], [algorithm], [procedure]
+/// See also: [function], [algorithm], [accessor], [procedure]
 ///
 /// [function]: crate::traits::function::Function
 /// [algorithm]: crate::traits::algorithm::Algorithm
 /// [procedure]: crate::traits::procedure::Procedure
+/// [accessor]: crate::traits::accessor::Accessor
 pub trait Closure: BasicSnippet {
     fn rust_shadow(&self, stack: &mut Vec<BFieldElement>);
 
@@ -34,6 +39,30 @@ pub trait Closure: BasicSnippet {
     fn corner_case_initial_states(&self) -> Vec<Vec<BFieldElement>> {
         vec![]
     }
+
+    /// Corner cases together with the category they exercise, which must be one of the
+    /// [declared categories](BasicSnippet::corner_case_categories).
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, Vec<BFieldElement>)> {
+        vec![]
+    }
+
+    /// Initial states on which the snippet must fail, together with the error with which
+    /// Triton VM must halt. The test harness asserts that the Rust shadow panics on each of
+    /// them as well.
+    fn failing_initial_states(&self) -> Vec<(Vec<BFieldElement>, InstructionError)> {
+        vec![]
+    }
+
+    /// An initial stack derived from fuzzer-provided bytes. By default, it holds arbitrary
+    /// values of the closure's input types.
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<Vec<BFieldElement>> {
+        let input_types = self.inputs().into_iter().map(|(t, _)| t);
+        crate::fuzzing::arbitrary_stack(u, &input_types.collect::<Vec<_>>())
+    }
 }
 
 pub struct ShadowedClosure<C: Closure + 'static> {
@@ -46,6 +75,74 @@ impl<C: Closure + 'static> ShadowedClosure<C> {
             closure: Rc::new(RefCell::new(closure)),
         }
     }
+
+    /// Test the closure on initial stacks holding arbitrary values of its input types,
+    /// shrinking any failing stack to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test(&self) {
+        let input_types = self.closure.borrow().inputs().into_iter().map(|(t, _)| t);
+        self.prop_test_with(crate::strategies::arb_stack(
+            &input_types.collect::<Vec<_>>(),
+        ));
+    }
+
+    /// Test the closure on initial stacks drawn from the given strategy, shrinking any
+    /// failing stack to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test_with(
+        &self,
+        strategy: impl proptest::strategy::Strategy<Value = Vec<BFieldElement>>,
+    ) {
+        proptest::test_runner::TestRunner::default()
+            .run(&strategy, |stack| {
+                test_rust_equivalence_given_complete_state(
+                    self,
+                    &stack,
+                    &[],
+                    &NonDeterminism::default(),
+                    &None,
+                    0,
+                    None,
+                );
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /// Test the closure on an initial state derived from fuzzer-provided bytes, for use in
+    /// fuzz targets. Bytes that don't suffice to derive a state are ignored. States on which
+    /// the Rust shadow panics are accepted if Triton VM fails on them, too.
+    #[cfg(feature = "fuzz")]
+    pub fn fuzz(&self, data: &[u8]) {
+        let mut unstructured = arbitrary::Unstructured::new(data);
+        let Ok(stack) = self
+            .closure
+            .borrow()
+            .arbitrary_initial_state(&mut unstructured)
+        else {
+            return;
+        };
+        let nondeterminism = NonDeterminism::default();
+        if crate::test_helpers::rust_shadow_and_tasm_fail(
+            self,
+            &stack,
+            &[],
+            &nondeterminism,
+            &None,
+            0,
+        ) {
+            return;
+        }
+        test_rust_equivalence_given_complete_state(
+            self,
+            &stack,
+            &[],
+            &nondeterminism,
+            &None,
+            0,
+            None,
+        );
+    }
 }
 
 impl<C: Closure + 'static> RustShadow for ShadowedClosure<C> {
@@ -70,7 +167,21 @@ impl<C: Closure + 'static> RustShadow for ShadowedClosure<C> {
         let mut rng = thread_rng();
 
         // First test corner-cases as they're easier to debug on failure
-        for init_stack_corner_case in self.closure.borrow().corner_case_initial_states() {
+        let categorized_corner_cases = self
+            .closure
+            .borrow()
+            .categorized_corner_case_initial_states();
+        let corner_cases = self
+            .closure
+            .borrow()
+            .corner_case_initial_states()
+            .into_iter()
+            .chain(
+                categorized_corner_cases
+                    .iter()
+                    .map(|(_, stack)| stack.clone()),
+            );
+        for init_stack_corner_case in corner_cases {
             let stdin = vec![];
             test_rust_equivalence_given_complete_state(
                 self,
@@ -82,6 +193,7 @@ impl<C: Closure + 'static> RustShadow for ShadowedClosure<C> {
                 None,
             );
         }
+        verify_corner_case_coverage(&*self.closure.borrow(), &categorized_corner_cases);
 
         for _ in 0..num_states {
             let seed: [u8; 32] = rng.gen();
@@ -103,9 +215,31 @@ impl<C: Closure + 'static> RustShadow for ShadowedClosure<C> {
                 None,
             );
         }
+
+        for (i, (stack, expected_error)) in self
+            .closure
+            .borrow()
+            .failing_initial_states()
+            .into_iter()
+            .enumerate()
+        {
+            println!(
+                "testing {} failing case number {i}",
+                self.closure.borrow().entrypoint(),
+            );
+            test_rust_tasm_equivalence_expect_failure(
+                self,
+                &stack,
+                &[],
+                &NonDeterminism::default(),
+                &None,
+                0,
+                expected_error,
+            );
+        }
     }
 
-    fn bench(&self) {
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
         let mut rng: StdRng = SeedableRng::from_seed(
             hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
                 .unwrap()
@@ -120,18 +254,22 @@ impl<C: Closure + 'static> RustShadow for ShadowedClosure<C> {
                 .borrow()
                 .pseudorandom_initial_state(rng.gen(), Some(bench_case));
             let program = link_for_isolated_run(self.closure.clone(), 1);
-            let execution_result =
-                execute_bench(&program, &stack, vec![], NonDeterminism::new(vec![]), None);
+            let nondeterminism = NonDeterminism::new(vec![]);
+            let cycle_breakdown =
+                cycle_breakdown_if_requested(self, 1, &stack, &[], &nondeterminism, &None);
+            let execution_result = execute_bench(&program, &stack, vec![], nondeterminism, None);
             let benchmark = BenchmarkResult {
                 name: self.closure.borrow().entrypoint(),
                 clock_cycle_count: execution_result.cycle_count,
                 hash_table_height: execution_result.hash_table_height,
                 u32_table_height: execution_result.u32_table_height,
                 case: bench_case,
+                allocation_statistics: Default::default(),
+                cycle_breakdown,
             };
             benchmarks.push(benchmark);
         }
 
-        write_benchmarks(benchmarks);
+        benchmarks
     }
 }
@@ -15,9 +15,17 @@ pub trait CompiledProgram {
     fn program() -> Program {
         let (program_instructions, library) = Self::code();
 
+        let static_data_initialization = library.static_data_initialization();
         let library_instructions = library.all_imports();
 
-        Program::new(&[program_instructions, library_instructions].concat())
+        Program::new(
+            &[
+                static_data_initialization,
+                program_instructions,
+                library_instructions,
+            ]
+            .concat(),
+        )
     }
 
     fn run(
@@ -60,8 +68,14 @@ pub fn bench_and_profile_program<P: CompiledProgram>(
     use std::io::Write;
 
     let (program_instructions, library) = P::code();
+    let static_data_initialization = library.static_data_initialization();
     let library_instructions = library.all_imports();
-    let all_instructions = [program_instructions, library_instructions].concat();
+    let all_instructions = [
+        static_data_initialization,
+        program_instructions,
+        library_instructions,
+    ]
+    .concat();
     let program = Program::new(&all_instructions);
 
     // run in trace mode to get table heights
@@ -72,6 +86,8 @@ pub fn bench_and_profile_program<P: CompiledProgram>(
             clock_cycle_count: aet.processor_table_length(),
             hash_table_height: aet.hash_table_length(),
             u32_table_height: aet.u32_table_length(),
+            allocation_statistics: Default::default(),
+            cycle_breakdown: None,
         },
         Err(_) => panic!(),
     };
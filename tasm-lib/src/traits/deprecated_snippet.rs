@@ -3,20 +3,25 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use triton_vm::instruction::LabelledInstruction;
 use triton_vm::parser::{to_labelled_instructions, tokenize};
 use triton_vm::{triton_asm, NonDeterminism, Program};
 use twenty_first::shared_math::b_field_element::BFieldElement;
 
 use crate::data_type::DataType;
-use crate::execute_with_terminal_state;
+use crate::execute_with_terminal_state_and_source_map;
 use crate::library::Library;
+use crate::linker::SourceMap;
+use crate::snippet_bencher::{benchmark_snippet_deprecated, BenchmarkCase, BenchmarkResult};
 use crate::test_helpers::test_rust_equivalence_given_execution_state_deprecated;
 use crate::VmHasherState;
 use crate::{execute_bench_deprecated, ExecutionResult, VmOutputState, DIGEST_LENGTH};
-use crate::{execute_test, ExecutionState};
+use crate::{execute_test_with_source_map, ExecutionState};
 
 use super::basic_snippet::BasicSnippet;
+use super::function::{Function, FunctionInitialState};
 use super::rust_shadow::RustShadow;
 
 pub trait DeprecatedSnippet {
@@ -76,30 +81,49 @@ pub trait DeprecatedSnippet {
         &self,
         words_statically_allocated: Option<u32>,
     ) -> Vec<LabelledInstruction> {
+        let (code, _) = self.link_for_isolated_run_with_source_map(words_statically_allocated);
+        code
+    }
+
+    /// Like [`link_for_isolated_run`](Self::link_for_isolated_run), but additionally return a
+    /// [`SourceMap`] of the linked program.
+    fn link_for_isolated_run_with_source_map(
+        &self,
+        words_statically_allocated: Option<u32>,
+    ) -> (Vec<LabelledInstruction>, SourceMap) {
         let mut snippet_state = if let Some(number_of_words) = words_statically_allocated {
             Library::with_preallocated_memory(number_of_words)
         } else {
             Library::new()
         };
         let entrypoint = self.entrypoint_name();
-        let mut function_body = self.function_code(&mut snippet_state);
-        function_body.push('\n'); // added bc of limitations in `triton_asm!`
+        let function_body = self.function_code_as_instructions(&mut snippet_state);
         let library_code = snippet_state.all_imports();
+        let static_data_initialization = snippet_state.static_data_initialization();
 
         // The TASM code is always run through a function call, so the 1st instruction
-        // is a call to the function in question.
-        let code = triton_asm!(
+        // (after initializing static data) is a call to the function in question.
+        let preamble = triton_asm!(
+            {&static_data_initialization}
             call {entrypoint}
             halt
+        );
+        let code = triton_asm!(
+            {&preamble}
 
-            {function_body}
+            {&function_body}
             {&library_code}
         );
 
         // Verify that program compiles, otherwise panics
         let _program = Program::new(&code);
 
-        code
+        let mut source_map = SourceMap::default();
+        source_map.append("preamble", &preamble);
+        source_map.append(&entrypoint, &function_body);
+        source_map.extend(snippet_state.source_map());
+
+        (code, source_map)
     }
 
     /// Return Ok(vm_output_state) if execution succeeds, Err(error_message) if VM execution fails,
@@ -119,25 +143,35 @@ pub trait DeprecatedSnippet {
             "Declared stack diff must match type indicators"
         );
 
-        let code = self.link_for_isolated_run(words_allocated);
+        let (code, source_map) = self.link_for_isolated_run_with_source_map(words_allocated);
         let program = Program::new(&code);
-        let tvm_result =
-            execute_with_terminal_state(&program, &std_in, stack, &nondeterminism, None);
+        let tvm_result = execute_with_terminal_state_and_source_map(
+            &program,
+            &std_in,
+            stack,
+            &nondeterminism,
+            None,
+            &source_map,
+        );
 
         let final_state = tvm_result.map(|st| VmOutputState {
             final_ram: st.ram,
             final_sponge_state: st.sponge_state.map(|state| VmHasherState { state }),
             final_stack: st.op_stack.stack,
             output: st.public_output,
+            individual_tokens_read: nondeterminism.individual_tokens.len()
+                - st.secret_individual_tokens.len(),
+            digests_read: nondeterminism.digests.len() - st.secret_digests.len(),
         })?;
 
-        execute_test(
+        execute_test_with_source_map(
             &code,
             stack,
             Self::stack_diff(self),
             std_in,
             nondeterminism,
             None,
+            &source_map,
         );
         Ok(final_state)
     }
@@ -209,10 +243,74 @@ pub trait DeprecatedSnippet {
     }
 }
 
-struct DeprecatedSnippetWrapper<S: DeprecatedSnippet> {
+/// Exposes a [`DeprecatedSnippet`] through the newer snippet traits, namely
+/// [`BasicSnippet`] and, for snippets that neither read input nor depend on statically
+/// allocated memory, [`Function`].
+pub struct DeprecatedSnippetWrapper<S: DeprecatedSnippet> {
     pub deprecated_snippet: S,
 }
 
+impl<S: DeprecatedSnippet> DeprecatedSnippetWrapper<S> {
+    pub fn new(deprecated_snippet: S) -> Self {
+        Self { deprecated_snippet }
+    }
+}
+
+impl<S: DeprecatedSnippet> BasicSnippet for DeprecatedSnippetWrapper<S> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        self.deprecated_snippet.inputs()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        self.deprecated_snippet.outputs()
+    }
+
+    fn entrypoint(&self) -> String {
+        self.deprecated_snippet.entrypoint()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        self.deprecated_snippet.code(library)
+    }
+
+    fn stack_diff(&self) -> isize {
+        DeprecatedSnippet::stack_diff(&self.deprecated_snippet)
+    }
+}
+
+impl<S: DeprecatedSnippet> Function for DeprecatedSnippetWrapper<S> {
+    fn rust_shadow(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        self.deprecated_snippet
+            .rust_shadowing(stack, vec![], vec![], memory);
+    }
+
+    fn pseudorandom_initial_state(
+        &self,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let execution_state = match bench_case {
+            Some(BenchmarkCase::CommonCase) => self.deprecated_snippet.common_case_input_state(),
+            Some(BenchmarkCase::WorstCase) => self.deprecated_snippet.worst_case_input_state(),
+            None => {
+                let mut execution_states = self.deprecated_snippet.gen_input_states();
+                let mut rng: StdRng = SeedableRng::from_seed(seed);
+                let index = rng.gen_range(0..execution_states.len());
+                execution_states.swap_remove(index)
+            }
+        };
+
+        FunctionInitialState {
+            stack: execution_state.stack,
+            memory: execution_state.nondeterminism.ram,
+        }
+    }
+}
+
 impl<S: DeprecatedSnippet + Clone + 'static> RustShadow for DeprecatedSnippetWrapper<S> {
     fn rust_shadow_wrapper(
         &self,
@@ -246,8 +344,8 @@ impl<S: DeprecatedSnippet + Clone + 'static> RustShadow for DeprecatedSnippetWra
         }
     }
 
-    fn bench(&self) {
-        todo!()
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
+        benchmark_snippet_deprecated(self.deprecated_snippet.clone())
     }
 
     fn inner(&self) -> Rc<RefCell<dyn BasicSnippet>> {
@@ -316,6 +414,7 @@ mod tests {
     use itertools::Itertools;
 
     use crate::arithmetic;
+    use crate::traits::function::ShadowedFunction;
 
     use super::*;
 
@@ -331,6 +430,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deprecated_snippet_as_function() {
+        let wrapped = DeprecatedSnippetWrapper::new(arithmetic::u32::safeadd::Safeadd);
+        assert_eq!(
+            arithmetic::u32::safeadd::Safeadd.entrypoint_name(),
+            Library::new().import(Box::new(DeprecatedSnippetWrapper::new(
+                arithmetic::u32::safeadd::Safeadd
+            )))
+        );
+        ShadowedFunction::new(wrapped).test();
+    }
+
     #[test]
     fn data_type_string_rep() {
         assert_eq!("DataType::Digest", DataType::Digest.variant_name());
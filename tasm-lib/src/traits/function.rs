@@ -1,12 +1,19 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
 use triton_vm::{BFieldElement, NonDeterminism};
 
 use crate::{
     linker::{execute_bench, link_for_isolated_run},
-    snippet_bencher::{write_benchmarks, BenchmarkCase, BenchmarkResult},
-    test_helpers::test_rust_equivalence_given_complete_state,
+    snippet_bencher::{
+        cycle_breakdown_if_requested, rust_shadow_allocation_statistics, BenchmarkCase,
+        BenchmarkResult,
+    },
+    test_helpers::{
+        test_rust_equivalence_given_complete_state, test_rust_tasm_equivalence_expect_failure,
+        verify_corner_case_coverage,
+    },
     VmHasherState,
 };
 
@@ -17,11 +24,12 @@ use super::{basic_snippet::BasicSnippet, rust_shadow::RustShadow};
 /// larger than the dynamic memory allocator and the dynamic memory allocator value has to
 /// be updated accordingly.
 ///
-/// See also: [closure], [algorithm], [procedure]
+/// See also: [closure], [algorithm], [accessor], [procedure]
 ///
 /// [closure]: crate::traits::closure::Closure
 /// [algorithm]: crate::traits::algorithm::Algorithm
 /// [procedure]: crate::traits::procedure::Procedure
+/// [accessor]: crate::traits::accessor::Accessor
 pub trait Function: BasicSnippet {
     fn rust_shadow(
         &self,
@@ -39,6 +47,31 @@ pub trait Function: BasicSnippet {
     fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
         vec![]
     }
+
+    /// Corner cases together with the category they exercise, which must be one of the
+    /// [declared categories](BasicSnippet::corner_case_categories).
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, FunctionInitialState)> {
+        vec![]
+    }
+
+    /// Initial states on which the snippet must fail, together with the error with which
+    /// Triton VM must halt. The test harness asserts that the Rust shadow panics on each of
+    /// them as well.
+    fn failing_initial_states(&self) -> Vec<(FunctionInitialState, InstructionError)> {
+        vec![]
+    }
+
+    /// An initial state derived from fuzzer-provided bytes. By default, the bytes seed
+    /// [`pseudorandom_initial_state`](Self::pseudorandom_initial_state). Override this to
+    /// let the fuzzer control the structure of the state, for example with the helpers in
+    /// [`fuzzing`](crate::fuzzing).
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<FunctionInitialState> {
+        Ok(self.pseudorandom_initial_state(u.arbitrary()?, None))
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -57,6 +90,58 @@ impl<F: Function + 'static> ShadowedFunction<F> {
             function: Rc::new(RefCell::new(function)),
         }
     }
+
+    /// Test the function on initial states with arbitrary values of its input types on the
+    /// stack, shrinking any failing state to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test(&self) {
+        let input_types = self.function.borrow().inputs().into_iter().map(|(t, _)| t);
+        self.prop_test_with(proptest::arbitrary::any_with::<FunctionInitialState>(
+            input_types.collect(),
+        ));
+    }
+
+    /// Test the function on initial states drawn from the given strategy, shrinking any
+    /// failing state to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test_with(
+        &self,
+        strategy: impl proptest::strategy::Strategy<Value = FunctionInitialState>,
+    ) {
+        proptest::test_runner::TestRunner::default()
+            .run(&strategy, |state| {
+                self.test_initial_state(state);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /// Test the function on an initial state derived from fuzzer-provided bytes, for use in
+    /// fuzz targets. Bytes that don't suffice to derive a state are ignored. States on which
+    /// the Rust shadow panics are accepted if Triton VM fails on them, too.
+    #[cfg(feature = "fuzz")]
+    pub fn fuzz(&self, data: &[u8]) {
+        let mut unstructured = arbitrary::Unstructured::new(data);
+        let Ok(state) = self
+            .function
+            .borrow()
+            .arbitrary_initial_state(&mut unstructured)
+        else {
+            return;
+        };
+        let nondeterminism = NonDeterminism::default().with_ram(state.memory.clone());
+        if crate::test_helpers::rust_shadow_and_tasm_fail(
+            self,
+            &state.stack,
+            &[],
+            &nondeterminism,
+            &None,
+            0,
+        ) {
+            return;
+        }
+        self.test_initial_state(state);
+    }
 }
 
 impl<P: Function + 'static> ShadowedFunction<P> {
@@ -100,16 +185,25 @@ where
     /// Test rust-tasm equivalence.
     fn test(&self) {
         let entrypoint = self.function.borrow().entrypoint();
-        for (i, cornercase_test) in self
+        let categorized_corner_cases = self
+            .function
+            .borrow()
+            .categorized_corner_case_initial_states();
+        let corner_cases = self
             .function
             .borrow()
             .corner_case_initial_states()
             .into_iter()
-            .enumerate()
-        {
+            .chain(
+                categorized_corner_cases
+                    .iter()
+                    .map(|(_, state)| state.clone()),
+            );
+        for (i, cornercase_test) in corner_cases.enumerate() {
             println!("testing {entrypoint} corner case number {i}");
             self.test_initial_state(cornercase_test);
         }
+        verify_corner_case_coverage(&*self.function.borrow(), &categorized_corner_cases);
 
         let num_rng_states = 5;
         let mut rng = thread_rng();
@@ -123,11 +217,33 @@ where
                     .pseudorandom_initial_state(seed, None),
             )
         }
+
+        for (i, (FunctionInitialState { stack, memory }, expected_error)) in self
+            .function
+            .borrow()
+            .failing_initial_states()
+            .into_iter()
+            .enumerate()
+        {
+            println!(
+                "testing {} failing case number {i}",
+                self.function.borrow().entrypoint(),
+            );
+            test_rust_tasm_equivalence_expect_failure(
+                self,
+                &stack,
+                &[],
+                &NonDeterminism::default().with_ram(memory),
+                &None,
+                0,
+                expected_error,
+            );
+        }
     }
 
     /// Count number of cycles and other performance indicators and save them in directory
     /// benchmarks/.
-    fn bench(&self) {
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
         let mut rng: StdRng = SeedableRng::from_seed(
             hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
                 .unwrap()
@@ -143,6 +259,10 @@ where
                 .pseudorandom_initial_state(rng.gen(), Some(bench_case));
             let program = link_for_isolated_run(self.function.clone(), 1);
             let non_determinism = NonDeterminism::default().with_ram(memory);
+            let allocation_statistics =
+                rust_shadow_allocation_statistics(self, &stack, &[], &non_determinism, &None);
+            let cycle_breakdown =
+                cycle_breakdown_if_requested(self, 1, &stack, &[], &non_determinism, &None);
             let execution_result = execute_bench(&program, &stack, vec![], non_determinism, None);
             let benchmark = BenchmarkResult {
                 name: self.function.borrow().entrypoint(),
@@ -150,11 +270,13 @@ where
                 hash_table_height: execution_result.hash_table_height,
                 u32_table_height: execution_result.u32_table_height,
                 case: bench_case,
+                allocation_statistics,
+                cycle_breakdown,
             };
             benchmarks.push(benchmark);
         }
 
-        write_benchmarks(benchmarks);
+        benchmarks
     }
 
     fn inner(&self) -> Rc<RefCell<dyn BasicSnippet>> {
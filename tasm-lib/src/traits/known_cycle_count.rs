@@ -0,0 +1,200 @@
+use std::{cell::RefCell, rc::Rc};
+
+use itertools::Itertools;
+use triton_vm::instruction::LabelledInstruction;
+use triton_vm::vm::VMState;
+use triton_vm::{Program, PublicInput};
+
+use super::basic_snippet::BasicSnippet;
+use super::procedure::ProcedureInitialState;
+use crate::linker::link_for_isolated_run;
+
+/// Whether a snippet's declared [cycle count](KnownCycleCount::cycle_count) is exact, or
+/// only an upper bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleCountBound {
+    Exact,
+    UpperBound,
+}
+
+/// A snippet whose number of clock cycles is a known function of some size parameters of
+/// its input, like the length of a list or the number of iterations. Unlike benchmark
+/// results, such a formula can be evaluated for any input size, for example for metering.
+/// Use [`verify_cycle_count`] to check the formula against actual executions.
+pub trait KnownCycleCount: BasicSnippet {
+    /// The names of the size parameters the cycle count depends on, in order.
+    fn size_parameters(&self) -> Vec<String>;
+
+    /// The number of clock cycles spent from the first instruction up to and including the
+    /// final `return`, counting all snippets that are called, on an input of the given
+    /// sizes.
+    fn cycle_count(&self, sizes: &[usize]) -> usize;
+
+    fn cycle_count_bound(&self) -> CycleCountBound {
+        CycleCountBound::Exact
+    }
+
+    /// An initial state for an input of the given sizes.
+    fn initial_state_for_sizes(&self, sizes: &[usize]) -> ProcedureInitialState;
+
+    /// The sizes for which [`verify_cycle_count`] checks the formula.
+    fn sizes_to_verify(&self) -> Vec<Vec<usize>>;
+}
+
+/// Execute the snippet on an input of every size it declares for verification, and assert
+/// that the number of clock cycles matches the declared formula, or stays below it if the
+/// formula is only an upper bound.
+pub fn verify_cycle_count<T: KnownCycleCount + 'static>(snippet: T) {
+    let snippet = Rc::new(RefCell::new(snippet));
+    let code = link_for_isolated_run(snippet.clone(), 0);
+    let snippet = snippet.borrow();
+    let size_parameters = snippet.size_parameters();
+
+    for sizes in snippet.sizes_to_verify() {
+        assert_eq!(
+            size_parameters.len(),
+            sizes.len(),
+            "Sizes must have one entry per size parameter of `{}`",
+            snippet.entrypoint()
+        );
+        let sizes_description = size_parameters
+            .iter()
+            .zip_eq(sizes.iter())
+            .map(|(parameter, size)| format!("{parameter} = {size}"))
+            .join(", ");
+
+        let declared = snippet.cycle_count(&sizes);
+        let actual = measure_cycle_count(&code, snippet.initial_state_for_sizes(&sizes));
+        println!(
+            "{} with {sizes_description}: {actual} cycles, {declared} declared",
+            snippet.entrypoint()
+        );
+
+        match snippet.cycle_count_bound() {
+            CycleCountBound::Exact => assert_eq!(
+                declared,
+                actual,
+                "Cycle count of `{}` with {sizes_description} must match the declared formula",
+                snippet.entrypoint()
+            ),
+            CycleCountBound::UpperBound => assert!(
+                actual <= declared,
+                "Cycle count of `{}` with {sizes_description} must not exceed the declared \
+                bound {declared}, but is {actual}",
+                snippet.entrypoint()
+            ),
+        }
+    }
+}
+
+/// The number of clock cycles spent inside the function called by a program linked with
+/// [`link_for_isolated_run`], which excludes the preamble's `call` and `halt`.
+fn measure_cycle_count(
+    code: &[LabelledInstruction],
+    initial_state: ProcedureInitialState,
+) -> usize {
+    let ProcedureInitialState {
+        stack,
+        nondeterminism,
+        public_input,
+        sponge_state,
+    } = initial_state;
+
+    let program = Program::new(code);
+    let mut vm_state = VMState::new(&program, PublicInput::new(public_input), nondeterminism);
+    vm_state.op_stack.stack = stack;
+    vm_state.sponge_state = sponge_state.map(|state| state.state);
+
+    let mut cycle_count = 0;
+    while !vm_state.halting {
+        if !vm_state.jump_stack.is_empty() {
+            cycle_count += 1;
+        }
+        vm_state.step().unwrap();
+    }
+
+    cycle_count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hashing::squeeze_repeatedly::SqueezeRepeatedly;
+    use crate::library::Library;
+
+    use super::*;
+
+    /// Declares the cycle count of [`SqueezeRepeatedly`] with the given offset.
+    struct SkewedSqueezeRepeatedly {
+        offset: isize,
+        bound: CycleCountBound,
+    }
+
+    impl BasicSnippet for SkewedSqueezeRepeatedly {
+        fn inputs(&self) -> Vec<(crate::data_type::DataType, String)> {
+            SqueezeRepeatedly.inputs()
+        }
+
+        fn outputs(&self) -> Vec<(crate::data_type::DataType, String)> {
+            SqueezeRepeatedly.outputs()
+        }
+
+        fn entrypoint(&self) -> String {
+            SqueezeRepeatedly.entrypoint()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            SqueezeRepeatedly.code(library)
+        }
+    }
+
+    impl KnownCycleCount for SkewedSqueezeRepeatedly {
+        fn size_parameters(&self) -> Vec<String> {
+            SqueezeRepeatedly.size_parameters()
+        }
+
+        fn cycle_count(&self, sizes: &[usize]) -> usize {
+            SqueezeRepeatedly
+                .cycle_count(sizes)
+                .checked_add_signed(self.offset)
+                .unwrap()
+        }
+
+        fn cycle_count_bound(&self) -> CycleCountBound {
+            self.bound
+        }
+
+        fn initial_state_for_sizes(&self, sizes: &[usize]) -> ProcedureInitialState {
+            SqueezeRepeatedly.initial_state_for_sizes(sizes)
+        }
+
+        fn sizes_to_verify(&self) -> Vec<Vec<usize>> {
+            SqueezeRepeatedly.sizes_to_verify()
+        }
+    }
+
+    #[test]
+    fn loose_upper_bound_is_accepted() {
+        verify_cycle_count(SkewedSqueezeRepeatedly {
+            offset: 1,
+            bound: CycleCountBound::UpperBound,
+        });
+    }
+
+    #[should_panic(expected = "must match the declared formula")]
+    #[test]
+    fn inexact_formula_is_detected() {
+        verify_cycle_count(SkewedSqueezeRepeatedly {
+            offset: 1,
+            bound: CycleCountBound::Exact,
+        });
+    }
+
+    #[should_panic(expected = "must not exceed the declared bound")]
+    #[test]
+    fn exceeded_upper_bound_is_detected() {
+        verify_cycle_count(SkewedSqueezeRepeatedly {
+            offset: -1,
+            bound: CycleCountBound::UpperBound,
+        });
+    }
+}
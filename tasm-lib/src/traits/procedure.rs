@@ -2,17 +2,28 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use itertools::Itertools;
 use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use triton_vm::error::InstructionError;
 use triton_vm::{BFieldElement, NonDeterminism};
 use twenty_first::shared_math::bfield_codec::BFieldCodec;
 
 use crate::{
+    data_type::DataType,
     linker::{execute_bench, link_for_isolated_run},
-    snippet_bencher::{write_benchmarks, BenchmarkCase, BenchmarkResult},
+    snippet_bencher::{
+        cycle_breakdown_if_requested, rust_shadow_allocation_statistics, BenchmarkCase,
+        BenchmarkResult,
+    },
     test_helpers::{
-        rust_final_state, tasm_final_state, verify_memory_equivalence, verify_sponge_equivalence,
+        rust_final_state, tasm_final_state, test_rust_tasm_equivalence_expect_failure,
+        verify_corner_case_coverage, verify_memory_equivalence, verify_memory_isolation,
+        verify_nondeterminism_consumption, verify_public_output, verify_sponge_equivalence,
         verify_stack_equivalence, verify_stack_growth,
     },
-    traits::{basic_snippet::BasicSnippet, rust_shadow::RustShadow},
+    traits::{
+        algorithm::DigestCursor,
+        basic_snippet::BasicSnippet,
+        rust_shadow::{NondeterminismConsumption, RustShadow},
+    },
     VmHasherState,
 };
 
@@ -22,11 +33,12 @@ use crate::{
 /// in a function (lower case f, as in 'labelled scope'); and cannot be proved as
 /// a standalone program.
 ///
-/// See also: [closure], [function], [algorithm]
+/// See also: [closure], [function], [algorithm], [accessor]
 ///
 /// [closure]: crate::traits::closure::Closure
 /// [function]: crate::traits::function::Function
 /// [algorithm]: crate::traits::algorithm::Algorithm
+/// [accessor]: crate::traits::accessor::Accessor
 pub trait Procedure: BasicSnippet {
     /// Returns standard output
     fn rust_shadow(
@@ -38,6 +50,58 @@ pub trait Procedure: BasicSnippet {
         sponge_state: &mut Option<VmHasherState>,
     ) -> Vec<BFieldElement>;
 
+    /// Like [`rust_shadow`](Self::rust_shadow), but reads the nondeterministic individual
+    /// tokens through a cursor, in the order in which the tasm code divines them. Override
+    /// this if the snippet divines individual tokens, and let `rust_shadow` defer to it. The
+    /// test harness then checks that the Rust shadow and the tasm code read the same number
+    /// of individual tokens.
+    ///
+    /// By default, defers to `rust_shadow`, which is taken to read all individual tokens.
+    fn rust_shadow_with_token_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+    ) -> Vec<BFieldElement> {
+        let output = self.rust_shadow(stack, memory, nondeterminism, public_input, sponge_state);
+        tokens.skip_remaining();
+        output
+    }
+
+    /// Like [`rust_shadow_with_token_cursor`](Self::rust_shadow_with_token_cursor), but
+    /// additionally reads the nondeterministic digests through a cursor, in the order in
+    /// which the tasm code divines them. Override this if the snippet divines digests, and
+    /// let `rust_shadow` defer to it. The test harness then checks that the Rust shadow and
+    /// the tasm code read the same number of digests.
+    ///
+    /// By default, defers to `rust_shadow_with_token_cursor`, which is taken to read all
+    /// digests.
+    #[allow(clippy::too_many_arguments)]
+    fn rust_shadow_with_digest_cursor(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        public_input: &[BFieldElement],
+        sponge_state: &mut Option<VmHasherState>,
+        tokens: &mut TokenCursor,
+        digests: &mut DigestCursor,
+    ) -> Vec<BFieldElement> {
+        let output = self.rust_shadow_with_token_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            public_input,
+            sponge_state,
+            tokens,
+        );
+        digests.skip_remaining();
+        output
+    }
+
     fn preprocess<T: BFieldCodec>(
         _meta_input: T,
         _nondeterminism: &mut NonDeterminism<BFieldElement>,
@@ -53,6 +117,92 @@ pub trait Procedure: BasicSnippet {
     fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
         vec![]
     }
+
+    /// Corner cases together with the category they exercise, which must be one of the
+    /// [declared categories](BasicSnippet::corner_case_categories).
+    fn categorized_corner_case_initial_states(&self) -> Vec<(String, ProcedureInitialState)> {
+        vec![]
+    }
+
+    /// Initial states on which the snippet must fail, together with the error with which
+    /// Triton VM must halt. The test harness asserts that the Rust shadow panics on each of
+    /// them as well.
+    fn failing_initial_states(&self) -> Vec<(ProcedureInitialState, InstructionError)> {
+        vec![]
+    }
+
+    /// An initial state derived from fuzzer-provided bytes. By default, the bytes seed
+    /// [`pseudorandom_initial_state`](Self::pseudorandom_initial_state). Override this to
+    /// let the fuzzer control the structure of the state, for example with the helpers in
+    /// [`fuzzing`](crate::fuzzing).
+    #[cfg(feature = "fuzz")]
+    fn arbitrary_initial_state(
+        &self,
+        u: &mut arbitrary::Unstructured,
+    ) -> arbitrary::Result<ProcedureInitialState> {
+        Ok(self.pseudorandom_initial_state(u.arbitrary()?, None))
+    }
+
+    /// The values the procedure writes to standard output when started in the given state,
+    /// as runs of values of the same type: each entry is a type and the number of
+    /// consecutive values of that type. If declared, the test harness asserts that the
+    /// output consists of exactly these values, each a valid value of its type, decodes the
+    /// output, and reports mismatches per value.
+    fn public_output_types(
+        &self,
+        _initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<(DataType, usize)>> {
+        None
+    }
+
+    /// The exact public output the procedure must write when started in the given state, if
+    /// it is known independently of the Rust shadow, for example because the state holds a
+    /// committed expectation. If declared, the test harness asserts that Triton VM writes
+    /// exactly this output.
+    fn expected_public_output(
+        &self,
+        _initial_state: &ProcedureInitialState,
+    ) -> Option<Vec<BFieldElement>> {
+        None
+    }
+}
+
+/// Sequential access to the nondeterministic individual tokens, mirroring how Triton VM's
+/// `divine` consumes them.
+#[derive(Debug, Clone)]
+pub struct TokenCursor<'a> {
+    tokens: &'a [BFieldElement],
+    num_read: usize,
+}
+
+impl<'a> TokenCursor<'a> {
+    pub fn new(tokens: &'a [BFieldElement]) -> Self {
+        Self {
+            tokens,
+            num_read: 0,
+        }
+    }
+
+    /// Read the next individual token. Panics if all tokens have been read, in which case
+    /// Triton VM would crash.
+    pub fn read(&mut self) -> BFieldElement {
+        let Some(&token) = self.tokens.get(self.num_read) else {
+            panic!(
+                "Cannot read nondeterministic individual token: all {} tokens were read already",
+                self.tokens.len()
+            );
+        };
+        self.num_read += 1;
+        token
+    }
+
+    pub fn num_read(&self) -> usize {
+        self.num_read
+    }
+
+    pub fn skip_remaining(&mut self) {
+        self.num_read = self.tokens.len();
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -73,6 +223,57 @@ impl<P: Procedure + 'static> ShadowedProcedure<P> {
             procedure: Rc::new(RefCell::new(procedure)),
         }
     }
+
+    /// Test the procedure on initial states with arbitrary values of its input types on the
+    /// stack, shrinking any failing state to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test(&self) {
+        let input_types = self.procedure.borrow().inputs().into_iter().map(|(t, _)| t);
+        self.prop_test_with(proptest::arbitrary::any_with::<ProcedureInitialState>(
+            input_types.collect(),
+        ));
+    }
+
+    /// Test the procedure on initial states drawn from the given strategy, shrinking any
+    /// failing state to a minimal one.
+    #[cfg(feature = "proptest")]
+    pub fn prop_test_with(
+        &self,
+        strategy: impl proptest::strategy::Strategy<Value = ProcedureInitialState>,
+    ) {
+        proptest::test_runner::TestRunner::default()
+            .run(&strategy, |state| {
+                self.test_initial_state(state);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /// Test the procedure on an initial state derived from fuzzer-provided bytes, for use in
+    /// fuzz targets. Bytes that don't suffice to derive a state are ignored. States on which
+    /// the Rust shadow panics are accepted if Triton VM fails on them, too.
+    #[cfg(feature = "fuzz")]
+    pub fn fuzz(&self, data: &[u8]) {
+        let mut unstructured = arbitrary::Unstructured::new(data);
+        let Ok(state) = self
+            .procedure
+            .borrow()
+            .arbitrary_initial_state(&mut unstructured)
+        else {
+            return;
+        };
+        if crate::test_helpers::rust_shadow_and_tasm_fail(
+            self,
+            &state.stack,
+            &state.public_input,
+            &state.nondeterminism,
+            &state.sponge_state,
+            0,
+        ) {
+            return;
+        }
+        self.test_initial_state(state);
+    }
 }
 
 impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
@@ -93,6 +294,32 @@ impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
             .rust_shadow(stack, memory, nondeterminism, stdin, sponge_state)
     }
 
+    fn rust_shadow_wrapper_counting_nondeterminism(
+        &self,
+        stdin: &[BFieldElement],
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> (Vec<BFieldElement>, NondeterminismConsumption) {
+        let mut tokens = TokenCursor::new(&nondeterminism.individual_tokens);
+        let mut digests = DigestCursor::new(&nondeterminism.digests);
+        let output = self.procedure.borrow().rust_shadow_with_digest_cursor(
+            stack,
+            memory,
+            nondeterminism,
+            stdin,
+            sponge_state,
+            &mut tokens,
+            &mut digests,
+        );
+        let consumption = NondeterminismConsumption {
+            individual_tokens_read: tokens.num_read(),
+            digests_read: digests.num_read(),
+        };
+        (output, consumption)
+    }
+
     fn test(&self) {
         let num_states = 5;
         let seed: [u8; 32] = thread_rng().gen();
@@ -105,14 +332,17 @@ impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
         let procedure = &self.procedure.borrow();
         let entrypoint = procedure.entrypoint();
 
-        for (i, corner_case) in procedure
-            .corner_case_initial_states()
-            .into_iter()
-            .enumerate()
-        {
+        let categorized_corner_cases = procedure.categorized_corner_case_initial_states();
+        let corner_cases = procedure.corner_case_initial_states().into_iter().chain(
+            categorized_corner_cases
+                .iter()
+                .map(|(_, state)| state.clone()),
+        );
+        for (i, corner_case) in corner_cases.enumerate() {
             println!("testing {entrypoint} corner case number {i}");
             self.test_initial_state(corner_case);
         }
+        verify_corner_case_coverage(&**procedure, &categorized_corner_cases);
 
         for _ in 0..num_states {
             let seed: [u8; 32] = rng.gen();
@@ -124,9 +354,24 @@ impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
 
             self.test_initial_state(state);
         }
+
+        for (i, (state, expected_error)) in
+            procedure.failing_initial_states().into_iter().enumerate()
+        {
+            println!("testing {entrypoint} failing case number {i}");
+            test_rust_tasm_equivalence_expect_failure(
+                self,
+                &state.stack,
+                &state.public_input,
+                &state.nondeterminism,
+                &state.sponge_state,
+                0,
+                expected_error,
+            );
+        }
     }
 
-    fn bench(&self) {
+    fn benchmark(&self) -> Vec<BenchmarkResult> {
         let mut rng: StdRng = SeedableRng::from_seed(
             hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
                 .unwrap()
@@ -147,6 +392,21 @@ impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
                 .pseudorandom_initial_state(rng.gen(), Some(bench_case));
             let words_statically_allocated = 10; // okay buffer
             let program = link_for_isolated_run(self.procedure.clone(), words_statically_allocated);
+            let allocation_statistics = rust_shadow_allocation_statistics(
+                self,
+                &stack,
+                &public_input,
+                &nondeterminism,
+                &sponge_state,
+            );
+            let cycle_breakdown = cycle_breakdown_if_requested(
+                self,
+                words_statically_allocated,
+                &stack,
+                &public_input,
+                &nondeterminism,
+                &sponge_state,
+            );
             let execution_result =
                 execute_bench(&program, &stack, public_input, nondeterminism, sponge_state);
             let benchmark = BenchmarkResult {
@@ -155,16 +415,20 @@ impl<P: Procedure + 'static> RustShadow for ShadowedProcedure<P> {
                 hash_table_height: execution_result.hash_table_height,
                 u32_table_height: execution_result.u32_table_height,
                 case: bench_case,
+                allocation_statistics,
+                cycle_breakdown,
             };
             benchmarks.push(benchmark);
         }
 
-        write_benchmarks(benchmarks);
+        benchmarks
     }
 }
 
 impl<P: Procedure + 'static> ShadowedProcedure<P> {
     fn test_initial_state(&self, state: ProcedureInitialState) {
+        let public_output_types = self.procedure.borrow().public_output_types(&state);
+        let expected_public_output = self.procedure.borrow().expected_public_output(&state);
         let ProcedureInitialState {
             stack,
             nondeterminism,
@@ -175,6 +439,7 @@ impl<P: Procedure + 'static> ShadowedProcedure<P> {
         let rust = rust_final_state(self, &stack, &public_input, &nondeterminism, &sponge_state);
 
         // run tvm
+        let initial_memory = nondeterminism.ram.clone();
         let words_statically_allocated = 0;
         let tasm = tasm_final_state(
             self,
@@ -185,18 +450,58 @@ impl<P: Procedure + 'static> ShadowedProcedure<P> {
             words_statically_allocated,
         );
 
-        // assert_eq!(tasm.final_sponge_state.state, rust.final_sponge_state.state);
-        // can't do this without changing the VM interface, unfortunately ...
-
+        if let Some(output_types) = public_output_types {
+            verify_public_output(&output_types, &rust.output, &tasm.output);
+        }
         assert_eq!(
             rust.output, tasm.output,
             "Rust shadowing and VM std out must agree"
         );
+        if let Some(expected_output) = expected_public_output {
+            assert_eq!(
+                expected_output, tasm.output,
+                "VM std out must match the expected output"
+            );
+        }
 
         verify_stack_growth(self, &stack, &tasm.final_stack);
 
         verify_stack_equivalence(&rust.final_stack, &tasm.final_stack);
         verify_memory_equivalence(&rust.final_ram, &tasm.final_ram);
+        verify_nondeterminism_consumption(&rust, &tasm);
+        verify_memory_isolation(
+            self,
+            &stack,
+            &initial_memory,
+            &tasm.final_ram,
+            words_statically_allocated,
+        );
         verify_sponge_equivalence(&rust.final_sponge_state, &tasm.final_sponge_state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_cursor_reads_tokens_in_order() {
+        let tokens: [BFieldElement; 3] = rand::random();
+        let mut cursor = TokenCursor::new(&tokens);
+        assert_eq!(tokens[0], cursor.read());
+        assert_eq!(tokens[1], cursor.read());
+        assert_eq!(2, cursor.num_read());
+
+        cursor.skip_remaining();
+        assert_eq!(3, cursor.num_read());
+    }
+
+    #[should_panic(expected = "all 1 tokens were read already")]
+    #[test]
+    fn reading_past_the_last_token_panics() {
+        let tokens = [BFieldElement::new(0)];
+        let mut cursor = TokenCursor::new(&tokens);
+        cursor.read();
+        cursor.read();
+    }
+}
@@ -2,10 +2,18 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use triton_vm::{BFieldElement, NonDeterminism};
 
+use crate::snippet_bencher::{write_benchmarks, BenchmarkResult};
 use crate::VmHasherState;
 
 use super::basic_snippet::BasicSnippet;
 
+/// The amount of nondeterminism a Rust shadow read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NondeterminismConsumption {
+    pub individual_tokens_read: usize,
+    pub digests_read: usize,
+}
+
 pub trait RustShadow {
     fn inner(&self) -> Rc<RefCell<dyn BasicSnippet>>;
 
@@ -18,7 +26,32 @@ pub trait RustShadow {
         sponge_state: &mut Option<VmHasherState>,
     ) -> Vec<BFieldElement>;
 
+    /// Like [`rust_shadow_wrapper`](Self::rust_shadow_wrapper), but additionally returns
+    /// how much nondeterminism the Rust shadow read. Unless overridden, the Rust shadow is
+    /// taken to read all of it.
+    fn rust_shadow_wrapper_counting_nondeterminism(
+        &self,
+        stdin: &[BFieldElement],
+        nondeterminism: &NonDeterminism<BFieldElement>,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+        sponge_state: &mut Option<VmHasherState>,
+    ) -> (Vec<BFieldElement>, NondeterminismConsumption) {
+        let output = self.rust_shadow_wrapper(stdin, nondeterminism, stack, memory, sponge_state);
+        let consumption = NondeterminismConsumption {
+            individual_tokens_read: nondeterminism.individual_tokens.len(),
+            digests_read: nondeterminism.digests.len(),
+        };
+        (output, consumption)
+    }
+
     fn test(&self);
 
-    fn bench(&self);
+    /// Run the snippet on its benchmark inputs and measure its performance.
+    fn benchmark(&self) -> Vec<BenchmarkResult>;
+
+    /// [Benchmark](Self::benchmark) the snippet and write the results to disk.
+    fn bench(&self) {
+        write_benchmarks(self.benchmark());
+    }
 }